@@ -29,9 +29,14 @@ pub fn command(packages_to_add: Vec<String>, dev: bool) -> Result<()> {
         UseManifest::Yes,
     )?;
 
-    // Read gleam.toml and manifest.toml so we can insert new deps into it
+    // Read gleam.toml and manifest.toml so we can insert new deps into it.
+    // The manifest of a workspace is at its root, and is updated by resolving
+    // the dependencies again once gleam.toml has been written.
     let mut gleam_toml = read_toml_edit("gleam.toml")?;
-    let mut manifest_toml = read_toml_edit("manifest.toml")?;
+    let mut manifest_toml = match paths.workspace_root() {
+        Some(_) => None,
+        None => Some(read_toml_edit("manifest.toml")?),
+    };
 
     // Insert the new deps
     for (added_package, _) in new_package_requirements {
@@ -65,9 +70,11 @@ pub fn command(packages_to_add: Vec<String>, dev: bool) -> Result<()> {
             } else {
                 gleam_toml["dependencies"][&added_package] = toml_edit::value(range.clone());
             };
-            manifest_toml["requirements"][&added_package]
-                .as_inline_table_mut()
-                .expect("Invalid manifest format")["version"] = range.into();
+            if let Some(manifest_toml) = &mut manifest_toml {
+                manifest_toml["requirements"][&added_package]
+                    .as_inline_table_mut()
+                    .expect("Invalid manifest format")["version"] = range.into();
+            }
         }
 
         cli::print_added(&format!("{added_package} v{version}"));
@@ -75,7 +82,20 @@ pub fn command(packages_to_add: Vec<String>, dev: bool) -> Result<()> {
 
     // Write the updated config
     fs::write(Utf8Path::new("gleam.toml"), &gleam_toml.to_string())?;
-    fs::write(Utf8Path::new("manifest.toml"), &manifest_toml.to_string())?;
+    match manifest_toml {
+        Some(manifest_toml) => {
+            fs::write(Utf8Path::new("manifest.toml"), &manifest_toml.to_string())?
+        }
+        None => {
+            let _ = crate::dependencies::download(
+                &paths,
+                cli::Reporter::new(),
+                None,
+                Vec::new(),
+                UseManifest::Yes,
+            )?;
+        }
+    }
 
     Ok(())
 }
@@ -2,8 +2,9 @@ use std::{rc::Rc, time::Instant};
 
 use gleam_core::{
     build::{Built, Codegen, NullTelemetry, Options, ProjectCompiler, Telemetry},
+    config::PackageConfig,
     manifest::Manifest,
-    paths::ProjectPaths,
+    paths::{self, ProjectPaths},
     warning::WarningEmitterIO,
     Result,
 };
@@ -12,12 +13,19 @@ use crate::{
     build_lock::BuildLock,
     cli,
     dependencies::UseManifest,
-    fs::{self, get_current_directory, get_project_root, ConsoleWarningEmitter},
+    fs::{self, ConsoleWarningEmitter},
+    workspace::Workspace,
 };
 
+#[cfg(test)]
+mod tests;
+
 pub fn download_dependencies(telemetry: impl Telemetry) -> Result<Manifest> {
     let paths = crate::find_project_paths()?;
-    crate::dependencies::download(&paths, telemetry, None, Vec::new(), UseManifest::Yes)
+    let manifest =
+        crate::dependencies::download(&paths, telemetry, None, Vec::new(), UseManifest::Yes)?;
+    crate::dependencies::licences::check_policy(&paths, &manifest)?;
+    Ok(manifest)
 }
 
 pub fn main(options: Options, manifest: Manifest) -> Result<Built> {
@@ -30,8 +38,62 @@ pub(crate) fn main_with_warnings(
     warnings: Rc<dyn WarningEmitterIO>,
 ) -> Result<Built> {
     let paths = crate::find_project_paths()?;
-    let perform_codegen = options.codegen;
     let root_config = crate::config::root_config()?;
+    compile(&paths, root_config, options, manifest, warnings)
+}
+
+/// Compiles the project, or each of the members in turn when at the root of a
+/// workspace.
+///
+pub(crate) fn all(
+    options: Options,
+    manifest: Manifest,
+    warnings: Rc<dyn WarningEmitterIO>,
+) -> Result<Vec<Built>> {
+    let paths = crate::find_project_paths()?;
+    all_at(&paths, options, manifest, warnings)
+}
+
+/// Compiles the project at the given paths, or each of the members in turn
+/// when they are the root of a workspace.
+///
+fn all_at(
+    paths: &ProjectPaths,
+    options: Options,
+    manifest: Manifest,
+    warnings: Rc<dyn WarningEmitterIO>,
+) -> Result<Vec<Built>> {
+    match Workspace::at_root(paths)? {
+        Some(workspace) => workspace
+            .members
+            .into_iter()
+            .map(|member| {
+                compile(
+                    &member.paths,
+                    member.config,
+                    options.clone(),
+                    manifest.clone(),
+                    warnings.clone(),
+                )
+            })
+            .collect(),
+        None => {
+            let root_config = crate::config::read(paths.root_config())?;
+            compile(paths, root_config, options, manifest, warnings).map(|built| vec![built])
+        }
+    }
+}
+
+/// Compiles the package at the given paths as the root package.
+///
+pub(crate) fn compile(
+    paths: &ProjectPaths,
+    root_config: PackageConfig,
+    options: Options,
+    manifest: Manifest,
+    warnings: Rc<dyn WarningEmitterIO>,
+) -> Result<Built> {
+    let perform_codegen = options.codegen;
     let telemetry: &'static dyn Telemetry = if options.no_print_progress {
         &NullTelemetry
     } else {
@@ -40,24 +102,24 @@ pub(crate) fn main_with_warnings(
     let io = fs::ProjectIO::new();
     let start = Instant::now();
     let lock = BuildLock::new_target(
-        &paths,
+        paths,
         options.mode,
         options.target.unwrap_or(root_config.target),
     )?;
-    let current_dir = get_project_root(get_current_directory()?)?;
 
     tracing::info!("Compiling packages");
     let result = {
         let _guard = lock.lock(telemetry);
-        let compiler = ProjectCompiler::new(
+        let mut compiler = ProjectCompiler::new(
             root_config,
             options,
             manifest.packages,
             telemetry,
             warnings,
-            ProjectPaths::new(current_dir),
+            paths.clone(),
             io,
         );
+        compiler.use_global_build_cache(paths::global_build_cache());
         compiler.compile()?
     };
 
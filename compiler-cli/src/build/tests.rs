@@ -0,0 +1,124 @@
+use std::{collections::HashMap, rc::Rc};
+
+use camino::{Utf8Path, Utf8PathBuf};
+use hexpm::version::Version;
+use pretty_assertions::assert_eq;
+
+use gleam_core::{
+    analyse::TargetSupport,
+    build::{Codegen, Compile, Mode, Options, Target},
+    manifest::{Manifest, ManifestPackage, ManifestPackageSource},
+    paths::ProjectPaths,
+    warning::NullWarningEmitterIO,
+};
+
+fn write(path: Utf8PathBuf, content: &str) {
+    crate::fs::write(&path, content).expect("Failed to write file");
+}
+
+fn options() -> Options {
+    Options {
+        warnings_as_errors: false,
+        warning_levels: HashMap::new(),
+        compile: Compile::All,
+        codegen: Codegen::None,
+        mode: Mode::Dev,
+        target: Some(Target::JavaScript),
+        root_target_support: TargetSupport::Enforced,
+        no_print_progress: true,
+        tooling: false,
+    }
+}
+
+fn root_package_names(paths: &ProjectPaths, manifest: Manifest) -> Vec<String> {
+    super::all_at(paths, options(), manifest, Rc::new(NullWarningEmitterIO))
+        .expect("Failed to compile")
+        .into_iter()
+        .map(|built| built.root_package.config.name.to_string())
+        .collect()
+}
+
+/// A workspace with an `app` member using a `lib` member by path.
+///
+fn write_workspace(root: &Utf8Path) -> Manifest {
+    write(
+        root.join("gleam.toml"),
+        "[workspace]\nmembers = [\"packages/app\", \"packages/lib\"]\n",
+    );
+    write(
+        root.join("packages/app/gleam.toml"),
+        "name = \"app\"\nversion = \"1.0.0\"\n\n[dependencies]\nlib = { path = \"../lib\" }\n",
+    );
+    write(
+        root.join("packages/app/src/app.gleam"),
+        "import lib\n\npub fn main() {\n  lib.hello()\n}\n",
+    );
+    write(
+        root.join("packages/lib/gleam.toml"),
+        "name = \"lib\"\nversion = \"1.0.0\"\n",
+    );
+    write(
+        root.join("packages/lib/src/lib.gleam"),
+        "pub fn hello() {\n  \"Hello\"\n}\n",
+    );
+
+    Manifest {
+        requirements: HashMap::new(),
+        packages: vec![ManifestPackage {
+            name: "lib".into(),
+            version: Version::new(1, 0, 0),
+            build_tools: vec!["gleam".into()],
+            otp_app: None,
+            requirements: vec![],
+            source: ManifestPackageSource::Local {
+                path: root.join("packages/lib"),
+            },
+        }],
+    }
+}
+
+#[test]
+fn all_compiles_each_member_of_a_workspace() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create a temp directory");
+    let root = Utf8PathBuf::from_path_buf(temp_dir.path().to_path_buf())
+        .expect("Path should be valid UTF-8");
+    let manifest = write_workspace(&root);
+
+    let paths = ProjectPaths::in_workspace(root.clone(), root.clone());
+    assert_eq!(root_package_names(&paths, manifest), vec!["app", "lib"]);
+    assert!(root.join("build/dev/javascript/lib").is_dir());
+    assert!(!root.join("packages/app/build").exists());
+}
+
+#[test]
+fn all_compiles_only_the_member_it_is_run_in() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create a temp directory");
+    let root = Utf8PathBuf::from_path_buf(temp_dir.path().to_path_buf())
+        .expect("Path should be valid UTF-8");
+    let manifest = write_workspace(&root);
+
+    let paths = ProjectPaths::in_workspace(root.join("packages/app"), root.clone());
+    assert_eq!(root_package_names(&paths, manifest), vec!["app"]);
+    assert!(root.join("build/dev/javascript/app").is_dir());
+}
+
+#[test]
+fn all_compiles_a_project_outside_of_a_workspace() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create a temp directory");
+    let root = Utf8PathBuf::from_path_buf(temp_dir.path().to_path_buf())
+        .expect("Path should be valid UTF-8");
+    write(
+        root.join("gleam.toml"),
+        "name = \"wibble\"\nversion = \"1.0.0\"\n",
+    );
+    write(root.join("src/wibble.gleam"), "pub fn main() {\n  Nil\n}\n");
+
+    let manifest = Manifest {
+        requirements: HashMap::new(),
+        packages: vec![],
+    };
+    assert_eq!(
+        root_package_names(&ProjectPaths::new(root), manifest),
+        vec!["wibble"]
+    );
+}
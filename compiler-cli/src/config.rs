@@ -5,6 +5,7 @@ use gleam_core::{
     error::{Error, FileIoAction, FileKind},
     manifest::{Manifest, ManifestPackage, ManifestPackageSource},
     paths::ProjectPaths,
+    workspace::WorkspaceConfig,
 };
 
 use crate::fs::{get_current_directory, get_project_root};
@@ -52,7 +53,7 @@ pub fn find_package_config_for_module(
 
 fn package_root(package: &ManifestPackage, project_paths: &ProjectPaths) -> Utf8PathBuf {
     match &package.source {
-        ManifestPackageSource::Local { path } => project_paths.root().join(path),
+        ManifestPackageSource::Local { path } => project_paths.manifest_directory().join(path),
 
         ManifestPackageSource::Hex { .. } | ManifestPackageSource::Git { .. } => {
             project_paths.build_packages_package(&package.name)
@@ -62,6 +63,11 @@ fn package_root(package: &ManifestPackage, project_paths: &ProjectPaths) -> Utf8
 
 pub fn read(config_path: Utf8PathBuf) -> Result<PackageConfig, Error> {
     let toml = crate::fs::read(&config_path)?;
+    if let Ok(Some(_)) = WorkspaceConfig::parse(&toml) {
+        return Err(Error::WorkspaceRootIsNotAPackage {
+            path: config_path.parent().unwrap_or(&config_path).to_path_buf(),
+        });
+    }
     let config: PackageConfig = toml::from_str(&toml).map_err(|e| Error::FileIo {
         action: FileIoAction::Parse,
         kind: FileKind::File,
@@ -24,6 +24,8 @@ use itertools::Itertools;
 use same_file::is_same_file;
 use strum::IntoEnumIterator;
 
+pub mod audit;
+pub mod licences;
 #[cfg(test)]
 mod tests;
 
@@ -32,6 +34,7 @@ use crate::{
     cli,
     fs::{self, ProjectIO},
     http::HttpClient,
+    workspace::Workspace,
     TreeOptions,
 };
 
@@ -78,9 +81,8 @@ pub fn tree(options: TreeOptions) -> Result<()> {
 
 fn get_manifest_details() -> Result<(Utf8PathBuf, PackageConfig, Manifest)> {
     let runtime = tokio::runtime::Runtime::new().expect("Unable to start Tokio async runtime");
-    let project = fs::get_project_root(fs::get_current_directory()?)?;
-    let paths = ProjectPaths::new(project.clone());
-    let config = crate::config::root_config()?;
+    let (paths, config) = resolution_root(&crate::find_project_paths()?)?;
+    let project = paths.root().to_path_buf();
     let (_, manifest) = get_manifest(
         &paths,
         runtime.handle().clone(),
@@ -233,11 +235,13 @@ pub fn cleanup<Telem: Telemetry>(paths: &ProjectPaths, telemetry: Telem) -> Resu
     // build directory if there is no gleam.toml
     crate::config::ensure_config_exists(paths)?;
 
+    // Read the project config
+    let (paths, config) = resolution_root(paths)?;
+    let paths = &paths;
+
     let lock = BuildLock::new_packages(paths)?;
     let _guard = lock.lock(&telemetry);
 
-    // Read the project config
-    let config = crate::config::read(paths.root_config())?;
     let mut manifest = read_manifest_from_disc(paths)?;
 
     remove_extra_requirements(&config, &mut manifest)?;
@@ -364,15 +368,16 @@ pub fn download<Telem: Telemetry>(
     // build directory if there is no gleam.toml
     crate::config::ensure_config_exists(paths)?;
 
+    // Read the project config
+    let (paths, mut config) = resolution_root(paths)?;
+    let paths = &paths;
+    let project_name = config.name.clone();
+
     let lock = BuildLock::new_packages(paths)?;
     let _guard = lock.lock(&telemetry);
 
     let fs = ProjectIO::boxed();
 
-    // Read the project config
-    let mut config = crate::config::read(paths.root_config())?;
-    let project_name = config.name.clone();
-
     // Insert the new packages to add, if it exists
     if let Some((packages, dev)) = new_package {
         for (package, requirement) in packages {
@@ -420,9 +425,29 @@ pub fn download<Telem: Telemetry>(
     }
     LocalPackages::from_manifest(&manifest).write_to_disc(paths)?;
 
+    // The licences of the packages are cached now that their tarballs are
+    // in the global package cache, so that they can be checked offline.
+    let mut licences = licences::LicenceCache::read_from_disc(paths);
+    licences.update(&manifest);
+    licences.write_to_disc(paths)?;
+
     Ok(manifest)
 }
 
+/// The paths and config the dependencies are resolved for. The members of a
+/// workspace have their dependencies resolved together, at its root.
+///
+fn resolution_root(paths: &ProjectPaths) -> Result<(ProjectPaths, PackageConfig)> {
+    match paths.workspace_root() {
+        Some(root) => {
+            let workspace = Workspace::read(root)?;
+            let config = workspace.package_config()?;
+            Ok((ProjectPaths::new(root.to_path_buf()), config))
+        }
+        None => Ok((paths.clone(), crate::config::read(paths.root_config())?)),
+    }
+}
+
 async fn add_missing_packages<Telem: Telemetry>(
     paths: &ProjectPaths,
     fs: Box<ProjectIO>,
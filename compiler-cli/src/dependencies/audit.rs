@@ -0,0 +1,154 @@
+//! Checks of the dependency packages for retired Hex releases and security
+//! advisories, as run by `gleam deps audit`.
+
+use std::collections::HashMap;
+
+use camino::Utf8Path;
+use ecow::EcoString;
+use futures::future;
+use gleam_core::{
+    audit::{self, AdvisoryDatabase, Finding, Severity},
+    error::StandardIoAction,
+    hex,
+    manifest::{Manifest, ManifestPackageSource},
+    Error, Result,
+};
+use itertools::Itertools;
+
+use crate::{cli, dependencies::UseManifest, http::HttpClient};
+
+pub fn run(advisories: Option<&Utf8Path>, fail_on: Severity, json: bool) -> Result<()> {
+    let paths = crate::find_project_paths()?;
+    let manifest = super::download(
+        &paths,
+        cli::Reporter::new(),
+        None,
+        Vec::new(),
+        UseManifest::Yes,
+    )?;
+    let database = match advisories {
+        Some(path) => read_advisories(path)?,
+        None => AdvisoryDatabase::default(),
+    };
+
+    let runtime = tokio::runtime::Runtime::new().expect("Unable to start Tokio async runtime");
+    let retirements = runtime.block_on(retirements(&manifest))?;
+    let findings = findings(&manifest, &retirements, &database);
+
+    if json {
+        write_json(std::io::stdout(), &findings)?;
+    } else {
+        write_report(std::io::stdout(), &findings)?;
+    }
+    check(&findings, fail_on)
+}
+
+fn read_advisories(path: &Utf8Path) -> Result<AdvisoryDatabase> {
+    let json = crate::fs::read(path)?;
+    AdvisoryDatabase::parse(&json).map_err(|error| Error::InvalidAdvisoryDatabase {
+        path: path.to_path_buf(),
+        error: error.to_string(),
+    })
+}
+
+/// The retirement status of the Hex releases of the manifest that have been
+/// retired, by package name.
+///
+async fn retirements(manifest: &Manifest) -> Result<HashMap<EcoString, hexpm::RetirementStatus>> {
+    let config = hexpm::Config::new();
+    let http = HttpClient::new();
+    let lookups = manifest
+        .packages
+        .iter()
+        .filter(|package| matches!(package.source, ManifestPackageSource::Hex { .. }))
+        .map(|package| async {
+            hex::get_package_release(&package.name, &package.version, &config, &http)
+                .await
+                .map(|release| (package.name.clone(), release.retirement_status))
+        });
+    Ok(future::try_join_all(lookups)
+        .await?
+        .into_iter()
+        .filter_map(|(name, status)| Some((name, status?)))
+        .collect())
+}
+
+/// The problems with the packages of the manifest, sorted by package name and
+/// then from the most severe.
+///
+pub(crate) fn findings(
+    manifest: &Manifest,
+    retirements: &HashMap<EcoString, hexpm::RetirementStatus>,
+    database: &AdvisoryDatabase,
+) -> Vec<Finding> {
+    manifest
+        .packages
+        .iter()
+        .sorted_by(|a, b| a.name.cmp(&b.name))
+        .flat_map(|package| {
+            let retirement = retirements
+                .get(&package.name)
+                .map(|status| audit::retirement_finding(package, status));
+            retirement
+                .into_iter()
+                .chain(database.findings(package))
+                .sorted_by(|a, b| b.severity.cmp(&a.severity))
+        })
+        .collect()
+}
+
+/// Returns an error if any of the packages has a problem of at least the
+/// given severity.
+///
+pub(crate) fn check(findings: &[Finding], fail_on: Severity) -> Result<()> {
+    let packages = findings
+        .iter()
+        .filter(|finding| finding.severity >= fail_on)
+        .into_group_map_by(|finding| finding.package.clone())
+        .into_iter()
+        .filter_map(|(package, findings)| {
+            let severity = findings.iter().map(|finding| finding.severity).max()?;
+            Some((package, severity))
+        })
+        .sorted()
+        .collect_vec();
+    if packages.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::DependencyAuditFailed {
+            packages,
+            threshold: fail_on,
+        })
+    }
+}
+
+pub(crate) fn write_report<W: std::io::Write>(mut buffer: W, findings: &[Finding]) -> Result<()> {
+    let result = if findings.is_empty() {
+        writeln!(buffer, "No problems found")
+    } else {
+        findings.iter().try_for_each(|finding| {
+            writeln!(
+                buffer,
+                "{} {}: {} ({})",
+                finding.package, finding.version, finding.reason, finding.severity
+            )?;
+            if finding.message.is_empty() {
+                Ok(())
+            } else {
+                writeln!(buffer, "  {}", finding.message)
+            }
+        })
+    };
+    result.map_err(|e| Error::StandardIo {
+        action: StandardIoAction::Write,
+        err: Some(e.kind()),
+    })
+}
+
+pub(crate) fn write_json<W: std::io::Write>(mut buffer: W, findings: &[Finding]) -> Result<()> {
+    let json = serde_json::to_string_pretty(findings).expect("findings to JSON");
+    writeln!(buffer, "{json}").map_err(|e| Error::StandardIo {
+        action: StandardIoAction::Write,
+        err: Some(e.kind()),
+    })
+}
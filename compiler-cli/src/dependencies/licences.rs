@@ -0,0 +1,335 @@
+//! The licences of the dependency packages, as listed by `gleam deps licences`
+//! and checked against the `[licence_policy]` of the project when they are
+//! downloaded.
+//!
+//! The licences of Hex packages are in the metadata of their tarball, which
+//! isn't extracted to the build directory, so they are read when the packages
+//! are downloaded and cached in `build/packages/licences.toml`.
+
+mod metadata_config;
+
+use std::{
+    collections::{HashMap, HashSet},
+    io::Read,
+};
+
+use camino::Utf8Path;
+use ecow::EcoString;
+use gleam_core::{
+    config::PackageConfig,
+    error::StandardIoAction,
+    manifest::{Manifest, ManifestPackage, ManifestPackageSource},
+    paths::{self, ProjectPaths},
+    workspace, Error, Result,
+};
+use hexpm::version::Version;
+use itertools::Itertools;
+
+use crate::{cli, dependencies::UseManifest, fs, workspace::Workspace};
+use metadata_config::Term;
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct PackageLicences {
+    pub name: EcoString,
+    pub version: String,
+    /// Empty when the licences of the package are not known.
+    pub licences: Vec<EcoString>,
+}
+
+pub fn run(json: bool) -> Result<()> {
+    let paths = crate::find_project_paths()?;
+    // The policy is not checked here so that the packages breaking it can be
+    // listed.
+    let manifest = super::download(
+        &paths,
+        cli::Reporter::new(),
+        None,
+        Vec::new(),
+        UseManifest::Yes,
+    )?;
+    let packages = package_licences(&paths, &manifest);
+    if json {
+        write_json(std::io::stdout(), &packages)
+    } else {
+        write_table(std::io::stdout(), &packages)
+    }
+}
+
+/// Returns an error if any of the dependency packages is not allowed by the
+/// licence policy of the project.
+///
+pub fn check_policy(paths: &ProjectPaths, manifest: &Manifest) -> Result<()> {
+    // At the root of a workspace the policy of each member is checked.
+    match Workspace::at_root(paths)? {
+        Some(workspace) => workspace
+            .members
+            .iter()
+            .try_for_each(|member| check_package_policy(paths, &member.config, manifest)),
+        None => {
+            let config = crate::config::read(paths.root_config())?;
+            check_package_policy(paths, &config, manifest)
+        }
+    }
+}
+
+fn check_package_policy(
+    paths: &ProjectPaths,
+    config: &PackageConfig,
+    manifest: &Manifest,
+) -> Result<()> {
+    let policy = &config.licence_policy;
+    if policy.is_empty() {
+        return Ok(());
+    }
+
+    // The manifest of a workspace has the packages of all its members, so
+    // only the ones this member uses are checked.
+    let used = paths.workspace_root().map(|_| {
+        workspace::member_packages(&manifest.packages, config)
+            .into_iter()
+            .map(|package| package.name)
+            .collect::<HashSet<_>>()
+    });
+    let packages = package_licences(paths, manifest)
+        .into_iter()
+        .filter(|package| match &used {
+            Some(used) => used.contains(&package.name),
+            None => true,
+        })
+        .filter(|package| !policy.permits(&package.licences))
+        .map(|package| (package.name, package.licences))
+        .collect_vec();
+    if packages.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::LicencePolicyViolation { packages })
+    }
+}
+
+/// The licences of the Hex packages, as read from their tarball when they
+/// were downloaded.
+///
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub(crate) struct LicenceCache {
+    packages: HashMap<EcoString, CachedLicences>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+struct CachedLicences {
+    version: Version,
+    licences: Vec<EcoString>,
+}
+
+impl LicenceCache {
+    /// Reads the cache, which is empty if it has not been written yet or
+    /// can't be read.
+    ///
+    pub fn read_from_disc(paths: &ProjectPaths) -> Self {
+        let path = paths.build_packages_licences_toml();
+        if !path.exists() {
+            return Self::default();
+        }
+        match fs::read(&path).map(|toml| toml::from_str(&toml)) {
+            Ok(Ok(cache)) => cache,
+            Ok(Err(error)) => {
+                tracing::debug!(error = %error, "invalid_licences_toml");
+                Self::default()
+            }
+            Err(error) => {
+                tracing::debug!(error = %error, "unreadable_licences_toml");
+                Self::default()
+            }
+        }
+    }
+
+    pub fn write_to_disc(&self, paths: &ProjectPaths) -> Result<()> {
+        let path = paths.build_packages_licences_toml();
+        let toml = toml::to_string(&self).expect("licences.toml serialization");
+        fs::write(&path, &toml)
+    }
+
+    /// Caches the licences of the Hex packages of the manifest that are not
+    /// cached for their locked version yet, reading them from their tarball
+    /// in the global package cache. Packages no longer in the manifest are
+    /// removed from the cache.
+    ///
+    pub fn update(&mut self, manifest: &Manifest) {
+        let mut packages = HashMap::new();
+        for package in &manifest.packages {
+            let ManifestPackageSource::Hex { .. } = &package.source else {
+                continue;
+            };
+            let cached = self
+                .packages
+                .remove(&package.name)
+                .filter(|cached| cached.version == package.version)
+                .or_else(|| {
+                    let licences = hex_licences(&package.name, &package.version.to_string())?;
+                    Some(CachedLicences {
+                        version: package.version.clone(),
+                        licences,
+                    })
+                });
+            if let Some(cached) = cached {
+                let _ = packages.insert(package.name.clone(), cached);
+            }
+        }
+        self.packages = packages;
+    }
+
+    /// The cached licences of a Hex package, if they are for the version it
+    /// is locked to.
+    ///
+    pub fn licences(&self, package: &ManifestPackage) -> Option<Vec<EcoString>> {
+        self.packages
+            .get(&package.name)
+            .filter(|cached| cached.version == package.version)
+            .map(|cached| cached.licences.clone())
+    }
+}
+
+pub(crate) fn package_licences(paths: &ProjectPaths, manifest: &Manifest) -> Vec<PackageLicences> {
+    let cache = LicenceCache::read_from_disc(paths);
+    manifest
+        .packages
+        .iter()
+        .sorted_by(|a, b| a.name.cmp(&b.name))
+        .map(|package| PackageLicences {
+            name: package.name.clone(),
+            version: package.version.to_string(),
+            licences: licences(paths, &cache, package),
+        })
+        .collect()
+}
+
+/// Not all Hex packages are Gleam packages, but they all have their licences
+/// in the metadata of their tarball, which are cached when they're downloaded.
+/// Other packages have them in their `gleam.toml`.
+///
+fn licences(
+    paths: &ProjectPaths,
+    cache: &LicenceCache,
+    package: &ManifestPackage,
+) -> Vec<EcoString> {
+    match &package.source {
+        ManifestPackageSource::Hex { .. } => cache.licences(package),
+        ManifestPackageSource::Git { .. } => {
+            gleam_toml_licences(&paths.build_packages_package(&package.name))
+        }
+        ManifestPackageSource::Local { path } => {
+            gleam_toml_licences(&paths.manifest_directory().join(path))
+        }
+    }
+    .unwrap_or_default()
+}
+
+fn hex_licences(name: &str, version: &str) -> Option<Vec<EcoString>> {
+    let tarball = paths::global_package_cache_package_tarball(name, version);
+    let mut archive = tar::Archive::new(std::fs::File::open(tarball).ok()?);
+    let mut entry = archive
+        .entries()
+        .ok()?
+        .filter_map(Result::ok)
+        .find(|entry| {
+            entry
+                .path()
+                .is_ok_and(|path| path.as_os_str() == "metadata.config")
+        })?;
+    let mut metadata = String::new();
+    let _ = entry.read_to_string(&mut metadata).ok()?;
+    Some(metadata_config_licences(&metadata))
+}
+
+fn gleam_toml_licences(directory: &Utf8Path) -> Option<Vec<EcoString>> {
+    let config = crate::config::read(directory.join("gleam.toml")).ok()?;
+    Some(
+        config
+            .licences
+            .iter()
+            .map(|licence| licence.as_ref().into())
+            .collect(),
+    )
+}
+
+/// The licences in the `metadata.config` file of a Hex package, which is made
+/// of Erlang terms such as `{<<"licenses">>, [<<"Apache-2.0">>]}.`
+///
+pub(crate) fn metadata_config_licences(metadata: &str) -> Vec<EcoString> {
+    let (terms, result) = metadata_config::parse(metadata);
+    if let Err(error) = result {
+        tracing::debug!(error = error, "invalid_metadata_config");
+    }
+    terms
+        .into_iter()
+        .find_map(|term| match term {
+            Term::Tuple(elements) => match elements.as_slice() {
+                [Term::Binary(key), Term::List(licences)] if key == "licenses" => Some(
+                    licences
+                        .iter()
+                        .filter_map(|licence| match licence {
+                            Term::Binary(licence) | Term::String(licence) => {
+                                Some(licence.as_str().into())
+                            }
+                            _ => None,
+                        })
+                        .collect(),
+                ),
+                _ => None,
+            },
+            _ => None,
+        })
+        .unwrap_or_default()
+}
+
+pub(crate) fn write_table<W: std::io::Write>(
+    mut buffer: W,
+    packages: &[PackageLicences],
+) -> Result<()> {
+    let rows = packages
+        .iter()
+        .map(|package| {
+            let licences = if package.licences.is_empty() {
+                "unknown".into()
+            } else {
+                package.licences.join(", ")
+            };
+            (package.name.as_str(), package.version.as_str(), licences)
+        })
+        .collect_vec();
+    let name_width = rows
+        .iter()
+        .map(|(name, _, _)| name.len())
+        .chain(["Package".len()])
+        .max()
+        .unwrap_or_default();
+    let version_width = rows
+        .iter()
+        .map(|(_, version, _)| version.len())
+        .chain(["Version".len()])
+        .max()
+        .unwrap_or_default();
+
+    std::iter::once(("Package", "Version", "Licences".to_string()))
+        .chain(rows)
+        .try_for_each(|(name, version, licences)| {
+            writeln!(
+                buffer,
+                "{name:name_width$}  {version:version_width$}  {licences}"
+            )
+        })
+        .map_err(|e| Error::StandardIo {
+            action: StandardIoAction::Write,
+            err: Some(e.kind()),
+        })
+}
+
+pub(crate) fn write_json<W: std::io::Write>(
+    mut buffer: W,
+    packages: &[PackageLicences],
+) -> Result<()> {
+    let json = serde_json::to_string_pretty(packages).expect("licences to JSON");
+    writeln!(buffer, "{json}").map_err(|e| Error::StandardIo {
+        action: StandardIoAction::Write,
+        err: Some(e.kind()),
+    })
+}
@@ -0,0 +1,203 @@
+//! A parser for the `metadata.config` file of Hex package tarballs, which is
+//! made of Erlang terms each followed by a full stop, such as
+//! `{<<"licenses">>, [<<"Apache-2.0">>]}.`
+//!
+//! Only the terms found in these files are supported: tuples, lists, maps,
+//! binaries, strings, atoms and numbers.
+
+use std::{iter::Peekable, str::Chars};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Term {
+    Tuple(Vec<Self>),
+    List(Vec<Self>),
+    Map(Vec<(Self, Self)>),
+    Binary(String),
+    String(String),
+    Atom(String),
+    Number(String),
+}
+
+/// Parses all the terms of the file. If one of them is invalid the ones
+/// before it are returned along with an error.
+///
+pub fn parse(src: &str) -> (Vec<Term>, Result<(), String>) {
+    let mut parser = Parser {
+        chars: src.chars().peekable(),
+    };
+    let mut terms = vec![];
+    loop {
+        parser.skip_whitespace();
+        if parser.chars.peek().is_none() {
+            return (terms, Ok(()));
+        }
+        match parser.term().and_then(|term| {
+            parser.expect('.')?;
+            Ok(term)
+        }) {
+            Ok(term) => terms.push(term),
+            Err(error) => return (terms, Err(error)),
+        }
+    }
+}
+
+struct Parser<'a> {
+    chars: Peekable<Chars<'a>>,
+}
+
+impl Parser<'_> {
+    fn skip_whitespace(&mut self) {
+        while let Some(char) = self.chars.peek() {
+            match char {
+                '%' => while self.chars.next_if(|char| *char != '\n').is_some() {},
+                char if char.is_whitespace() => _ = self.chars.next(),
+                _ => return,
+            }
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        self.skip_whitespace();
+        match self.chars.next() {
+            Some(char) if char == expected => Ok(()),
+            Some(char) => Err(format!("expected `{expected}`, got `{char}`")),
+            None => Err(format!("expected `{expected}`, got the end of the file")),
+        }
+    }
+
+    fn term(&mut self) -> Result<Term, String> {
+        self.skip_whitespace();
+        match self.chars.peek().copied() {
+            Some('{') => {
+                _ = self.chars.next();
+                self.sequence('}', Self::term).map(Term::Tuple)
+            }
+            Some('[') => {
+                _ = self.chars.next();
+                self.sequence(']', Self::term).map(Term::List)
+            }
+            Some('#') => {
+                _ = self.chars.next();
+                self.expect('{')?;
+                self.sequence('}', Self::association).map(Term::Map)
+            }
+            Some('<') => {
+                _ = self.chars.next();
+                self.expect('<')?;
+                self.binary().map(Term::Binary)
+            }
+            Some('"') => self.string('"').map(Term::String),
+            Some('\'') => self.string('\'').map(Term::Atom),
+            Some(char) if char.is_ascii_lowercase() => {
+                Ok(Term::Atom(self.take_while(|char| {
+                    char.is_alphanumeric() || char == '_' || char == '@'
+                })))
+            }
+            Some(char) if char.is_ascii_digit() || char == '-' => {
+                Ok(Term::Number(self.take_while(|char| {
+                    char.is_ascii_alphanumeric() || "-+.#_".contains(char)
+                })))
+            }
+            Some(char) => Err(format!("unexpected `{char}`")),
+            None => Err("unexpected end of the file".into()),
+        }
+    }
+
+    fn association(&mut self) -> Result<(Term, Term), String> {
+        let key = self.term()?;
+        self.expect('=')?;
+        self.expect('>')?;
+        Ok((key, self.term()?))
+    }
+
+    /// The comma separated elements of a tuple, list or map, after its opening
+    /// bracket.
+    fn sequence<T>(
+        &mut self,
+        close: char,
+        element: impl Fn(&mut Self) -> Result<T, String>,
+    ) -> Result<Vec<T>, String> {
+        let mut elements = vec![];
+        self.skip_whitespace();
+        if self.chars.next_if_eq(&close).is_some() {
+            return Ok(elements);
+        }
+        loop {
+            elements.push(element(self)?);
+            self.skip_whitespace();
+            match self.chars.next() {
+                Some(',') => (),
+                Some(char) if char == close => return Ok(elements),
+                Some(char) => return Err(format!("expected `,` or `{close}`, got `{char}`")),
+                None => return Err("unexpected end of the file".into()),
+            }
+        }
+    }
+
+    /// The segments of a binary, after its opening `<<`. Strings are
+    /// concatenated, a `/utf8` type is allowed after them.
+    fn binary(&mut self) -> Result<String, String> {
+        let mut binary = String::new();
+        self.skip_whitespace();
+        if self.chars.next_if_eq(&'>').is_some() {
+            self.expect('>')?;
+            return Ok(binary);
+        }
+        loop {
+            self.skip_whitespace();
+            match self.chars.peek() {
+                Some('"') => binary.push_str(&self.string('"')?),
+                Some(next) if next.is_ascii_digit() => {
+                    let byte = self.take_while(|char| char.is_ascii_digit());
+                    let byte = byte
+                        .parse::<u8>()
+                        .map_err(|_| format!("invalid binary segment `{byte}`"))?;
+                    binary.push(char::from(byte));
+                }
+                _ => return Err("expected a string or an integer in a binary".into()),
+            }
+            self.skip_whitespace();
+            if self.chars.next_if_eq(&'/').is_some() {
+                _ = self.take_while(|char| char.is_alphanumeric() || char == '-');
+                self.skip_whitespace();
+            }
+            match self.chars.next() {
+                Some(',') => (),
+                Some('>') => {
+                    self.expect('>')?;
+                    return Ok(binary);
+                }
+                Some(char) => return Err(format!("expected `,` or `>>`, got `{char}`")),
+                None => return Err("unexpected end of the file".into()),
+            }
+        }
+    }
+
+    fn string(&mut self, quote: char) -> Result<String, String> {
+        _ = self.chars.next();
+        let mut string = String::new();
+        loop {
+            match self.chars.next() {
+                Some('\\') => match self.chars.next() {
+                    Some('n') => string.push('\n'),
+                    Some('t') => string.push('\t'),
+                    Some('r') => string.push('\r'),
+                    Some('s') => string.push(' '),
+                    Some(char) => string.push(char),
+                    None => return Err("unterminated string".into()),
+                },
+                Some(char) if char == quote => return Ok(string),
+                Some(char) => string.push(char),
+                None => return Err("unterminated string".into()),
+            }
+        }
+    }
+
+    fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> String {
+        let mut taken = String::new();
+        while let Some(char) = self.chars.next_if(|char| predicate(*char)) {
+            taken.push(char);
+        }
+        taken
+    }
+}
@@ -1205,6 +1205,8 @@ fn package_config(
         },
         target: Target::Erlang,
         internal_modules: None,
+        warnings: HashMap::new(),
+        licence_policy: Default::default(),
     }
 }
 
@@ -1327,3 +1329,377 @@ fn test_remove_package_that_is_also_a_transitive_dependency() {
     assert_eq!(manifest.requirements, config.dependencies);
     assert_eq!(manifest.packages, manifest_copy.packages);
 }
+
+#[test]
+fn metadata_config_licences() {
+    let metadata = r#"{<<"name">>, <<"wibble">>}.
+{<<"app">>, <<"wibble">>}.
+{<<"version">>, <<"1.0.0">>}.
+{<<"description">>, <<"A package"/utf8>>}.
+{<<"licenses">>, [<<"Apache-2.0">>, <<"MIT">>]}.
+{<<"build_tools">>, [<<"gleam">>]}.
+"#;
+    assert_eq!(
+        licences::metadata_config_licences(metadata),
+        vec![EcoString::from("Apache-2.0"), EcoString::from("MIT")]
+    );
+}
+
+#[test]
+fn metadata_config_without_licences() {
+    let metadata = r#"{<<"name">>, <<"wibble">>}.
+{<<"licenses">>, []}.
+{<<"build_tools">>, [<<"gleam">>]}.
+"#;
+    assert_eq!(
+        licences::metadata_config_licences(metadata),
+        Vec::<EcoString>::new()
+    );
+    assert_eq!(
+        licences::metadata_config_licences("{<<\"name\">>, <<\"wibble\">>}."),
+        Vec::<EcoString>::new()
+    );
+}
+
+#[test]
+fn metadata_config_licences_mentioned_before_them() {
+    let metadata = r#"%% The licenses are below.
+{<<"description">>, <<"Not {<<\"licenses\">>, [<<\"MIT\">>]}"/utf8>>}.
+{<<"requirements">>, #{<<"cowlib">> => #{<<"app">> => <<"cowlib">>, <<"optional">> => false}}}.
+{<<"links">>, [{<<"GitHub">>, <<"https://github.com/wibble/wobble">>}]}.
+{<<"licenses">>, [<<"ISC">>]}.
+"#;
+    assert_eq!(
+        licences::metadata_config_licences(metadata),
+        vec![EcoString::from("ISC")]
+    );
+}
+
+#[test]
+fn metadata_config_licences_before_an_invalid_term() {
+    let metadata = r#"{<<"licenses">>, [<<"Apache-2.0">>]}.
+{<<"wibble">>, <<"wobble">>
+"#;
+    assert_eq!(
+        licences::metadata_config_licences(metadata),
+        vec![EcoString::from("Apache-2.0")]
+    );
+}
+
+fn package_licences() -> Vec<licences::PackageLicences> {
+    vec![
+        licences::PackageLicences {
+            name: "gleam_stdlib".into(),
+            version: "0.40.0".into(),
+            licences: vec!["Apache-2.0".into()],
+        },
+        licences::PackageLicences {
+            name: "wibble".into(),
+            version: "1.10.2".into(),
+            licences: vec!["MIT".into(), "GPL-3.0-only".into()],
+        },
+        licences::PackageLicences {
+            name: "wobble".into(),
+            version: "2.0.0".into(),
+            licences: vec![],
+        },
+    ]
+}
+
+#[test]
+fn licences_table_format() {
+    let mut buffer = vec![];
+    licences::write_table(&mut buffer, &package_licences()).unwrap();
+    assert_eq!(
+        std::str::from_utf8(&buffer).unwrap(),
+        r#"Package       Version  Licences
+gleam_stdlib  0.40.0   Apache-2.0
+wibble        1.10.2   MIT, GPL-3.0-only
+wobble        2.0.0    unknown
+"#
+    )
+}
+
+#[test]
+fn licences_json_format() {
+    let mut buffer = vec![];
+    let packages = package_licences().into_iter().take(1).collect::<Vec<_>>();
+    licences::write_json(&mut buffer, &packages).unwrap();
+    assert_eq!(
+        std::str::from_utf8(&buffer).unwrap(),
+        r#"[
+  {
+    "name": "gleam_stdlib",
+    "version": "0.40.0",
+    "licences": [
+      "Apache-2.0"
+    ]
+  }
+]
+"#
+    )
+}
+
+#[test]
+fn licences_of_hex_packages_are_read_from_the_cache() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create a temp directory");
+    let root = Utf8PathBuf::from_path_buf(temp_dir.path().to_path_buf())
+        .expect("Path should be valid UTF-8");
+    let paths = ProjectPaths::new(root);
+    fs::write(
+        &paths.build_packages_licences_toml(),
+        r#"[packages.wibble]
+version = "1.0.0"
+licences = ["MIT"]
+
+[packages.wobble]
+version = "0.2.0"
+licences = ["ISC"]
+
+[packages.wubble]
+version = "1.0.0"
+licences = ["ISC"]
+"#,
+    )
+    .expect("Failed to write file");
+    let package = |name: &str, version| ManifestPackage {
+        name: name.into(),
+        version: Version::parse(version).expect("Invalid version"),
+        build_tools: ["gleam".into()].into(),
+        otp_app: None,
+        requirements: vec![],
+        source: ManifestPackageSource::Hex {
+            outer_checksum: Base16Checksum(vec![]),
+        },
+    };
+    let manifest = Manifest {
+        requirements: HashMap::new(),
+        packages: vec![package("wibble", "1.0.0"), package("wobble", "0.3.0")],
+    };
+
+    // The licences cached for another version than the locked one are not
+    // used.
+    let expected = vec![
+        licences::PackageLicences {
+            name: "wibble".into(),
+            version: "1.0.0".into(),
+            licences: vec!["MIT".into()],
+        },
+        licences::PackageLicences {
+            name: "wobble".into(),
+            version: "0.3.0".into(),
+            licences: vec![],
+        },
+    ];
+    assert_eq!(licences::package_licences(&paths, &manifest), expected);
+
+    // Updating the cache drops the packages and versions no longer locked.
+    let mut cache = licences::LicenceCache::read_from_disc(&paths);
+    cache.update(&manifest);
+    cache
+        .write_to_disc(&paths)
+        .expect("Failed to write the cache");
+    let toml = fs::read(paths.build_packages_licences_toml()).expect("Failed to read file");
+    assert!(!toml.contains("wobble"));
+    assert!(!toml.contains("wubble"));
+    assert_eq!(licences::package_licences(&paths, &manifest), expected);
+}
+
+fn audit_manifest() -> Manifest {
+    let package = |name: &str, version| ManifestPackage {
+        name: name.into(),
+        version: Version::parse(version).unwrap(),
+        build_tools: ["gleam".into()].into(),
+        otp_app: None,
+        requirements: vec![],
+        source: ManifestPackageSource::Hex {
+            outer_checksum: Base16Checksum(vec![]),
+        },
+    };
+    Manifest {
+        requirements: HashMap::new(),
+        packages: vec![
+            package("wobble", "0.3.0"),
+            package("wibble", "1.0.0"),
+            package("gleam_stdlib", "0.40.0"),
+        ],
+    }
+}
+
+fn audit_findings() -> Vec<gleam_core::audit::Finding> {
+    let database = gleam_core::audit::AdvisoryDatabase::parse(
+        r#"{
+  "id": "GHSA-0001",
+  "summary": "Wibble can be made to crash",
+  "affected": [
+    {
+      "package": { "ecosystem": "Hex", "name": "wibble" },
+      "ranges": [
+        { "type": "SEMVER", "events": [{ "introduced": "0" }, { "fixed": "1.2.0" }] }
+      ]
+    }
+  ],
+  "database_specific": { "severity": "moderate" }
+}"#,
+    )
+    .unwrap();
+    let retirements = HashMap::from([
+        (
+            "wibble".into(),
+            hexpm::RetirementStatus {
+                reason: hexpm::RetirementReason::Security,
+                message: "Upgrade to 1.2.0".into(),
+            },
+        ),
+        (
+            "wobble".into(),
+            hexpm::RetirementStatus {
+                reason: hexpm::RetirementReason::Renamed,
+                message: "".into(),
+            },
+        ),
+    ]);
+    audit::findings(&audit_manifest(), &retirements, &database)
+}
+
+#[test]
+fn audit_report_format() {
+    let mut buffer = vec![];
+    audit::write_report(&mut buffer, &audit_findings()).unwrap();
+    assert_eq!(
+        std::str::from_utf8(&buffer).unwrap(),
+        r#"wibble 1.0.0: retired (security) (high)
+  Upgrade to 1.2.0
+wibble 1.0.0: GHSA-0001 (moderate)
+  Wibble can be made to crash
+wobble 0.3.0: retired (renamed) (low)
+"#
+    )
+}
+
+#[test]
+fn audit_report_without_findings() {
+    let mut buffer = vec![];
+    audit::write_report(&mut buffer, &[]).unwrap();
+    assert_eq!(std::str::from_utf8(&buffer).unwrap(), "No problems found\n")
+}
+
+#[test]
+fn audit_json_format() {
+    let mut buffer = vec![];
+    let findings = audit_findings().into_iter().take(1).collect::<Vec<_>>();
+    audit::write_json(&mut buffer, &findings).unwrap();
+    assert_eq!(
+        std::str::from_utf8(&buffer).unwrap(),
+        r#"[
+  {
+    "package": "wibble",
+    "version": "1.0.0",
+    "severity": "high",
+    "reason": "retired (security)",
+    "message": "Upgrade to 1.2.0"
+  }
+]
+"#
+    )
+}
+
+#[test]
+fn audit_fails_on_severity_threshold() {
+    use gleam_core::audit::Severity;
+
+    let findings = audit_findings();
+    assert_eq!(
+        audit::check(&findings, Severity::Low),
+        Err(Error::DependencyAuditFailed {
+            packages: vec![
+                ("wibble".into(), Severity::High),
+                ("wobble".into(), Severity::Low)
+            ],
+            threshold: Severity::Low,
+        })
+    );
+    assert_eq!(
+        audit::check(&findings, Severity::High),
+        Err(Error::DependencyAuditFailed {
+            packages: vec![("wibble".into(), Severity::High)],
+            threshold: Severity::High,
+        })
+    );
+    assert_eq!(audit::check(&findings, Severity::Critical), Ok(()));
+}
+
+fn write_workspace_member(root: &Utf8Path, name: &str, toml: &str) {
+    let path = root.join(format!("packages/{name}/gleam.toml"));
+    fs::write(
+        &path,
+        &format!("name = \"{name}\"\nversion = \"1.0.0\"\n{toml}"),
+    )
+    .expect("Failed to write file");
+}
+
+#[test]
+fn resolution_root_of_a_workspace_member_is_the_workspace() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create a temp directory");
+    let root = Utf8PathBuf::from_path_buf(temp_dir.path().to_path_buf())
+        .expect("Path should be valid UTF-8");
+    fs::write(
+        &root.join("gleam.toml"),
+        "[workspace]\nmembers = [\"packages/app\", \"packages/lib\"]\n",
+    )
+    .expect("Failed to write file");
+    write_workspace_member(
+        &root,
+        "app",
+        "\n[dependencies]\nlib = { path = \"../lib\" }\nwibble = \">= 1.0.0\"\n",
+    );
+    write_workspace_member(&root, "lib", "");
+
+    let member = ProjectPaths::in_workspace(root.join("packages/app"), root.clone());
+    let (paths, config) = resolution_root(&member).expect("Failed to find the resolution root");
+
+    assert_eq!(paths.root(), root);
+    assert!(paths.workspace_root().is_none());
+    assert_eq!(config.name, gleam_core::workspace::WORKSPACE_PACKAGE_NAME);
+    assert_eq!(
+        config.dependencies,
+        [
+            (
+                "app".into(),
+                Requirement::Path {
+                    path: "packages/app".into()
+                }
+            ),
+            (
+                "lib".into(),
+                Requirement::Path {
+                    path: "packages/lib".into()
+                }
+            ),
+            ("wibble".into(), Requirement::hex(">= 1.0.0")),
+        ]
+        .into()
+    );
+}
+
+#[test]
+fn resolution_root_outside_of_a_workspace_is_the_project() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create a temp directory");
+    let root = Utf8PathBuf::from_path_buf(temp_dir.path().to_path_buf())
+        .expect("Path should be valid UTF-8");
+    fs::write(
+        &root.join("gleam.toml"),
+        "name = \"wibble\"\nversion = \"1.0.0\"\n\n[dependencies]\nwobble = \">= 1.0.0\"\n",
+    )
+    .expect("Failed to write file");
+
+    let project = ProjectPaths::new(root.clone());
+    let (paths, config) = resolution_root(&project).expect("Failed to find the resolution root");
+
+    assert_eq!(paths.root(), root);
+    assert_eq!(config.name, "wibble");
+    assert_eq!(
+        config.dependencies,
+        [("wobble".into(), Requirement::hex(">= 1.0.0"))].into()
+    );
+}
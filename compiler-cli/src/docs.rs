@@ -1,4 +1,7 @@
-use std::time::{Instant, SystemTime};
+use std::{
+    collections::HashMap,
+    time::{Instant, SystemTime},
+};
 
 use camino::{Utf8Path, Utf8PathBuf};
 
@@ -54,8 +57,10 @@ pub fn build(options: BuildOptions) -> Result<()> {
             codegen: Codegen::All,
             compile: Compile::All,
             warnings_as_errors: false,
+            warning_levels: HashMap::new(),
             root_target_support: TargetSupport::Enforced,
             no_print_progress: false,
+            tooling: false,
         },
         crate::build::download_dependencies(cli::Reporter::new())?,
     )?;
@@ -141,11 +146,13 @@ pub fn publish() -> Result<()> {
         Options {
             root_target_support: TargetSupport::Enforced,
             warnings_as_errors: false,
+            warning_levels: HashMap::new(),
             codegen: Codegen::All,
             compile: Compile::All,
             mode: Mode::Prod,
             target: None,
             no_print_progress: false,
+            tooling: false,
         },
         crate::build::download_dependencies(cli::Reporter::new())?,
     )?;
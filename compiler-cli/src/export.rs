@@ -1,10 +1,20 @@
-use camino::Utf8PathBuf;
+use std::{collections::HashMap, time::SystemTime};
+
+use camino::{Utf8Path, Utf8PathBuf};
 use gleam_core::{
     analyse::TargetSupport,
-    build::{Codegen, Compile, Mode, Options, Target},
+    build::{Built, Codegen, Compile, Mode, Options, Target},
+    error::Error,
+    io::{CommandExecutor, Stdio},
+    sbom::{self, Sbom, SbomFormat},
     Result,
 };
 
+use crate::fs::ProjectIO;
+
+#[cfg(test)]
+mod tests;
+
 #[cfg(target_os = "windows")]
 static ENTRYPOINT_FILENAME: &str = "entrypoint.ps1";
 #[cfg(not(target_os = "windows"))]
@@ -39,19 +49,7 @@ pub(crate) fn erlang_shipment() -> Result<()> {
     crate::fs::delete_directory(&build)?;
     crate::fs::delete_directory(&out)?;
 
-    // Build project in production mode
-    let built = crate::build::main(
-        Options {
-            root_target_support: TargetSupport::Enforced,
-            warnings_as_errors: false,
-            codegen: Codegen::All,
-            compile: Compile::All,
-            mode,
-            target: Some(target),
-            no_print_progress: false,
-        },
-        crate::build::download_dependencies(crate::cli::Reporter::new())?,
-    )?;
+    let built = build_erlang_for_production()?;
 
     for entry in crate::fs::read_dir(&build)?.filter_map(Result::ok) {
         let path = entry.path();
@@ -100,6 +98,235 @@ the {ENTRYPOINT_FILENAME} script.
     Ok(())
 }
 
+static RELEASE_ESCRIPT: &str = include_str!("../templates/gleam@@release.erl");
+static RELEASE_START_SCRIPT_TEMPLATE: &str = include_str!("../templates/erlang-release-start.sh");
+
+static RELEASE_SYS_CONFIG_TEMPLATE: &str = r#"%% Configuration of the applications in the release, loaded when it boots.
+%% See https://www.erlang.org/doc/man/config.html
+[].
+"#;
+
+static RELEASE_VM_ARGS_TEMPLATE: &str = r#"## Flags given to the Erlang virtual machine when the release boots.
+## See https://www.erlang.org/doc/man/erl.html
+
+## The name of the node, needed for distribution and remote shells.
+## -sname my_node
+
+## The cookie shared by the nodes of a cluster.
+## -setcookie my_cookie
+"#;
+
+/// Generate an OTP release of the project, suitable for deployment to a server.
+///
+/// The release contains each Erlang application (aka package) the project
+/// needs, including the ones from OTP, along with a boot script that starts
+/// them in embedded mode. The `releases` directory follows the layout expected
+/// by the release handler, so later versions of the release can be installed
+/// as hot upgrades.
+///
+/// The previous release exported to the same directory is kept. When it has
+/// another version a relup is written to upgrade from it, with a stub appup
+/// restarting each application whose version changed.
+///
+/// The runtime configuration is taken from the project's `config/sys.config`
+/// and `config/vm.args` files, when present.
+///
+/// The start script is a POSIX shell script, so the release can't be started
+/// on Windows.
+pub(crate) fn erlang_release(include_erts: bool) -> Result<()> {
+    let paths = crate::find_project_paths()?;
+    let build = paths.build_directory_for_target(Mode::Prod, Target::Erlang);
+    let out = paths.erlang_release_directory();
+
+    // Reset the build directory to ensure we have a clean slate and no old
+    // code. The release directory is kept for the relup.
+    crate::fs::delete_directory(&build)?;
+    crate::fs::mkdir(&out)?;
+
+    let built = build_erlang_for_production()?;
+    let config = &built.root_package.config;
+    let name = config.name.as_str();
+    let version = config.version.to_string();
+    let up_from = previous_release_version(&out, name).filter(|previous| *previous != version);
+
+    // The release itself is assembled by Erlang, which knows how to find the
+    // OTP applications and to write the boot script.
+    let escript = build.join("gleam@@release.erl");
+    crate::fs::write(&escript, RELEASE_ESCRIPT)?;
+    let mut args = vec![
+        escript.to_string(),
+        crate::fs::canonicalise(&out)?.to_string(),
+        name.to_string(),
+        version.clone(),
+        name.to_string(),
+        include_erts.to_string(),
+        up_from.clone().unwrap_or_default(),
+    ];
+    for entry in crate::fs::read_dir(&build)?.filter_map(Result::ok) {
+        let path = entry.path();
+        if path.is_dir() {
+            args.push(crate::fs::canonicalise(path)?.to_string());
+        }
+    }
+    let status = ProjectIO::new().exec("escript", &args, &[], None, Stdio::Inherit)?;
+    if status != 0 {
+        return Err(Error::ShellCommand {
+            program: "escript".into(),
+            err: None,
+        });
+    }
+
+    let start_script = write_release_files(paths.root(), &out, name, &version)?;
+
+    crate::cli::print_exported(name);
+
+    if let Some(up_from) = up_from {
+        println!(
+            "
+A relup has been written to upgrade from version {up_from} of the release."
+        );
+    }
+
+    println!(
+        "
+Your Erlang release has been generated to {out}.
+
+It can be copied to a compatible server{erlang} and started in
+embedded mode with the {name} script.
+
+    {start_script} start
+",
+        erlang = if include_erts {
+            ""
+        } else {
+            " with Erlang installed"
+        },
+    );
+
+    Ok(())
+}
+
+/// The version of the release with the given name previously exported to the
+/// directory, as recorded by its `start_erl.data` file.
+fn previous_release_version(out: &Utf8Path, name: &str) -> Option<String> {
+    let releases = out.join("releases");
+    let start_erl_data = crate::fs::read(releases.join("start_erl.data")).ok()?;
+    let version = start_erl_data.split_whitespace().nth(1)?;
+    releases
+        .join(version)
+        .join(format!("{name}.rel"))
+        .is_file()
+        .then(|| version.to_string())
+}
+
+/// Write the runtime configuration and the start script of a release, once
+/// Erlang has assembled the rest of it. Returns the path to the start script.
+fn write_release_files(
+    project_root: &Utf8Path,
+    out: &Utf8Path,
+    name: &str,
+    version: &str,
+) -> Result<Utf8PathBuf> {
+    // Write the runtime configuration
+    let release_directory = out.join("releases").join(version);
+    for (file, template) in [
+        ("sys.config", RELEASE_SYS_CONFIG_TEMPLATE),
+        ("vm.args", RELEASE_VM_ARGS_TEMPLATE),
+    ] {
+        let project_file = project_root.join("config").join(file);
+        let release_file = release_directory.join(file);
+        if project_file.is_file() {
+            crate::fs::copy(&project_file, &release_file)?;
+        } else {
+            crate::fs::write(&release_file, template)?;
+        }
+    }
+
+    // Write start script
+    let start_script = out.join("bin").join(name);
+    let text = RELEASE_START_SCRIPT_TEMPLATE.replace("$PACKAGE_NAME_FROM_GLEAM", name);
+    crate::fs::write(&start_script, &text)?;
+    crate::fs::make_executable(&start_script)?;
+    Ok(start_script)
+}
+
+static ESCRIPTIZE_ESCRIPT: &str = include_str!("../templates/gleam@@escriptize.erl");
+
+/// Generate a single executable escript running the main function of the
+/// project. Suitable for distributing command line programs.
+///
+/// The escript bundles the ebin and priv directories of each Erlang application
+/// (aka package), it can be run on any machine with Erlang installed. The
+/// applications with priv files are extracted to a cache directory when the
+/// escript first runs, so that they can be read.
+pub(crate) fn escript() -> Result<()> {
+    let paths = crate::find_project_paths()?;
+    let build = paths.build_directory_for_target(Mode::Prod, Target::Erlang);
+
+    // Reset the build directory to ensure we have a clean slate and no old code
+    crate::fs::delete_directory(&build)?;
+
+    let built = build_erlang_for_production()?;
+    let name = &built.root_package.config.name;
+
+    // Make sure there is a main function to run
+    let _ = built.get_main_function(name, Target::Erlang)?;
+
+    let out = paths.erlang_escript(name);
+    crate::fs::delete_file(&out)?;
+    crate::fs::mkdir(out.parent().expect("escript directory"))?;
+
+    let escript = build.join("gleam@@escriptize.erl");
+    crate::fs::write(&escript, ESCRIPTIZE_ESCRIPT)?;
+    let mut args = vec![escript.to_string(), out.to_string(), name.to_string()];
+    for entry in crate::fs::read_dir(&build)?.filter_map(Result::ok) {
+        let path = entry.path();
+        if path.is_dir() {
+            args.push(crate::fs::canonicalise(path)?.to_string());
+        }
+    }
+    let status = ProjectIO::new().exec("escript", &args, &[], None, Stdio::Inherit)?;
+    if status != 0 {
+        return Err(Error::ShellCommand {
+            program: "escript".into(),
+            err: None,
+        });
+    }
+    crate::fs::make_executable(&out)?;
+
+    crate::cli::print_exported(name);
+
+    println!(
+        "
+Your escript has been generated to {out}.
+
+It can be copied to any machine with Erlang installed and run directly.
+
+    {out}
+",
+    );
+
+    Ok(())
+}
+
+/// Build the project in production mode for the Erlang target.
+fn build_erlang_for_production() -> Result<Built> {
+    crate::build::main(
+        Options {
+            root_target_support: TargetSupport::Enforced,
+            warnings_as_errors: false,
+            warning_levels: HashMap::new(),
+            codegen: Codegen::All,
+            compile: Compile::All,
+            mode: Mode::Prod,
+            target: Some(Target::Erlang),
+            no_print_progress: false,
+            tooling: false,
+        },
+        crate::build::download_dependencies(crate::cli::Reporter::new())?,
+    )
+}
+
 pub fn hex_tarball() -> Result<()> {
     let paths = crate::find_project_paths()?;
     let mut config = crate::config::root_config()?;
@@ -135,8 +362,10 @@ pub fn package_interface(path: Utf8PathBuf) -> Result<()> {
             codegen: Codegen::All,
             compile: Compile::All,
             warnings_as_errors: false,
+            warning_levels: HashMap::new(),
             root_target_support: TargetSupport::Enforced,
             no_print_progress: false,
+            tooling: false,
         },
         crate::build::download_dependencies(crate::cli::Reporter::new())?,
     )?;
@@ -146,3 +375,42 @@ pub fn package_interface(path: Utf8PathBuf) -> Result<()> {
     crate::fs::write_outputs_under(&[out], crate::find_project_paths()?.root())?;
     Ok(())
 }
+
+pub fn sbom(
+    format: SbomFormat,
+    include_dev_dependencies: bool,
+    output: Option<Utf8PathBuf>,
+) -> Result<()> {
+    let paths = crate::find_project_paths()?;
+    let config = crate::config::root_config()?;
+    // The licence policy is not checked so that a bill of materials can be
+    // made for a project that breaks it.
+    let manifest = crate::dependencies::download(
+        &paths,
+        crate::cli::Reporter::new(),
+        None,
+        Vec::new(),
+        crate::dependencies::UseManifest::Yes,
+    )?;
+    let licences = crate::dependencies::licences::package_licences(&paths, &manifest)
+        .into_iter()
+        .map(|package| (package.name, package.licences))
+        .collect();
+
+    let json = Sbom {
+        config: &config,
+        manifest: &manifest,
+        licences: &licences,
+        created: &sbom::timestamp(SystemTime::now()),
+        include_dev_dependencies,
+    }
+    .to_json(format);
+
+    match output {
+        Some(path) => crate::fs::write(&path, &json),
+        None => {
+            println!("{json}");
+            Ok(())
+        }
+    }
+}
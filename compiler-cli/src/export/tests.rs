@@ -0,0 +1,65 @@
+use camino::Utf8PathBuf;
+
+use super::{previous_release_version, write_release_files};
+
+#[test]
+fn release_files() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = Utf8PathBuf::from_path_buf(tmp.path().to_path_buf()).expect("Non Utf8 Path");
+    let project = root.join("my_project");
+    let out = root.join("release");
+    crate::fs::write(&project.join("config/vm.args"), "-sname my_node\n").unwrap();
+
+    let start_script = write_release_files(&project, &out, "my_project", "1.0.0").unwrap();
+    assert_eq!(start_script, out.join("bin/my_project"));
+
+    // The configuration files of the project are used when there are some
+    assert_eq!(
+        crate::fs::read(out.join("releases/1.0.0/vm.args")).unwrap(),
+        "-sname my_node\n"
+    );
+    insta::assert_snapshot!(
+        "sys_config",
+        crate::fs::read(out.join("releases/1.0.0/sys.config")).unwrap()
+    );
+    insta::assert_snapshot!("start_script", crate::fs::read(&start_script).unwrap());
+}
+
+#[cfg(not(target_os = "windows"))]
+#[test]
+fn release_start_script_is_executable() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let tmp = tempfile::tempdir().unwrap();
+    let root = Utf8PathBuf::from_path_buf(tmp.path().to_path_buf()).expect("Non Utf8 Path");
+    let start_script =
+        write_release_files(&root, &root.join("release"), "my_project", "1.0.0").unwrap();
+    let mode = std::fs::metadata(start_script)
+        .unwrap()
+        .permissions()
+        .mode();
+    assert_eq!(mode & 0o111, 0o111);
+}
+
+#[test]
+fn previous_release_version_is_read_from_start_erl_data() {
+    let tmp = tempfile::tempdir().unwrap();
+    let out = Utf8PathBuf::from_path_buf(tmp.path().to_path_buf()).expect("Non Utf8 Path");
+    crate::fs::write(&out.join("releases/start_erl.data"), "15.1 1.0.0\n").unwrap();
+    crate::fs::write(&out.join("releases/1.0.0/my_project.rel"), "").unwrap();
+
+    assert_eq!(
+        previous_release_version(&out, "my_project"),
+        Some("1.0.0".into())
+    );
+    // A release with another name can't be upgraded from
+    assert_eq!(previous_release_version(&out, "other_project"), None);
+}
+
+#[test]
+fn no_previous_release_version() {
+    let tmp = tempfile::tempdir().unwrap();
+    let out = Utf8PathBuf::from_path_buf(tmp.path().to_path_buf()).expect("Non Utf8 Path");
+
+    assert_eq!(previous_release_version(&out, "my_project"), None);
+}
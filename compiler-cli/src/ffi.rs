@@ -0,0 +1,90 @@
+use camino::{Utf8Path, Utf8PathBuf};
+use gleam_core::{
+    error::{FileIoAction, FileKind},
+    io::{CommandExecutor, Stdio},
+    Error, Result,
+};
+
+use crate::fs::ProjectIO;
+
+#[cfg(test)]
+mod tests;
+
+static BEAM_SPECS_ESCRIPT: &str = include_str!("../templates/gleam@@beam_specs.erl");
+
+/// Print or write a Gleam module binding to the code described by the given
+/// file.
+pub fn generate(from: Utf8PathBuf, module: Option<String>, out: Option<Utf8PathBuf>) -> Result<()> {
+    let file_name = from.file_name().unwrap_or_default();
+
+    // The JavaScript module a declaration file describes sits next to it.
+    let javascript_module = [(".d.ts", "js"), (".d.mts", "mjs"), (".d.cts", "cjs")]
+        .into_iter()
+        .find_map(|(suffix, extension)| {
+            let stem = file_name.strip_suffix(suffix)?;
+            Some(format!("./{stem}.{extension}"))
+        });
+    // Erlang modules are named after their file.
+    let erlang_module = from.file_stem().unwrap_or_default().to_string();
+
+    let gleam = match (javascript_module, from.extension()) {
+        (Some(default_module), _) => {
+            let declarations = crate::fs::read(&from)?;
+            let module = module.unwrap_or(default_module);
+            gleam_core::ffi::typescript::generate(&declarations, &module, file_name)?
+        }
+        (None, Some("erl")) => {
+            let src = crate::fs::read(&from)?;
+            let module = module.unwrap_or(erlang_module);
+            gleam_core::ffi::erlang::generate(&src, &module, file_name)?
+        }
+        (None, Some("beam")) => {
+            let src = beam_specs(&ProjectIO::new(), &from)?;
+            let module = module.unwrap_or(erlang_module);
+            gleam_core::ffi::erlang::generate(&src, &module, file_name)?
+        }
+        (None, _) => return Err(Error::UnsupportedFfiSource { path: from }),
+    };
+
+    match out {
+        Some(out) => {
+            crate::fs::write(&out, &gleam)?;
+            println!("Bindings written to {out}");
+        }
+        None => print!("{gleam}"),
+    }
+    Ok(())
+}
+
+/// The specs and types of a compiled Erlang module, written as Erlang source
+/// from its debug info by an escript.
+fn beam_specs(io: &impl CommandExecutor, beam: &Utf8Path) -> Result<String> {
+    // The escript and its output are written to a directory unique to this
+    // run, which is deleted once it is dropped.
+    let directory = tempfile::Builder::new()
+        .prefix("gleam@@beam_specs")
+        .tempdir()
+        .map_err(|error| Error::FileIo {
+            action: FileIoAction::Create,
+            kind: FileKind::Directory,
+            path: Utf8PathBuf::from_path_buf(std::env::temp_dir()).unwrap_or_default(),
+            err: Some(error.to_string()),
+        })?;
+    let directory = Utf8Path::from_path(directory.path()).ok_or_else(|| Error::NonUtf8Path {
+        path: directory.path().to_path_buf(),
+    })?;
+    let escript = directory.join("gleam@@beam_specs.erl");
+    let out = directory.join("specs.erl");
+    crate::fs::write(&escript, BEAM_SPECS_ESCRIPT)?;
+
+    tracing::trace!(beam=?beam, "reading_beam_specs");
+    let args = [escript.to_string(), beam.to_string(), out.to_string()];
+    let status = io.exec("escript", &args, &[], None, Stdio::Inherit)?;
+    if status != 0 {
+        return Err(Error::ShellCommand {
+            program: "escript".into(),
+            err: None,
+        });
+    }
+    crate::fs::read(&out)
+}
@@ -0,0 +1,77 @@
+use std::cell::RefCell;
+
+use camino::Utf8Path;
+use gleam_core::{
+    io::{CommandExecutor, Stdio},
+    Error, Result,
+};
+
+use super::beam_specs;
+
+/// Runs no command, writing the given output to the file given as the last
+/// argument instead.
+struct FakeEscript {
+    status: i32,
+    output: &'static str,
+    calls: RefCell<Vec<(String, Vec<String>)>>,
+}
+
+impl FakeEscript {
+    fn new(status: i32, output: &'static str) -> Self {
+        Self {
+            status,
+            output,
+            calls: RefCell::new(vec![]),
+        }
+    }
+}
+
+impl CommandExecutor for FakeEscript {
+    fn exec(
+        &self,
+        program: &str,
+        args: &[String],
+        _env: &[(&str, String)],
+        _cwd: Option<&Utf8Path>,
+        _stdio: Stdio,
+    ) -> Result<i32, Error> {
+        self.calls
+            .borrow_mut()
+            .push((program.to_string(), args.to_vec()));
+        if self.status == 0 {
+            let out = args.last().expect("output file argument");
+            crate::fs::write(Utf8Path::new(out), self.output)?;
+        }
+        Ok(self.status)
+    }
+}
+
+#[test]
+fn beam_specs_are_read_from_the_escript_output() {
+    let io = FakeEscript::new(0, "-spec wibble() -> ok.\n");
+    let specs = beam_specs(&io, Utf8Path::new("ebin/wibble.beam")).expect("beam specs");
+    assert_eq!(specs, "-spec wibble() -> ok.\n");
+
+    let calls = io.calls.into_inner();
+    let [(program, args)] = calls.as_slice() else {
+        panic!("expected a single command, got {calls:?}");
+    };
+    let [escript, beam, _out] = args.as_slice() else {
+        panic!("unexpected arguments {args:?}");
+    };
+    assert_eq!(program, "escript");
+    assert!(escript.ends_with("gleam@@beam_specs.erl"));
+    assert_eq!(beam, "ebin/wibble.beam");
+}
+
+#[test]
+fn beam_specs_escript_failure() {
+    let io = FakeEscript::new(1, "");
+    assert_eq!(
+        beam_specs(&io, Utf8Path::new("ebin/wibble.beam")),
+        Err(Error::ShellCommand {
+            program: "escript".into(),
+            err: None,
+        })
+    );
+}
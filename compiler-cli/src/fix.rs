@@ -1,51 +1,109 @@
-use std::rc::Rc;
+use std::{collections::HashMap, rc::Rc};
 
 use camino::{Utf8Path, Utf8PathBuf};
 use gleam_core::{
     analyse::TargetSupport,
-    build::{Codegen, Compile, Mode, Options},
+    build::{Built, Codegen, Compile, Mode, Options},
     error::{FileIoAction, FileKind},
+    fix::{self, Fix},
     type_,
     warning::VectorWarningEmitterIO,
     Error, Result, Warning,
 };
 use hexpm::version::Version;
+use itertools::Itertools;
+use similar::TextDiff;
 
 use crate::{build, cli};
 
-pub fn run() -> Result<()> {
+pub fn run(only: Vec<Fix>, dry_run: bool) -> Result<()> {
+    let fixes = if only.is_empty() { Fix::all() } else { only };
+
+    let paths = crate::find_project_paths()?;
+
     // When running gleam fix we want all the compilation warnings to be hidden,
     // at the same time we need to access those to apply the fixes: so we
     // accumulate those into a vector.
     let warnings = Rc::new(VectorWarningEmitterIO::new());
-    let _built = build::main_with_warnings(
+    let built = build::main_with_warnings(
         Options {
             root_target_support: TargetSupport::Enforced,
             warnings_as_errors: false,
+            warning_levels: HashMap::new(),
             codegen: Codegen::DepsOnly,
             compile: Compile::All,
             mode: Mode::Dev,
             target: None,
             no_print_progress: false,
+            // All the modules are type checked again rather than loaded from
+            // the cache, to have the code of all of them to fix, and their
+            // warnings are never denied nor allowed.
+            tooling: true,
         },
         build::download_dependencies(cli::Reporter::new())?,
         warnings.clone(),
     )?;
     let warnings = warnings.take();
 
-    fix_minimum_required_version(warnings)?;
+    fix_modules(&built, &fixes, paths.root(), dry_run)?;
+    if fixes.contains(&Fix::GleamVersion) {
+        fix_minimum_required_version(warnings, dry_run)?;
+    }
 
-    println!("Done!");
+    if !dry_run {
+        println!("Done!");
+    }
     Ok(())
 }
 
-fn fix_minimum_required_version(warnings: Vec<Warning>) -> Result<()> {
+/// Applies the fixes to all the modules of the project. If it's a dry run the
+/// changes are printed as a diff instead.
+fn fix_modules(built: &Built, fixes: &[Fix], root: &Utf8Path, dry_run: bool) -> Result<()> {
+    for module in &built.root_package.modules {
+        let module_fixes =
+            fix::fix_module(fixes, &module.ast, &module.code, built.module_interfaces());
+        if module_fixes.is_empty() {
+            continue;
+        }
+
+        let edits = module_fixes
+            .iter()
+            .flat_map(|(_, edits)| edits.iter().cloned())
+            .collect_vec();
+        let code = fix::apply_edits(&module.code, &edits);
+        let path = module
+            .input_path
+            .strip_prefix(root)
+            .unwrap_or(&module.input_path);
+
+        if dry_run {
+            print_diff(path, &module.code, &code);
+        } else {
+            crate::fs::write(&module.input_path, &code)?;
+            let names = module_fixes.iter().map(|(fix, _)| fix).join(", ");
+            println!("- Fixed {path} ({names})");
+        }
+    }
+    Ok(())
+}
+
+fn print_diff(path: &Utf8Path, old: &str, new: &str) {
+    let diff = TextDiff::from_lines(old, new);
+    print!(
+        "{}",
+        diff.unified_diff()
+            .header(&format!("a/{path}"), &format!("b/{path}"))
+    );
+}
+
+fn fix_minimum_required_version(warnings: Vec<Warning>, dry_run: bool) -> Result<()> {
     let Some(minimum_required_version) = minimum_required_version_from_warnings(warnings) else {
         return Ok(());
     };
 
     // Set the version requirement in gleam.toml
-    let mut toml = crate::fs::read("gleam.toml")?
+    let original = crate::fs::read("gleam.toml")?;
+    let mut toml = original
         .parse::<toml_edit::DocumentMut>()
         .map_err(|e| Error::FileIo {
             kind: FileKind::File,
@@ -59,6 +117,11 @@ fn fix_minimum_required_version(warnings: Vec<Warning>) -> Result<()> {
         toml["gleam"] = toml_edit::value(format!(">= {minimum_required_version}"));
     }
 
+    if dry_run {
+        print_diff(Utf8Path::new("gleam.toml"), &original, &toml.to_string());
+        return Ok(());
+    }
+
     // Write the updated config
     crate::fs::write(Utf8Path::new("gleam.toml"), &toml.to_string())?;
 
@@ -1,24 +1,126 @@
 use gleam_core::{
+    analyse::TargetSupport,
+    ast::SrcSpan,
+    build::{Codegen, Compile, Mode, Options},
     error::{Error, FileIoAction, FileKind, Result, StandardIoAction, Unformatted},
     io::Content,
     io::OutputFile,
+    warning::VectorWarningEmitterIO,
+    Warning,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    io::Read,
+    rc::Rc,
+    str::FromStr,
 };
-use std::{io::Read, str::FromStr};
 
 use camino::{Utf8Path, Utf8PathBuf};
+use ecow::EcoString;
+
+use crate::{build, cli};
+
+/// What to do with the imports of the formatted modules.
+///
+pub enum Imports {
+    /// Imports are left as they are, the formatter will just sort them.
+    Keep,
+    /// Imports are merged and sorted, and the unused ones found in each file
+    /// are removed.
+    Organize {
+        unused: HashMap<Utf8PathBuf, HashSet<SrcSpan>>,
+    },
+}
+
+impl Imports {
+    fn format(&self, out: &mut String, src: &EcoString, path: &Utf8Path) -> Result<()> {
+        match self {
+            Self::Keep => gleam_core::format::pretty(out, src, path),
+            Self::Organize { unused } => {
+                let unused = crate::fs::canonicalise(path)
+                    .ok()
+                    .and_then(|path| unused.get(&path).cloned())
+                    .unwrap_or_default();
+                gleam_core::format::imports::pretty(out, src, path, &unused)
+            }
+        }
+    }
+}
+
+pub fn run(stdin: bool, check: bool, organize_imports: bool, files: Vec<String>) -> Result<()> {
+    let imports = match (organize_imports, stdin) {
+        (false, _) => Imports::Keep,
+        // There's no way of knowing which imports are unused in a module coming
+        // from stdin, so those are only going to be merged and sorted.
+        (true, true) => Imports::Organize {
+            unused: HashMap::new(),
+        },
+        (true, false) => Imports::Organize {
+            unused: project_unused_imports()?,
+        },
+    };
 
-pub fn run(stdin: bool, check: bool, files: Vec<String>) -> Result<()> {
     if stdin {
-        process_stdin(check)
+        process_stdin(check, &imports)
     } else {
-        process_files(check, files)
+        process_files(check, files, &imports)
+    }
+}
+
+/// Type checks the project, if we are in one, to find all the unused imports
+/// of each of its modules.
+///
+fn project_unused_imports() -> Result<HashMap<Utf8PathBuf, HashSet<SrcSpan>>> {
+    if crate::find_project_paths().is_err() {
+        return Ok(HashMap::new());
+    }
+
+    // The warnings are not printed, we only need those to know which imports
+    // can be removed.
+    let warnings = Rc::new(VectorWarningEmitterIO::new());
+    let built = build::all(
+        Options {
+            root_target_support: TargetSupport::Enforced,
+            warnings_as_errors: false,
+            warning_levels: HashMap::new(),
+            codegen: Codegen::DepsOnly,
+            compile: Compile::All,
+            mode: Mode::Dev,
+            target: None,
+            no_print_progress: false,
+            // The levels set for the warnings must not hide unused imports,
+            // nor make the build fail because of them.
+            tooling: true,
+        },
+        build::download_dependencies(cli::Reporter::new())?,
+        warnings.clone(),
+    );
+
+    // Duplicate imports are a compile error, so we don't want to fail here:
+    // the imports can still be merged and sorted.
+    if built.is_err() {
+        cli::print_colourful_prefix("Skipped", "removing unused imports, the project has errors");
+        return Ok(HashMap::new());
+    }
+
+    let mut unused: HashMap<Utf8PathBuf, HashSet<SrcSpan>> = HashMap::new();
+    for warning in warnings.take() {
+        let Warning::Type { path, warning, .. } = warning else {
+            continue;
+        };
+        let Some(location) = warning.unused_import_location() else {
+            continue;
+        };
+        let path = crate::fs::canonicalise(&path).unwrap_or(path);
+        let _ = unused.entry(path).or_default().insert(location);
     }
+    Ok(unused)
 }
 
-fn process_stdin(check: bool) -> Result<()> {
+fn process_stdin(check: bool, imports: &Imports) -> Result<()> {
     let src = read_stdin()?.into();
     let mut out = String::new();
-    gleam_core::format::pretty(&mut out, &src, Utf8Path::new("<stdin>"))?;
+    imports.format(&mut out, &src, Utf8Path::new("<stdin>"))?;
 
     if !check {
         print!("{out}");
@@ -39,16 +141,16 @@ fn process_stdin(check: bool) -> Result<()> {
     Ok(())
 }
 
-fn process_files(check: bool, files: Vec<String>) -> Result<()> {
+fn process_files(check: bool, files: Vec<String>, imports: &Imports) -> Result<()> {
     if check {
-        check_files(files)
+        check_files(files, imports)
     } else {
-        format_files(files)
+        format_files(files, imports)
     }
 }
 
-fn check_files(files: Vec<String>) -> Result<()> {
-    let problem_files = unformatted_files(files)?;
+fn check_files(files: Vec<String>, imports: &Imports) -> Result<()> {
+    let problem_files = unformatted_files(files, imports)?;
 
     if problem_files.is_empty() {
         Ok(())
@@ -57,8 +159,8 @@ fn check_files(files: Vec<String>) -> Result<()> {
     }
 }
 
-fn format_files(files: Vec<String>) -> Result<()> {
-    for file in unformatted_files(files)? {
+fn format_files(files: Vec<String>, imports: &Imports) -> Result<()> {
+    for file in unformatted_files(files, imports)? {
         crate::fs::write_output(&OutputFile {
             path: file.destination,
             content: Content::Text(file.output),
@@ -67,7 +169,7 @@ fn format_files(files: Vec<String>) -> Result<()> {
     Ok(())
 }
 
-pub fn unformatted_files(files: Vec<String>) -> Result<Vec<Unformatted>> {
+pub fn unformatted_files(files: Vec<String>, imports: &Imports) -> Result<Vec<Unformatted>> {
     let mut problem_files = Vec::with_capacity(files.len());
 
     for file_path in files {
@@ -80,20 +182,24 @@ pub fn unformatted_files(files: Vec<String>) -> Result<Vec<Unformatted>> {
 
         if path.is_dir() {
             for path in crate::fs::gleam_files_excluding_gitignore(&path) {
-                format_file(&mut problem_files, path)?;
+                format_file(&mut problem_files, path, imports)?;
             }
         } else {
-            format_file(&mut problem_files, path)?;
+            format_file(&mut problem_files, path, imports)?;
         }
     }
 
     Ok(problem_files)
 }
 
-fn format_file(problem_files: &mut Vec<Unformatted>, path: Utf8PathBuf) -> Result<()> {
+fn format_file(
+    problem_files: &mut Vec<Unformatted>,
+    path: Utf8PathBuf,
+    imports: &Imports,
+) -> Result<()> {
     let src = crate::fs::read(&path)?.into();
     let mut output = String::new();
-    gleam_core::format::pretty(&mut output, &src, &path)?;
+    imports.format(&mut output, &src, &path)?;
 
     if src != output {
         problem_files.push(Unformatted {
@@ -156,6 +156,10 @@ impl FileSystemWriter for ProjectIO {
         copy_dir(from, to)
     }
 
+    fn rename(&self, from: &Utf8Path, to: &Utf8Path) -> Result<()> {
+        rename(from, to)
+    }
+
     fn mkdir(&self, path: &Utf8Path) -> Result<(), Error> {
         mkdir(path)
     }
@@ -508,6 +512,18 @@ pub fn read(path: impl AsRef<Utf8Path> + Debug) -> Result<String, Error> {
     })
 }
 
+pub fn file_size(path: impl AsRef<Utf8Path> + Debug) -> Result<u64, Error> {
+    path.as_ref()
+        .metadata()
+        .map(|metadata| metadata.len())
+        .map_err(|err| Error::FileIo {
+            action: FileIoAction::ReadMetadata,
+            kind: FileKind::File,
+            path: Utf8PathBuf::from(path.as_ref()),
+            err: Some(err.to_string()),
+        })
+}
+
 pub fn read_bytes(path: impl AsRef<Utf8Path> + Debug) -> Result<Vec<u8>, Error> {
     tracing::trace!(path=?path,"reading_file");
 
@@ -560,19 +576,22 @@ pub fn copy(
         .map(|_| ())
 }
 
-// pub fn rename(path: impl AsRef<Utf8Path> + Debug, to: impl AsRef<Utf8Path> + Debug) -> Result<(), Error> {
-//     tracing::trace!(from=?path, to=?to, "renaming_file");
+pub fn rename(
+    path: impl AsRef<Utf8Path> + Debug,
+    to: impl AsRef<Utf8Path> + Debug,
+) -> Result<(), Error> {
+    tracing::trace!(from=?path, to=?to, "renaming_file");
 
-//     // TODO: include the destination in the error message
-//     std::fs::rename(&path, &to)
-//         .map_err(|err| Error::FileIo {
-//             action: FileIoAction::Rename,
-//             kind: FileKind::File,
-//             path: Utf8PathBuf::from(path.as_ref()),
-//             err: Some(err.to_string()),
-//         })
-//         .map(|_| ())
-// }
+    // TODO: include the destination in the error message
+    std::fs::rename(path.as_ref(), to.as_ref())
+        .map_err(|err| Error::FileIo {
+            action: FileIoAction::Rename,
+            kind: FileKind::File,
+            path: Utf8PathBuf::from(path.as_ref()),
+            err: Some(err.to_string()),
+        })
+        .map(|_| ())
+}
 
 pub fn copy_dir(
     path: impl AsRef<Utf8Path> + Debug,
@@ -2,10 +2,11 @@ mod auth;
 
 use crate::{cli, http::HttpClient};
 use gleam_core::{
-    hex::{self, RetirementReason},
+    hex::{self, OwnerLevel, RetirementReason},
     io::HttpClient as _,
     Error, Result,
 };
+use itertools::Itertools;
 
 pub use auth::HexAuthentication;
 
@@ -86,6 +87,115 @@ pub fn revert(package: Option<String>, version: Option<String>) -> Result<()> {
     Ok(())
 }
 
+pub fn list_owners(package: String) -> Result<()> {
+    let runtime = tokio::runtime::Runtime::new().expect("Unable to start Tokio async runtime");
+    let config = hexpm::Config::new();
+    let api_key = HexAuthentication::new(&runtime, config.clone()).get_or_create_api_key()?;
+
+    let owners = runtime.block_on(hex::list_package_owners(
+        &package,
+        &config,
+        &api_key,
+        &HttpClient::new(),
+    ))?;
+    for owner in owners {
+        match owner.email {
+            Some(email) => println!("{} <{email}> ({})", owner.username, owner.level),
+            None => println!("{} ({})", owner.username, owner.level),
+        }
+    }
+    Ok(())
+}
+
+pub fn add_owner(package: String, owner: String, level: OwnerLevel) -> Result<()> {
+    let runtime = tokio::runtime::Runtime::new().expect("Unable to start Tokio async runtime");
+    let config = hexpm::Config::new();
+    let api_key = HexAuthentication::new(&runtime, config.clone()).get_or_create_api_key()?;
+
+    runtime.block_on(hex::add_package_owner(
+        &package,
+        &owner,
+        level,
+        &config,
+        &api_key,
+        &HttpClient::new(),
+    ))?;
+    println!("{owner} is now a {level} owner of {package}");
+    Ok(())
+}
+
+pub fn remove_owner(package: String, owner: String) -> Result<()> {
+    let runtime = tokio::runtime::Runtime::new().expect("Unable to start Tokio async runtime");
+    let config = hexpm::Config::new();
+    let api_key = HexAuthentication::new(&runtime, config.clone()).get_or_create_api_key()?;
+
+    runtime.block_on(hex::remove_package_owner(
+        &package,
+        &owner,
+        &config,
+        &api_key,
+        &HttpClient::new(),
+    ))?;
+    println!("{owner} is no longer an owner of {package}");
+    Ok(())
+}
+
+pub fn list_keys() -> Result<()> {
+    let runtime = tokio::runtime::Runtime::new().expect("Unable to start Tokio async runtime");
+    let config = hexpm::Config::new();
+    let api_key = HexAuthentication::new(&runtime, config.clone()).get_or_create_api_key()?;
+
+    let keys = runtime.block_on(hex::list_api_keys(&config, &api_key, &HttpClient::new()))?;
+    for key in keys {
+        let permissions = key
+            .permissions
+            .iter()
+            .map(|permission| match &permission.resource {
+                Some(resource) => format!("{}:{resource}", permission.domain),
+                None => permission.domain.clone(),
+            })
+            .join(", ");
+        let last_used = key
+            .last_use
+            .and_then(|last_use| last_use.used_at)
+            .unwrap_or_else(|| "never".into());
+        println!("{} ({permissions}), last used {last_used}", key.name);
+    }
+    Ok(())
+}
+
+pub fn revoke_key(name: String) -> Result<()> {
+    let question = format!("Do you wish to revoke the Hex API key {name}?");
+    if !cli::confirm(&question)? {
+        println!("Not revoking.");
+        return Ok(());
+    }
+
+    let runtime = tokio::runtime::Runtime::new().expect("Unable to start Tokio async runtime");
+    let config = hexpm::Config::new();
+    let mut auth = HexAuthentication::new(&runtime, config.clone());
+    let is_stored = auth
+        .read_stored_api_key()?
+        .is_some_and(|stored| hex::key_name(&stored.name) == name);
+    let api_key = auth.get_or_create_api_key()?;
+
+    runtime.block_on(hex::remove_api_key(
+        &name,
+        &config,
+        &api_key,
+        &HttpClient::new(),
+    ))?;
+    println!("The Hex API key {name} has been revoked");
+
+    // The key stored on this computer can no longer be used, so it's deleted
+    // for a new one to be created the next time one is needed.
+    if is_stored {
+        auth.delete_stored_api_key()?;
+        println!("It was the key stored on this computer, so it has been deleted");
+    }
+    Ok(())
+}
+
 pub(crate) fn authenticate() -> Result<()> {
     let runtime = tokio::runtime::Runtime::new().expect("Unable to start Tokio async runtime");
     let http = HttpClient::new();
@@ -106,7 +216,7 @@ with a new one?";
     if let Some(previous) = previous {
         println!("Deleting previous key `{}` from Hex", previous.name);
         runtime.block_on(hex::remove_api_key(
-            &previous.name,
+            &hex::key_name(&previous.name),
             &config,
             &new_key.unencrypted,
             &http,
@@ -133,6 +133,10 @@ encrypt your Hex API key.
             encrypted: encrypted.to_string(),
         }))
     }
+
+    pub fn delete_stored_api_key(&self) -> Result<()> {
+        crate::fs::delete_file(&global_hexpm_credentials_path())
+    }
 }
 
 fn ask_local_password() -> std::result::Result<String, Error> {
@@ -58,18 +58,21 @@ mod config;
 mod dependencies;
 mod docs;
 mod export;
+mod ffi;
 mod fix;
 mod format;
 mod fs;
 mod hex;
 mod http;
 mod lsp;
+mod move_module;
 mod new;
 mod panic;
 mod publish;
 mod remove;
 mod run;
 mod shell;
+mod workspace;
 
 use config::root_config;
 use dependencies::UseManifest;
@@ -78,20 +81,24 @@ pub use gleam_core::error::{Error, Result};
 
 use gleam_core::{
     analyse::TargetSupport,
+    audit::Severity,
     build::{Codegen, Compile, Mode, NullTelemetry, Options, Runtime, Target},
-    hex::RetirementReason,
+    fix::Fix,
+    hex::{OwnerLevel, RetirementReason},
     paths::ProjectPaths,
+    sbom::SbomFormat,
     version::COMPILER_VERSION,
+    warning::{WarningKind, WarningLevel},
 };
-use std::str::FromStr;
+use std::{collections::HashMap, rc::Rc, str::FromStr};
 
 use camino::Utf8PathBuf;
 
 use clap::{
-    builder::{styling, PossibleValuesParser, Styles, TypedValueParser},
+    builder::{styling, PossibleValue, PossibleValuesParser, Styles, TypedValueParser},
     Args, Parser, Subcommand,
 };
-use strum::VariantNames;
+use strum::{IntoEnumIterator, VariantNames};
 
 #[derive(Args, Debug, Clone)]
 struct UpdateOptions {
@@ -122,6 +129,36 @@ struct TreeOptions {
     invert: Option<String>,
 }
 
+/// The levels to report warnings at, taking precedence over the `[warnings]`
+/// table of gleam.toml.
+#[derive(Args, Debug, Clone)]
+struct WarningLevelOptions {
+    /// Don't report the given kind of warning. Can be given multiple times
+    #[arg(long, value_name = "WARNING", value_parser = PossibleValuesParser::new(WarningKind::VARIANTS).map(|s| WarningKind::from_str(&s).unwrap()))]
+    allow: Vec<WarningKind>,
+
+    /// Report the given kind of warning. Can be given multiple times
+    #[arg(long, value_name = "WARNING", value_parser = PossibleValuesParser::new(WarningKind::VARIANTS).map(|s| WarningKind::from_str(&s).unwrap()))]
+    warn: Vec<WarningKind>,
+
+    /// Fail if the given kind of warning is emitted. Can be given multiple
+    /// times
+    #[arg(long, value_name = "WARNING", value_parser = PossibleValuesParser::new(WarningKind::VARIANTS).map(|s| WarningKind::from_str(&s).unwrap()))]
+    deny: Vec<WarningKind>,
+}
+
+impl WarningLevelOptions {
+    fn levels(self) -> HashMap<WarningKind, WarningLevel> {
+        let allow = self
+            .allow
+            .into_iter()
+            .map(|kind| (kind, WarningLevel::Allow));
+        let warn = self.warn.into_iter().map(|kind| (kind, WarningLevel::Warn));
+        let deny = self.deny.into_iter().map(|kind| (kind, WarningLevel::Deny));
+        allow.chain(warn).chain(deny).collect()
+    }
+}
+
 #[derive(Parser, Debug)]
 #[command(
     version,
@@ -144,6 +181,9 @@ enum Command {
         #[arg(long)]
         warnings_as_errors: bool,
 
+        #[command(flatten)]
+        warning_levels: WarningLevelOptions,
+
         #[arg(short, long, ignore_case = true, help = target_doc())]
         target: Option<Target>,
 
@@ -156,6 +196,9 @@ enum Command {
     Check {
         #[arg(short, long, ignore_case = true, help = target_doc())]
         target: Option<Target>,
+
+        #[command(flatten)]
+        warning_levels: WarningLevelOptions,
     },
 
     /// Publish the project to the Hex package manager
@@ -171,6 +214,10 @@ enum Command {
         replace: bool,
         #[arg(short, long)]
         yes: bool,
+        /// Run the checks and build the package without publishing it,
+        /// showing what would be published
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// Render HTML documentation
@@ -204,9 +251,37 @@ enum Command {
         /// Check if inputs are formatted without changing them
         #[arg(long)]
         check: bool,
+
+        /// Merge and sort imports, removing the unused ones. The project is
+        /// type checked to find which imports are unused
+        #[arg(long)]
+        organize_imports: bool,
     },
     /// Rewrite deprecated Gleam code
-    Fix,
+    Fix {
+        /// Only apply the given fix. Can be given multiple times
+        #[arg(long, value_parser = PossibleValuesParser::new(fix_values()).map(|s| Fix::from_str(&s).unwrap()))]
+        only: Vec<Fix>,
+
+        /// Print the changes that would be made without applying them
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Move a module, updating the modules importing it
+    Mv {
+        /// The module to move, such as `app/user` or `src/app/user.gleam`
+        old: String,
+
+        /// Where to move it, such as `app/accounts/user`
+        new: String,
+    },
+
+    /// Explain an error or warning code, such as E0216
+    Explain {
+        /// The code to explain
+        code: String,
+    },
 
     /// Start an Erlang shell
     Shell,
@@ -279,6 +354,10 @@ enum Command {
     /// Export something useful from the Gleam project
     #[command(subcommand)]
     Export(ExportTarget),
+
+    /// Work with code written in other languages
+    #[command(subcommand)]
+    Ffi(Ffi),
 }
 
 fn template_doc() -> &'static str {
@@ -293,10 +372,34 @@ fn runtime_doc() -> String {
     format!("The runtime to target ({})", Runtime::VARIANTS.join("|"))
 }
 
+fn fix_values() -> impl Iterator<Item = PossibleValue> {
+    Fix::iter()
+        .zip(Fix::VARIANTS)
+        .map(|(fix, name)| PossibleValue::new(*name).help(fix.description()))
+}
+
 #[derive(Subcommand, Debug, Clone)]
 pub enum ExportTarget {
     /// Precompiled Erlang, suitable for deployment
     ErlangShipment,
+    /// An OTP release, suitable for deployment in embedded mode and for hot
+    /// upgrades
+    ///
+    /// The release is started with a POSIX shell script, so it can't be
+    /// started on Windows.
+    ///
+    /// The release is laid out for the release handler, but only its
+    /// `RELEASES` file is written: the `.appup` and `.relup` files needed to
+    /// perform a hot upgrade must be written by hand.
+    ErlangRelease {
+        /// Bundle the Erlang runtime system so that the release can run on
+        /// machines without Erlang installed
+        #[arg(long)]
+        include_erts: bool,
+    },
+    /// Precompiled Erlang bundled into a single executable escript, suitable
+    /// for command line programs
+    Escript,
     /// The package bundled into a tarball, suitable for publishing to Hex
     HexTarball,
     /// The JavaScript prelude module
@@ -309,6 +412,41 @@ pub enum ExportTarget {
         /// The path to write the JSON file to
         output: Utf8PathBuf,
     },
+    /// A software bill of materials listing the packages of the project, with
+    /// their sources, checksums, licences, and dependencies
+    Sbom {
+        /// The format of the bill of materials
+        #[arg(long, value_parser = PossibleValuesParser::new(SbomFormat::VARIANTS).map(|s| SbomFormat::from_str(&s).unwrap()))]
+        format: SbomFormat,
+        /// Leave out the packages only needed by the dev dependencies
+        #[arg(long)]
+        exclude_dev_dependencies: bool,
+        /// The path to write the bill of materials to, rather than printing it
+        #[arg(long = "out")]
+        output: Option<Utf8PathBuf>,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum Ffi {
+    /// Generate a Gleam module of external types and functions from a
+    /// TypeScript declaration file or from the specs of an Erlang module
+    Generate {
+        /// The `.d.ts`, `.d.mts`, `.d.cts`, `.erl`, or `.beam` file to read.
+        /// Compiled Erlang modules must have been compiled with debug info
+        #[arg(long)]
+        from: Utf8PathBuf,
+
+        /// The module the external functions refer to. Defaults to the
+        /// JavaScript module next to the declaration file, or to the Erlang
+        /// module named after the file
+        #[arg(long)]
+        module: Option<String>,
+
+        /// The path to write the Gleam module to. Defaults to printing it
+        #[arg(long)]
+        out: Option<Utf8PathBuf>,
+    },
 }
 
 #[derive(Args, Debug, Clone)]
@@ -379,6 +517,28 @@ enum Dependencies {
 
     /// Tree of all the dependency packages
     Tree(TreeOptions),
+
+    /// List the licences of all the dependency packages
+    Licences {
+        /// Print the licences as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Check the dependency packages for retired Hex releases and security
+    /// advisories
+    Audit {
+        /// An OSV JSON file of security advisories to check the packages
+        /// against
+        #[arg(long, value_name = "PATH")]
+        advisories: Option<Utf8PathBuf>,
+        /// Fail if any problem is of this severity or higher
+        #[arg(long, value_name = "SEVERITY", default_value = "low", value_parser = PossibleValuesParser::new(Severity::VARIANTS).map(|s| Severity::from_str(&s).unwrap()))]
+        fail_on: Severity,
+        /// Print the problems as JSON
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -430,6 +590,85 @@ enum Hex {
 
     /// Authenticate with Hex
     Authenticate,
+
+    /// Manage the owners of a package on Hex
+    #[command(subcommand)]
+    Owner(HexOwner),
+
+    /// Manage your Hex API keys
+    #[command(subcommand)]
+    Key(HexKey),
+}
+
+#[derive(Subcommand, Debug)]
+enum HexOwner {
+    /// Add an owner to a package, or change their level
+    ///
+    /// This command uses this environment variables:
+    ///
+    /// - HEXPM_USER: (optional) The Hex username to authenticate with.
+    /// - HEXPM_PASS: (optional) The Hex password to authenticate with.
+    /// - HEXPM_API_KEY: (optional) A Hex API key to use instead of authenticating.
+    #[command(verbatim_doc_comment)]
+    Add {
+        package: String,
+
+        /// The username or email address of the new owner
+        owner: String,
+
+        /// Maintainers can publish and retire releases, full owners can also
+        /// manage the owners of the package
+        #[arg(long, default_value = "maintainer", value_parser = PossibleValuesParser::new(OwnerLevel::VARIANTS).map(|s| OwnerLevel::from_str(&s).unwrap()))]
+        level: OwnerLevel,
+    },
+
+    /// Remove an owner from a package
+    ///
+    /// This command uses this environment variables:
+    ///
+    /// - HEXPM_USER: (optional) The Hex username to authenticate with.
+    /// - HEXPM_PASS: (optional) The Hex password to authenticate with.
+    /// - HEXPM_API_KEY: (optional) A Hex API key to use instead of authenticating.
+    #[command(verbatim_doc_comment)]
+    Remove {
+        package: String,
+
+        /// The username or email address of the owner
+        owner: String,
+    },
+
+    /// List the owners of a package
+    ///
+    /// This command uses this environment variables:
+    ///
+    /// - HEXPM_USER: (optional) The Hex username to authenticate with.
+    /// - HEXPM_PASS: (optional) The Hex password to authenticate with.
+    /// - HEXPM_API_KEY: (optional) A Hex API key to use instead of authenticating.
+    #[command(verbatim_doc_comment)]
+    List { package: String },
+}
+
+#[derive(Subcommand, Debug)]
+enum HexKey {
+    /// List your Hex API keys
+    ///
+    /// This command uses this environment variables:
+    ///
+    /// - HEXPM_USER: (optional) The Hex username to authenticate with.
+    /// - HEXPM_PASS: (optional) The Hex password to authenticate with.
+    /// - HEXPM_API_KEY: (optional) A Hex API key to use instead of authenticating.
+    #[command(verbatim_doc_comment)]
+    List,
+
+    /// Revoke one of your Hex API keys
+    ///
+    /// This command uses this environment variables:
+    ///
+    /// - HEXPM_USER: (optional) The Hex username to authenticate with.
+    /// - HEXPM_PASS: (optional) The Hex password to authenticate with.
+    /// - HEXPM_API_KEY: (optional) A Hex API key to use instead of authenticating.
+    #[command(verbatim_doc_comment)]
+    Revoke { name: String },
 }
 
 #[derive(Subcommand, Debug)]
@@ -482,10 +721,19 @@ fn main() {
         Command::Build {
             target,
             warnings_as_errors,
+            warning_levels,
+            no_print_progress,
+        } => command_build(
+            target,
+            warnings_as_errors,
+            warning_levels.levels(),
             no_print_progress,
-        } => command_build(target, warnings_as_errors, no_print_progress),
+        ),
 
-        Command::Check { target } => command_check(target),
+        Command::Check {
+            target,
+            warning_levels,
+        } => command_check(target, warning_levels.levels()),
 
         Command::Docs(Docs::Build { open, target }) => {
             docs::build(docs::BuildOptions { open, target })
@@ -499,9 +747,12 @@ fn main() {
             stdin,
             files,
             check,
-        } => format::run(stdin, check, files),
+            organize_imports,
+        } => format::run(stdin, check, organize_imports, files),
+
+        Command::Fix { only, dry_run } => fix::run(only, dry_run),
 
-        Command::Fix => fix::run(),
+        Command::Mv { old, new } => move_module::run(old, new),
 
         Command::Deps(Dependencies::List) => dependencies::list(),
 
@@ -511,10 +762,20 @@ fn main() {
 
         Command::Deps(Dependencies::Tree(options)) => dependencies::tree(options),
 
+        Command::Deps(Dependencies::Licences { json }) => dependencies::licences::run(json),
+
+        Command::Deps(Dependencies::Audit {
+            advisories,
+            fail_on,
+            json,
+        }) => dependencies::audit::run(advisories.as_deref(), fail_on, json),
+
         Command::Hex(Hex::Authenticate) => hex::authenticate(),
 
         Command::New(options) => new::create(options, COMPILER_VERSION),
 
+        Command::Explain { code } => explain(code),
+
         Command::Shell => shell::command(),
 
         Command::Run {
@@ -540,7 +801,11 @@ fn main() {
 
         Command::CompilePackage(opts) => compile_package::command(opts),
 
-        Command::Publish { replace, yes } => publish::command(replace, yes),
+        Command::Publish {
+            replace,
+            yes,
+            dry_run,
+        } => publish::command(replace, yes, dry_run),
 
         Command::PrintConfig => print_config(),
 
@@ -555,6 +820,22 @@ fn main() {
 
         Command::Hex(Hex::Revert { package, version }) => hex::revert(package, version),
 
+        Command::Hex(Hex::Owner(HexOwner::Add {
+            package,
+            owner,
+            level,
+        })) => hex::add_owner(package, owner, level),
+
+        Command::Hex(Hex::Owner(HexOwner::Remove { package, owner })) => {
+            hex::remove_owner(package, owner)
+        }
+
+        Command::Hex(Hex::Owner(HexOwner::List { package })) => hex::list_owners(package),
+
+        Command::Hex(Hex::Key(HexKey::List)) => hex::list_keys(),
+
+        Command::Hex(Hex::Key(HexKey::Revoke { name })) => hex::revoke_key(name),
+
         Command::Add { packages, dev } => add::command(packages, dev),
 
         Command::Remove { packages } => remove::command(packages),
@@ -566,12 +847,24 @@ fn main() {
         Command::LanguageServer => lsp::main(),
 
         Command::Export(ExportTarget::ErlangShipment) => export::erlang_shipment(),
+        Command::Export(ExportTarget::ErlangRelease { include_erts }) => {
+            export::erlang_release(include_erts)
+        }
+        Command::Export(ExportTarget::Escript) => export::escript(),
         Command::Export(ExportTarget::HexTarball) => export::hex_tarball(),
         Command::Export(ExportTarget::JavascriptPrelude) => export::javascript_prelude(),
         Command::Export(ExportTarget::TypescriptPrelude) => export::typescript_prelude(),
         Command::Export(ExportTarget::PackageInterface { output }) => {
             export::package_interface(output)
         }
+
+        Command::Export(ExportTarget::Sbom {
+            format,
+            exclude_dev_dependencies,
+            output,
+        }) => export::sbom(format, !exclude_dev_dependencies, output),
+
+        Command::Ffi(Ffi::Generate { from, module, out }) => ffi::generate(from, module, out),
     };
 
     match result {
@@ -588,18 +881,24 @@ fn main() {
     }
 }
 
-fn command_check(target: Option<Target>) -> Result<()> {
-    let _ = build::main(
+fn command_check(
+    target: Option<Target>,
+    warning_levels: HashMap<WarningKind, WarningLevel>,
+) -> Result<()> {
+    let _ = build::all(
         Options {
             root_target_support: TargetSupport::Enforced,
             warnings_as_errors: false,
+            warning_levels,
             codegen: Codegen::DepsOnly,
             compile: Compile::All,
             mode: Mode::Dev,
             target,
             no_print_progress: false,
+            tooling: false,
         },
         build::download_dependencies(cli::Reporter::new())?,
+        Rc::new(fs::ConsoleWarningEmitter),
     )?;
     Ok(())
 }
@@ -607,6 +906,7 @@ fn command_check(target: Option<Target>) -> Result<()> {
 fn command_build(
     target: Option<Target>,
     warnings_as_errors: bool,
+    warning_levels: HashMap<WarningKind, WarningLevel>,
     no_print_progress: bool,
 ) -> Result<()> {
     let manifest = if no_print_progress {
@@ -614,17 +914,20 @@ fn command_build(
     } else {
         build::download_dependencies(cli::Reporter::new())?
     };
-    let _ = build::main(
+    let _ = build::all(
         Options {
             root_target_support: TargetSupport::Enforced,
             warnings_as_errors,
+            warning_levels,
             codegen: Codegen::All,
             compile: Compile::All,
             mode: Mode::Dev,
             target,
             no_print_progress,
+            tooling: false,
         },
         manifest,
+        Rc::new(fs::ConsoleWarningEmitter),
     )?;
     Ok(())
 }
@@ -635,6 +938,13 @@ fn print_config() -> Result<()> {
     Ok(())
 }
 
+fn explain(code: String) -> Result<()> {
+    let explanation =
+        gleam_core::explain::explanation(&code).ok_or(Error::UnknownErrorCode { code })?;
+    print!("{explanation}");
+    Ok(())
+}
+
 fn clean() -> Result<()> {
     let paths = find_project_paths()?;
     fs::delete_directory(&paths.build_directory())
@@ -653,7 +963,15 @@ fn initialise_logger() {
 
 fn find_project_paths() -> Result<ProjectPaths> {
     let current_dir = get_current_directory()?;
-    get_project_root(current_dir).map(ProjectPaths::new)
+    let root = get_project_root(current_dir)?;
+    // The packages of a workspace keep their manifest and build directory at
+    // its root.
+    Ok(
+        match gleam_core::workspace::find_root(&fs::ProjectIO::new(), &root) {
+            Some(workspace_root) => ProjectPaths::in_workspace(root, workspace_root),
+            None => ProjectPaths::new(root),
+        },
+    )
 }
 
 #[cfg(test)]
@@ -664,12 +982,12 @@ fn project_paths_at_current_directory_without_toml() -> ProjectPaths {
 
 fn download_dependencies() -> Result<()> {
     let paths = find_project_paths()?;
-    _ = dependencies::download(
+    let manifest = dependencies::download(
         &paths,
         cli::Reporter::new(),
         None,
         Vec::new(),
         UseManifest::Yes,
     )?;
-    Ok(())
+    dependencies::licences::check_policy(&paths, &manifest)
 }
@@ -0,0 +1,131 @@
+use std::{collections::HashMap, rc::Rc};
+
+use camino::{Utf8Path, Utf8PathBuf};
+use ecow::EcoString;
+use gleam_core::{
+    analyse::TargetSupport,
+    build::{Codegen, Compile, Mode, Options},
+    fix, move_module,
+    paths::ProjectPaths,
+    warning::VectorWarningEmitterIO,
+    Error, Result,
+};
+
+use crate::{build, cli, fs::ProjectIO};
+
+pub fn run(old: String, new: String) -> Result<()> {
+    let paths = crate::find_project_paths()?;
+
+    let (directory, old) = parse_module(&paths, &old, None)?;
+    let (new_directory, new) = parse_module(&paths, &new, Some(&directory))?;
+    let old_path = directory.join(format!("{old}.gleam"));
+    let new_path = new_directory.join(format!("{new}.gleam"));
+
+    if !old_path.is_file() {
+        return Err(Error::ModuleDoesNotExist {
+            module: old,
+            suggestion: None,
+        });
+    }
+    if new_path.exists() {
+        return Err(Error::OutputFilesAlreadyExist {
+            file_names: vec![new_path],
+        });
+    }
+
+    let built = build::main_with_warnings(
+        Options {
+            root_target_support: TargetSupport::Enforced,
+            warnings_as_errors: false,
+            warning_levels: HashMap::new(),
+            codegen: Codegen::DepsOnly,
+            compile: Compile::All,
+            mode: Mode::Dev,
+            target: None,
+            no_print_progress: false,
+            // All the modules are type checked again rather than loaded from
+            // the cache, to have the code of all the modules importing the
+            // moved one.
+            tooling: true,
+        },
+        build::download_dependencies(cli::Reporter::new())?,
+        Rc::new(VectorWarningEmitterIO::new()),
+    )?;
+
+    for module in &built.root_package.modules {
+        let edits = move_module::module_move_edits(module, &old, &new);
+        if edits.is_empty() {
+            continue;
+        }
+        crate::fs::write(&module.input_path, &fix::apply_edits(&module.code, &edits))?;
+        let path = module
+            .input_path
+            .strip_prefix(paths.root())
+            .unwrap_or(&module.input_path);
+        println!("- Updated {path}");
+    }
+
+    let native_files = move_module::native_file_moves(&ProjectIO::new(), &old_path, &new_path);
+    if let Some(parent) = new_path.parent() {
+        crate::fs::mkdir(parent)?;
+    }
+    crate::fs::rename(&old_path, &new_path)?;
+    for (old_native_path, new_native_path) in native_files {
+        crate::fs::rename(old_native_path, new_native_path)?;
+    }
+
+    println!("- Moved {old} to {new}");
+    println!("Done!");
+    Ok(())
+}
+
+/// Reads a module given on the command line either by its name or by the path
+/// of its file, returning the directory it belongs to along with its name.
+///
+/// A module given by name is looked up in the `default` directory if there is
+/// one, otherwise in `src` or in `test` if it's only found there.
+///
+fn parse_module(
+    paths: &ProjectPaths,
+    module: &str,
+    default: Option<&Utf8Path>,
+) -> Result<(Utf8PathBuf, EcoString)> {
+    let invalid = || Error::InvalidModuleName {
+        module: module.into(),
+    };
+
+    let (directory, name) = if module.ends_with(".gleam") {
+        let path = Utf8Path::new(module.strip_prefix("./").unwrap_or(module));
+        let (directory, path) = if let Ok(path) = path.strip_prefix("src") {
+            (paths.src_directory(), path)
+        } else if let Ok(path) = path.strip_prefix("test") {
+            (paths.test_directory(), path)
+        } else {
+            return Err(invalid());
+        };
+        let name = move_module::module_name_from_path(path).ok_or_else(invalid)?;
+        (directory, name)
+    } else {
+        let directory = match default {
+            Some(directory) => directory.to_path_buf(),
+            None if !paths
+                .src_directory()
+                .join(format!("{module}.gleam"))
+                .is_file()
+                && paths
+                    .test_directory()
+                    .join(format!("{module}.gleam"))
+                    .is_file() =>
+            {
+                paths.test_directory()
+            }
+            None => paths.src_directory(),
+        };
+        (directory, module.into())
+    };
+
+    if !crate::run::is_gleam_module(&name) {
+        return Err(invalid());
+    }
+    Ok((directory, name))
+}
@@ -5,7 +5,7 @@ use gleam_core::{
     build::{Codegen, Compile, Mode, Options, Package, Target},
     config::{PackageConfig, SpdxLicense},
     docs::DocContext,
-    error::{wrap, SmallVersion},
+    error::{wrap, SmallVersion, StandardIoAction},
     hex,
     paths::{self, ProjectPaths},
     requirement::Requirement,
@@ -14,17 +14,17 @@ use gleam_core::{
 use hexpm::version::{Range, Version};
 use itertools::Itertools;
 use sha2::Digest;
-use std::{io::Write, path::PathBuf, time::Instant};
+use std::{collections::HashMap, io::Write, path::PathBuf, time::Instant};
 
 use crate::{build, cli, docs, fs, http::HttpClient};
 
-pub fn command(replace: bool, i_am_sure: bool) -> Result<()> {
+pub fn command(replace: bool, i_am_sure: bool, dry_run: bool) -> Result<()> {
     let paths = crate::find_project_paths()?;
     let mut config = crate::config::root_config()?;
 
-    let should_publish = check_for_gleam_prefix(&config)?
-        && check_for_version_zero(&config)?
-        && check_repo_url(&config, i_am_sure)?;
+    let should_publish = check_for_gleam_prefix(&config, dry_run)?
+        && check_for_version_zero(&config, dry_run)?
+        && check_repo_url(&config, i_am_sure, dry_run)?;
 
     if !should_publish {
         println!("Not publishing.");
@@ -36,10 +36,11 @@ pub fn command(replace: bool, i_am_sure: bool) -> Result<()> {
         data: package_tarball,
         src_files_added,
         generated_files_added,
+        metadata,
     } = do_build_hex_tarball(&paths, &mut config)?;
 
     check_for_name_squatting(&compile_result)?;
-    check_for_multiple_top_level_modules(&compile_result, i_am_sure)?;
+    check_for_multiple_top_level_modules(&compile_result, i_am_sure, dry_run)?;
 
     // Build HTML documentation
     let docs_tarball = fs::create_tar_archive(docs::build_documentation(
@@ -48,6 +49,28 @@ pub fn command(replace: bool, i_am_sure: bool) -> Result<()> {
         DocContext::HexPublish,
     )?)?;
 
+    // Show everything that would be published, without asking for Hex
+    // credentials, so that releases can be checked before they are made.
+    if dry_run {
+        let src_files = src_files_added
+            .iter()
+            .sorted()
+            .map(|path| Ok((path.as_path(), fs::file_size(path)?)))
+            .collect::<Result<Vec<_>>>()?;
+        return write_dry_run_report(
+            std::io::stdout(),
+            &DryRunReport {
+                name: &config.name,
+                version: &config.version,
+                generated_files: &generated_files_added,
+                src_files: &src_files,
+                metadata: &metadata,
+                package_size: package_tarball.len(),
+                docs_size: docs_tarball.len(),
+            },
+        );
+    }
+
     // Ask user if this is correct
     if !generated_files_added.is_empty() {
         println!("\nGenerated files:");
@@ -148,7 +171,11 @@ fn check_for_name_squatting(package: &Package) -> Result<(), Error> {
     Ok(())
 }
 
-fn check_for_multiple_top_level_modules(package: &Package, i_am_sure: bool) -> Result<(), Error> {
+fn check_for_multiple_top_level_modules(
+    package: &Package,
+    i_am_sure: bool,
+    dry_run: bool,
+) -> Result<(), Error> {
     // Collect top-level module names
     let mut top_level_module_names = package
         .modules
@@ -179,8 +206,11 @@ For example:
         ));
         println!("{text}\n");
 
-        let should_publish =
-            i_am_sure || cli::confirm("\nDo you wish to continue publishing this package?")?;
+        let should_publish = confirm(
+            "\nDo you wish to continue publishing this package?",
+            i_am_sure,
+            dry_run,
+        )?;
         println!();
 
         if !should_publish {
@@ -192,7 +222,7 @@ For example:
     Ok(())
 }
 
-fn check_repo_url(config: &PackageConfig, i_am_sure: bool) -> Result<bool, Error> {
+fn check_repo_url(config: &PackageConfig, i_am_sure: bool, dry_run: bool) -> Result<bool, Error> {
     let Some(url) = config.repository.url() else {
         return Ok(true);
     };
@@ -210,13 +240,13 @@ valid, {} returned status {}",
         &url,
         response.status()
     );
-    let should_publish = i_am_sure || cli::confirm("\nDo you wish to continue?")?;
+    let should_publish = confirm("\nDo you wish to continue?", i_am_sure, dry_run)?;
     println!();
     Ok(should_publish)
 }
 
 /// Ask for confirmation if the package name if a v0.x.x version
-fn check_for_version_zero(config: &PackageConfig) -> Result<bool, Error> {
+fn check_for_version_zero(config: &PackageConfig, dry_run: bool) -> Result<bool, Error> {
     if config.version.major != 0 {
         return Ok(true);
     }
@@ -233,13 +263,13 @@ If your package is not ready to be used in production it should not
 be published.
 \n"
     );
-    let should_publish = cli::confirm_with_text("I am not using semantic versioning")?;
+    let should_publish = confirm_with_text("I am not using semantic versioning", dry_run)?;
     println!();
     Ok(should_publish)
 }
 
 /// Ask for confirmation if the package name if `gleam_*`
-fn check_for_gleam_prefix(config: &PackageConfig) -> Result<bool, Error> {
+fn check_for_gleam_prefix(config: &PackageConfig, dry_run: bool) -> Result<bool, Error> {
     if !config.name.starts_with("gleam_") || config.name.starts_with("gleam_community_") {
         return Ok(true);
     }
@@ -249,16 +279,39 @@ fn check_for_gleam_prefix(config: &PackageConfig) -> Result<bool, Error> {
 the prefix `gleam_`, which is for packages maintained by the Gleam
 core team.\n",
     );
-    let should_publish = cli::confirm_with_text("I am part of the Gleam core team")?;
+    let should_publish = confirm_with_text("I am part of the Gleam core team", dry_run)?;
     println!();
     Ok(should_publish)
 }
 
+/// A dry run doesn't wait for answers, as it is meant to be run in CI, so
+/// the questions are printed for the release to be checked by someone and
+/// the checks carry on.
+///
+fn confirm(question: &str, i_am_sure: bool, dry_run: bool) -> Result<bool> {
+    if dry_run {
+        println!("{question} (not asked in a dry run)");
+        Ok(true)
+    } else {
+        Ok(i_am_sure || cli::confirm(question)?)
+    }
+}
+
+fn confirm_with_text(response: &str, dry_run: bool) -> Result<bool> {
+    if dry_run {
+        println!("Type '{response}' to continue (not asked in a dry run)");
+        Ok(true)
+    } else {
+        cli::confirm_with_text(response)
+    }
+}
+
 struct Tarball {
     compile_result: Package,
     data: Vec<u8>,
     src_files_added: Vec<Utf8PathBuf>,
     generated_files_added: Vec<(Utf8PathBuf, String)>,
+    metadata: String,
 }
 
 pub fn build_hex_tarball(paths: &ProjectPaths, config: &mut PackageConfig) -> Result<Vec<u8>> {
@@ -278,11 +331,13 @@ fn do_build_hex_tarball(paths: &ProjectPaths, config: &mut PackageConfig) -> Res
         Options {
             root_target_support: TargetSupport::Enforced,
             warnings_as_errors: false,
+            warning_levels: HashMap::new(),
             mode: Mode::Prod,
             target: Some(target),
             codegen: Codegen::All,
             compile: Compile::All,
             no_print_progress: false,
+            tooling: false,
         },
         build::download_dependencies(cli::Reporter::new())?,
     )?;
@@ -374,6 +429,7 @@ fn do_build_hex_tarball(paths: &ProjectPaths, config: &mut PackageConfig) -> Res
         data: tarball,
         src_files_added: src_files,
         generated_files_added: generated_files,
+        metadata,
     })
 }
 
@@ -523,6 +579,59 @@ fn generated_erlang_files(
     Ok(files)
 }
 
+struct DryRunReport<'a> {
+    name: &'a str,
+    version: &'a Version,
+    generated_files: &'a [(Utf8PathBuf, String)],
+    /// The source files, sorted, with their size in bytes.
+    src_files: &'a [(&'a Utf8Path, u64)],
+    metadata: &'a str,
+    package_size: usize,
+    docs_size: usize,
+}
+
+fn write_dry_run_report<W: Write>(mut buffer: W, report: &DryRunReport<'_>) -> Result<()> {
+    let mut write = || -> std::io::Result<()> {
+        if !report.generated_files.is_empty() {
+            writeln!(buffer, "\nGenerated files:")?;
+            for (path, contents) in report.generated_files.iter().sorted() {
+                let size = human_size(contents.len() as u64);
+                writeln!(buffer, "  - {path} ({size})")?;
+            }
+        }
+        writeln!(buffer, "\nSource files:")?;
+        for (path, size) in report.src_files {
+            writeln!(buffer, "  - {path} ({})", human_size(*size))?;
+        }
+        writeln!(buffer, "\nMetadata:\n{}", report.metadata.trim_end())?;
+        writeln!(buffer, "\nName: {}", report.name)?;
+        writeln!(buffer, "Version: {}", report.version)?;
+        writeln!(
+            buffer,
+            "Package tarball: {}",
+            human_size(report.package_size as u64)
+        )?;
+        writeln!(
+            buffer,
+            "Documentation tarball: {}",
+            human_size(report.docs_size as u64)
+        )?;
+        writeln!(buffer, "\nThis was a dry run, nothing has been published.")
+    };
+    write().map_err(|e| Error::StandardIo {
+        action: StandardIoAction::Write,
+        err: Some(e.kind()),
+    })
+}
+
+fn human_size(bytes: u64) -> String {
+    match bytes {
+        0..=1023 => format!("{bytes} B"),
+        1024..=1_048_575 => format!("{:.1} KiB", bytes as f64 / 1024.0),
+        _ => format!("{:.1} MiB", bytes as f64 / 1_048_576.0),
+    }
+}
+
 fn add_to_tar<P, W>(tarball: &mut tar::Builder<W>, path: P, data: &[u8]) -> Result<()>
 where
     P: AsRef<Utf8Path>,
@@ -753,3 +862,50 @@ fn prevent_publish_git_dependency() {
 fn quotes(x: &str) -> String {
     format!(r#"<<"{x}">>"#)
 }
+
+#[test]
+fn dry_run_report() {
+    let version = "1.2.3".try_into().unwrap();
+    let mut buffer = vec![];
+    write_dry_run_report(
+        &mut buffer,
+        &DryRunReport {
+            name: "myapp",
+            version: &version,
+            generated_files: &[
+                (Utf8PathBuf::from("src/myapp.erl"), "-module(myapp).".into()),
+                (Utf8PathBuf::from("src/myapp.app.src"), "".into()),
+            ],
+            src_files: &[
+                (Utf8Path::new("gleam.toml"), 120),
+                (Utf8Path::new("src/myapp.gleam"), 2048),
+            ],
+            metadata: "{<<\"name\">>, <<\"myapp\">>}.\n",
+            package_size: 3_500_000,
+            docs_size: 20_000,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        std::str::from_utf8(&buffer).unwrap(),
+        r#"
+Generated files:
+  - src/myapp.app.src (0 B)
+  - src/myapp.erl (15 B)
+
+Source files:
+  - gleam.toml (120 B)
+  - src/myapp.gleam (2.0 KiB)
+
+Metadata:
+{<<"name">>, <<"myapp">>}.
+
+Name: myapp
+Version: 1.2.3
+Package tarball: 3.3 MiB
+Documentation tarball: 19.5 KiB
+
+This was a dry run, nothing has been published.
+"#
+    );
+}
@@ -1,6 +1,6 @@
-use std::sync::OnceLock;
+use std::{collections::HashMap, rc::Rc, sync::OnceLock};
 
-use camino::Utf8PathBuf;
+use camino::{Utf8Path, Utf8PathBuf};
 use ecow::EcoString;
 use gleam_core::{
     analyse::TargetSupport,
@@ -8,11 +8,19 @@ use gleam_core::{
     config::{DenoFlag, PackageConfig},
     error::Error,
     io::{CommandExecutor, Stdio},
+    manifest::Manifest,
     paths::ProjectPaths,
     type_::ModuleFunction,
 };
 
-use crate::{config::PackageKind, fs::ProjectIO};
+use crate::{
+    config::PackageKind,
+    fs::{ConsoleWarningEmitter, ProjectIO},
+    workspace::Workspace,
+};
+
+#[cfg(test)]
+mod tests;
 
 #[derive(Debug, Clone, Copy)]
 pub enum Which {
@@ -53,6 +61,25 @@ pub fn command(
         crate::build::download_dependencies(crate::cli::Reporter::new())?
     };
 
+    // At the root of a workspace the tests of each of its members are run.
+    if let Which::Test = which {
+        if let Some(workspace) = Workspace::at_root(&paths)? {
+            // Don't exit on ctrl+c as it is used by child erlang shell
+            ctrlc::set_handler(move || {}).expect("Error setting Ctrl-C handler");
+
+            let status = test_workspace(
+                workspace,
+                manifest,
+                arguments,
+                target,
+                runtime,
+                telemetry,
+                no_print_progress,
+            )?;
+            std::process::exit(status);
+        }
+    }
+
     // Get the config for the module that is being run to check the target.
     // Also get the kind of the package the module belongs to: wether the module
     // belongs to a dependency or to the root package.
@@ -76,6 +103,7 @@ pub fn command(
 
     let options = Options {
         warnings_as_errors: false,
+        warning_levels: HashMap::new(),
         compile: match package_kind {
             // If we're trying to run a dependecy module we do not compile and
             // check the root package. So we can run the main function from a
@@ -95,6 +123,7 @@ pub fn command(
             PackageKind::Dependency => TargetSupport::NotEnforced,
         },
         no_print_progress,
+        tooling: false,
     };
 
     let built = crate::build::main(options, manifest)?;
@@ -108,30 +137,107 @@ pub fn command(
     telemetry.running(&format!("{module}.main"));
 
     // Run the command
-    let status = match target {
+    let status = run_module(
+        &paths,
+        &root_config,
+        &mod_config,
+        &main_function.package,
+        &module,
+        target,
+        runtime,
+        arguments,
+        None,
+    )?;
+
+    std::process::exit(status);
+}
+
+/// Runs the tests of each of the members of a workspace in turn, from their
+/// own directories, returning the status of the first one to fail.
+///
+fn test_workspace(
+    workspace: Workspace,
+    manifest: Manifest,
+    arguments: Vec<String>,
+    target: Option<Target>,
+    runtime: Option<Runtime>,
+    telemetry: &dyn Telemetry,
+    no_print_progress: bool,
+) -> Result<i32, Error> {
+    let mut status = 0;
+    for member in workspace.members {
+        let target = target.unwrap_or(member.config.target);
+        let options = Options {
+            warnings_as_errors: false,
+            warning_levels: HashMap::new(),
+            compile: Compile::All,
+            codegen: Codegen::All,
+            mode: Mode::Dev,
+            target: Some(target),
+            root_target_support: TargetSupport::Enforced,
+            no_print_progress,
+            tooling: false,
+        };
+        let built = crate::build::compile(
+            &member.paths,
+            member.config.clone(),
+            options,
+            manifest.clone(),
+            Rc::new(ConsoleWarningEmitter),
+        )?;
+
+        let module = format!("{}_test", member.config.name);
+        let main_function = get_or_suggest_main_function(built, &module, target)?;
+        telemetry.running(&format!("{module}.main"));
+        let member_status = run_module(
+            &member.paths,
+            &member.config,
+            &member.config,
+            &main_function.package,
+            &module,
+            target,
+            runtime,
+            arguments.clone(),
+            Some(member.paths.root()),
+        )?;
+        if status == 0 {
+            status = member_status;
+        }
+    }
+    Ok(status)
+}
+
+/// Runs the main function of a module, from the given directory or else from
+/// the current one.
+///
+#[allow(clippy::too_many_arguments)]
+fn run_module(
+    paths: &ProjectPaths,
+    root_config: &PackageConfig,
+    mod_config: &PackageConfig,
+    package: &str,
+    module: &str,
+    target: Target,
+    runtime: Option<Runtime>,
+    arguments: Vec<String>,
+    directory: Option<&Utf8Path>,
+) -> Result<i32, Error> {
+    match target {
         Target::Erlang => match runtime {
             Some(r) => Err(Error::InvalidRuntime {
                 target: Target::Erlang,
                 invalid_runtime: r,
             }),
-            _ => run_erlang(&paths, &root_config.name, &module, arguments),
+            _ => run_erlang(paths, &root_config.name, module, arguments, directory),
         },
         Target::JavaScript => match runtime.unwrap_or(mod_config.javascript.runtime) {
-            Runtime::Deno => run_javascript_deno(
-                &paths,
-                &root_config,
-                &main_function.package,
-                &module,
-                arguments,
-            ),
-            Runtime::NodeJs => {
-                run_javascript_node(&paths, &main_function.package, &module, arguments)
+            Runtime::Deno => {
+                run_javascript_deno(paths, root_config, package, module, arguments, directory)
             }
-            Runtime::Bun => run_javascript_bun(&paths, &main_function.package, &module, arguments),
+            Runtime::NodeJs => run_javascript_node(paths, package, module, arguments, directory),
+            Runtime::Bun => run_javascript_bun(paths, package, module, arguments, directory),
         },
-    }?;
-
-    std::process::exit(status);
+    }
 }
 
 fn run_erlang(
@@ -139,6 +245,7 @@ fn run_erlang(
     package: &str,
     module: &str,
     arguments: Vec<String>,
+    directory: Option<&Utf8Path>,
 ) -> Result<i32, Error> {
     let mut args = vec![];
 
@@ -165,7 +272,7 @@ fn run_erlang(
         args.push(argument);
     }
 
-    ProjectIO::new().exec("erl", &args, &[], None, Stdio::Inherit)
+    ProjectIO::new().exec("erl", &args, &[], directory, Stdio::Inherit)
 }
 
 fn run_javascript_bun(
@@ -173,6 +280,7 @@ fn run_javascript_bun(
     package: &str,
     module: &str,
     arguments: Vec<String>,
+    directory: Option<&Utf8Path>,
 ) -> Result<i32, Error> {
     let mut args = vec!["run".to_string()];
     let entry = write_javascript_entrypoint(paths, package, module)?;
@@ -183,7 +291,7 @@ fn run_javascript_bun(
         args.push(arg);
     }
 
-    ProjectIO::new().exec("bun", &args, &[], None, Stdio::Inherit)
+    ProjectIO::new().exec("bun", &args, &[], directory, Stdio::Inherit)
 }
 
 fn run_javascript_node(
@@ -191,6 +299,7 @@ fn run_javascript_node(
     package: &str,
     module: &str,
     arguments: Vec<String>,
+    directory: Option<&Utf8Path>,
 ) -> Result<i32, Error> {
     let mut args = vec![];
     let entry = write_javascript_entrypoint(paths, package, module)?;
@@ -201,7 +310,7 @@ fn run_javascript_node(
         args.push(argument);
     }
 
-    ProjectIO::new().exec("node", &args, &[], None, Stdio::Inherit)
+    ProjectIO::new().exec("node", &args, &[], directory, Stdio::Inherit)
 }
 
 fn write_javascript_entrypoint(
@@ -228,6 +337,7 @@ fn run_javascript_deno(
     package: &str,
     module: &str,
     arguments: Vec<String>,
+    directory: Option<&Utf8Path>,
 ) -> Result<i32, Error> {
     let mut args = vec![];
 
@@ -295,7 +405,7 @@ fn run_javascript_deno(
         args.push(argument);
     }
 
-    ProjectIO::new().exec("deno", &args, &[], None, Stdio::Inherit)
+    ProjectIO::new().exec("deno", &args, &[], directory, Stdio::Inherit)
 }
 
 fn add_deno_flag(args: &mut Vec<String>, flag: &str, flags: &DenoFlag) {
@@ -310,7 +420,7 @@ fn add_deno_flag(args: &mut Vec<String>, flag: &str, flags: &DenoFlag) {
 }
 
 /// Check if a module name is a valid gleam module name.
-fn is_gleam_module(module: &str) -> bool {
+pub(crate) fn is_gleam_module(module: &str) -> bool {
     use regex::Regex;
     static RE: OnceLock<Regex> = OnceLock::new();
 
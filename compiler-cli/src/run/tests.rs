@@ -0,0 +1,87 @@
+use std::collections::HashMap;
+
+use camino::{Utf8Path, Utf8PathBuf};
+use pretty_assertions::assert_eq;
+
+use gleam_core::{
+    build::{NullTelemetry, Runtime, Target},
+    manifest::Manifest,
+};
+
+use crate::workspace::Workspace;
+
+fn write(path: Utf8PathBuf, content: &str) {
+    crate::fs::write(&path, content).expect("Failed to write file");
+}
+
+/// A workspace of two members, the test of the second one having the given
+/// main function body.
+///
+fn write_workspace(root: &Utf8Path, lib_test: &str) {
+    write(
+        root.join("gleam.toml"),
+        "[workspace]\nmembers = [\"packages/app\", \"packages/lib\"]\n",
+    );
+    for name in ["app", "lib"] {
+        write(
+            root.join(format!("packages/{name}/gleam.toml")),
+            &format!("name = \"{name}\"\nversion = \"1.0.0\"\ntarget = \"javascript\"\n"),
+        );
+        write(
+            root.join(format!("packages/{name}/src/{name}.gleam")),
+            "pub fn main() {\n  Nil\n}\n",
+        );
+    }
+    write(
+        root.join("packages/app/test/app_test.gleam"),
+        "pub fn main() {\n  Nil\n}\n",
+    );
+    write(
+        root.join("packages/lib/test/lib_test.gleam"),
+        &format!("pub fn main() {{\n  {lib_test}\n}}\n"),
+    );
+}
+
+fn test_workspace(root: &Utf8Path) -> i32 {
+    let workspace = Workspace::read(root).expect("Failed to read the workspace");
+    let manifest = Manifest {
+        requirements: HashMap::new(),
+        packages: vec![],
+    };
+    super::test_workspace(
+        workspace,
+        manifest,
+        vec![],
+        Some(Target::JavaScript),
+        Some(Runtime::NodeJs),
+        &NullTelemetry,
+        true,
+    )
+    .expect("Failed to run the tests")
+}
+
+#[test]
+fn test_workspace_runs_the_tests_of_each_member() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create a temp directory");
+    let root = Utf8PathBuf::from_path_buf(temp_dir.path().to_path_buf())
+        .expect("Path should be valid UTF-8");
+    write_workspace(&root, "Nil");
+
+    assert_eq!(test_workspace(&root), 0);
+    assert!(root
+        .join("build/dev/javascript/app/gleam.main.mjs")
+        .is_file());
+    assert!(root
+        .join("build/dev/javascript/lib/gleam.main.mjs")
+        .is_file());
+}
+
+#[test]
+fn test_workspace_fails_if_the_tests_of_a_member_fail() {
+    let temp_dir = tempfile::tempdir().expect("Failed to create a temp directory");
+    let root = Utf8PathBuf::from_path_buf(temp_dir.path().to_path_buf())
+        .expect("Path should be valid UTF-8");
+    write_workspace(&root, "panic as \"Failing test\"");
+
+    assert_eq!(test_workspace(&root), 1);
+}
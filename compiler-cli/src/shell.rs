@@ -3,7 +3,7 @@ use gleam_core::{
     build::{Codegen, Compile, Mode, Options, Target},
     error::Error,
 };
-use std::process::Command;
+use std::{collections::HashMap, process::Command};
 
 pub fn command() -> Result<(), Error> {
     let paths = crate::find_project_paths()?;
@@ -13,11 +13,13 @@ pub fn command() -> Result<(), Error> {
         Options {
             root_target_support: TargetSupport::Enforced,
             warnings_as_errors: false,
+            warning_levels: HashMap::new(),
             codegen: Codegen::All,
             compile: Compile::All,
             mode: Mode::Dev,
             target: Some(Target::Erlang),
             no_print_progress: false,
+            tooling: false,
         },
         crate::build::download_dependencies(crate::cli::Reporter::new())?,
     )?;
@@ -0,0 +1,60 @@
+//! Workspaces of several packages sharing one manifest and one build
+//! directory at the root of the workspace.
+
+use camino::Utf8Path;
+use gleam_core::{config::PackageConfig, paths::ProjectPaths, workspace::WorkspaceConfig, Result};
+
+use crate::fs::ProjectIO;
+
+#[derive(Debug)]
+pub struct Workspace {
+    pub config: WorkspaceConfig,
+    pub members: Vec<Member>,
+}
+
+#[derive(Debug)]
+pub struct Member {
+    pub paths: ProjectPaths,
+    pub config: PackageConfig,
+}
+
+impl Workspace {
+    /// Reads the workspace at the given root, and the configs of its members.
+    ///
+    pub fn read(root: &Utf8Path) -> Result<Self> {
+        let config =
+            WorkspaceConfig::read(&root.join("gleam.toml"), &ProjectIO::new())?.unwrap_or_default();
+        let members = config
+            .members
+            .iter()
+            .map(|directory| {
+                let paths = ProjectPaths::in_workspace(root.join(directory), root.to_path_buf());
+                let config = crate::config::read(paths.root_config())?;
+                Ok(Member { paths, config })
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { config, members })
+    }
+
+    /// The workspace these are the paths of the root of, if they are.
+    ///
+    pub fn at_root(paths: &ProjectPaths) -> Result<Option<Self>> {
+        if paths.is_workspace_root() {
+            Self::read(paths.root()).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// The config of the package standing for the whole workspace, which the
+    /// dependencies of the members are resolved for.
+    ///
+    pub fn package_config(&self) -> Result<PackageConfig> {
+        let members = self
+            .members
+            .iter()
+            .map(|member| member.config.clone())
+            .collect::<Vec<_>>();
+        self.config.package_config(&members)
+    }
+}
@@ -0,0 +1,59 @@
+#!/bin/sh
+set -eu
+
+PACKAGE=$PACKAGE_NAME_FROM_GLEAM
+RELEASE_ROOT=$(cd "$(dirname "$0")/.." && pwd)
+read -r ERTS_VSN RELEASE_VSN < "$RELEASE_ROOT/releases/start_erl.data"
+RELEASE_DIR="$RELEASE_ROOT/releases/$RELEASE_VSN"
+COMMAND="${1-default}"
+
+# Use the Erlang runtime system bundled with the release if there is one,
+# otherwise use the one installed on this machine.
+if [ -d "$RELEASE_ROOT/erts-$ERTS_VSN" ]; then
+  ROOTDIR="$RELEASE_ROOT"
+  BINDIR="$RELEASE_ROOT/erts-$ERTS_VSN/bin"
+  EMU=beam
+  PROGNAME=erl
+  export ROOTDIR BINDIR EMU PROGNAME
+  ERL="$BINDIR/erlexec"
+else
+  ERL=erl
+fi
+
+boot() {
+  "$ERL" \
+    -boot "$RELEASE_DIR/start" \
+    -boot_var RELEASE_LIB "$RELEASE_ROOT/lib" \
+    -config "$RELEASE_DIR/sys" \
+    -args_file "$RELEASE_DIR/vm.args" \
+    -sasl releases_dir "\"$RELEASE_ROOT/releases\"" \
+    -mode embedded \
+    "$@"
+}
+
+case "$COMMAND" in
+start)
+  shift
+  boot -noshell -noinput -extra "$@"
+  ;;
+
+run)
+  shift
+  boot -noshell -eval "$PACKAGE@@main:run($PACKAGE)" -extra "$@"
+  ;;
+
+shell)
+  boot
+  ;;
+
+*)
+  echo "usage:" >&2
+  echo "  $(basename "$0") \$COMMAND" >&2
+  echo "" >&2
+  echo "commands:" >&2
+  echo "  start  Start the release in the foreground" >&2
+  echo "  run    Start the release and run the project main function" >&2
+  echo "  shell  Start the release with an Erlang shell" >&2
+  exit 1
+  ;;
+esac
@@ -0,0 +1,34 @@
+#!/usr/bin/env escript
+-mode(compile).
+
+% Writes the attributes describing the interface of a compiled module, taken
+% from its debug info, as Erlang source. Used by `gleam ffi generate`.
+%
+% Arguments:
+%   Beam - the path of the compiled module
+%   Out - the path of the file the attributes are written to
+
+main([Beam, Out]) ->
+    case beam_lib:chunks(Beam, [debug_info]) of
+        {ok, {Module, [{debug_info, {debug_info_v1, Backend, Data}}]}} ->
+            case Backend:debug_info(erlang_v1, Module, Data, []) of
+                {ok, Forms} ->
+                    Source = [erl_pp:form(Form) || Form <- Forms, is_interface(Form)],
+                    ok = file:write_file(Out, unicode:characters_to_binary(Source));
+                {error, Reason} ->
+                    fail("Could not read the debug info of ~ts: ~p", [Beam, Reason])
+            end;
+        {ok, {_, [{debug_info, _}]}} ->
+            fail("~ts was compiled without debug info", [Beam]);
+        {error, beam_lib, Reason} ->
+            fail("~ts", [beam_lib:format_error(Reason)])
+    end.
+
+is_interface({attribute, _, Name, _}) ->
+    lists:member(Name, [module, export, compile, type, opaque, spec]);
+is_interface(_) ->
+    false.
+
+fail(Format, Arguments) ->
+    io:format(standard_error, Format ++ "~n", Arguments),
+    halt(1).
@@ -0,0 +1,129 @@
+#!/usr/bin/env escript
+-mode(compile).
+
+% Bundles the compiled applications of a Gleam project into a single escript.
+% Used by `gleam export escript`.
+%
+% Arguments:
+%   Out     - the path to write the escript to
+%   Package - the name of the Gleam project, whose main function is run
+%   AppDirs - the compiled applications of the project and its dependencies
+
+main([Out, Package | AppDirs]) ->
+    ok = io:setopts([binary, {encoding, utf8}]),
+    Entrypoint = list_to_atom(Package ++ "@@escript"),
+    PrivApps = [filename:basename(AppDir) || AppDir <- AppDirs, has_priv_files(AppDir)],
+    EntrypointBeam = {
+        filename:join([Package, "ebin", atom_to_list(Entrypoint) ++ ".beam"]),
+        compile_entrypoint(Entrypoint, Package, PrivApps)
+    },
+    Files = lists:flatmap(fun application_files/1, AppDirs),
+    Sections = [
+        shebang,
+        {emu_args, "-escript main " ++ atom_to_list(Entrypoint)},
+        {archive, [EntrypointBeam | Files], []}
+    ],
+    case escript:create(Out, Sections) of
+        ok -> ok;
+        {error, Reason} -> fail("Could not create escript: ~p", [Reason])
+    end.
+
+% The escript calls the `main/1` function of this module, which runs the main
+% function of the project the same way `gleam run` does.
+%
+% Files inside the archive of an escript can only be read with
+% `erl_prim_loader`, so before that the applications with priv files are
+% extracted to a cache directory, once per version of the escript, and added
+% to the front of the code path. `code:priv_dir` then finds their priv
+% directory there and `file:read_file` can read its files.
+compile_entrypoint(Entrypoint, Package, PrivApps) ->
+    Source = io_lib:format(
+        "-module('~ts').~n"
+        "-export([main/1]).~n"
+        "main(_) ->~n"
+        "    ok = extract_priv_apps(~p),~n"
+        "    '~ts@@main':run('~ts').~n"
+        "extract_priv_apps([]) ->~n"
+        "    ok;~n"
+        "extract_priv_apps(Apps) ->~n"
+        "    {ok, Sections} = escript:extract(escript:script_name(), []),~n"
+        "    {archive, Archive} = lists:keyfind(archive, 1, Sections),~n"
+        "    Version = integer_to_list(erlang:phash2(Archive)),~n"
+        "    Cache = filename:basedir(user_cache, \"gleam\"),~n"
+        "    Dir = filename:join([Cache, \"escript\", \"~ts\", Version]),~n"
+        "    case filelib:is_dir(Dir) of~n"
+        "        true -> ok;~n"
+        "        false -> extract(Archive, Apps, Dir)~n"
+        "    end,~n"
+        "    lists:foreach(~n"
+        "        fun(App) -> code:add_patha(filename:join([Dir, App, \"ebin\"])) end,~n"
+        "        Apps~n"
+        "    ).~n"
+        "extract(Archive, Apps, Dir) ->~n"
+        "    Temporary = Dir ++ \".\" ++ os:getpid(),~n"
+        "    InApps = fun(File) -> lists:member(hd(filename:split(element(2, File))), Apps) end,~n"
+        "    {ok, _} = zip:extract(Archive, [{cwd, Temporary}, {file_filter, InApps}]),~n"
+        "    case file:rename(Temporary, Dir) of~n"
+        "        ok -> ok;~n"
+        "        {error, _} -> file:del_dir_r(Temporary)~n"
+        "    end.~n",
+        [atom_to_list(Entrypoint), PrivApps, Package, Package, Package]
+    ),
+    {ok, Tokens, _} = erl_scan:string(lists:flatten(Source)),
+    Forms = [parse_form(Form) || Form <- split_forms(Tokens, [])],
+    {ok, Entrypoint, Beam} = compile:forms(Forms),
+    Beam.
+
+split_forms([], []) ->
+    [];
+split_forms([{dot, _} = Dot | Rest], Form) ->
+    [lists:reverse([Dot | Form]) | split_forms(Rest, [])];
+split_forms([Token | Rest], Form) ->
+    split_forms(Rest, [Token | Form]).
+
+parse_form(Tokens) ->
+    {ok, Form} = erl_parse:parse_form(Tokens),
+    Form.
+
+% The files of an application to include in the escript archive. Escripts
+% add the `ebin` directory of each application in the archive to the code
+% path.
+application_files(AppDir) ->
+    App = filename:basename(AppDir),
+    lists:flatmap(
+        fun(Directory) ->
+            Root = filename:join(AppDir, Directory),
+            [
+                {filename:join([App, Directory, File]), read(filename:join(Root, File))}
+             || File <- files(Root, "")
+            ]
+        end,
+        ["ebin", "priv"]
+    ).
+
+has_priv_files(AppDir) ->
+    files(filename:join(AppDir, "priv"), "") =/= [].
+
+% All the files in a directory, relative to it. Symlinks are followed.
+files(Root, Relative) ->
+    Path = filename:join(Root, Relative),
+    case filelib:is_dir(Path) of
+        true ->
+            {ok, Names} = file:list_dir(Path),
+            lists:flatmap(fun(Name) -> files(Root, join(Relative, Name)) end, lists:sort(Names));
+        false when Relative =:= "" ->
+            [];
+        false ->
+            [Relative]
+    end.
+
+join("", Name) -> Name;
+join(Directory, Name) -> filename:join(Directory, Name).
+
+read(Path) ->
+    {ok, Binary} = file:read_file(Path),
+    Binary.
+
+fail(Format, Arguments) ->
+    io:format(standard_error, Format ++ "~n", Arguments),
+    halt(1).
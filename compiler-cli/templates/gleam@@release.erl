@@ -0,0 +1,185 @@
+#!/usr/bin/env escript
+-mode(compile).
+
+% Assembles an OTP release out of the compiled applications of a Gleam project.
+% Used by `gleam export erlang-release`.
+%
+% Arguments:
+%   Out         - the directory to write the release to
+%   Name        - the name of the release
+%   Vsn         - the version of the release
+%   RootApp     - the application of the Gleam project
+%   IncludeErts - "true" if the Erlang runtime system is to be bundled
+%   UpFrom      - the version of the release previously written to Out, to
+%                 write the relup from, or "" if there is none
+%   AppDirs     - the compiled applications of the project and its dependencies
+
+-include_lib("kernel/include/file.hrl").
+
+main([Out, Name, Vsn, RootApp, IncludeErts, UpFrom | AppDirs]) ->
+    ok = io:setopts([binary, {encoding, utf8}]),
+    lists:foreach(fun(Dir) -> code:add_patha(filename:join(Dir, "ebin")) end, AppDirs),
+
+    Lib = filename:join(Out, "lib"),
+    Releases = filename:join(Out, "releases"),
+    ReleaseDir = filename:join(Releases, Vsn),
+    ok = filelib:ensure_dir(filename:join(ReleaseDir, "x")),
+
+    % `sasl` is always included as it holds the release handler, which is
+    % needed to perform hot upgrades.
+    Apps = applications([kernel, stdlib, sasl, list_to_atom(RootApp)], []),
+    Paths = [copy_application(App, Lib) || App <- Apps],
+
+    ErtsVsn = erlang:system_info(version),
+    RelFile = filename:join(ReleaseDir, Name),
+    Versions = [{App, vsn(App)} || App <- Apps],
+    Release = {release, {Name, Vsn}, {erts, ErtsVsn}, Versions},
+    ok = file:write_file(RelFile ++ ".rel", io_lib:format("~tp.~n", [Release])),
+
+    % The boot script refers to the applications relative to the
+    % `RELEASE_LIB` variable, set by the start script, so that the release
+    % can be moved anywhere.
+    Options = [{path, Paths}, {outdir, ReleaseDir}, {variables, [{"RELEASE_LIB", Lib}]}, silent],
+    case systools:make_script(RelFile, Options) of
+        {ok, _, _} -> ok;
+        {error, Module, Reason} -> fail("~ts", [Module:format_error(Reason)])
+    end,
+
+    case UpFrom of
+        "" -> ok;
+        _ -> make_relup(Releases, ReleaseDir, Lib, RelFile, Paths, Versions, UpFrom)
+    end,
+
+    ok = file:write_file(
+        filename:join(Releases, "start_erl.data"), [ErtsVsn, " ", Vsn, "\n"]
+    ),
+    ok = release_handler:create_RELEASES(Out, Releases, RelFile ++ ".rel", []),
+
+    case IncludeErts of
+        "true" ->
+            Erts = "erts-" ++ ErtsVsn,
+            copy(filename:join(code:root_dir(), Erts), filename:join(Out, Erts));
+        _ ->
+            ok
+    end.
+
+% Writes the relup of the release, which the release handler uses to upgrade
+% to it from the previous release and to downgrade back. The applications
+% whose version changed are given a stub appup restarting them, unless they
+% have an appup already.
+make_relup(Releases, ReleaseDir, Lib, RelFile, Paths, Versions, UpFrom) ->
+    UpFromRelFile = filename:join([Releases, UpFrom, filename:basename(RelFile)]),
+    {ok, [{release, _, _, UpFromVersions}]} = file:consult(UpFromRelFile ++ ".rel"),
+    lists:foreach(
+        fun({App, Vsn}) ->
+            case lists:keyfind(App, 1, UpFromVersions) of
+                {App, UpFromVsn} when UpFromVsn =/= Vsn -> write_appup(Lib, App, Vsn, UpFromVsn);
+                _ -> ok
+            end
+        end,
+        Versions
+    ),
+    UpFromPaths = [filename:join(app_directory(Lib, App, Vsn), "ebin") || {App, Vsn} <- UpFromVersions],
+    Options = [{path, Paths ++ UpFromPaths}, {outdir, ReleaseDir}, silent],
+    case systools:make_relup(RelFile, [UpFromRelFile], [UpFromRelFile], Options) of
+        ok -> ok;
+        {ok, _, _, _} -> ok;
+        {error, Module, Reason} -> fail("~ts", [Module:format_error(Reason)])
+    end.
+
+write_appup(Lib, App, Vsn, UpFromVsn) ->
+    Path = filename:join([app_directory(Lib, App, Vsn), "ebin", atom_to_list(App) ++ ".appup"]),
+    case filelib:is_file(Path) of
+        true ->
+            ok;
+        false ->
+            Instructions = [{restart_application, App}],
+            AppUp = {Vsn, [{UpFromVsn, Instructions}], [{UpFromVsn, Instructions}]},
+            ok = file:write_file(Path, io_lib:format("~tp.~n", [AppUp]))
+    end.
+
+% All the applications needed to start the given ones.
+applications([], Seen) ->
+    lists:reverse(Seen);
+applications([App | Rest], Seen) ->
+    case lists:member(App, Seen) of
+        true ->
+            applications(Rest, Seen);
+        false ->
+            case application:load(App) of
+                ok -> ok;
+                {error, {already_loaded, _}} -> ok;
+                {error, Reason} -> fail("Could not load application ~p: ~p", [App, Reason])
+            end,
+            Dependencies = key(App, applications) ++ key(App, included_applications),
+            applications(Rest ++ Dependencies, [App | Seen])
+    end.
+
+% Copies an application into the release and returns the path to its ebin
+% directory. The applications of the previous release are kept next to it,
+% but a copy of this same version is replaced.
+copy_application(App, Lib) ->
+    Source = code:lib_dir(App),
+    Destination = app_directory(Lib, App, vsn(App)),
+    _ = file:del_dir_r(Destination),
+    lists:foreach(
+        fun(Directory) ->
+            Path = filename:join(Source, Directory),
+            case filelib:is_dir(Path) of
+                true -> copy(Path, filename:join(Destination, Directory));
+                false -> ok
+            end
+        end,
+        ["ebin", "priv", "include"]
+    ),
+    Ebin = filename:join(Destination, "ebin"),
+    case lists:prefix(code:lib_dir(), Source) of
+        true -> ok;
+        false -> list_all_modules(App, Ebin)
+    end,
+    Ebin.
+
+% In embedded mode only the modules listed in the application resource file
+% are loaded. The ones generated for Gleam packages do not list the Erlang FFI
+% modules nor the entrypoint module, so all the modules in ebin are listed.
+list_all_modules(App, Ebin) ->
+    AppFile = filename:join(Ebin, atom_to_list(App) ++ ".app"),
+    {ok, [{application, App, Properties}]} = file:consult(AppFile),
+    Beams = filelib:wildcard("*.beam", Ebin),
+    Modules = [list_to_atom(filename:basename(Beam, ".beam")) || Beam <- Beams],
+    Resource = {application, App, lists:keystore(modules, 1, Properties, {modules, Modules})},
+    ok = file:write_file(AppFile, io_lib:format("~tp.~n", [Resource])).
+
+copy(Source, Destination) ->
+    case filelib:is_dir(Source) of
+        true ->
+            {ok, Names} = file:list_dir(Source),
+            ok = filelib:ensure_dir(filename:join(Destination, "x")),
+            lists:foreach(
+                fun(Name) -> copy(filename:join(Source, Name), filename:join(Destination, Name)) end,
+                Names
+            );
+        false ->
+            % Permissions are preserved so that executables such as ports and
+            % the runtime system binaries still work.
+            {ok, _} = file:copy(Source, Destination),
+            {ok, #file_info{mode = Mode}} = file:read_file_info(Source),
+            ok = file:change_mode(Destination, Mode)
+    end.
+
+app_directory(Lib, App, Vsn) ->
+    filename:join(Lib, atom_to_list(App) ++ "-" ++ Vsn).
+
+vsn(App) ->
+    {ok, Vsn} = application:get_key(App, vsn),
+    Vsn.
+
+key(App, Key) ->
+    case application:get_key(App, Key) of
+        {ok, Value} -> Value;
+        undefined -> []
+    end.
+
+fail(Format, Arguments) ->
+    io:format(standard_error, Format ++ "~n", Arguments),
+    halt(1).
@@ -1,7 +1,47 @@
+use std::{env, fs};
+
+use camino::Utf8PathBuf;
+
 fn main() {
     // capnpc::CompilerCommand::new()
     //     .file("schema.capnp")
     //     .output_path("generated/")
     //     .run()
     //     .expect("compiling schema.capnp");
+
+    explanations();
+}
+
+/// Writes the table of the explanations of the error and warning codes, with
+/// one entry for each markdown file in `src/explain` named after its code.
+///
+fn explanations() {
+    let directory = Utf8PathBuf::from(env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR"))
+        .join("src/explain");
+    println!("cargo:rerun-if-changed={directory}");
+
+    let mut explanations = fs::read_dir(&directory)
+        .expect("reading the explanations directory")
+        .map(|entry| {
+            Utf8PathBuf::try_from(entry.expect("reading an explanation").path())
+                .expect("explanation path")
+        })
+        .filter(|path| path.extension() == Some("md"))
+        .map(|path| {
+            let code = path.file_stem().expect("explanation file name").to_string();
+            (code, path)
+        })
+        .collect::<Vec<_>>();
+    explanations.sort();
+
+    let entries = explanations
+        .iter()
+        .map(|(code, path)| format!("    ({code:?}, include_str!({:?})),\n", path.as_str()))
+        .collect::<String>();
+    let out = Utf8PathBuf::from(env::var("OUT_DIR").expect("OUT_DIR")).join("explanations.rs");
+    fs::write(
+        out,
+        format!("const EXPLANATIONS: &[(&str, &str)] = &[\n{entries}];\n"),
+    )
+    .expect("writing the explanations table");
 }
@@ -32,7 +32,7 @@ use crate::{
         ValueConstructor, ValueConstructorVariant, Warning,
     },
     uid::UniqueIdGenerator,
-    warning::TypeWarningEmitter,
+    warning::{TypeWarningEmitter, WarningKind},
     GLEAM_CORE_PACKAGE_NAME,
 };
 use camino::Utf8PathBuf;
@@ -199,6 +199,7 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
         }
 
         let documentation = std::mem::take(&mut module.documentation);
+        let allowed_warnings = allowed_warnings(&module);
         let env = Environment::new(
             self.ids.clone(),
             self.package_config.name.clone(),
@@ -312,7 +313,16 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
         // make the output predictable.
         self.problems.sort();
 
-        let warnings = self.problems.take_warnings();
+        let warnings = self
+            .problems
+            .take_warnings()
+            .into_iter()
+            .filter(|warning| {
+                !allowed_warnings.iter().any(|(location, kinds)| {
+                    location.contains(warning.location().start) && kinds.contains(&warning.kind())
+                })
+            })
+            .collect_vec();
         for warning in &warnings {
             // TODO: remove this clone
             self.warnings.emit(warning.clone());
@@ -753,11 +763,12 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
             && module_info.package != self.package_config.name
             && !self.direct_dependencies.contains_key(&module_info.package)
         {
-            self.warnings.emit(Warning::TransitiveDependencyImported {
-                location,
-                module: module_info.name.clone(),
-                package: module_info.package.clone(),
-            })
+            self.problems
+                .warning(Warning::TransitiveDependencyImported {
+                    location,
+                    module: module_info.name.clone(),
+                    package: module_info.package.clone(),
+                })
         }
 
         Some(Definition::Import(Import {
@@ -1437,6 +1448,37 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
     }
 }
 
+/// The locations of the definitions with an `@allow` attribute, along with
+/// the warnings that are not reported for them.
+///
+fn allowed_warnings(module: &UntypedModule) -> Vec<(SrcSpan, Vec<WarningKind>)> {
+    module
+        .definitions
+        .iter()
+        .filter(|definition| !definition.allowed_warnings.is_empty())
+        .map(|definition| {
+            let location = match &definition.definition {
+                Definition::Function(Function {
+                    location,
+                    end_position,
+                    ..
+                })
+                | Definition::CustomType(CustomType {
+                    location,
+                    end_position,
+                    ..
+                }) => SrcSpan::new(location.start, *end_position),
+                Definition::ModuleConstant(ModuleConstant {
+                    location, value, ..
+                }) => SrcSpan::new(location.start, value.location().end),
+                Definition::TypeAlias(TypeAlias { location, .. })
+                | Definition::Import(Import { location, .. }) => *location,
+            };
+            (location, definition.allowed_warnings.clone())
+        })
+        .collect()
+}
+
 fn optionally_push<T>(vector: &mut Vec<T>, item: Option<T>) {
     if let Some(item) = item {
         vector.push(item)
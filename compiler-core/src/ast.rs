@@ -20,6 +20,7 @@ use crate::type_::printer::Names;
 use crate::type_::{
     self, Deprecation, ModuleValueConstructor, PatternConstructor, Type, ValueConstructor,
 };
+use crate::warning::WarningKind;
 use std::sync::Arc;
 
 use ecow::EcoString;
@@ -70,10 +71,19 @@ impl TypedModule {
 /// pub fn main(a) { ...}
 /// ```
 ///
+/// The warnings listed by an `@allow` attribute are not reported for the
+/// definition.
+///
+/// ```gleam
+/// @allow(unused_variable, todo)
+/// pub fn main(a) { todo }
+/// ```
+///
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TargetedDefinition {
     pub definition: UntypedDefinition,
     pub target: Option<Target>,
+    pub allowed_warnings: Vec<WarningKind>,
 }
 
 impl TargetedDefinition {
@@ -699,6 +709,18 @@ impl<T> Import<T> {
     pub(crate) fn alias_location(&self) -> Option<SrcSpan> {
         self.as_name.as_ref().map(|(_, location)| *location)
     }
+
+    /// The location of the path of the imported module, for example the
+    /// `wibble/wobble` in `import wibble/wobble.{Wubble} as wubble`.
+    ///
+    pub(crate) fn module_location(&self, code: &str) -> SrcSpan {
+        let keyword_end = self.location.start + "import".len() as u32;
+        let start = code
+            .get(keyword_end as usize..self.location.end as usize)
+            .and_then(|rest| rest.find(self.module.as_str()))
+            .map_or(self.location.start, |offset| keyword_end + offset as u32);
+        SrcSpan::new(start, start + self.module.len() as u32)
+    }
 }
 
 pub type UntypedModuleConstant = ModuleConstant<(), ()>;
@@ -825,16 +847,25 @@ impl TypedDefinition {
     pub fn find_node(&self, byte_index: u32) -> Option<Located<'_>> {
         match self {
             Definition::Function(function) => {
+                // Check if location is within the return annotation. This is
+                // done first as the location of a function without a body, such
+                // as an external one, ends before its return annotation.
+                if let Some(l) = function
+                    .return_annotation
+                    .iter()
+                    .find_map(|a| a.find_node(byte_index, function.return_type.clone()))
+                {
+                    return Some(l);
+                };
+
                 // Search for the corresponding node inside the function
                 // only if the index falls within the function's full location.
                 if !function.full_location().contains(byte_index) {
                     return None;
                 }
 
-                if let Some(found) = function.body.iter().find_map(|s| s.find_node(byte_index)) {
-                    return Some(found);
-                }
-
+                // The arguments are checked before the body as functions
+                // without one have a placeholder body spanning their head.
                 if let Some(found_arg) = function
                     .arguments
                     .iter()
@@ -843,6 +874,10 @@ impl TypedDefinition {
                     return Some(found_arg);
                 };
 
+                if let Some(found) = function.body.iter().find_map(|s| s.find_node(byte_index)) {
+                    return Some(found);
+                }
+
                 if let Some(found_statement) = function
                     .body
                     .iter()
@@ -851,15 +886,6 @@ impl TypedDefinition {
                     return Some(Located::Statement(found_statement));
                 };
 
-                // Check if location is within the return annotation.
-                if let Some(l) = function
-                    .return_annotation
-                    .iter()
-                    .find_map(|a| a.find_node(byte_index, function.return_type.clone()))
-                {
-                    return Some(l);
-                };
-
                 // Note that the fn `.location` covers the function head, not
                 // the entire statement.
                 if function.location.contains(byte_index) {
@@ -1666,7 +1692,9 @@ impl TypedClauseGuard {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Default, Clone, Copy, serde::Serialize, serde::Deserialize)]
+#[derive(
+    Debug, PartialEq, Eq, Hash, Default, Clone, Copy, serde::Serialize, serde::Deserialize,
+)]
 pub struct SrcSpan {
     pub start: u32,
     pub end: u32,
@@ -26,36 +26,60 @@ pub trait UntypedModuleFolder: TypeAstFolder + UntypedExprFolder {
             .definitions
             .into_iter()
             .map(|d| {
-                let TargetedDefinition { definition, target } = d;
+                let TargetedDefinition {
+                    definition,
+                    target,
+                    allowed_warnings,
+                } = d;
                 match definition {
                     Definition::Function(f) => {
                         let f = self.fold_function_definition(f, target);
                         let definition = self.walk_function_definition(f);
-                        TargetedDefinition { definition, target }
+                        TargetedDefinition {
+                            definition,
+                            target,
+                            allowed_warnings,
+                        }
                     }
 
                     Definition::TypeAlias(a) => {
                         let a = self.fold_type_alias(a, target);
                         let definition = self.walk_type_alias(a);
-                        TargetedDefinition { definition, target }
+                        TargetedDefinition {
+                            definition,
+                            target,
+                            allowed_warnings,
+                        }
                     }
 
                     Definition::CustomType(t) => {
                         let t = self.fold_custom_type(t, target);
                         let definition = self.walk_custom_type(t);
-                        TargetedDefinition { definition, target }
+                        TargetedDefinition {
+                            definition,
+                            target,
+                            allowed_warnings,
+                        }
                     }
 
                     Definition::Import(i) => {
                         let i = self.fold_import(i, target);
                         let definition = self.walk_import(i);
-                        TargetedDefinition { definition, target }
+                        TargetedDefinition {
+                            definition,
+                            target,
+                            allowed_warnings,
+                        }
                     }
 
                     Definition::ModuleConstant(c) => {
                         let c = self.fold_module_constant(c, target);
                         let definition = self.walk_module_constant(c);
-                        TargetedDefinition { definition, target }
+                        TargetedDefinition {
+                            definition,
+                            target,
+                            allowed_warnings,
+                        }
                     }
                 }
             })
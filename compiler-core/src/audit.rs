@@ -0,0 +1,257 @@
+//! Checks of the dependency packages of a project for known problems, as
+//! reported by `gleam deps audit`: Hex releases that have been retired, and
+//! versions affected by the advisories of an OSV database.
+
+#[cfg(test)]
+mod tests;
+
+use ecow::EcoString;
+use hexpm::{version::Version, RetirementReason, RetirementStatus};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumString, VariantNames};
+
+use crate::manifest::ManifestPackage;
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Display,
+    EnumString,
+    VariantNames,
+    Serialize,
+)]
+#[strum(serialize_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Low,
+    Moderate,
+    High,
+    Critical,
+}
+
+/// A problem with a dependency package.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Finding {
+    pub package: EcoString,
+    pub version: String,
+    pub severity: Severity,
+    /// What the problem is: the reason the release was retired for, or the ID
+    /// of the advisory.
+    pub reason: EcoString,
+    pub message: EcoString,
+}
+
+/// The finding for a retired Hex release. Releases retired for security
+/// reasons are considered to be of high severity, invalid ones of moderate
+/// severity, and others, such as deprecated or renamed ones, of low severity.
+///
+pub fn retirement_finding(package: &ManifestPackage, status: &RetirementStatus) -> Finding {
+    let (reason, severity) = match status.reason {
+        RetirementReason::Security => ("security", Severity::High),
+        RetirementReason::Invalid => ("invalid", Severity::Moderate),
+        RetirementReason::Deprecated => ("deprecated", Severity::Low),
+        RetirementReason::Renamed => ("renamed", Severity::Low),
+        RetirementReason::Other => ("other", Severity::Low),
+    };
+    Finding {
+        package: package.name.clone(),
+        version: package.version.to_string(),
+        severity,
+        reason: format!("retired ({reason})").into(),
+        message: status.message.as_str().into(),
+    }
+}
+
+/// Security advisories in the OSV format, which an advisory database file
+/// holds either as a single advisory or as a list of them.
+///
+/// <https://ossf.github.io/osv-schema/>
+///
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AdvisoryDatabase {
+    advisories: Vec<Advisory>,
+}
+
+impl AdvisoryDatabase {
+    pub fn parse(json: &str) -> Result<Self, serde_json::Error> {
+        let advisories = match serde_json::from_str(json)? {
+            value @ serde_json::Value::Array(_) => serde_json::from_value(value)?,
+            value => vec![serde_json::from_value(value)?],
+        };
+        Ok(Self { advisories })
+    }
+
+    /// The advisories of the Hex ecosystem affecting the given package.
+    ///
+    pub fn findings(&self, package: &ManifestPackage) -> Vec<Finding> {
+        self.advisories
+            .iter()
+            .filter(|advisory| {
+                advisory
+                    .affected
+                    .iter()
+                    .any(|affected| affected.affects(package))
+            })
+            .map(|advisory| Finding {
+                package: package.name.clone(),
+                version: package.version.to_string(),
+                severity: advisory.severity(),
+                reason: advisory.id.clone(),
+                message: advisory
+                    .summary
+                    .clone()
+                    .or_else(|| advisory.details.clone())
+                    .unwrap_or_default(),
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+struct Advisory {
+    id: EcoString,
+    #[serde(default)]
+    summary: Option<EcoString>,
+    #[serde(default)]
+    details: Option<EcoString>,
+    #[serde(default)]
+    affected: Vec<Affected>,
+    #[serde(default)]
+    database_specific: SeverityField,
+}
+
+impl Advisory {
+    /// OSV databases give severities in different ways, so the GitHub style
+    /// one is used, either for the whole advisory or for the affected
+    /// packages. Advisories without one are treated as critical so that they
+    /// are never left out.
+    ///
+    fn severity(&self) -> Severity {
+        self.database_specific
+            .severity()
+            .into_iter()
+            .chain(
+                self.affected
+                    .iter()
+                    .filter_map(|affected| affected.ecosystem_specific.severity()),
+            )
+            .max()
+            .unwrap_or(Severity::Critical)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+struct SeverityField {
+    #[serde(default)]
+    severity: Option<EcoString>,
+}
+
+impl SeverityField {
+    fn severity(&self) -> Option<Severity> {
+        match self.severity.as_ref()?.to_lowercase().as_str() {
+            "low" => Some(Severity::Low),
+            "moderate" | "medium" => Some(Severity::Moderate),
+            "high" => Some(Severity::High),
+            "critical" => Some(Severity::Critical),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+struct Affected {
+    package: AffectedPackage,
+    #[serde(default)]
+    ranges: Vec<Range>,
+    #[serde(default)]
+    versions: Vec<EcoString>,
+    #[serde(default)]
+    ecosystem_specific: SeverityField,
+}
+
+impl Affected {
+    fn affects(&self, package: &ManifestPackage) -> bool {
+        if self.package.ecosystem != "Hex" || self.package.name != package.name {
+            return false;
+        }
+        let version = package.version.to_string();
+        self.versions.iter().any(|affected| *affected == version)
+            || self
+                .ranges
+                .iter()
+                .any(|range| range.contains(&package.version))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+struct AffectedPackage {
+    ecosystem: EcoString,
+    name: EcoString,
+}
+
+/// The versions a problem was introduced and fixed in. Git ranges are given
+/// by commit rather than by version, so they can't be checked against the
+/// manifest and never match.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+struct Range {
+    #[serde(rename = "type")]
+    type_: EcoString,
+    #[serde(default)]
+    events: Vec<Event>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Event {
+    Introduced(EcoString),
+    Fixed(EcoString),
+    LastAffected(EcoString),
+    Limit(EcoString),
+}
+
+impl Range {
+    fn contains(&self, version: &Version) -> bool {
+        if self.type_ == "GIT" {
+            return false;
+        }
+
+        // "0" stands for all the versions since the first one.
+        let parse = |event_version: &str| match event_version {
+            "0" => Some(Version::new(0, 0, 0)),
+            _ => Version::parse(event_version).ok(),
+        };
+        let events = self
+            .events
+            .iter()
+            .filter_map(|event| match event {
+                Event::Introduced(v) | Event::Fixed(v) | Event::LastAffected(v) => {
+                    Some((parse(v)?, event))
+                }
+                Event::Limit(_) => None,
+            })
+            .sorted_by(|(a, _), (b, _)| a.cmp(b));
+
+        let mut affected = false;
+        for (event_version, event) in events {
+            match event {
+                Event::Introduced(_) if *version >= event_version => affected = true,
+                Event::Fixed(_) if *version >= event_version => affected = false,
+                Event::LastAffected(_) if *version > event_version => affected = false,
+                Event::Introduced(_)
+                | Event::Fixed(_)
+                | Event::LastAffected(_)
+                | Event::Limit(_) => (),
+            }
+        }
+        affected
+    }
+}
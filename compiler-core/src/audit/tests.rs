@@ -0,0 +1,173 @@
+use hexpm::{version::Version, RetirementReason, RetirementStatus};
+
+use super::{retirement_finding, AdvisoryDatabase, Finding, Severity};
+use crate::manifest::{Base16Checksum, ManifestPackage, ManifestPackageSource};
+
+fn package(name: &str, version: &str) -> ManifestPackage {
+    ManifestPackage {
+        name: name.into(),
+        version: Version::parse(version).unwrap(),
+        build_tools: vec!["gleam".into()],
+        otp_app: None,
+        requirements: vec![],
+        source: ManifestPackageSource::Hex {
+            outer_checksum: Base16Checksum(vec![]),
+        },
+    }
+}
+
+const ADVISORIES: &str = r#"[
+  {
+    "id": "GHSA-0001",
+    "summary": "Wibble can be made to crash",
+    "affected": [
+      {
+        "package": { "ecosystem": "Hex", "name": "wibble" },
+        "ranges": [
+          {
+            "type": "SEMVER",
+            "events": [{ "introduced": "0" }, { "fixed": "1.2.0" }]
+          },
+          {
+            "type": "SEMVER",
+            "events": [{ "introduced": "2.0.0" }, { "last_affected": "2.1.0" }]
+          }
+        ]
+      }
+    ],
+    "database_specific": { "severity": "HIGH" }
+  },
+  {
+    "id": "GHSA-0002",
+    "details": "Wobble leaks secrets",
+    "affected": [
+      {
+        "package": { "ecosystem": "Hex", "name": "wobble" },
+        "versions": ["0.3.0"],
+        "ecosystem_specific": { "severity": "moderate" }
+      }
+    ]
+  },
+  {
+    "id": "PYSEC-0003",
+    "affected": [
+      {
+        "package": { "ecosystem": "PyPI", "name": "wibble" },
+        "ranges": [{ "type": "ECOSYSTEM", "events": [{ "introduced": "0" }] }]
+      }
+    ]
+  }
+]"#;
+
+fn findings(name: &str, version: &str) -> Vec<(String, Severity)> {
+    AdvisoryDatabase::parse(ADVISORIES)
+        .unwrap()
+        .findings(&package(name, version))
+        .into_iter()
+        .map(|finding| (finding.reason.to_string(), finding.severity))
+        .collect()
+}
+
+#[test]
+fn versions_before_a_fix_are_affected() {
+    assert_eq!(
+        findings("wibble", "1.1.9"),
+        vec![("GHSA-0001".into(), Severity::High)]
+    );
+    assert_eq!(findings("wibble", "1.2.0"), vec![]);
+}
+
+#[test]
+fn versions_up_to_the_last_affected_one_are_affected() {
+    assert_eq!(findings("wibble", "1.9.0"), vec![]);
+    assert_eq!(
+        findings("wibble", "2.1.0"),
+        vec![("GHSA-0001".into(), Severity::High)]
+    );
+    assert_eq!(findings("wibble", "2.1.1"), vec![]);
+}
+
+#[test]
+fn listed_versions_are_affected() {
+    assert_eq!(
+        findings("wobble", "0.3.0"),
+        vec![("GHSA-0002".into(), Severity::Moderate)]
+    );
+    assert_eq!(findings("wobble", "0.3.1"), vec![]);
+}
+
+#[test]
+fn other_ecosystems_are_ignored() {
+    assert_eq!(findings("wubble", "1.0.0"), vec![]);
+    assert!(!findings("wibble", "3.0.0")
+        .iter()
+        .any(|(id, _)| id == "PYSEC-0003"));
+}
+
+#[test]
+fn advisories_without_a_severity_are_critical() {
+    let database = AdvisoryDatabase::parse(
+        r#"{
+  "id": "OSV-0004",
+  "affected": [
+    {
+      "package": { "ecosystem": "Hex", "name": "wibble" },
+      "versions": ["1.0.0"]
+    }
+  ]
+}"#,
+    )
+    .unwrap();
+    assert_eq!(
+        database.findings(&package("wibble", "1.0.0")),
+        vec![Finding {
+            package: "wibble".into(),
+            version: "1.0.0".into(),
+            severity: Severity::Critical,
+            reason: "OSV-0004".into(),
+            message: "".into(),
+        }]
+    );
+}
+
+#[test]
+fn advisory_summaries_are_preferred_to_details() {
+    let database = AdvisoryDatabase::parse(ADVISORIES).unwrap();
+    let messages = |name, version| {
+        database
+            .findings(&package(name, version))
+            .into_iter()
+            .map(|finding| finding.message)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        messages("wibble", "1.0.0"),
+        vec!["Wibble can be made to crash"]
+    );
+    assert_eq!(messages("wobble", "0.3.0"), vec!["Wobble leaks secrets"]);
+}
+
+#[test]
+fn retired_releases() {
+    let status = RetirementStatus {
+        reason: RetirementReason::Security,
+        message: "Please upgrade".into(),
+    };
+    assert_eq!(
+        retirement_finding(&package("wibble", "1.0.0"), &status),
+        Finding {
+            package: "wibble".into(),
+            version: "1.0.0".into(),
+            severity: Severity::High,
+            reason: "retired (security)".into(),
+            message: "Please upgrade".into(),
+        }
+    );
+}
+
+#[test]
+fn severities_are_ordered() {
+    assert!(Severity::Low < Severity::Moderate);
+    assert!(Severity::Moderate < Severity::High);
+    assert!(Severity::High < Severity::Critical);
+}
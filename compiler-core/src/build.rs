@@ -1,6 +1,7 @@
 #![allow(warnings)]
 
 mod elixir_libraries;
+mod global_build_cache;
 mod module_loader;
 mod native_file_copier;
 pub mod package_compiler;
@@ -53,6 +54,7 @@ use vec1::Vec1;
     Copy,
     PartialEq,
     Eq,
+    Hash,
 )]
 #[strum(serialize_all = "lowercase")]
 pub enum Target {
@@ -0,0 +1,204 @@
+//! A cache of compiled Hex packages shared by all the projects of a user.
+//!
+//! Hex packages are immutable, so the artefacts produced by compiling one with
+//! a given version of the compiler, for a given target and against the same
+//! dependencies are always the same. Rather than recompiling them in every
+//! project (and in every fresh CI checkout) the project compiler copies the
+//! artefacts into the global cache after compiling a package, and copies them
+//! back into the build directory the next time the same package is needed.
+//!
+//! Entries live in `~/.cache/gleam/build/<compiler>/<target>/` and are named
+//! after the package, its version, and a hash identifying the exact inputs
+//! that went into the build. Once in the build directory the artefacts are
+//! validated like any other cache: the module loader checks the fingerprint of
+//! each source file, so a mismatching entry results in a recompilation rather
+//! than a broken build.
+//!
+//! Files are copied rather than hard linked as the build tool writes some
+//! artefacts (such as the `.app` file) in place, which would otherwise corrupt
+//! the shared copy.
+
+#[cfg(test)]
+mod tests;
+
+use std::{collections::HashMap, fmt::Write};
+
+use camino::{Utf8Path, Utf8PathBuf};
+use ecow::{eco_format, EcoString};
+use itertools::Itertools;
+
+use crate::{
+    build::Target,
+    io::{DirWalker, FileSystemReader, FileSystemWriter},
+    manifest::{ManifestPackage, ManifestPackageSource},
+    paths,
+    version::COMPILER_VERSION,
+    Result,
+};
+
+/// This file is written once all the artefacts of an entry have been copied
+/// into the cache, so that entries that are only partially written (because
+/// the build was interrupted, or because another build is writing the same
+/// entry) are never used.
+///
+const COMPLETE_MARKER: &str = "gleam_cache_complete";
+
+#[derive(Debug)]
+pub(crate) struct GlobalBuildCache<IO> {
+    io: IO,
+    directory: Utf8PathBuf,
+    target: Target,
+    typescript_declarations: bool,
+    /// The key of each package seen so far, `None` if the package can't be
+    /// cached.
+    keys: HashMap<EcoString, Option<EcoString>>,
+}
+
+impl<IO> GlobalBuildCache<IO>
+where
+    IO: FileSystemReader + FileSystemWriter + Clone,
+{
+    pub fn new(
+        io: IO,
+        directory: Utf8PathBuf,
+        target: Target,
+        typescript_declarations: bool,
+    ) -> Self {
+        Self {
+            io,
+            directory,
+            target,
+            typescript_declarations,
+            keys: HashMap::new(),
+        }
+    }
+
+    /// Returns the directory holding the cached artefacts for the given
+    /// package, if the package can be cached.
+    ///
+    /// Only Hex packages are cached, as their checksum identifies their
+    /// source. A package is cached only if all its dependencies can be cached
+    /// too, so packages must be given in dependency order.
+    ///
+    pub fn entry(&mut self, package: &ManifestPackage) -> Option<Utf8PathBuf> {
+        let key = self.key(package);
+        let _ = self.keys.insert(package.name.clone(), key.clone());
+        let key = key?;
+        let directory = self
+            .directory
+            .join(COMPILER_VERSION)
+            .join(self.target.to_string())
+            .join(format!("{}-{}-{key}", package.name, package.version));
+        Some(directory)
+    }
+
+    fn key(&self, package: &ManifestPackage) -> Option<EcoString> {
+        let ManifestPackageSource::Hex { outer_checksum } = &package.source else {
+            return None;
+        };
+
+        let mut inputs = String::new();
+        let _ = writeln!(inputs, "compiler {COMPILER_VERSION}");
+        let _ = writeln!(inputs, "target {}", self.target);
+        if self.target == Target::JavaScript {
+            let _ = writeln!(inputs, "typescript {}", self.typescript_declarations);
+        }
+        let _ = writeln!(
+            inputs,
+            "package {} {} {} {}",
+            package.name,
+            package.version,
+            outer_checksum.to_string(),
+            package.application_name()
+        );
+        for requirement in package.requirements.iter().sorted() {
+            let key = self.keys.get(requirement)?.as_ref()?;
+            let _ = writeln!(inputs, "requirement {requirement} {key}");
+        }
+
+        let hash = xxhash_rust::xxh3::xxh3_128(inputs.as_bytes());
+        Some(eco_format!("{hash:032x}"))
+    }
+
+    /// Copies the artefacts of a cache entry into the given build directory,
+    /// returning whether the entry was complete and could be used.
+    ///
+    /// Nothing is copied if the build directory already holds some compiled
+    /// artefacts, in which case those are used instead.
+    ///
+    pub fn restore(&self, entry: &Utf8Path, out: &Utf8Path) -> Result<bool> {
+        if !self.io.is_file(&entry.join(COMPLETE_MARKER))
+            || self
+                .io
+                .is_directory(&out.join(paths::ARTEFACT_DIRECTORY_NAME))
+        {
+            return Ok(false);
+        }
+
+        tracing::debug!(%entry, "restoring_from_global_build_cache");
+        self.copy_artefacts(entry, out)?;
+        Ok(true)
+    }
+
+    /// Copies the artefacts in the given build directory into a cache entry,
+    /// unless the entry already exists.
+    ///
+    /// The artefacts are first copied into a directory unique to this process
+    /// which is then renamed to the entry, so other builds never see an entry
+    /// that is only partially written.
+    ///
+    pub fn store(&self, out: &Utf8Path, entry: &Utf8Path) -> Result<()> {
+        if self.io.is_file(&entry.join(COMPLETE_MARKER)) {
+            return Ok(());
+        }
+
+        tracing::debug!(%entry, "storing_in_global_build_cache");
+        let temporary = temporary_directory(entry);
+        self.io.delete_directory(&temporary)?;
+        self.copy_artefacts(out, &temporary)?;
+        self.io.write(&temporary.join(COMPLETE_MARKER), "")?;
+
+        // An incomplete entry left behind by an interrupted build is replaced.
+        if !self.io.is_file(&entry.join(COMPLETE_MARKER)) {
+            self.io.delete_directory(entry)?;
+        }
+        if let Err(error) = self.io.rename(&temporary, entry) {
+            // Another build stored the same entry first, in which case the
+            // one it wrote is used.
+            self.io.delete_directory(&temporary)?;
+            if !self.io.is_file(&entry.join(COMPLETE_MARKER)) {
+                return Err(error);
+            }
+        }
+        Ok(())
+    }
+
+    fn copy_artefacts(&self, from: &Utf8Path, to: &Utf8Path) -> Result<()> {
+        // The `priv` directory is a symlink to the package's sources that the
+        // package compiler creates in each build directory, it is never
+        // copied.
+        let priv_directory = from.join("priv");
+        let marker = from.join(COMPLETE_MARKER);
+
+        let mut walker = DirWalker::new(from.to_path_buf());
+        while let Some(path) = walker.next_file(&self.io)? {
+            if path.starts_with(&priv_directory) || path == marker {
+                continue;
+            }
+            let relative = path
+                .strip_prefix(from)
+                .expect("walked file outside of the walked directory");
+            let destination = to.join(relative);
+            if let Some(parent) = destination.parent() {
+                self.io.mkdir(parent)?;
+            }
+            self.io.copy(&path, &destination)?;
+        }
+        Ok(())
+    }
+}
+
+fn temporary_directory(entry: &Utf8Path) -> Utf8PathBuf {
+    let name = entry.file_name().unwrap_or_default();
+    entry.with_file_name(format!(".{name}.{}.tmp", std::process::id()))
+}
@@ -0,0 +1,218 @@
+use super::GlobalBuildCache;
+use crate::{
+    build::Target,
+    io::{memory::InMemoryFileSystem, FileSystemReader, FileSystemWriter},
+    manifest::{Base16Checksum, ManifestPackage, ManifestPackageSource},
+};
+use camino::{Utf8Path, Utf8PathBuf};
+use hexpm::version::Version;
+use itertools::Itertools;
+
+fn cache(fs: &InMemoryFileSystem) -> GlobalBuildCache<InMemoryFileSystem> {
+    GlobalBuildCache::new(fs.clone(), "/cache".into(), Target::Erlang, false)
+}
+
+fn hex_package(name: &str, checksum: u8, requirements: &[&str]) -> ManifestPackage {
+    ManifestPackage {
+        name: name.into(),
+        version: Version::new(1, 0, 0),
+        build_tools: vec!["gleam".into()],
+        otp_app: None,
+        requirements: requirements.iter().map(|name| (*name).into()).collect(),
+        source: ManifestPackageSource::Hex {
+            outer_checksum: Base16Checksum(vec![checksum]),
+        },
+    }
+}
+
+fn local_package(name: &str) -> ManifestPackage {
+    ManifestPackage {
+        source: ManifestPackageSource::Local {
+            path: Utf8PathBuf::from("/wibble"),
+        },
+        ..hex_package(name, 0, &[])
+    }
+}
+
+#[test]
+fn hex_packages_are_cached() {
+    let fs = InMemoryFileSystem::new();
+    let entry = cache(&fs).entry(&hex_package("wibble", 1, &[])).unwrap();
+    assert!(entry.starts_with("/cache"));
+    assert!(entry.file_name().unwrap().starts_with("wibble-1.0.0-"));
+}
+
+#[test]
+fn local_packages_are_not_cached() {
+    let fs = InMemoryFileSystem::new();
+    assert_eq!(cache(&fs).entry(&local_package("wibble")), None);
+}
+
+#[test]
+fn packages_depending_on_uncacheable_packages_are_not_cached() {
+    let fs = InMemoryFileSystem::new();
+    let mut cache = cache(&fs);
+    assert_eq!(cache.entry(&local_package("wobble")), None);
+    assert_eq!(cache.entry(&hex_package("wibble", 1, &["wobble"])), None);
+}
+
+#[test]
+fn entry_depends_on_checksum() {
+    let fs = InMemoryFileSystem::new();
+    let one = cache(&fs).entry(&hex_package("wibble", 1, &[]));
+    let other = cache(&fs).entry(&hex_package("wibble", 2, &[]));
+    assert_ne!(one, other);
+}
+
+#[test]
+fn entry_depends_on_target() {
+    let fs = InMemoryFileSystem::new();
+    let erlang = cache(&fs).entry(&hex_package("wibble", 1, &[]));
+    let javascript = GlobalBuildCache::new(fs.clone(), "/cache".into(), Target::JavaScript, false)
+        .entry(&hex_package("wibble", 1, &[]));
+    assert_ne!(erlang, javascript);
+}
+
+#[test]
+fn entry_depends_on_dependencies() {
+    let fs = InMemoryFileSystem::new();
+
+    let mut one = cache(&fs);
+    let _ = one.entry(&hex_package("wobble", 1, &[]));
+    let one = one.entry(&hex_package("wibble", 1, &["wobble"]));
+
+    let mut other = cache(&fs);
+    let _ = other.entry(&hex_package("wobble", 2, &[]));
+    let other = other.entry(&hex_package("wibble", 1, &["wobble"]));
+
+    assert_ne!(one, other);
+}
+
+#[test]
+fn stored_artefacts_are_restored() {
+    let fs = InMemoryFileSystem::new();
+    fs.write(Utf8Path::new("/out/_gleam_artefacts/wibble.cache"), "1")
+        .unwrap();
+    fs.write(Utf8Path::new("/out/ebin/wibble.beam"), "2")
+        .unwrap();
+    fs.write(Utf8Path::new("/out/priv/wibble.txt"), "3")
+        .unwrap();
+
+    let cache = cache(&fs);
+    let entry = Utf8Path::new("/cache/wibble");
+    cache.store(Utf8Path::new("/out"), entry).unwrap();
+    assert!(!fs.exists(Utf8Path::new("/cache/wibble/priv/wibble.txt")));
+
+    assert!(cache.restore(entry, Utf8Path::new("/new")).unwrap());
+    assert_eq!(
+        fs.read(Utf8Path::new("/new/_gleam_artefacts/wibble.cache"))
+            .unwrap(),
+        "1"
+    );
+    assert_eq!(
+        fs.read(Utf8Path::new("/new/ebin/wibble.beam")).unwrap(),
+        "2"
+    );
+    assert!(!fs.exists(Utf8Path::new("/new/priv/wibble.txt")));
+    assert!(!fs.exists(Utf8Path::new("/new/gleam_cache_complete")));
+}
+
+#[test]
+fn incomplete_entries_are_not_restored() {
+    let fs = InMemoryFileSystem::new();
+    fs.write(Utf8Path::new("/cache/wibble/ebin/wibble.beam"), "1")
+        .unwrap();
+
+    let restored = cache(&fs)
+        .restore(Utf8Path::new("/cache/wibble"), Utf8Path::new("/out"))
+        .unwrap();
+    assert!(!restored);
+    assert!(!fs.exists(Utf8Path::new("/out/ebin/wibble.beam")));
+}
+
+#[test]
+fn existing_artefacts_are_not_overwritten() {
+    let fs = InMemoryFileSystem::new();
+    fs.write(
+        Utf8Path::new("/cache/wibble/_gleam_artefacts/wibble.cache"),
+        "1",
+    )
+    .unwrap();
+    fs.write(Utf8Path::new("/cache/wibble/gleam_cache_complete"), "")
+        .unwrap();
+    fs.write(Utf8Path::new("/out/_gleam_artefacts/wibble.cache"), "2")
+        .unwrap();
+
+    let restored = cache(&fs)
+        .restore(Utf8Path::new("/cache/wibble"), Utf8Path::new("/out"))
+        .unwrap();
+    assert!(!restored);
+    assert_eq!(
+        fs.read(Utf8Path::new("/out/_gleam_artefacts/wibble.cache"))
+            .unwrap(),
+        "2"
+    );
+}
+
+#[test]
+fn incomplete_entries_are_replaced_when_storing() {
+    let fs = InMemoryFileSystem::new();
+    fs.write(Utf8Path::new("/out/ebin/wibble.beam"), "1")
+        .unwrap();
+    fs.write(Utf8Path::new("/cache/wibble/ebin/wobble.beam"), "2")
+        .unwrap();
+
+    let cache = cache(&fs);
+    let entry = Utf8Path::new("/cache/wibble");
+    cache.store(Utf8Path::new("/out"), entry).unwrap();
+    assert!(fs.is_file(&entry.join("gleam_cache_complete")));
+    assert!(fs.is_file(&entry.join("ebin/wibble.beam")));
+    assert!(!fs.exists(&entry.join("ebin/wobble.beam")));
+}
+
+#[test]
+fn entries_are_not_written_in_place() {
+    let fs = InMemoryFileSystem::new();
+    fs.write(Utf8Path::new("/out/ebin/wibble.beam"), "1")
+        .unwrap();
+
+    let cache = cache(&fs);
+    let entry = Utf8Path::new("/cache/wibble");
+    cache.store(Utf8Path::new("/out"), entry).unwrap();
+
+    // Only the complete entry is left in the cache, the directory the
+    // artefacts were copied into first was renamed to it.
+    let files = fs
+        .files()
+        .into_iter()
+        .filter(|path| path.starts_with("/cache"))
+        .sorted()
+        .collect::<Vec<_>>();
+    assert_eq!(
+        files,
+        vec![
+            Utf8PathBuf::from("/cache/wibble/ebin/wibble.beam"),
+            Utf8PathBuf::from("/cache/wibble/gleam_cache_complete"),
+        ]
+    );
+}
+
+#[test]
+fn entries_stored_by_another_build_are_kept() {
+    let fs = InMemoryFileSystem::new();
+    fs.write(Utf8Path::new("/out/ebin/wibble.beam"), "1")
+        .unwrap();
+    fs.write(Utf8Path::new("/cache/wibble/ebin/wibble.beam"), "2")
+        .unwrap();
+    fs.write(Utf8Path::new("/cache/wibble/gleam_cache_complete"), "")
+        .unwrap();
+
+    cache(&fs)
+        .store(Utf8Path::new("/out"), Utf8Path::new("/cache/wibble"))
+        .unwrap();
+    assert_eq!(
+        fs.read(Utf8Path::new("/cache/wibble/ebin/wibble.beam"))
+            .unwrap(),
+        "2"
+    );
+}
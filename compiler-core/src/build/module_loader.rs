@@ -34,6 +34,8 @@ pub(crate) struct ModuleLoader<'a, IO> {
     /// The set of modules that have had partial compilation done since the last
     /// successful compilation.
     pub incomplete_modules: &'a HashSet<EcoString>,
+    /// If set to false modules are always read from their source.
+    pub use_cache: bool,
 }
 
 impl<'a, IO> ModuleLoader<'a, IO>
@@ -55,6 +57,10 @@ where
 
         let read_source = |name| self.read_source(path, name, source_mtime);
 
+        if !self.use_cache {
+            return read_source(name).map(Input::New);
+        }
+
         let meta = match self.read_cache_metadata(&artefact)? {
             Some(meta) => meta,
             None => return read_source(name).map(Input::New),
@@ -47,6 +47,28 @@ fn cache_present_and_fresh() {
     assert!(result.is_cached());
 }
 
+#[test]
+fn cache_present_and_fresh_but_not_used() {
+    let name = "package".into();
+    let src = Utf8Path::new("/src");
+    let artefact = Utf8Path::new("/artefact");
+    let fs = InMemoryFileSystem::new();
+    let warnings = WarningEmitter::null();
+    let incomplete_modules = HashSet::new();
+    let mut loader = make_loader(&warnings, &name, &fs, src, artefact, &incomplete_modules);
+    loader.use_cache = false;
+
+    // The mtime of the source is older than that of the cache
+    write_src(&fs, TEST_SOURCE_1, "/src/main.gleam", 0);
+    write_cache(&fs, TEST_SOURCE_1, "/artefact/main.cache_meta", 1, false);
+
+    let result = loader
+        .load(Utf8Path::new("/src/main.gleam").to_path_buf())
+        .unwrap();
+
+    assert!(result.is_new());
+}
+
 #[test]
 fn cache_present_and_stale() {
     let name = "package".into();
@@ -247,5 +269,6 @@ fn make_loader<'a>(
         artefact_directory: &artefact,
         origin: Origin::Src,
         incomplete_modules,
+        use_cache: true,
     }
 }
@@ -41,6 +41,9 @@ pub struct PackageCompiler<'a, IO> {
     pub config: &'a PackageConfig,
     pub ids: UniqueIdGenerator,
     pub write_metadata: bool,
+    /// If set to false all the package's modules are analysed, rather than
+    /// loading the ones that haven't changed from the cache.
+    pub use_cache: bool,
     pub perform_codegen: bool,
     /// If set to false the compiler won't load and analyse any of the package's
     /// modules and always succeed compilation returning no compile modules.
@@ -55,6 +58,10 @@ pub struct PackageCompiler<'a, IO> {
     pub subprocess_stdio: Stdio,
     pub target_support: TargetSupport,
     pub cached_warnings: CachedWarnings,
+    /// The packages of the modules compiled elsewhere and loaded into the
+    /// importable modules, which the package can import as if they were
+    /// direct dependencies.
+    pub precompiled_packages: HashSet<EcoString>,
 }
 
 impl<'a, IO> PackageCompiler<'a, IO>
@@ -81,6 +88,7 @@ where
             config,
             target,
             write_metadata: true,
+            use_cache: true,
             perform_codegen: true,
             compile_modules: true,
             write_entrypoint: false,
@@ -89,6 +97,7 @@ where
             subprocess_stdio: Stdio::Inherit,
             target_support: TargetSupport::NotEnforced,
             cached_warnings: CachedWarnings::Ignore,
+            precompiled_packages: HashSet::new(),
         }
     }
 
@@ -126,6 +135,7 @@ where
             self.mode,
             self.root,
             self.cached_warnings,
+            self.use_cache,
             warnings,
             codegen_required,
             &artefact_directory,
@@ -180,6 +190,7 @@ where
             warnings,
             self.target_support,
             incomplete_modules,
+            &self.precompiled_packages,
         );
 
         let mut modules = match outcome {
@@ -443,9 +454,16 @@ fn analyse(
     warnings: &WarningEmitter,
     target_support: TargetSupport,
     incomplete_modules: &mut HashSet<EcoString>,
+    precompiled_packages: &HashSet<EcoString>,
 ) -> Outcome<Vec<Module>, Error> {
     let mut modules = Vec::with_capacity(parsed_modules.len() + 1);
-    let direct_dependencies = package_config.dependencies_for(mode).expect("Package deps");
+    let direct_dependencies: HashMap<EcoString, ()> = package_config
+        .dependencies_for(mode)
+        .expect("Package deps")
+        .into_keys()
+        .chain(precompiled_packages.iter().cloned())
+        .map(|package| (package, ()))
+        .collect();
 
     // Insert the prelude
     // DUPE: preludeinsertion
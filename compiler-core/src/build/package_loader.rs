@@ -68,6 +68,7 @@ pub struct PackageLoader<'a, IO> {
     already_defined_modules: &'a mut im::HashMap<EcoString, Utf8PathBuf>,
     incomplete_modules: &'a HashSet<EcoString>,
     cached_warnings: CachedWarnings,
+    use_cache: bool,
 }
 
 impl<'a, IO> PackageLoader<'a, IO>
@@ -80,6 +81,7 @@ where
         mode: Mode,
         root: &'a Utf8Path,
         cached_warnings: CachedWarnings,
+        use_cache: bool,
         warnings: &'a WarningEmitter,
         codegen: CodegenRequired,
         artefact_directory: &'a Utf8Path,
@@ -99,6 +101,7 @@ where
             target,
             package_name,
             cached_warnings,
+            use_cache,
             artefact_directory,
             stale_modules,
             already_defined_modules,
@@ -185,6 +188,11 @@ where
         let bytes = self.io.read_bytes(&path)?;
         let mut module = metadata::ModuleDecoder::new(self.ids.clone()).read(bytes.as_slice())?;
 
+        // The cache may have been written for a copy of the module somewhere
+        // else, such as in the global build cache, so the path it records is
+        // replaced with the one of the source that was checked against it.
+        module.src_path = info.source_path;
+
         // Load warnings
         if self.cached_warnings.should_use() {
             let path = dir.join(name.as_ref()).with_extension("cache_warnings");
@@ -240,6 +248,7 @@ where
             source_directory: &src,
             origin: Origin::Src,
             incomplete_modules: self.incomplete_modules,
+            use_cache: self.use_cache,
         };
 
         // Src
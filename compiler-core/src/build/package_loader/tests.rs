@@ -17,6 +17,7 @@ use std::time::Duration;
 struct LoaderTestOutput {
     to_compile: Vec<EcoString>,
     cached: Vec<EcoString>,
+    cached_paths: Vec<Utf8PathBuf>,
     warnings: Vec<Warning>,
 }
 
@@ -89,11 +90,13 @@ fn run_loader(fs: InMemoryFileSystem, root: &Utf8Path, artefact: &Utf8Path) -> L
         already_defined_modules: &mut defined,
         incomplete_modules: &mut HashSet::new(),
         cached_warnings: CachedWarnings::Ignore,
+        use_cache: true,
     };
     let loaded = loader.run().unwrap();
 
     LoaderTestOutput {
         to_compile: loaded.to_compile.into_iter().map(|m| m.name).collect(),
+        cached_paths: loaded.cached.iter().map(|m| m.src_path.clone()).collect(),
         cached: loaded.cached.into_iter().map(|m| m.name).collect(),
         warnings: warnings.take(),
     }
@@ -172,6 +175,25 @@ fn reading_cache() {
     assert_eq!(loaded.cached, vec![EcoString::from("one")]);
 }
 
+#[test]
+fn cached_module_path_is_the_one_of_its_source() {
+    let fs = InMemoryFileSystem::new();
+    let root = Utf8Path::new("/project");
+    let artefact = Utf8Path::new("/artefact");
+
+    // The cache was written for a module at `/src/one.gleam`, for example by
+    // another project sharing it through the global build cache.
+    write_src(&fs, "/project/src/one.gleam", 0, TEST_SOURCE_1);
+    write_cache(&fs, "one", 0, vec![], TEST_SOURCE_1);
+
+    let loaded = run_loader(fs, root, artefact);
+    assert_eq!(loaded.cached, vec![EcoString::from("one")]);
+    assert_eq!(
+        loaded.cached_paths,
+        vec![Utf8PathBuf::from("/project/src/one.gleam")]
+    );
+}
+
 #[test]
 fn module_is_stale_if_cache_older() {
     let fs = InMemoryFileSystem::new();
@@ -12,10 +12,11 @@ use crate::{
     manifest::{ManifestPackage, ManifestPackageSource},
     metadata,
     paths::{self, ProjectPaths},
+    requirement::Requirement,
     type_::{self, ModuleFunction},
     uid::UniqueIdGenerator,
     version::COMPILER_VERSION,
-    warning::{self, WarningEmitter, WarningEmitterIO},
+    warning::{self, WarningEmitter, WarningEmitterIO, WarningKind, WarningLevel, WarningLevels},
     Error, Result, Warning,
 };
 use ecow::EcoString;
@@ -33,8 +34,8 @@ use std::{
 };
 
 use super::{
-    elixir_libraries::ElixirLibraries, package_compiler::CachedWarnings, Codegen, Compile,
-    ErlangAppCodegenConfiguration, Outcome,
+    elixir_libraries::ElixirLibraries, global_build_cache::GlobalBuildCache,
+    package_compiler::CachedWarnings, Codegen, Compile, ErlangAppCodegenConfiguration, Outcome,
 };
 
 use camino::{Utf8Path, Utf8PathBuf};
@@ -51,15 +52,24 @@ const ELIXIR_EXECUTABLE: &str = "elixir";
 #[cfg(target_os = "windows")]
 const ELIXIR_EXECUTABLE: &str = "elixir.bat";
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Options {
     pub mode: Mode,
     pub target: Option<Target>,
     pub compile: Compile,
     pub codegen: Codegen,
     pub warnings_as_errors: bool,
+    /// Levels for the warnings of the root package, taking precedence over
+    /// the ones set in its `gleam.toml`.
+    pub warning_levels: HashMap<WarningKind, WarningLevel>,
     pub root_target_support: TargetSupport,
     pub no_print_progress: bool,
+    /// Whether the build is done for a tool needing the typed code of all the
+    /// modules of the root package, such as `gleam fix`. These are analysed
+    /// again rather than loaded from the cache, the cache of the root package
+    /// is left untouched, and all of its warnings are reported as warnings
+    /// regardless of the levels set for them.
+    pub tooling: bool,
 }
 
 #[derive(Debug)]
@@ -84,6 +94,11 @@ impl Built {
         }
     }
 
+    /// The interfaces of the modules of the project and of its dependencies.
+    pub fn module_interfaces(&self) -> &im::HashMap<EcoString, type_::ModuleInterface> {
+        &self.module_interfaces
+    }
+
     pub fn minimum_required_version(&self) -> Version {
         self.module_interfaces
             .values()
@@ -101,6 +116,8 @@ pub struct ProjectCompiler<IO> {
     pub(crate) packages: HashMap<String, ManifestPackage>,
     importable_modules: im::HashMap<EcoString, type_::ModuleInterface>,
     defined_modules: im::HashMap<EcoString, Utf8PathBuf>,
+    /// The packages of the modules loaded with `load_importable_modules`.
+    precompiled_packages: HashSet<EcoString>,
     stale_modules: StaleTracker,
     /// The set of modules that have had partial compilation done since the last
     /// successful compilation.
@@ -111,6 +128,9 @@ pub struct ProjectCompiler<IO> {
     paths: ProjectPaths,
     ids: UniqueIdGenerator,
     pub(crate) io: IO,
+    /// The cache of compiled Hex packages shared with other projects, if
+    /// enabled.
+    global_build_cache: Option<GlobalBuildCache<IO>>,
     /// We may want to silence subprocess stdout if we are running in LSP mode.
     /// The language server talks over stdio so printing would break that.
     pub subprocess_stdio: Stdio,
@@ -132,6 +152,12 @@ where
         paths: ProjectPaths,
         io: IO,
     ) -> Self {
+        // The manifest of a workspace has the packages of all its members,
+        // including the root one, so only the ones it needs are compiled.
+        let packages = match paths.workspace_root() {
+            Some(_) => crate::workspace::member_packages(&packages, &config),
+            None => packages,
+        };
         let packages = packages
             .into_iter()
             .map(|p| (p.name.to_string(), p))
@@ -140,10 +166,12 @@ where
         Self {
             importable_modules: im::HashMap::new(),
             defined_modules: im::HashMap::new(),
+            precompiled_packages: HashSet::new(),
             stale_modules: StaleTracker::default(),
             incomplete_modules: HashSet::new(),
             ids: UniqueIdGenerator::new(),
             warnings: WarningEmitter::new(warning_emitter),
+            global_build_cache: None,
             subprocess_stdio: Stdio::Inherit,
             telemetry,
             packages,
@@ -154,6 +182,35 @@ where
         }
     }
 
+    /// Reuse compiled Hex dependencies from the given global cache directory,
+    /// and add the ones compiled by this project to it.
+    pub fn use_global_build_cache(&mut self, directory: Utf8PathBuf) {
+        self.global_build_cache = Some(GlobalBuildCache::new(
+            self.io.clone(),
+            directory,
+            self.target(),
+            self.config.javascript.typescript_declarations,
+        ));
+    }
+
+    /// Makes modules compiled elsewhere importable by the root package, such
+    /// as the precompiled dependencies given to the WebAssembly compiler.
+    /// `load` decodes them with the given generator of unique ids. The root
+    /// package can import their packages as if they were direct dependencies.
+    pub fn load_importable_modules(
+        &mut self,
+        load: impl FnOnce(&UniqueIdGenerator) -> Result<Vec<type_::ModuleInterface>>,
+    ) -> Result<()> {
+        for module in load(&self.ids)? {
+            _ = self.precompiled_packages.insert(module.package.clone());
+            _ = self
+                .defined_modules
+                .insert(module.name.clone(), module.src_path.clone());
+            _ = self.importable_modules.insert(module.name.clone(), module);
+        }
+        Ok(())
+    }
+
     pub fn get_importable_modules(&self) -> &im::HashMap<EcoString, type_::ModuleInterface> {
         &self.importable_modules
     }
@@ -182,7 +239,6 @@ where
         // The JavaScript target requires a prelude module to be written.
         self.write_prelude()?;
 
-        // Dependencies are compiled first.
         let compiled_dependency_modules = self.compile_dependencies()?;
 
         // We reset the warning count as we don't want to fail the build if a
@@ -191,11 +247,12 @@ where
 
         let root_package = self.compile_root_package().into_result()?;
 
-        // TODO: test
-        if self.options.warnings_as_errors && self.warnings.count() > 0 {
-            return Err(Error::ForbiddenWarnings {
-                count: self.warnings.count(),
-            });
+        let denied = self.warnings.denied_count();
+        if denied > 0 && self.options.warnings_as_errors {
+            return Err(Error::ForbiddenWarnings { count: denied });
+        }
+        if denied > 0 {
+            return Err(Error::DeniedWarnings { count: denied });
         }
 
         Ok(Built {
@@ -206,6 +263,19 @@ where
     }
 
     pub fn compile_root_package(&mut self) -> Outcome<Package, Error> {
+        // Tools rely on the warnings of the root package and must not fail
+        // because of them, so the levels set by the package are ignored.
+        let levels = if self.options.tooling {
+            WarningLevels::default()
+        } else {
+            WarningLevels::new(
+                &self.config.warnings,
+                &self.options.warning_levels,
+                self.options.warnings_as_errors,
+            )
+        };
+        self.warnings.set_levels(levels);
+
         let config = self.config.clone();
         self.compile_gleam_package(&config, true, self.paths.root().to_path_buf())
             .map(|modules| Package { config, modules })
@@ -244,6 +314,10 @@ where
     }
 
     pub fn compile_dependencies(&mut self) -> Result<Vec<Module>, Error> {
+        // The warnings of the dependencies are not subject to the levels set
+        // by the root package.
+        self.warnings.set_levels(WarningLevels::default());
+
         let sequence = order_packages(&self.packages)?;
         let mut modules = vec![];
 
@@ -495,12 +569,12 @@ where
         // TODO: Test
         let package_root = match &package.source {
             // If the path is relative it is relative to the root of the
-            // project, not to the current working directory. The language server
-            // could have the working directory and the project root in different
-            // places.
-            ManifestPackageSource::Local { path } if path.is_relative() => {
-                self.io.canonicalise(&self.paths.root().join(path))?
-            }
+            // project (or of its workspace), not to the current working
+            // directory. The language server could have the working directory
+            // and the project root in different places.
+            ManifestPackageSource::Local { path } if path.is_relative() => self
+                .io
+                .canonicalise(&self.paths.manifest_directory().join(path))?,
 
             // If the path is absolute we can use it as-is.
             ManifestPackageSource::Local { path } => path.clone(),
@@ -513,8 +587,38 @@ where
         };
         let config_path = package_root.join("gleam.toml");
         let config = PackageConfig::read(config_path, &self.io)?;
-        self.compile_gleam_package(&config, false, package_root)
-            .into_result()
+        let out_path =
+            self.paths
+                .build_directory_for_package(self.mode(), self.target(), &config.name);
+
+        let cache_entry = self
+            .global_build_cache
+            .as_mut()
+            .and_then(|cache| Some((cache.entry(package)?, &*cache)));
+        if let Some((entry, cache)) = &cache_entry {
+            // The global cache is only an optimisation, if it can't be used
+            // the package is compiled as usual.
+            if let Err(error) = cache.restore(entry, &out_path) {
+                tracing::debug!(%error, "global_build_cache_restore_failed");
+            }
+        }
+        let cache_entry = cache_entry.map(|(entry, _)| entry);
+
+        let modules = self
+            .compile_gleam_package(&config, false, package_root)
+            .into_result()?;
+
+        // Packages are only stored in the cache once fully compiled, so that
+        // the artefacts can be used by any kind of build.
+        if let (Some(entry), Some(cache)) = (cache_entry, &self.global_build_cache) {
+            if self.options.codegen.should_codegen(false) {
+                if let Err(error) = cache.store(&out_path, &entry) {
+                    tracing::debug!(%error, "global_build_cache_store_failed");
+                }
+            }
+        }
+
+        Ok(modules)
     }
 
     fn compile_gleam_package(
@@ -565,7 +669,8 @@ where
             self.ids.clone(),
             self.io.clone(),
         );
-        compiler.write_metadata = true;
+        compiler.write_metadata = !(self.options.tooling && is_root);
+        compiler.use_cache = !(self.options.tooling && is_root);
         compiler.write_entrypoint = is_root;
         compiler.perform_codegen = self.options.codegen.should_codegen(is_root);
         compiler.compile_beam_bytecode = self.options.codegen.should_codegen(is_root);
@@ -590,6 +695,9 @@ where
         } else {
             CachedWarnings::Ignore
         };
+        if is_root {
+            compiler.precompiled_packages = self.precompiled_packages.clone();
+        }
 
         // Compile project to Erlang or JavaScript source code
         compiler.compile(
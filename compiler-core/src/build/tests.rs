@@ -1,6 +1,27 @@
-use crate::{manifest::ManifestPackage, Error};
+use std::{collections::HashMap, rc::Rc};
 
-use super::project_compiler::{usable_build_tools, BuildTool};
+use hexpm::version::Version;
+
+use crate::{
+    analyse::TargetSupport,
+    ast::{Publicity, SrcSpan},
+    config::PackageConfig,
+    io::{memory::InMemoryFileSystem, FileSystemWriter},
+    line_numbers::LineNumbers,
+    manifest::ManifestPackage,
+    paths::ProjectPaths,
+    type_::{
+        self, expression::Implementations, Deprecation, ModuleInterface, ValueConstructor,
+        ValueConstructorVariant,
+    },
+    warning::{VectorWarningEmitterIO, Warning, WarningKind, WarningLevel},
+    Error,
+};
+
+use super::{
+    project_compiler::{usable_build_tools, BuildTool},
+    Built, Codegen, Compile, Mode, NullTelemetry, Options, Origin, ProjectCompiler,
+};
 
 #[test]
 fn usable_build_tool_unknown() {
@@ -55,3 +76,148 @@ fn usable_build_tool_mix_then_rebar3() {
         Ok(vec![BuildTool::Mix, BuildTool::Rebar3])
     )
 }
+
+fn compile_with_denied_unused_variables(tooling: bool) -> (Result<Built, Error>, Vec<WarningKind>) {
+    let io = InMemoryFileSystem::new();
+    let paths = ProjectPaths::new("/app".into());
+    io.write(
+        &paths.src_directory().join("app.gleam"),
+        "pub fn main() {\n  let x = 1\n}",
+    )
+    .unwrap();
+
+    let mut config = PackageConfig::default();
+    config.name = "app".into();
+    _ = config
+        .warnings
+        .insert(WarningKind::UnusedVariable, WarningLevel::Deny);
+    let options = Options {
+        mode: Mode::Dev,
+        target: None,
+        compile: Compile::All,
+        codegen: Codegen::None,
+        warnings_as_errors: false,
+        warning_levels: HashMap::new(),
+        root_target_support: TargetSupport::Enforced,
+        no_print_progress: true,
+        tooling,
+    };
+    let warnings = Rc::new(VectorWarningEmitterIO::default());
+    let built = ProjectCompiler::new(
+        config,
+        options,
+        vec![],
+        &NullTelemetry,
+        warnings.clone(),
+        paths,
+        io,
+    )
+    .compile();
+
+    let kinds = warnings
+        .take()
+        .into_iter()
+        .filter_map(|warning| match warning {
+            Warning::Type { warning, .. } => Some(warning.kind()),
+            _ => None,
+        })
+        .collect();
+    (built, kinds)
+}
+
+#[test]
+fn denied_warnings_fail_the_build() {
+    let (built, warnings) = compile_with_denied_unused_variables(false);
+    assert_eq!(built.unwrap_err(), Error::DeniedWarnings { count: 1 });
+    assert_eq!(warnings, vec![WarningKind::UnusedVariable]);
+}
+
+#[test]
+fn denied_warnings_do_not_fail_a_tooling_build() {
+    let (built, warnings) = compile_with_denied_unused_variables(true);
+    assert!(built.is_ok());
+    assert_eq!(warnings, vec![WarningKind::UnusedVariable]);
+}
+
+#[test]
+fn importing_a_loaded_module_is_not_a_transitive_dependency() {
+    let io = InMemoryFileSystem::new();
+    let paths = ProjectPaths::new("/app".into());
+    io.write(
+        &paths.src_directory().join("app.gleam"),
+        "import wibble/thing\n\npub fn main() {\n  thing.new()\n}",
+    )
+    .unwrap();
+
+    let mut config = PackageConfig::default();
+    config.name = "app".into();
+    let options = Options {
+        mode: Mode::Dev,
+        target: None,
+        compile: Compile::All,
+        codegen: Codegen::None,
+        warnings_as_errors: false,
+        warning_levels: HashMap::new(),
+        root_target_support: TargetSupport::Enforced,
+        no_print_progress: true,
+        tooling: true,
+    };
+    let warnings = Rc::new(VectorWarningEmitterIO::default());
+    let mut compiler = ProjectCompiler::new(
+        config,
+        options,
+        vec![],
+        &NullTelemetry,
+        warnings.clone(),
+        paths,
+        io,
+    );
+    compiler
+        .load_importable_modules(|_| {
+            Ok(vec![ModuleInterface {
+                name: "wibble/thing".into(),
+                origin: Origin::Src,
+                package: "wibble".into(),
+                types: HashMap::new(),
+                types_value_constructors: HashMap::new(),
+                values: [(
+                    "new".into(),
+                    ValueConstructor {
+                        publicity: Publicity::Public,
+                        deprecation: Deprecation::NotDeprecated,
+                        type_: type_::fn_(vec![], type_::int()),
+                        variant: ValueConstructorVariant::ModuleFn {
+                            documentation: None,
+                            name: "new".into(),
+                            field_map: None,
+                            module: "wibble/thing".into(),
+                            arity: 0,
+                            location: SrcSpan::default(),
+                            external_erlang: None,
+                            external_javascript: None,
+                            implementations: Implementations {
+                                gleam: true,
+                                uses_erlang_externals: false,
+                                uses_javascript_externals: false,
+                                can_run_on_erlang: true,
+                                can_run_on_javascript: true,
+                            },
+                        },
+                    },
+                )]
+                .into(),
+                accessors: HashMap::new(),
+                line_numbers: LineNumbers::new(""),
+                src_path: "/lib/wibble/src/wibble/thing.gleam".into(),
+                is_internal: false,
+                warnings: vec![],
+                minimum_required_version: Version::new(0, 1, 0),
+            }])
+        })
+        .unwrap();
+
+    // The package of the loaded module is not added to the config
+    assert!(compiler.config.dependencies.is_empty());
+    assert!(compiler.compile().is_ok());
+    assert_eq!(warnings.take(), vec![]);
+}
@@ -4,6 +4,7 @@ use crate::io::FileSystemReader;
 use crate::manifest::Manifest;
 use crate::requirement::Requirement;
 use crate::version::COMPILER_VERSION;
+use crate::warning::{WarningKind, WarningLevel};
 use crate::{Error, Result};
 use camino::{Utf8Path, Utf8PathBuf};
 use ecow::EcoString;
@@ -103,6 +104,12 @@ pub struct PackageConfig {
     pub target: Target,
     #[serde(default)]
     pub internal_modules: Option<Vec<Glob>>,
+    /// The level each kind of warning is reported at, the ones not listed
+    /// are reported as warnings.
+    #[serde(default)]
+    pub warnings: HashMap<WarningKind, WarningLevel>,
+    #[serde(default, alias = "license_policy")]
+    pub licence_policy: LicencePolicy,
 }
 
 pub fn serialise_range<S>(
@@ -579,11 +586,48 @@ impl Default for PackageConfig {
             licences: Default::default(),
             links: Default::default(),
             internal_modules: Default::default(),
+            warnings: Default::default(),
+            licence_policy: Default::default(),
             target: Target::Erlang,
         }
     }
 }
 
+/// The licences the dependency packages of a project are allowed to use, as
+/// set in the `[licence_policy]` table of its `gleam.toml`.
+///
+#[derive(Deserialize, Debug, PartialEq, Eq, Default, Clone)]
+pub struct LicencePolicy {
+    /// When not empty, only these licences are allowed.
+    #[serde(default)]
+    pub allow: Vec<SpdxLicense>,
+    #[serde(default)]
+    pub deny: Vec<SpdxLicense>,
+}
+
+impl LicencePolicy {
+    pub fn is_empty(&self) -> bool {
+        self.allow.is_empty() && self.deny.is_empty()
+    }
+
+    /// Whether a package with the given licences can be used. A package with
+    /// several licences can be used under any of them, so it is permitted if
+    /// one of them is allowed and not denied. Packages with no known licence
+    /// are only permitted when there is no allow list.
+    ///
+    pub fn permits(&self, licences: &[EcoString]) -> bool {
+        if licences.is_empty() {
+            return self.allow.is_empty();
+        }
+        let listed = |list: &[SpdxLicense], licence: &EcoString| {
+            list.iter().any(|listed| listed.licence == licence.as_str())
+        };
+        licences.iter().any(|licence| {
+            (self.allow.is_empty() || listed(&self.allow, licence)) && !listed(&self.deny, licence)
+        })
+    }
+}
+
 #[derive(Deserialize, Debug, PartialEq, Eq, Default, Clone)]
 pub struct ErlangConfig {
     #[serde(default)]
@@ -897,3 +941,80 @@ name = "1"
         "Package names may only contain lowercase letters, numbers, and underscores for key `name` at line 1 column 1"
     )
 }
+
+#[test]
+fn warning_levels() {
+    let input = r#"
+name = "wibble"
+
+[warnings]
+todo = "deny"
+deprecated_item = "allow"
+javascript_int_unsafe = "warn"
+"#;
+    assert_eq!(
+        toml::from_str::<PackageConfig>(input).unwrap().warnings,
+        HashMap::from([
+            (WarningKind::Todo, WarningLevel::Deny),
+            (WarningKind::DeprecatedItem, WarningLevel::Allow),
+            (WarningKind::JavaScriptIntUnsafe, WarningLevel::Warn),
+        ])
+    )
+}
+
+#[test]
+fn unknown_warning_level() {
+    let input = r#"
+name = "wibble"
+
+[warnings]
+unused_wibble = "deny"
+"#;
+    assert!(toml::from_str::<PackageConfig>(input).is_err())
+}
+
+#[test]
+fn licence_policy() {
+    let input = r#"
+name = "wibble"
+
+[licence_policy]
+allow = ["MIT", "Apache-2.0"]
+deny = ["GPL-3.0-only"]
+"#;
+    let policy = toml::from_str::<PackageConfig>(input)
+        .unwrap()
+        .licence_policy;
+    assert!(policy.permits(&["MIT".into()]));
+    assert!(policy.permits(&["GPL-3.0-only".into(), "Apache-2.0".into()]));
+    assert!(!policy.permits(&["GPL-3.0-only".into()]));
+    assert!(!policy.permits(&["BSD-3-Clause".into()]));
+    assert!(!policy.permits(&[]));
+}
+
+#[test]
+fn licence_policy_deny_only() {
+    let input = r#"
+name = "wibble"
+
+[license_policy]
+deny = ["GPL-3.0-only"]
+"#;
+    let policy = toml::from_str::<PackageConfig>(input)
+        .unwrap()
+        .licence_policy;
+    assert!(policy.permits(&["BSD-3-Clause".into()]));
+    assert!(policy.permits(&[]));
+    assert!(!policy.permits(&["GPL-3.0-only".into()]));
+}
+
+#[test]
+fn licence_policy_invalid_licence() {
+    let input = r#"
+name = "wibble"
+
+[licence_policy]
+allow = ["Wobble"]
+"#;
+    assert!(toml::from_str::<PackageConfig>(input).is_err())
+}
@@ -55,6 +55,9 @@ pub struct Diagnostic {
     pub title: String,
     pub text: String,
     pub level: Level,
+    /// The stable code identifying the kind of problem, which can be looked
+    /// up with `gleam explain`.
+    pub code: Option<&'static str>,
     pub location: Option<Location>,
     pub hint: Option<String>,
 }
@@ -112,9 +115,12 @@ impl Diagnostic {
             Level::Warning => Severity::Warning,
         };
 
-        let diagnostic = codespan_reporting::diagnostic::Diagnostic::new(severity)
+        let mut diagnostic = codespan_reporting::diagnostic::Diagnostic::new(severity)
             .with_message(&self.title)
             .with_labels(labels);
+        if let Some(code) = self.code {
+            diagnostic = diagnostic.with_code(code);
+        }
         let config = codespan_reporting::term::Config::default();
         codespan_reporting::term::emit(buffer, &config, &files, &diagnostic)
             .expect("write_diagnostic");
@@ -131,6 +137,9 @@ impl Diagnostic {
             .set_color(ColorSpec::new().set_bold(true).set_fg(Some(colour)))
             .expect("write_title_color1");
         write!(buffer, "{kind}").expect("write_title_kind");
+        if let Some(code) = self.code {
+            write!(buffer, "[{code}]").expect("write_title_code");
+        }
         buffer
             .set_color(ColorSpec::new().set_bold(true))
             .expect("write_title_color2");
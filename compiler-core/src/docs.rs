@@ -554,7 +554,7 @@ fn function<'a>(
                 source_url: source_links.url(*location),
                 deprecation_message: match deprecation {
                     Deprecation::NotDeprecated => "".to_string(),
-                    Deprecation::Deprecated { message } => message.to_string(),
+                    Deprecation::Deprecated { message, .. } => message.to_string(),
                 },
             })
         }
@@ -620,7 +620,7 @@ fn type_<'a>(source_links: &SourceLinker, statement: &'a TypedDefinition) -> Opt
             text_documentation: text_documentation(&ct.documentation),
             deprecation_message: match &ct.deprecation {
                 Deprecation::NotDeprecated => "".to_string(),
-                Deprecation::Deprecated { message } => message.to_string(),
+                Deprecation::Deprecated { message, .. } => message.to_string(),
             },
             constructors: ct
                 .constructors
@@ -667,7 +667,7 @@ fn type_<'a>(source_links: &SourceLinker, statement: &'a TypedDefinition) -> Opt
             source_url: source_links.url(*location),
             deprecation_message: match deprecation {
                 Deprecation::NotDeprecated => "".to_string(),
-                Deprecation::Deprecated { message } => message.to_string(),
+                Deprecation::Deprecated { message, .. } => message.to_string(),
             },
             opaque: true,
         }),
@@ -694,7 +694,7 @@ fn type_<'a>(source_links: &SourceLinker, statement: &'a TypedDefinition) -> Opt
             source_url: source_links.url(*location),
             deprecation_message: match deprecation {
                 Deprecation::NotDeprecated => "".to_string(),
-                Deprecation::Deprecated { message } => message.to_string(),
+                Deprecation::Deprecated { message, .. } => message.to_string(),
             },
             opaque: false,
         }),
@@ -210,6 +210,9 @@ file_names.iter().map(|x| x.as_str()).join(", "))]
     #[error("warnings are not permitted")]
     ForbiddenWarnings { count: usize },
 
+    #[error("denied warnings were emitted")]
+    DeniedWarnings { count: usize },
+
     #[error("javascript codegen failed")]
     JavaScript {
         path: Utf8PathBuf,
@@ -277,6 +280,9 @@ file_names.iter().map(|x| x.as_str()).join(", "))]
     #[error("Opening docs at {path} failed: {error}")]
     FailedToOpenDocs { path: Utf8PathBuf, error: String },
 
+    #[error("Bindings cannot be generated from {path}")]
+    UnsupportedFfiSource { path: Utf8PathBuf },
+
     #[error(
         "The package {package} requires a Gleam version satisfying \
 {required_version} and you are using v{gleam_version}"
@@ -319,6 +325,36 @@ file_names.iter().map(|x| x.as_str()).join(", "))]
 
     #[error("Failed to decrypt data")]
     FailedToDecrypt { detail: String },
+
+    #[error("Unknown error code {code}")]
+    UnknownErrorCode { code: String },
+
+    #[error("Dependency licences are not allowed by the licence policy")]
+    LicencePolicyViolation {
+        /// Each package not allowed, with its licences.
+        packages: Vec<(EcoString, Vec<EcoString>)>,
+    },
+
+    #[error("Dependency audit found problems of {threshold} severity or higher")]
+    DependencyAuditFailed {
+        /// Each package with a problem, with the highest severity of its
+        /// problems.
+        packages: Vec<(EcoString, crate::audit::Severity)>,
+        threshold: crate::audit::Severity,
+    },
+
+    #[error("Advisory database {path} could not be read")]
+    InvalidAdvisoryDatabase { path: Utf8PathBuf, error: String },
+
+    #[error("The root of the workspace at {path} is not a package")]
+    WorkspaceRootIsNotAPackage { path: Utf8PathBuf },
+
+    #[error("Members of the workspace require the package {package} differently")]
+    ConflictingWorkspaceRequirements {
+        package: EcoString,
+        /// The two members and the requirement each of them has.
+        requirements: Vec<(EcoString, EcoString)>,
+    },
 }
 
 /// This is to make clippy happy and not make the error variant too big by
@@ -603,7 +639,7 @@ pub enum FileIoAction {
     Read,
     Parse,
     Delete,
-    // Rename,
+    Rename,
     Create,
     WriteTo,
     Canonicalise,
@@ -621,7 +657,7 @@ impl FileIoAction {
             FileIoAction::Read => "read",
             FileIoAction::Parse => "parse",
             FileIoAction::Delete => "delete",
-            // FileIoAction::Rename => "rename",
+            FileIoAction::Rename => "rename",
             FileIoAction::Create => "create",
             FileIoAction::WriteTo => "write to",
             FileIoAction::FindParent => "find the parent of",
@@ -819,6 +855,83 @@ fn did_you_mean(name: &str, options: &[EcoString]) -> Option<String> {
 }
 
 impl Error {
+    /// The stable code identifying this kind of error, which can be looked
+    /// up with `gleam explain`. Type errors, which come in groups, have the
+    /// code of the first error of the group.
+    ///
+    pub fn code(&self) -> &'static str {
+        match self {
+            Error::Parse { error, .. } => error.error.code(),
+            Error::Type { errors, .. } => errors.first().code(),
+            Error::UnknownImport { .. } => "E0001",
+            Error::DuplicateModule { .. } => "E0002",
+            Error::DuplicateSourceFile { .. } => "E0003",
+            Error::DuplicateNativeErlangModule { .. } => "E0004",
+            Error::ClashingGleamModuleAndNativeFileName { .. } => "E0005",
+            Error::ImportCycle { .. } => "E0006",
+            Error::PackageCycle { .. } => "E0007",
+            Error::FileIo { .. } => "E0008",
+            Error::NonUtf8Path { .. } => "E0009",
+            Error::GitInitialization { .. } => "E0010",
+            Error::StandardIo { .. } => "E0011",
+            Error::Format { .. } => "E0012",
+            Error::Hex(..) => "E0013",
+            Error::ExpandTar { .. } => "E0014",
+            Error::AddTar { .. } => "E0015",
+            Error::TarFinish(..) => "E0016",
+            Error::Gzip(..) => "E0017",
+            Error::ShellProgramNotFound { .. } => "E0018",
+            Error::ShellCommand { .. } => "E0019",
+            Error::InvalidProjectName { .. } => "E0020",
+            Error::InvalidModuleName { .. } => "E0021",
+            Error::ModuleDoesNotExist { .. } => "E0022",
+            Error::ModuleDoesNotHaveMainFunction { .. } => "E0023",
+            Error::MainFunctionHasWrongArity { .. } => "E0024",
+            Error::MainFunctionDoesNotSupportTarget { .. } => "E0025",
+            Error::InvalidVersionFormat { .. } => "E0026",
+            Error::ProjectRootAlreadyExist { .. } => "E0027",
+            Error::OutputFilesAlreadyExist { .. } => "E0028",
+            Error::RemovedPackagesNotExist { .. } => "E0029",
+            Error::UnableToFindProjectRoot { .. } => "E0030",
+            Error::VersionDoesNotMatch { .. } => "E0031",
+            Error::MetadataDecodeError { .. } => "E0032",
+            Error::ForbiddenWarnings { .. } => "E0033",
+            Error::DeniedWarnings { .. } => "E0034",
+            Error::JavaScript { .. } => "E0035",
+            Error::InvalidRuntime { .. } => "E0036",
+            Error::DownloadPackageError { .. } => "E0037",
+            Error::Http(..) => "E0038",
+            Error::GitDependencyUnsupported => "E0039",
+            Error::DependencyCanonicalizationFailed(..) => "E0040",
+            Error::DependencyResolutionFailed(..) => "E0041",
+            Error::DuplicateDependency(..) => "E0042",
+            Error::WrongDependencyProvided { .. } => "E0043",
+            Error::ProvidedDependencyConflict { .. } => "E0044",
+            Error::MissingHexPublishFields { .. } => "E0045",
+            Error::PublishNonHexDependencies { .. } => "E0046",
+            Error::UnsupportedBuildTool { .. } => "E0047",
+            Error::FailedToOpenDocs { .. } => "E0048",
+            Error::UnsupportedFfiSource { .. } => "E0049",
+            Error::IncompatibleCompilerVersion { .. } => "E0050",
+            Error::JavaScriptPreludeRequired => "E0051",
+            Error::CannotPublishTodo { .. } => "E0052",
+            Error::CannotPublishLeakedInternalType { .. } => "E0053",
+            Error::HexPackageSquatting => "E0054",
+            Error::CorruptManifest => "E0055",
+            Error::GleamModuleWouldOverwriteStandardErlangModule { .. } => "E0056",
+            Error::HexPublishReplaceRequired { .. } => "E0057",
+            Error::CannotPublishWrongVersion { .. } => "E0058",
+            Error::FailedToEncrypt { .. } => "E0059",
+            Error::FailedToDecrypt { .. } => "E0060",
+            Error::UnknownErrorCode { .. } => "E0061",
+            Error::LicencePolicyViolation { .. } => "E0062",
+            Error::DependencyAuditFailed { .. } => "E0063",
+            Error::InvalidAdvisoryDatabase { .. } => "E0064",
+            Error::WorkspaceRootIsNotAPackage { .. } => "E0065",
+            Error::ConflictingWorkspaceRequirements { .. } => "E0066",
+        }
+    }
+
     pub fn pretty_string(&self) -> String {
         let mut nocolor = Buffer::no_color();
         self.pretty(&mut nocolor);
@@ -847,6 +960,7 @@ package deletion or account suspension.
                     title: "Invalid Hex package".into(),
                     text,
                     level: Level::Error,
+                    code: Some(self.code()),
                     location: None,
                     hint: None,
                 }]
@@ -865,6 +979,7 @@ of the Gleam dependency modules."
                     title: "Failed to decode module metadata".into(),
                     text,
                     level: Level::Error,
+                    code: Some(self.code()),
                     location: None,
                     hint: None,
                 }]
@@ -878,6 +993,7 @@ of the Gleam dependency modules."
                     text,
                     hint: None,
                     level: Level::Error,
+                    code: Some(self.code()),
                     location: None,
                 }]
             }
@@ -890,6 +1006,7 @@ Module names can only contain lowercase letters, underscore, and
 forward slash and must not end with a slash."
                 ),
                 level: Level::Error,
+                code: Some(self.code()),
                 location: None,
                 hint: None,
             }],
@@ -903,6 +1020,7 @@ forward slash and must not end with a slash."
                     title: "Module does not exist".into(),
                     text: format!("Module `{module}` was not found."),
                     level: Level::Error,
+                    code: Some(self.code()),
                     location: None,
                     hint: Some(hint),
                 }]
@@ -914,6 +1032,7 @@ forward slash and must not end with a slash."
                     "`{module}` does not have a main function so the module can not be run."
                 ),
                 level: Level::Error,
+                code: Some(self.code()),
                 location: None,
                 hint: Some(format!(
                     "Add a public `main` function to \
@@ -928,6 +1047,7 @@ to `src/{module}.gleam`."
 target, so it cannot be run."
                 ),
                 level: Level::Error,
+                code: Some(self.code()),
                 location: None,
                 hint: None,
             }],
@@ -938,6 +1058,7 @@ target, so it cannot be run."
                     "`{module}:main` should have an arity of 0 to be run but its arity is {arity}."
                 ),
                 level: Level::Error,
+                code: Some(self.code()),
                 location: None,
                 hint: Some("Change the function signature of main to `pub fn main() {}`.".into()),
             }],
@@ -946,6 +1067,7 @@ target, so it cannot be run."
                 title: "Project folder already exists".into(),
                 text: format!("Project folder root:\n\n  {path}"),
                 level: Level::Error,
+                code: Some(self.code()),
                 hint: None,
                 location: None,
             }],
@@ -970,6 +1092,7 @@ If you want to overwrite these files, delete them and run the command again.
                         .join("\n")
                 ),
                 level: Level::Error,
+                code: Some(self.code()),
                 hint: None,
                 location: None,
             }],
@@ -989,6 +1112,7 @@ be removed.
                         .join("\n")
                     ),
                     level: Level::Error,
+                    code: Some(self.code()),
                     hint: None,
                     location: None,
                 }
@@ -1009,6 +1133,7 @@ Please remove them and try again.
                         .join("\n")
                 ),
                 level: Level::Error,
+                code: Some(self.code()),
                 hint: None,
                 location: None,
             }],
@@ -1022,6 +1147,7 @@ code to run on an earlier version like v{wrongfully_allowed_version}, \
 resulting in compilation errors!"
                 )),
                 level: Level::Error,
+                code: Some(self.code()),
                 hint: Some(format!(
                     "Remove the version constraint from your `gleam.toml` or update it to be:
 
@@ -1045,6 +1171,7 @@ Please make sure internal types do not appear in public functions and try again.
                         .join("\n")
                 ),
                 level: Level::Error,
+                code: Some(self.code()),
                 hint: None,
                 location: None,
             }],
@@ -1060,6 +1187,7 @@ We searched in {path} and all parent directories."
                     text,
                     hint: None,
                     level: Level::Error,
+                    code: Some(self.code()),
                     location: None,
                 }]
             }
@@ -1074,6 +1202,7 @@ your app.src file \"{app_ver}\"."
                     hint: None,
                     text,
                     level: Level::Error,
+                    code: Some(self.code()),
                     location: None,
                 }]
             }
@@ -1161,6 +1290,7 @@ https://git-scm.com/book/en/v2/Getting-Started-Installing-Git",
                     text,
                     hint: None,
                     level: Level::Error,
+                    code: Some(self.code()),
                     location: None,
                 }]
             }
@@ -1176,6 +1306,7 @@ https://git-scm.com/book/en/v2/Getting-Started-Installing-Git",
                     text,
                     hint: None,
                     level: Level::Error,
+                    code: Some(self.code()),
                     location: None,
                 }]
             }
@@ -1198,6 +1329,7 @@ The error from the shell command library was:
                     text,
                     hint: None,
                     level: Level::Error,
+                    code: Some(self.code()),
                     location: None,
                 }]
             }
@@ -1215,6 +1347,7 @@ This was error from the gzip library:
                     text,
                     hint: None,
                     level: Level::Error,
+                    code: Some(self.code()),
                     location: None,
                 }]
             }
@@ -1233,6 +1366,7 @@ This was error from the tar library:
                     text,
                     hint: None,
                     level: Level::Error,
+                    code: Some(self.code()),
                     location: None,
                 }]
             }
@@ -1250,6 +1384,7 @@ This was error from the tar library:
                     text,
                     hint: None,
                     level: Level::Error,
+                    code: Some(self.code()),
                     location: None,
                 }]
             }
@@ -1267,6 +1402,7 @@ This was error from the tar library:
                     text,
                     hint: None,
                     level: Level::Error,
+                    code: Some(self.code()),
                     location: None,
                 }]
             }
@@ -1284,6 +1420,7 @@ This was error from the Hex client library:
                     text,
                     hint: None,
                     level: Level::Error,
+                    code: Some(self.code()),
                     location: None,
                 }]
             }
@@ -1305,6 +1442,7 @@ Second: {second}"
                     text,
                     hint: None,
                     level: Level::Error,
+                    code: Some(self.code()),
                     location: None,
                 }]
             }
@@ -1325,6 +1463,7 @@ same name and extension, unintentionally overwriting the native file.");
                     text,
                     hint: Some("Consider renaming one of the files, such as by adding an `_ffi` suffix to the native file's name, and trying again.".into()),
                     level: Level::Error,
+                    code: Some(self.code()),
                     location: None,
                 }]
             },
@@ -1334,6 +1473,7 @@ same name and extension, unintentionally overwriting the native file.");
                 text: format!("The file `{file}` is defined multiple times."),
                 hint: None,
                 level: Level::Error,
+                code: Some(self.code()),
                 location: None,
             }],
 
@@ -1357,6 +1497,7 @@ Erlang modules must have unique names regardless of the subfolders where their
                     text,
                     hint: Some("Rename one of the native Erlang modules and try again.".into()),
                     level: Level::Error,
+                    code: Some(self.code()),
                     location: None,
                 }]
             },
@@ -1396,6 +1537,7 @@ https://learn.microsoft.com/en-us/windows/apps/get-started/enable-your-device-fo
                     text,
                     hint: None,
                     level: Level::Error,
+                    code: Some(self.code()),
                     location: None,
                 }]
             }
@@ -1412,6 +1554,7 @@ The error from the encryption library was:
                     text,
                     hint: None,
                     level: Level::Error,
+                    code: Some(self.code()),
                     location: None,
                 }]
             }
@@ -1427,6 +1570,7 @@ The error from the encryption library was:
                     text,
                     hint: None,
                     level: Level::Error,
+                    code: Some(self.code()),
                     location: None,
                 }]
             }
@@ -1440,6 +1584,7 @@ The error from the encryption library was:
                     title: "Non UTF-8 Path Encountered".into(),
                     text,
                     level: Level::Error,
+                    code: Some(self.code()),
                     location: None,
                     hint: None,
                 }]
@@ -1456,6 +1601,7 @@ The error from the encryption library was:
                     text,
                     hint: None,
                     level: Level::Error,
+                    code: Some(self.code()),
                     location: None,
                 }]
             }
@@ -1473,6 +1619,7 @@ Erlang's floating point type. To avoid this error float values must be in the ra
 -1.7976931348623157e308 - 1.7976931348623157e308."),
                     hint: None,
                     level: Level::Error,
+                    code: Some(error.code()),
                     location: Some(Location {
                         label: Label {
                             text: None,
@@ -1504,6 +1651,7 @@ module to the src directory.",
                         text,
                         hint: None,
                         level: Level::Error,
+                        code: Some(error.code()),
                         location: Some(Location {
                             label: Label {
                                 text: Some("Imported here".into()),
@@ -1566,6 +1714,7 @@ constructor accepts."
                         text,
                         hint: None,
                         level: Level::Error,
+                        code: Some(error.code()),
                         location: Some(Location {
                             label,
                             path: path.clone(),
@@ -1585,6 +1734,7 @@ not expect any. Please remove the label `{label}`."
                         text,
                         hint: None,
                         level: Level::Error,
+                        code: Some(error.code()),
                         location: Some(Location {
                             label: Label {
                                 text: None,
@@ -1608,6 +1758,7 @@ also be labelled.");
                         text,
                         hint: None,
                         level: Level::Error,
+                        code: Some(error.code()),
                         location: Some(Location {
                             label: Label {
                                 text: None,
@@ -1634,6 +1785,7 @@ Names in a Gleam module must be unique so one will need to be renamed."
                         text,
                         hint: None,
                         level: Level::Error,
+                        code: Some(error.code()),
                         location: Some(Location {
                             label: Label {
                                 text: Some("Reimported here".into()),
@@ -1672,6 +1824,7 @@ Names in a Gleam module must be unique so one will need to be renamed."
                         text,
                         hint: None,
                         level: Level::Error,
+                        code: Some(error.code()),
                         location: Some(Location {
                             label: Label {
                                 text: Some("Redefined here".into()),
@@ -1705,6 +1858,7 @@ Names in a Gleam module must be unique so one will need to be renamed."
                         text,
                         hint: None,
                         level: Level::Error,
+                        code: Some(error.code()),
                         location: Some(Location {
                             label: Label {
                                 text: Some("Redefined here".into()),
@@ -1731,6 +1885,7 @@ Names in a Gleam module must be unique so one will need to be renamed."
                         text,
                         hint: None,
                         level: Level::Error,
+                        code: Some(error.code()),
                         location: Some(Location {
                             label: Label {
                                 text: None,
@@ -1750,6 +1905,7 @@ Names in a Gleam module must be unique so one will need to be renamed."
                         text,
                         hint: None,
                         level: Level::Error,
+                        code: Some(error.code()),
                         location: Some(Location {
                             label: Label {
                                 text: None,
@@ -1773,6 +1929,7 @@ Hint: Add some type annotations and try again.")
                         text,
                         hint: None,
                         level: Level::Error,
+                        code: Some(error.code()),
                         location: Some(Location {
                             label: Label {
                                 text: None,
@@ -1796,6 +1953,7 @@ Hint: Add some type annotations and try again.")
                         text,
                         hint: None,
                         level: Level::Error,
+                        code: Some(error.code()),
                         location: Some(Location {
                             label: Label {
                                 text: None,
@@ -1888,6 +2046,7 @@ to call a method on this value you may want to use the function syntax instead."
                         text,
                         hint: None,
                         level: Level::Error,
+                        code: Some(error.code()),
                         location: Some(Location {
                             label: Label {
                                 text: Some(label),
@@ -1929,6 +2088,7 @@ But this argument has this type:
                         text,
                         hint: None,
                         level: Level::Error,
+                        code: Some(error.code()),
                         location: Some(Location {
                             label: Label {
                                 text: None,
@@ -1978,6 +2138,7 @@ But function expects:
                         text,
                         hint: None,
                         level: Level::Error,
+                        code: Some(error.code()),
                         location: Some(Location {
                             label: Label {
                                 text: Some("This function does not accept the piped type".into()),
@@ -2028,6 +2189,7 @@ But function expects:
                         text,
                         hint: None,
                         level: Level::Error,
+                        code: Some(error.code()),
                         location: Some(Location {
                             label: Label {
                                 text: main_message_text,
@@ -2058,6 +2220,7 @@ called with their expected number of arguments.");
                         text,
                         hint: None,
                         level: Level::Error,
+                        code: Some(error.code()),
                         location: Some(Location {
                             label: Label {
                                 text: Some(format!("Expected {expected}, got {given}")),
@@ -2097,6 +2260,7 @@ called with their expected number of arguments.");
                         text,
                         hint: None,
                         level: Level::Error,
+                        code: Some(error.code()),
                         location: Some(Location {
                             label: Label {
                                 text: Some(label),
@@ -2119,6 +2283,7 @@ assigned variables to all of them."
                         text,
                         hint: None,
                         level: Level::Error,
+                        code: Some(error.code()),
                         location: Some(Location {
                             label: Label {
                                 text: None,
@@ -2146,6 +2311,7 @@ constructing a new record with its values.");
                                 text,
                                 hint: None,
                                 level: Level::Error,
+                                code: Some(error.code()),
                                 location: Some(Location {
                                     label: Label {
                                         text: Some(format!("I'm not sure this is always a `{constructed_variant}`")),
@@ -2169,6 +2335,7 @@ specify all fields explicitly instead of using the record update syntax.");
                                 text,
                                 hint: None,
                                 level: Level::Error,
+                                code: Some(error.code()),
                                 location: Some(Location {
                                     label: Label {
                                         text: Some(format!("This is a `{spread_variant}`")),
@@ -2194,6 +2361,7 @@ Note: If the same type variable is used for multiple fields, all those fields ne
                                 text,
                                 hint: None,
                                 level: Level::Error,
+                                code: Some(error.code()),
                                 location: Some(Location {
                                     label: Label {
                                         text: Some(format!("This is a `{record_variant}`")),
@@ -2238,6 +2406,7 @@ but no type in scope with that name."
                         text,
                         hint: None,
                         level: Level::Error,
+                        code: Some(error.code()),
                         location: Some(Location {
                             label: Label {
                                 text: label_text,
@@ -2272,6 +2441,7 @@ but no type in scope with that name."
                         text,
                         hint: None,
                         level: Level::Error,
+                        code: Some(error.code()),
                         location: Some(Location {
                             label: Label {
                                 text: did_you_mean(name, variables),
@@ -2306,6 +2476,7 @@ Private types can only be used within the module that defines them.",
                         text,
                         hint: None,
                         level: Level::Error,
+                        code: Some(error.code()),
                         location: Some(Location {
                             label: Label {
                                 text: None,
@@ -2327,6 +2498,7 @@ Private types can only be used within the module that defines them.",
                     text: format!("No module has been found with the name `{name}`."),
                     hint: suggestions.first().map(|suggestion| suggestion.suggestion(name)),
                     level: Level::Error,
+                    code: Some(error.code()),
                     location: Some(Location {
                         label: Label {
                             text: None,
@@ -2355,6 +2527,7 @@ Private types can only be used within the module that defines them.",
                         text,
                         hint: None,
                         level: Level::Error,
+                        code: Some(error.code()),
                         location: Some(Location {
                             label: Label {
                                 text: if *imported_type_as_value {
@@ -2394,6 +2567,7 @@ Private types can only be used within the module that defines them.",
                         text,
                         hint: None,
                         level: Level::Error,
+                        code: Some(error.code()),
                         location: Some(Location {
                             label: Label {
                                 text: if *imported_value_as_type && matches!(context, ModuleValueUsageContext::UnqualifiedImport) {
@@ -2423,6 +2597,7 @@ them to functions, or anything else that you would do with a value."
                         text,
                         hint: None,
                         level: Level::Error,
+                        code: Some(error.code()),
                         location: Some(Location {
                             label: Label {
                                 text: None,
@@ -2450,6 +2625,7 @@ Each clause must have a pattern for every subject value.",
                         text,
                         hint: None,
                         level: Level::Error,
+                        code: Some(error.code()),
                         location: Some(Location {
                             label: Label {
                                 text: Some(format!("Expected {expected} patterns, got {given}")),
@@ -2473,6 +2649,7 @@ function, or be an argument to the function. The variable \
                         text,
                         hint: None,
                         level: Level::Error,
+                        code: Some(error.code()),
                         location: Some(Location {
                             label: Label {
                                 text: Some("Is not locally defined".into()),
@@ -2496,6 +2673,7 @@ previously defined.",
                         text,
                         hint: None,
                         level: Level::Error,
+                        code: Some(error.code()),
                         location: Some(Location {
                             label: Label {
                                 text: Some("Has not been previously defined".into()),
@@ -2518,6 +2696,7 @@ as the initial pattern, but the `{name}` variable is missing.",
                         text,
                         hint: None,
                         level: Level::Error,
+                        code: Some(error.code()),
                         location: Some(Location {
                             label: Label {
                                 text: Some("This does not define all required variables".into()),
@@ -2543,6 +2722,7 @@ e.g. (x, y) if x == y -> ...",
                         text,
                         hint: None,
                         level: Level::Error,
+                        code: Some(error.code()),
                         location: Some(Location {
                             label: Label {
                                 text: Some("This has already been used".into()),
@@ -2562,6 +2742,7 @@ e.g. (x, y) if x == y -> ...",
                     text: "This tuple has no elements so it cannot be indexed at all.".into(),
                     hint: None,
                     level: Level::Error,
+                    code: Some(error.code()),
                     location: Some(Location {
                         label: Label {
                             text: None,
@@ -2590,6 +2771,7 @@ tuple has {} elements so the highest valid index is {}.",
                         text,
                         hint: None,
                         level: Level::Error,
+                        code: Some(error.code()),
                         location: Some(Location {
                             label: Label {
                                 text: Some("This index is too large".into()),
@@ -2615,6 +2797,7 @@ tuple has {} elements so the highest valid index is {}.",
                         text,
                         hint: None,
                         level: Level::Error,
+                        code: Some(error.code()),
                         location: Some(Location {
                             label: Label {
                                 text: Some("This is not a tuple".into()),
@@ -2637,6 +2820,7 @@ Please add some type annotations so we can continue."
                         text,
                         hint: None,
                         level: Level::Error,
+                        code: Some(error.code()),
                         location: Some(Location {
                             label: Label {
                                 text: Some("What type is this?".into()),
@@ -2659,6 +2843,7 @@ function and try again.");
                         text,
                         hint: None,
                         level: Level::Error,
+                        code: Some(error.code()),
                         location: Some(Location {
                             label: Label {
                                 text: Some("I don't know what type this is".into()),
@@ -2671,8 +2856,8 @@ function and try again.");
                     }
                 }
 
-                TypeError::BitArraySegmentError { error, location } => {
-                    let (label, mut extra) = match error {
+                TypeError::BitArraySegmentError { error: segment_error, location } => {
+                    let (label, mut extra) = match segment_error {
                             bit_array::ErrorType::ConflictingTypeOptions { existing_type } => (
                                 "This is an extra type specifier",
                                 vec![format!("Hint: This segment already has the type {existing_type}.")],
@@ -2756,6 +2941,7 @@ function and try again.");
                         text,
                         hint: None,
                         level: Level::Error,
+                        code: Some(error.code()),
                         location: Some(Location {
                             label: Label {
                                 text: Some(label.into()),
@@ -2773,6 +2959,7 @@ function and try again.");
                     text: "Only record constructors can be used with the update syntax.".into(),
                     hint: None,
                     level: Level::Error,
+                    code: Some(error.code()),
                     location: Some(Location {
                         label: Label {
                             text: Some("This is not a record constructor".into()),
@@ -2789,6 +2976,7 @@ function and try again.");
                     text: "We need to know the exact type here so type holes cannot be used.".into(),
                     hint: None,
                     level: Level::Error,
+                    code: Some(error.code()),
                     location: Some(Location {
                         label: Label {
                             text: Some("I need to know what this is".into()),
@@ -2811,6 +2999,7 @@ Try a different name for this module."
                         hint: None,
                         location: None,
                         level: Level::Error,
+                        code: Some(error.code()),
                     }
                 }
 
@@ -2826,6 +3015,7 @@ Try a different name for this module."
                         hint: None,
                         location: None,
                         level: Level::Error,
+                        code: Some(error.code()),
                     }
                 }
 
@@ -2859,6 +3049,7 @@ value, or use `let assert` rather than `let`."
                         text,
                         hint: None,
                         level: Level::Error,
+                        code: Some(error.code()),
                         location: Some(Location {
                             label: Label {
                                 text: None,
@@ -2876,6 +3067,7 @@ value, or use `let assert` rather than `let`."
                     text: format!("Two `{name}` arguments have been defined for this function."),
                     hint: None,
                     level: Level::Error,
+                    code: Some(error.code()),
                     location: Some(Location {
                         label: Label {
                             text: None,
@@ -2892,6 +3084,7 @@ value, or use `let assert` rather than `let`."
                     text: wrap("All unlabelled arguments must come before any labelled arguments."),
                     hint: None,
                     level: Level::Error,
+                    code: Some(error.code()),
                     location: Some(Location {
                         label: Label {
                             text: None,
@@ -2915,6 +3108,7 @@ forever in a loop, and we'd never get the final type.",
                         text,
                         hint: None,
                         level: Level::Error,
+                        code: Some(error.code()),
                         location: Some(Location {
                             label: Label {
                                 text: None,
@@ -2943,6 +3137,7 @@ so we can tell what type of values they accept and return.",
                         text,
                         hint: None,
                         level: Level::Error,
+                        code: Some(error.code()),
                         location: Some(Location {
                             label: Label {
                                 text: None,
@@ -2965,6 +3160,7 @@ using the `@external` attribute."
                         text,
                         hint: None,
                         level: Level::Error,
+                        code: Some(error.code()),
                         location: Some(Location {
                             label: Label {
                                 text: None,
@@ -2991,6 +3187,7 @@ implementation but the module path `{module}` is not valid."
                         text,
                         hint: None,
                         level: Level::Error,
+                        code: Some(error.code()),
                         location: Some(Location {
                             label: Label {
                                 text: None,
@@ -3017,6 +3214,7 @@ implementation but the function name `{function}` is not valid."
                         text,
                         hint: None,
                         level: Level::Error,
+                        code: Some(error.code()),
                         location: Some(Location {
                             label: Label {
                                 text: None,
@@ -3047,6 +3245,7 @@ The missing patterns are:\n");
                         text,
                         hint: Some("Use a more general pattern or use `let assert` instead.".into()),
                         level: Level::Error,
+                        code: Some(error.code()),
                         location: Some(Location {
                             src: src.clone(),
                             path: path.to_path_buf(),
@@ -3076,6 +3275,7 @@ The missing patterns are:\n"
                         text,
                         hint: None,
                         level: Level::Error,
+                        code: Some(error.code()),
                         location: Some(Location {
                             src: src.clone(),
                             path: path.to_path_buf(),
@@ -3097,6 +3297,7 @@ The missing patterns are:\n"
                         text,
                         hint: None,
                         level: Level::Error,
+                        code: Some(error.code()),
                         location: Some(Location {
                             src: src.clone(),
                             path: path.to_path_buf(),
@@ -3127,6 +3328,7 @@ and there is no implementation for the {} target.\n",
                         text,
                         hint: Some(hint),
                         level: Level::Error,
+                        code: Some(error.code()),
                         location: Some(Location {
                             path: path.clone(),
                             src: src.clone(),
@@ -3158,6 +3360,7 @@ must be able to compile for a module to be valid."
                         text,
                         hint: None,
                         level: Level::Error,
+                        code: Some(error.code()),
                         location: Some(Location {
                             path: path.clone(),
                             src: src.clone(),
@@ -3179,6 +3382,7 @@ must be able to compile for a module to be valid."
                         text,
                         hint: None,
                         level: Level::Error,
+                        code: Some(error.code()),
                         location: Some(Location {
                             path: path.clone(),
                             src: src.clone(),
@@ -3201,6 +3405,7 @@ Rename or remove one of them.",
                         text,
                         hint: None,
                         level: Level::Error,
+                        code: Some(error.code()),
                         location: Some(Location {
                             path: path.clone(),
                             src: src.clone(),
@@ -3230,6 +3435,7 @@ See: https://tour.gleam.run/advanced-features/use/",
                         text,
                         hint: None,
                         level: Level::Error,
+                        code: Some(error.code()),
                         location: Some(Location {
                             label: Label {
                                 text: None,
@@ -3254,6 +3460,7 @@ See: https://tour.gleam.run/advanced-features/use/");
                         text,
                         hint: None,
                         level: Level::Error,
+                        code: Some(error.code()),
                         location: Some(Location {
                             label: Label {
                                 text: Some("Expected no arguments, got 1".into()),
@@ -3302,6 +3509,7 @@ so it cannot take the `use` callback function as a final argument.\n")
                         text: wrap(&text),
                         hint: None,
                         level: Level::Error,
+                        code: Some(error.code()),
                         location: Some(Location {
                             label: Label {
                                 text: Some(label),
@@ -3330,6 +3538,7 @@ See: https://tour.gleam.run/advanced-features/use/",
                         text: wrap(&text),
                         hint: None,
                         level: Level::Error,
+                        code: Some(error.code()),
                         location: Some(Location {
                             label: Label {
                                 text: None,
@@ -3364,6 +3573,7 @@ See: https://tour.gleam.run/advanced-features/use/");
                         text,
                         hint: None,
                         level: Level::Error,
+                        code: Some(error.code()),
                         location: Some(Location {
                             label: Label {
                                 text: None,
@@ -3410,6 +3620,7 @@ Try: _{}", kind_str.to_title_case(), name.to_snake_case()),
                         text,
                         hint: None,
                         level: Level::Error,
+                        code: Some(error.code()),
                         location: Some(Location {
                             label: Label {
                                 text: Some(label),
@@ -3435,6 +3646,7 @@ Try: _{}", kind_str.to_title_case(), name.to_snake_case()),
                                 text,
                                 hint: None,
                                 level: Level::Error,
+                                code: Some(error.code()),
                                 location: Some(Location {
                                     label: Label {
                                         text: None,
@@ -3456,6 +3668,7 @@ Consider removing the deprecation attribute on the variant.");
                                 text,
                                 hint: None,
                                 level: Level::Error,
+                                code: Some(error.code()),
                                 location: Some(Location {
                                     label: Label {
                                         text: None,
@@ -3489,6 +3702,7 @@ Consider removing the deprecation attribute on the variant.");
                     text,
                     hint: None,
                     level: Level::Error,
+                    code: Some(self.code()),
                     location: Some(Location {
                         label: Label {
                             text: Some(label.to_string()),
@@ -3524,6 +3738,7 @@ cycle to continue.",
                     text,
                     hint: None,
                     level: Level::Error,
+                    code: Some(self.code()),
                     location: Some(Location {
                         label: Label {
                             text: Some("Imported here".into()),
@@ -3550,6 +3765,7 @@ cycle to continue.",
                     text,
                     hint: None,
                     level: Level::Error,
+                    code: Some(self.code()),
                     location: None,
                 }]
             }
@@ -3571,6 +3787,7 @@ but it cannot be found."
                     text,
                     hint: None,
                     level: Level::Error,
+                    code: Some(self.code()),
                     location: Some(Location {
                         label: Label {
                             text: did_you_mean(import, modules),
@@ -3603,6 +3820,7 @@ but it cannot be found."
                     hint: None,
                     location: None,
                     level: Level::Error,
+                    code: Some(self.code()),
                 }]
             }
 
@@ -3621,6 +3839,25 @@ but it cannot be found."
                     hint: None,
                     location: None,
                     level: Level::Error,
+                    code: Some(self.code()),
+                }]
+            }
+
+            Error::DeniedWarnings { count } => {
+                let word_warning = match count {
+                    1 => "warning",
+                    _ => "warnings",
+                };
+                let text = "The `[warnings]` table of gleam.toml or the command line
+sets these warnings to `deny`. Fix them and try again."
+                    .into();
+                vec![Diagnostic {
+                    title: format!("{count} denied {word_warning} generated."),
+                    text,
+                    hint: None,
+                    location: None,
+                    level: Level::Error,
+                    code: Some(self.code()),
                 }]
             }
 
@@ -3638,6 +3875,7 @@ Fix the warnings and try again."
                     hint: None,
                     location: None,
                     level: Level::Error,
+                    code: Some(self.code()),
                 }]
             }
 
@@ -3647,6 +3885,7 @@ Fix the warnings and try again."
                     text: format!("{feature} is not supported for JavaScript compilation."),
                     hint: None,
                     level: Level::Error,
+                    code: Some(self.code()),
                     location: Some(Location {
                         label: Label {
                             text: None,
@@ -3676,6 +3915,7 @@ The error from the package manager client was:
                     hint: None,
                     location: None,
                     level: Level::Error,
+                    code: Some(self.code()),
                 }]
             }
 
@@ -3692,6 +3932,7 @@ The error from the HTTP client was:
                     hint: None,
                     location: None,
                     level: Level::Error,
+                    code: Some(self.code()),
                 }]
             }
 
@@ -3708,6 +3949,7 @@ The error from the parser was:
                     hint: None,
                     location: None,
                     level: Level::Error,
+                    code: Some(self.code()),
                 }]
             }
 
@@ -3720,6 +3962,7 @@ The error from the parser was:
                     hint: None,
                     location: None,
                     level: Level::Error,
+                    code: Some(self.code()),
                 }]
             }
 
@@ -3738,6 +3981,7 @@ The error from the version resolver library was:
                     hint: None,
                     location: None,
                     level: Level::Error,
+                    code: Some(self.code()),
                 }]
             }
 
@@ -3747,6 +3991,7 @@ The error from the version resolver library was:
                 hint: None,
                 location: None,
                 level: Level::Error,
+                code: Some(self.code()),
             }],
 
             Error::WrongDependencyProvided {
@@ -3764,6 +4009,7 @@ The error from the version resolver library was:
                     hint: None,
                     location: None,
                     level: Level::Error,
+                    code: Some(self.code()),
                 }]
             }
 
@@ -3782,6 +4028,7 @@ The error from the version resolver library was:
                     hint: None,
                     location: None,
                     level: Level::Error,
+                    code: Some(self.code()),
                 }]
             }
 
@@ -3796,6 +4043,7 @@ dev-dependencies sections of the gleam.toml file."
                     hint: None,
                     location: None,
                     level: Level::Error,
+                    code: Some(self.code()),
                 }]
             }
 
@@ -3827,6 +4075,7 @@ licences = ["Apache-2.0"]"#
                     hint: None,
                     location: None,
                     level: Level::Error,
+                    code: Some(self.code()),
                 }]
             }
 
@@ -3840,6 +4089,7 @@ because dependency `{package}` is not a Hex dependency.",
                 hint: None,
                 location: None,
                 level: Level::Error,
+                code: Some(self.code()),
             }],
 
             Error::UnsupportedBuildTool {
@@ -3861,9 +4111,24 @@ issue in our tracker: https://github.com/gleam-lang/gleam/issues",
                     hint: None,
                     location: None,
                     level: Level::Error,
+                    code: Some(self.code()),
                 }]
             }
 
+            Error::UnsupportedFfiSource { path } => vec![Diagnostic {
+                title: "Unsupported FFI source".into(),
+                text: wrap_format!(
+                    "Bindings cannot be generated from `{path}`. \
+Gleam can generate bindings from TypeScript declaration files, with the \
+`.d.ts`, `.d.mts`, or `.d.cts` extensions, from Erlang source files, with the \
+`.erl` extension, and from compiled Erlang modules, with the `.beam` extension."
+                ),
+                hint: None,
+                location: None,
+                level: Level::Error,
+                code: Some(self.code()),
+            }],
+
             Error::FailedToOpenDocs { path, error } => {
                 let error = format!("\nThe error message from the library was:\n\n    {error}\n");
                 let text = format!(
@@ -3877,6 +4142,7 @@ issue in our tracker: https://github.com/gleam-lang/gleam/issues",
                     text,
                     hint: None,
                     level: Level::Error,
+                    code: Some(self.code()),
                     location: None,
                 }]
             }
@@ -3896,6 +4162,7 @@ satisfying {required_version} but you are using v{gleam_version}.",
                     hint: None,
                     location: None,
                     level: Level::Error,
+                    code: Some(self.code()),
                 }]
             }
 
@@ -3921,6 +4188,7 @@ satisfying {required_version} but you are using v{gleam_version}.",
                     hint,
                     location: None,
                     level: Level::Error,
+                    code: Some(self.code()),
                 }]
             }
 
@@ -3929,6 +4197,7 @@ satisfying {required_version} but you are using v{gleam_version}.",
                 text: "The --javascript-prelude flag must be given when compiling to JavaScript."
                     .into(),
                 level: Level::Error,
+                code: Some(self.code()),
                 location: None,
                 hint: None,
             }],
@@ -3936,6 +4205,7 @@ satisfying {required_version} but you are using v{gleam_version}.",
                 title: "Corrupt manifest.toml".into(),
                 text: "The `manifest.toml` file is corrupt.".into(),
                 level: Level::Error,
+                code: Some(self.code()),
                 location: None,
                 hint: Some("Please run `gleam update` to fix it.".into()),
             }],
@@ -3951,6 +4221,7 @@ to compile and load your module it would overwrite the Erlang one, potentially \
 causing confusing errors and crashes.
 "),
                 level: Level::Error,
+                code: Some(self.code()),
                 location: None,
                 hint: Some("Rename this module and try again.".into()),
             }],
@@ -3961,9 +4232,113 @@ causing confusing errors and crashes.
 This release has been recently published so you can replace it \
 or you can publish it using a different version number"),
                 level: Level::Error,
+                code: Some(self.code()),
                 location: None,
                 hint: Some("Please add the --replace flag if you want to replace the release.".into()),
-            }]
+            }],
+
+            Error::UnknownErrorCode { code } => vec![Diagnostic {
+                title: "Unknown error code".into(),
+                text: wrap_format!("`{code}` is not the code of any error or warning. \
+Codes are shown in the title of errors and warnings, for example `E0216`."),
+                level: Level::Error,
+                code: Some(self.code()),
+                location: None,
+                hint: None,
+            }],
+
+            Error::LicencePolicyViolation { packages } => vec![Diagnostic {
+                title: "Dependency licence not allowed".into(),
+                text: format!(
+"The licence policy in gleam.toml does not allow these dependency packages:
+
+{}
+",
+                    packages
+                        .iter()
+                        .map(|(package, licences)| match licences.as_slice() {
+                            [] => format!("  - {package} (unknown licence)"),
+                            _ => format!("  - {package} ({})", licences.join(", ")),
+                        })
+                        .join("\n")
+                ),
+                level: Level::Error,
+                code: Some(self.code()),
+                location: None,
+                hint: Some("Replace these packages or change the `[licence_policy]` \
+of your project.".into()),
+            }],
+
+            Error::DependencyAuditFailed {
+                packages,
+                threshold,
+            } => vec![Diagnostic {
+                title: "Dependency audit failed".into(),
+                text: format!(
+"These dependency packages have problems of {threshold} severity or higher:
+
+{}
+",
+                    packages
+                        .iter()
+                        .map(|(package, severity)| format!("  - {package} ({severity})"))
+                        .join("\n")
+                ),
+                level: Level::Error,
+                code: Some(self.code()),
+                location: None,
+                hint: Some("Upgrade or replace these packages.".into()),
+            }],
+
+            Error::InvalidAdvisoryDatabase { path, error } => vec![Diagnostic {
+                title: "Invalid advisory database".into(),
+                text: format!(
+"The advisory database at {path} could not be read as OSV JSON:
+
+    {error}
+"
+                ),
+                level: Level::Error,
+                code: Some(self.code()),
+                location: None,
+                hint: None,
+            }],
+
+            Error::WorkspaceRootIsNotAPackage { path } => vec![Diagnostic {
+                title: "Not a package".into(),
+                text: format!(
+                    "This command works on a single package, but {path} is the root
+of a workspace. Run it in the directory of one of its members instead."
+                ),
+                level: Level::Error,
+                code: Some(self.code()),
+                location: None,
+                hint: None,
+            }],
+
+            Error::ConflictingWorkspaceRequirements {
+                package,
+                requirements,
+            } => {
+                let requirements = requirements
+                    .iter()
+                    .map(|(member, requirement)| format!("    - {member}: {requirement}"))
+                    .join("\n");
+                vec![Diagnostic {
+                    title: "Conflicting requirements".into(),
+                    text: format!(
+                        "The members of the workspace share their dependencies, but they
+require the package `{package}` in ways that can't be combined:
+
+{requirements}
+"
+                    ),
+                    level: Level::Error,
+                    code: Some(self.code()),
+                    location: None,
+                    hint: Some("Require the package in the same way in each member.".into()),
+                }]
+            }
         }
     }
 }
@@ -0,0 +1,33 @@
+//! Long-form explanations of the errors and warnings the compiler emits,
+//! looked up with `gleam explain <code>`.
+//!
+//! Each explanation is a markdown file in the `explain` directory named after
+//! its code, which the build script picks up on its own. Its first `gleam`
+//! code block is an example of code causing the problem; any other `gleam`
+//! code blocks show how it can be fixed. Examples that need other modules to
+//! be compiled can include them as code blocks starting with a
+//! `// In <module>.gleam` comment.
+//!
+
+#[cfg(test)]
+mod tests;
+
+// The table of the codes and their explanation, generated by the build
+// script from the markdown files in the `explain` directory.
+include!(concat!(env!("OUT_DIR"), "/explanations.rs"));
+
+/// The explanation of the error or warning with the given code, in markdown.
+/// Codes are case insensitive.
+///
+pub fn explanation(code: &str) -> Option<&'static str> {
+    EXPLANATIONS
+        .iter()
+        .find(|(known, _)| known.eq_ignore_ascii_case(code.trim()))
+        .map(|(_, explanation)| *explanation)
+}
+
+/// All the codes that have an explanation.
+///
+pub fn codes() -> impl Iterator<Item = &'static str> {
+    EXPLANATIONS.iter().map(|(code, _)| *code)
+}
@@ -0,0 +1,302 @@
+use std::rc::Rc;
+
+use camino::Utf8PathBuf;
+use itertools::Itertools;
+use vec1::Vec1;
+
+use crate::{
+    analyse::TargetSupport,
+    build::Target,
+    error::Error,
+    type_::tests::compile_module_with_opts,
+    warning::{VectorWarningEmitterIO, Warning, WarningEmitter},
+};
+
+use super::{codes, explanation};
+
+macro_rules! assert_explanation {
+    ($code:expr $(,)?) => {
+        let output = $crate::explain::tests::explain($code);
+        insta::assert_snapshot!(insta::internals::AutoName, output, $code);
+    };
+}
+
+/// The codes whose examples can't be checked by compiling a single module
+/// for Erlang, along with the build tool errors and warnings.
+///
+/// - E0108, E0123, E0141, E0239 and W0224 are never emitted by the compiler.
+/// - E0201, E0237 and E0238 depend on where the module is in the project.
+/// - W0217 needs the standard library.
+/// - W0218 needs the package's dependencies.
+/// - W0229 needs the package's `gleam` version range.
+/// - W0230 needs the JavaScript target.
+///
+const UNCHECKED: &[&str] = &[
+    "E0108", "E0123", "E0141", "E0201", "E0237", "E0238", "E0239", "W0217", "W0218", "W0224",
+    "W0229", "W0230",
+];
+
+struct Examples<'a> {
+    dependencies: Vec<(&'a str, &'a str)>,
+    problem: &'a str,
+    fixes: Vec<&'a str>,
+}
+
+fn examples(explanation: &str) -> Examples<'_> {
+    let mut blocks = vec![];
+    let mut rest = explanation;
+    while let Some((_, after)) = rest.split_once("```gleam\n") {
+        let (block, after) = after.split_once("```").expect("unclosed code block");
+        blocks.push(block);
+        rest = after;
+    }
+
+    let (dependencies, mut examples): (Vec<_>, Vec<_>) = blocks
+        .into_iter()
+        .partition(|block| block.starts_with("// In "));
+    let dependencies = dependencies
+        .into_iter()
+        .map(|block| {
+            let (first_line, src) = block.split_once('\n').unwrap_or((block, ""));
+            let name = first_line
+                .trim_start_matches("// In ")
+                .trim_end_matches(".gleam");
+            (name, src)
+        })
+        .collect();
+    assert!(!examples.is_empty(), "no example");
+    let problem = examples.remove(0);
+    Examples {
+        dependencies,
+        problem,
+        fixes: examples,
+    }
+}
+
+enum Compiled {
+    Parse(crate::parse::error::ParseError),
+    Type(Vec<crate::type_::Error>, crate::type_::printer::Names),
+    Ok,
+}
+
+fn compile(src: &str, dependencies: &[(&str, &str)]) -> (Compiled, Vec<Warning>) {
+    if let Err(error) =
+        crate::parse::parse_module(Utf8PathBuf::from("test/path"), src, &WarningEmitter::null())
+    {
+        return (Compiled::Parse(error), vec![]);
+    }
+
+    let warnings = VectorWarningEmitterIO::default();
+    let dependencies = dependencies
+        .iter()
+        .map(|(name, src)| ("wibble", *name, *src))
+        .collect_vec();
+    let compiled = match compile_module_with_opts(
+        "themodule",
+        src,
+        Some(Rc::new(warnings.clone())),
+        dependencies,
+        Target::Erlang,
+        TargetSupport::NotEnforced,
+        None,
+    ) {
+        Ok(_) => Compiled::Ok,
+        Err((errors, names)) => Compiled::Type(errors, names),
+    };
+    (compiled, warnings.take())
+}
+
+fn problem_codes(src: &str, dependencies: &[(&str, &str)]) -> Vec<&'static str> {
+    let (compiled, warnings) = compile(src, dependencies);
+    let errors = match compiled {
+        Compiled::Parse(error) => vec![error.error.code()],
+        Compiled::Type(errors, _) => errors.iter().map(|error| error.code()).collect(),
+        Compiled::Ok => vec![],
+    };
+    errors
+        .into_iter()
+        .chain(warnings.iter().map(|warning| warning.code()))
+        .collect()
+}
+
+fn explain(code: &str) -> String {
+    let explanation = explanation(code).expect("no explanation");
+    let examples = examples(explanation);
+    let (compiled, warnings) = compile(examples.problem, &examples.dependencies);
+    let path = Utf8PathBuf::from("/src/one/two.gleam");
+    let src = examples.problem.into();
+    let error = match compiled {
+        Compiled::Parse(error) => Some(Error::Parse { path, src, error }),
+        Compiled::Type(errors, names) => Some(Error::Type {
+            path,
+            src,
+            errors: Vec1::try_from_vec(errors).expect("no errors"),
+            names,
+        }),
+        Compiled::Ok => None,
+    };
+
+    let mut output = format!("{explanation}\n----- EXAMPLE\n");
+    if let Some(error) = error {
+        output.push_str(&error.pretty_string());
+    }
+    let mut nocolor = termcolor::Buffer::no_color();
+    for warning in warnings {
+        warning.pretty(&mut nocolor);
+    }
+    output.push_str(&String::from_utf8(nocolor.into_inner()).expect("invalid utf8"));
+    output
+}
+
+fn is_checked(code: &str) -> bool {
+    !code.starts_with("E00") && !code.starts_with("W00") && !UNCHECKED.contains(&code)
+}
+
+#[test]
+fn explanations_start_with_their_code() {
+    for code in codes() {
+        let explanation = explanation(code).expect("no explanation");
+        assert!(
+            explanation.starts_with(&format!("# {code}: ")),
+            "{code} doesn't start with its code"
+        );
+    }
+}
+
+#[test]
+fn codes_are_sorted_and_unique() {
+    let all = codes().collect_vec();
+    let sorted = all.iter().copied().sorted().dedup().collect_vec();
+    assert_eq!(all, sorted);
+}
+
+/// The codes returned by the `code` methods of the errors and warnings, taken
+/// from the source of the modules defining them.
+///
+const CODE_SOURCES: &[&str] = &[
+    include_str!("../error.rs"),
+    include_str!("../parse/error.rs"),
+    include_str!("../type_/error.rs"),
+    include_str!("../warning.rs"),
+];
+
+#[test]
+fn emitted_codes_all_have_an_explanation() {
+    let code = regex::Regex::new(r#""([EW]\d{4})""#).expect("code regex");
+    let unexplained = CODE_SOURCES
+        .iter()
+        .flat_map(|src| code.captures_iter(src))
+        .map(|captures| captures[1].to_string())
+        .filter(|code| explanation(code).is_none())
+        .sorted()
+        .dedup()
+        .collect_vec();
+    assert!(unexplained.is_empty(), "{unexplained:#?}");
+}
+
+#[test]
+fn codes_are_case_insensitive() {
+    assert_eq!(explanation("e0216"), explanation("E0216"));
+    assert!(explanation("E0216").is_some());
+}
+
+#[test]
+fn unknown_code() {
+    assert_eq!(explanation("E9999"), None);
+    assert_eq!(explanation("wibble"), None);
+}
+
+#[test]
+fn examples_cause_their_problem() {
+    let failures = codes()
+        .filter(|code| is_checked(code))
+        .filter_map(|code| {
+            let examples = examples(explanation(code).expect("no explanation"));
+            let codes = problem_codes(examples.problem, &examples.dependencies);
+            (!codes.contains(&code)).then(|| format!("{code} causes {codes:?}"))
+        })
+        .collect_vec();
+    assert!(failures.is_empty(), "{failures:#?}");
+}
+
+#[test]
+fn fixed_examples_dont_cause_their_problem() {
+    let failures = codes()
+        .filter(|code| is_checked(code))
+        .flat_map(|code| {
+            let examples = examples(explanation(code).expect("no explanation"));
+            examples
+                .fixes
+                .into_iter()
+                .filter(|fix| problem_codes(fix, &examples.dependencies).contains(&code))
+                .map(|fix| format!("{code}: {fix}"))
+                .collect_vec()
+        })
+        .collect_vec();
+    assert!(failures.is_empty(), "{failures:#?}");
+}
+
+#[test]
+fn explain_unexpected_token() {
+    assert_explanation!("E0140");
+}
+
+#[test]
+fn explain_reserved_word() {
+    assert_explanation!("E0139");
+}
+
+#[test]
+fn explain_invalid_tuple_access() {
+    assert_explanation!("E0121");
+}
+
+#[test]
+fn explain_unknown_variable() {
+    assert_explanation!("E0204");
+}
+
+#[test]
+fn explain_unknown_module() {
+    assert_explanation!("E0206");
+}
+
+#[test]
+fn explain_type_mismatch() {
+    assert_explanation!("E0216");
+}
+
+#[test]
+fn explain_invalid_guard_variable() {
+    assert_explanation!("E0227");
+}
+
+#[test]
+fn explain_inexhaustive_case_expression() {
+    assert_explanation!("E0248");
+}
+
+#[test]
+fn explain_deprecated_syntax() {
+    assert_explanation!("W0101");
+}
+
+#[test]
+fn explain_unused_type() {
+    assert_explanation!("W0207");
+}
+
+#[test]
+fn explain_unused_variable() {
+    assert_explanation!("W0214");
+}
+
+#[test]
+fn explain_unnecessary_double_int_negation() {
+    assert_explanation!("W0215");
+}
+
+#[test]
+fn explain_build_tool_error() {
+    assert_explanation!("E0024");
+}
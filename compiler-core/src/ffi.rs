@@ -0,0 +1,366 @@
+//! Generation of Gleam modules binding to code written in other languages, as
+//! done by `gleam ffi generate`.
+//!
+//! Each language has its own module reading the interface of some foreign code
+//! into [`Bindings`], which are then printed as a Gleam module of external
+//! types and `@external` functions.
+//!
+//! The translation is best effort: foreign types with no Gleam equivalent are
+//! bound as `Dynamic`, and declarations that can't be bound at all are listed
+//! at the top of the generated module so that they can be written by hand.
+
+pub mod erlang;
+pub mod typescript;
+
+use std::collections::HashSet;
+
+use camino::Utf8Path;
+use ecow::{eco_format, EcoString};
+use heck::{ToSnakeCase, ToUpperCamelCase};
+use itertools::Itertools;
+
+use crate::{build::Target, parse::lexer::str_to_keyword, Result};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Bindings {
+    target: Target,
+    /// The foreign module the externals refer to.
+    module: EcoString,
+    /// A description of where the bindings have been generated from.
+    source: EcoString,
+    types: Vec<ExternalType>,
+    functions: Vec<ExternalFunction>,
+    /// The foreign declarations that could not be bound.
+    skipped: Vec<EcoString>,
+    /// The Gleam names already in use, values and types.
+    used_names: HashSet<EcoString>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ExternalType {
+    pub name: EcoString,
+    pub parameters: Vec<EcoString>,
+    pub documentation: Option<EcoString>,
+    /// If the foreign type is an alias to a type that can be expressed in
+    /// Gleam a type alias is generated, otherwise an external type.
+    pub alias: Option<GleamType>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ExternalFunction {
+    pub name: EcoString,
+    pub external_name: EcoString,
+    pub parameters: Vec<(EcoString, GleamType)>,
+    pub return_type: GleamType,
+    pub documentation: Option<EcoString>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum GleamType {
+    Int,
+    Float,
+    String,
+    Bool,
+    Nil,
+    BitArray,
+    List(Box<GleamType>),
+    Result {
+        ok: Box<GleamType>,
+        error: Box<GleamType>,
+    },
+    Tuple(Vec<GleamType>),
+    Function {
+        arguments: Vec<GleamType>,
+        return_: Box<GleamType>,
+    },
+    Variable(EcoString),
+    /// A type defined in the generated module.
+    Named {
+        name: EcoString,
+        arguments: Vec<GleamType>,
+    },
+    /// A foreign type with no Gleam equivalent.
+    Dynamic,
+}
+
+impl GleamType {
+    fn is_dynamic(&self) -> bool {
+        match self {
+            GleamType::Dynamic => true,
+            GleamType::List(element) => element.is_dynamic(),
+            GleamType::Result { ok, error } => ok.is_dynamic() || error.is_dynamic(),
+            GleamType::Tuple(elements) => elements.iter().any(GleamType::is_dynamic),
+            GleamType::Function { arguments, return_ } => {
+                return_.is_dynamic() || arguments.iter().any(GleamType::is_dynamic)
+            }
+            GleamType::Named { arguments, .. } => arguments.iter().any(GleamType::is_dynamic),
+            GleamType::Int
+            | GleamType::Float
+            | GleamType::String
+            | GleamType::Bool
+            | GleamType::Nil
+            | GleamType::BitArray
+            | GleamType::Variable(_) => false,
+        }
+    }
+
+    /// Returns true if any of the types this type is made of satisfies the
+    /// given predicate, including the type itself.
+    ///
+    fn any(&self, predicate: &impl Fn(&GleamType) -> bool) -> bool {
+        if predicate(self) {
+            return true;
+        }
+        match self {
+            GleamType::List(element) => element.any(predicate),
+            GleamType::Result { ok, error } => ok.any(predicate) || error.any(predicate),
+            GleamType::Tuple(elements)
+            | GleamType::Named {
+                arguments: elements,
+                ..
+            } => elements.iter().any(|type_| type_.any(predicate)),
+            GleamType::Function { arguments, return_ } => {
+                return_.any(predicate) || arguments.iter().any(|type_| type_.any(predicate))
+            }
+            GleamType::Int
+            | GleamType::Float
+            | GleamType::String
+            | GleamType::Bool
+            | GleamType::Nil
+            | GleamType::BitArray
+            | GleamType::Variable(_)
+            | GleamType::Dynamic => false,
+        }
+    }
+
+    fn print(&self) -> EcoString {
+        match self {
+            GleamType::Int => "Int".into(),
+            GleamType::Float => "Float".into(),
+            GleamType::String => "String".into(),
+            GleamType::Bool => "Bool".into(),
+            GleamType::Nil => "Nil".into(),
+            GleamType::BitArray => "BitArray".into(),
+            GleamType::Dynamic => "dynamic.Dynamic".into(),
+            GleamType::Variable(name) => name.clone(),
+            GleamType::List(element) => eco_format!("List({})", element.print()),
+            GleamType::Result { ok, error } => {
+                eco_format!("Result({}, {})", ok.print(), error.print())
+            }
+            GleamType::Tuple(elements) => {
+                eco_format!("#({})", elements.iter().map(GleamType::print).join(", "))
+            }
+            GleamType::Function { arguments, return_ } => eco_format!(
+                "fn({}) -> {}",
+                arguments.iter().map(GleamType::print).join(", "),
+                return_.print()
+            ),
+            GleamType::Named { name, arguments } if arguments.is_empty() => name.clone(),
+            GleamType::Named { name, arguments } => eco_format!(
+                "{name}({})",
+                arguments.iter().map(GleamType::print).join(", ")
+            ),
+        }
+    }
+}
+
+impl Bindings {
+    pub fn new(target: Target, module: EcoString, source: EcoString) -> Self {
+        Self {
+            target,
+            module,
+            source,
+            types: vec![],
+            functions: vec![],
+            skipped: vec![],
+            used_names: HashSet::new(),
+        }
+    }
+
+    /// Adds a type to the bindings. If its alias can't be used in Gleam an
+    /// external type is generated instead.
+    ///
+    pub fn add_type(&mut self, mut type_: ExternalType) {
+        let unusable_alias = type_.alias.as_ref().is_some_and(|alias| {
+            *alias == GleamType::Dynamic
+                // Gleam doesn't allow unused type alias parameters
+                || type_.parameters.iter().any(|parameter| {
+                    !alias.any(&|type_| matches!(type_, GleamType::Variable(name) if name == parameter))
+                })
+                // nor recursive type aliases
+                || alias.any(&|alias| matches!(alias, GleamType::Named { name, .. } if *name == type_.name))
+        });
+        if unusable_alias {
+            type_.alias = None;
+        }
+
+        if self.used_names.insert(type_.name.clone()) {
+            self.types.push(type_);
+        } else {
+            self.skip(eco_format!("type {}", type_.name));
+        }
+    }
+
+    pub fn add_function(&mut self, function: ExternalFunction) {
+        if self.used_names.insert(function.name.clone()) {
+            self.functions.push(function);
+        } else {
+            self.skip(function.external_name);
+        }
+    }
+
+    pub fn skip(&mut self, declaration: EcoString) {
+        self.skipped.push(declaration);
+    }
+
+    /// Prints the bindings as a formatted Gleam module.
+    ///
+    pub fn to_gleam(&self) -> Result<String> {
+        let mut src = String::new();
+
+        let language = match self.target {
+            Target::Erlang => "Erlang",
+            Target::JavaScript => "JavaScript",
+        };
+        src.push_str(&format!(
+            "//// Bindings to the `{}` {language} module, generated from `{}`.\n",
+            self.module, self.source
+        ));
+        if !self.skipped.is_empty() {
+            src.push_str(
+                "////\n//// These declarations could not be bound and have been skipped:\n",
+            );
+            for skipped in &self.skipped {
+                src.push_str(&format!("//// - `{skipped}`\n"));
+            }
+        }
+
+        let uses_dynamic = self
+            .functions
+            .iter()
+            .flat_map(|function| {
+                std::iter::once(&function.return_type)
+                    .chain(function.parameters.iter().map(|(_, type_)| type_))
+            })
+            .chain(self.types.iter().filter_map(|type_| type_.alias.as_ref()))
+            .any(GleamType::is_dynamic);
+        if uses_dynamic {
+            src.push_str("\nimport gleam/dynamic\n");
+        }
+
+        for type_ in &self.types {
+            src.push('\n');
+            print_documentation(&mut src, &type_.documentation);
+            let parameters = if type_.parameters.is_empty() {
+                EcoString::new()
+            } else {
+                eco_format!("({})", type_.parameters.join(", "))
+            };
+            match &type_.alias {
+                Some(alias) => src.push_str(&format!(
+                    "pub type {}{parameters} =\n  {}\n",
+                    type_.name,
+                    alias.print()
+                )),
+                None => src.push_str(&format!("pub type {}{parameters}\n", type_.name)),
+            }
+        }
+
+        for function in &self.functions {
+            src.push('\n');
+            print_documentation(&mut src, &function.documentation);
+            let parameters = function
+                .parameters
+                .iter()
+                .map(|(name, type_)| format!("{name}: {}", type_.print()))
+                .join(", ");
+            src.push_str(&format!(
+                "@external({}, \"{}\", \"{}\")\npub fn {}({parameters}) -> {}\n",
+                self.target,
+                self.module,
+                function.external_name,
+                function.name,
+                function.return_type.print()
+            ));
+        }
+
+        let mut formatted = String::new();
+        crate::format::pretty(&mut formatted, &src.into(), Utf8Path::new("bindings.gleam"))?;
+        Ok(formatted)
+    }
+}
+
+fn print_documentation(src: &mut String, documentation: &Option<EcoString>) {
+    let Some(documentation) = documentation else {
+        return;
+    };
+    for line in documentation.lines() {
+        src.push_str("///");
+        if !line.is_empty() {
+            src.push(' ');
+            src.push_str(line);
+        }
+        src.push('\n');
+    }
+}
+
+/// Turns a foreign name into a valid Gleam name for values and type
+/// variables, returning `None` if it's not possible.
+///
+pub(crate) fn value_name(name: &str) -> Option<EcoString> {
+    let name: String = name
+        .to_snake_case()
+        .chars()
+        .filter(|char| char.is_ascii_alphanumeric() || *char == '_')
+        .collect();
+    let name = name.trim_start_matches('_');
+    let first = name.chars().next()?;
+    let name = if first.is_ascii_digit() {
+        eco_format!("n{name}")
+    } else {
+        name.into()
+    };
+    if str_to_keyword(&name).is_some() {
+        Some(eco_format!("{name}_"))
+    } else {
+        Some(name)
+    }
+}
+
+/// Turns a foreign name into a valid Gleam name for types, returning `None`
+/// if it's not possible.
+///
+pub(crate) fn type_name(name: &str) -> Option<EcoString> {
+    let name: String = name
+        .to_upper_camel_case()
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .collect();
+    if name.starts_with(|char: char| char.is_ascii_uppercase()) {
+        Some(name.into())
+    } else {
+        None
+    }
+}
+
+/// Gives unique names to the parameters of a function.
+///
+pub(crate) fn parameter_names(
+    names: impl IntoIterator<Item = Option<EcoString>>,
+) -> Vec<EcoString> {
+    let mut used = HashSet::new();
+    names
+        .into_iter()
+        .enumerate()
+        .map(|(index, name)| {
+            let name = name.unwrap_or_else(|| "arg".into());
+            let name = if used.contains(&name) {
+                eco_format!("{name}_{}", index + 1)
+            } else {
+                name
+            };
+            let _ = used.insert(name.clone());
+            name
+        })
+        .collect()
+}
@@ -0,0 +1,954 @@
+//! Reading the type specifications of Erlang modules into Gleam bindings.
+//!
+//! The `-spec`, `-type`, `-opaque` and `-export` attributes of an Erlang
+//! source file are read, anything else is skipped over. Compiled modules are
+//! supported by printing the attributes kept in their debug info as Erlang
+//! source first.
+//!
+//! Erlang types are translated as follows:
+//!
+//! | Erlang                                         | Gleam             |
+//! | ---------------------------------------------- | ----------------- |
+//! | `integer()`, `non_neg_integer()`, ranges, ...  | `Int`             |
+//! | `float()`                                      | `Float`           |
+//! | `unicode:unicode_binary()`                     | `String`          |
+//! | `binary()`, `bitstring()`                      | `BitArray`        |
+//! | `boolean()`, `true | false`                    | `Bool`            |
+//! | `nil`                                          | `Nil`             |
+//! | `list(A)`, `[A]`                               | `List(a)`         |
+//! | `{ok, A} | {error, B}`                         | `Result(a, b)`    |
+//! | `{A, B}`                                       | `#(a, b)`         |
+//! | `fun((A) -> B)`                                | `fn(a) -> b`      |
+//! | Types declared in the module                   | External types    |
+//! | Anything else                                  | `dynamic.Dynamic` |
+//!
+//! Only the exported functions are bound, using the first clause of their
+//! spec.
+
+#[cfg(test)]
+mod tests;
+
+use std::collections::{HashMap, HashSet};
+
+use ecow::{eco_format, EcoString};
+
+use crate::{build::Target, Result};
+
+use super::{
+    parameter_names, type_name, value_name, Bindings, ExternalFunction, ExternalType, GleamType,
+};
+
+/// Generates a Gleam module binding to the Erlang module `module`, whose
+/// interface is described by the given Erlang source.
+///
+/// `source` is a description of where the Erlang source comes from, usually
+/// the path of the file.
+///
+pub fn generate(src: &str, module: &str, source: &str) -> Result<String> {
+    let mut bindings = Bindings::new(Target::Erlang, module.into(), source.into());
+
+    let mut exports = vec![];
+    let mut export_all = false;
+    let mut types = vec![];
+    let mut specs = vec![];
+    let mut documentation = None;
+    for form in forms(tokenise(src)) {
+        let form_documentation = documentation.take().or(form.documentation);
+        match Parser::new(form.tokens).attribute() {
+            Some(Attribute::Export(functions)) => exports.extend(functions),
+            Some(Attribute::ExportAll) => export_all = true,
+            Some(Attribute::Documentation(text)) => documentation = Some(text),
+            Some(Attribute::Type(mut definition)) => {
+                definition.documentation = form_documentation;
+                types.push(definition);
+            }
+            Some(Attribute::Spec(mut spec)) => {
+                spec.documentation = form_documentation;
+                specs.push(spec);
+            }
+            None => {}
+        }
+    }
+
+    let declared_types: HashMap<(EcoString, usize), EcoString> = types
+        .iter()
+        .filter_map(|definition| {
+            let key = (definition.name.clone(), definition.parameters.len());
+            Some((key, type_name(&definition.name)?))
+        })
+        .collect();
+
+    for definition in types {
+        let Some(gleam_name) = declared_types.get(&definition.key()) else {
+            bindings.skip(eco_format!("type {}", definition.name));
+            continue;
+        };
+        let constraints = HashMap::new();
+        let converter = Converter::new(&declared_types, &constraints);
+        let alias = if definition.opaque {
+            None
+        } else {
+            Some(converter.convert(&definition.definition))
+        };
+        bindings.add_type(ExternalType {
+            name: gleam_name.clone(),
+            parameters: converter.variables(&definition.parameters),
+            documentation: definition.documentation,
+            alias,
+        });
+    }
+
+    let exported: HashSet<_> = exports.iter().cloned().collect();
+    let mut bound = HashSet::new();
+    let mut gleam_names = HashSet::new();
+    for spec in specs {
+        let key = spec.key();
+        if !(export_all || exported.contains(&key)) || !bound.insert(key) {
+            continue;
+        }
+        let description = eco_format!("{}/{}", spec.name, spec.parameters.len());
+        let Some(gleam_name) =
+            value_name(&spec.name).filter(|name| gleam_names.insert(name.clone()))
+        else {
+            // Functions with the same name and different arities can't all
+            // be bound with the same Gleam name.
+            bindings.skip(description);
+            continue;
+        };
+
+        let converter = Converter::new(&declared_types, &spec.constraints);
+        let names = parameter_names(
+            spec.parameters
+                .iter()
+                .map(|parameter| parameter.name.as_deref().and_then(value_name)),
+        );
+        let parameters = names
+            .into_iter()
+            .zip(spec.parameters.iter())
+            .map(|(name, parameter)| (name, converter.convert(&parameter.type_)))
+            .collect();
+        bindings.add_function(ExternalFunction {
+            name: gleam_name,
+            external_name: spec.name,
+            parameters,
+            return_type: converter.convert(&spec.return_type),
+            documentation: spec.documentation,
+        });
+    }
+
+    // Exported functions without a spec can't be bound as their types are
+    // not known.
+    for (name, arity) in exports {
+        if bound.insert((name.clone(), arity)) {
+            bindings.skip(eco_format!("{name}/{arity}"));
+        }
+    }
+
+    bindings.to_gleam()
+}
+
+/// Translates Erlang types into Gleam ones.
+///
+struct Converter<'a> {
+    declared_types: &'a HashMap<(EcoString, usize), EcoString>,
+    /// The types of the variables constrained in the `when` clause of a spec.
+    constraints: &'a HashMap<EcoString, ErlType>,
+}
+
+impl<'a> Converter<'a> {
+    fn new(
+        declared_types: &'a HashMap<(EcoString, usize), EcoString>,
+        constraints: &'a HashMap<EcoString, ErlType>,
+    ) -> Self {
+        Self {
+            declared_types,
+            constraints,
+        }
+    }
+
+    fn variables(&self, variables: &[EcoString]) -> Vec<EcoString> {
+        variables.iter().map(|name| self.variable(name)).collect()
+    }
+
+    fn variable(&self, name: &str) -> EcoString {
+        value_name(name).unwrap_or_else(|| "a".into())
+    }
+
+    fn convert(&self, type_: &ErlType) -> GleamType {
+        self.convert_type(type_, true)
+    }
+
+    /// Converts a type, constrained variables are replaced with their type
+    /// unless `substitute` is false. Substitution is only done once so that
+    /// recursive constraints can't loop forever.
+    ///
+    fn convert_type(&self, type_: &ErlType, substitute: bool) -> GleamType {
+        let convert = |type_| self.convert_type(type_, substitute);
+        match type_ {
+            ErlType::Variable(name) if name == "_" => GleamType::Dynamic,
+            ErlType::Variable(name) => match self.constraints.get(name) {
+                Some(constraint) if substitute => self.convert_type(constraint, false),
+                Some(_) => GleamType::Dynamic,
+                None => GleamType::Variable(self.variable(name)),
+            },
+
+            ErlType::Integer => GleamType::Int,
+            ErlType::Binary => GleamType::BitArray,
+            ErlType::Atom(atom) => match atom.as_str() {
+                "nil" => GleamType::Nil,
+                "true" | "false" => GleamType::Bool,
+                _ => GleamType::Dynamic,
+            },
+
+            ErlType::List(element) => GleamType::List(Box::new(convert(element))),
+            ErlType::Tuple(elements) => GleamType::Tuple(elements.iter().map(convert).collect()),
+            ErlType::Function {
+                parameters,
+                return_,
+            } => GleamType::Function {
+                arguments: parameters.iter().map(convert).collect(),
+                return_: Box::new(convert(return_)),
+            },
+
+            ErlType::Local { name, arguments } => {
+                if let Some(gleam_name) = self.declared_types.get(&(name.clone(), arguments.len()))
+                {
+                    return GleamType::Named {
+                        name: gleam_name.clone(),
+                        arguments: arguments.iter().map(convert).collect(),
+                    };
+                }
+                match (name.as_str(), arguments.as_slice()) {
+                    (
+                        "integer" | "non_neg_integer" | "pos_integer" | "neg_integer" | "char"
+                        | "byte" | "arity",
+                        [],
+                    ) => GleamType::Int,
+                    ("float", []) => GleamType::Float,
+                    ("binary" | "bitstring" | "nonempty_binary" | "nonempty_bitstring", []) => {
+                        GleamType::BitArray
+                    }
+                    ("boolean", []) => GleamType::Bool,
+                    ("list" | "nonempty_list" | "nil", []) => {
+                        GleamType::List(Box::new(GleamType::Dynamic))
+                    }
+                    ("list" | "nonempty_list", [element]) => {
+                        GleamType::List(Box::new(convert(element)))
+                    }
+                    _ => GleamType::Dynamic,
+                }
+            }
+
+            ErlType::Remote {
+                module,
+                name,
+                arguments,
+            } => match (module.as_str(), name.as_str(), arguments.as_slice()) {
+                ("unicode", "unicode_binary", []) => GleamType::String,
+                _ => GleamType::Dynamic,
+            },
+
+            ErlType::Union(variants) => self.convert_union(variants, substitute),
+
+            ErlType::Unknown => GleamType::Dynamic,
+        }
+    }
+
+    fn convert_union(&self, variants: &[ErlType], substitute: bool) -> GleamType {
+        let is_boolean = |variant: &ErlType| matches!(variant, ErlType::Atom(atom) if atom == "true" || atom == "false");
+        if variants.iter().all(is_boolean) {
+            return GleamType::Bool;
+        }
+        if variants.iter().all(|variant| *variant == ErlType::Integer) {
+            return GleamType::Int;
+        }
+
+        // Gleam results are represented as `{ok, Value}` and `{error, Error}`
+        // tuples.
+        let tagged = |variant: &ErlType, tag: &str| match variant {
+            ErlType::Tuple(elements) => match elements.as_slice() {
+                [ErlType::Atom(atom), value] if atom == tag => Some(value.clone()),
+                _ => None,
+            },
+            _ => None,
+        };
+        if let [first, second] = variants {
+            let result = tagged(first, "ok")
+                .zip(tagged(second, "error"))
+                .or_else(|| tagged(second, "ok").zip(tagged(first, "error")));
+            if let Some((ok, error)) = result {
+                return GleamType::Result {
+                    ok: Box::new(self.convert_type(&ok, substitute)),
+                    error: Box::new(self.convert_type(&error, substitute)),
+                };
+            }
+        }
+
+        GleamType::Dynamic
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Attribute {
+    Export(Vec<(EcoString, usize)>),
+    /// `-compile(export_all).`
+    ExportAll,
+    /// A `-doc` attribute, documenting the following spec.
+    Documentation(EcoString),
+    Type(TypeDefinition),
+    Spec(Spec),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TypeDefinition {
+    name: EcoString,
+    parameters: Vec<EcoString>,
+    definition: ErlType,
+    opaque: bool,
+    documentation: Option<EcoString>,
+}
+
+impl TypeDefinition {
+    fn key(&self) -> (EcoString, usize) {
+        (self.name.clone(), self.parameters.len())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Spec {
+    name: EcoString,
+    parameters: Vec<Parameter>,
+    return_type: ErlType,
+    constraints: HashMap<EcoString, ErlType>,
+    documentation: Option<EcoString>,
+}
+
+impl Spec {
+    fn key(&self) -> (EcoString, usize) {
+        (self.name.clone(), self.parameters.len())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Parameter {
+    name: Option<EcoString>,
+    type_: ErlType,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ErlType {
+    Variable(EcoString),
+    Atom(EcoString),
+    /// An integer literal or range.
+    Integer,
+    Binary,
+    List(Box<ErlType>),
+    Tuple(Vec<ErlType>),
+    Function {
+        parameters: Vec<ErlType>,
+        return_: Box<ErlType>,
+    },
+    /// A built in type or a type defined in the module.
+    Local {
+        name: EcoString,
+        arguments: Vec<ErlType>,
+    },
+    Remote {
+        module: EcoString,
+        name: EcoString,
+        arguments: Vec<ErlType>,
+    },
+    Union(Vec<ErlType>),
+    /// A type that has no Gleam equivalent.
+    Unknown,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Atom(EcoString),
+    Variable(EcoString),
+    Number(EcoString),
+    String(EcoString),
+    Char,
+    Punctuation(char),
+    /// `->`
+    Arrow,
+    /// `::`
+    DoubleColon,
+    /// `..`
+    DoubleDot,
+    /// `...`
+    Ellipsis,
+    /// The `.` ending a form.
+    Dot,
+    Comment(EcoString),
+}
+
+fn tokenise(src: &str) -> Vec<Token> {
+    let chars: Vec<char> = src.chars().collect();
+    let mut tokens = vec![];
+    let mut position = 0;
+    let at = |position: usize| chars.get(position).copied();
+    let slice = |start: usize, end: usize| -> String {
+        chars.get(start..end).unwrap_or_default().iter().collect()
+    };
+
+    while let Some(char) = at(position) {
+        position += 1;
+        match char {
+            _ if char.is_whitespace() => {}
+
+            '%' => {
+                let start = position;
+                while at(position).is_some_and(|char| char != '\n') {
+                    position += 1;
+                }
+                let line = slice(start, position);
+                let line = line.trim_start_matches('%');
+                let line = line.strip_prefix(' ').unwrap_or(line);
+                // Consecutive comment lines make up a single comment
+                if let Some(Token::Comment(comment)) = tokens.last_mut() {
+                    comment.push('\n');
+                    comment.push_str(line);
+                } else {
+                    tokens.push(Token::Comment(line.into()));
+                }
+            }
+
+            '"' if at(position) == Some('"') && at(position + 1) == Some('"') => {
+                // A triple quoted string, its lines are indented as much as
+                // the closing quotes.
+                position += 2;
+                let start = position;
+                while position < chars.len() && slice(position, position + 3) != "\"\"\"" {
+                    position += 1;
+                }
+                let string = slice(start, position);
+                position += 3;
+                let text = string
+                    .lines()
+                    .skip(1)
+                    .map(str::trim)
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                tokens.push(Token::String(text.trim().into()));
+            }
+
+            '"' | '\'' => {
+                let mut text = String::new();
+                while let Some(next) = at(position) {
+                    position += 1;
+                    match next {
+                        '\\' => {
+                            match at(position) {
+                                Some('n') => text.push('\n'),
+                                Some(escaped) => text.push(escaped),
+                                None => {}
+                            }
+                            position += 1;
+                        }
+                        _ if next == char => break,
+                        _ => text.push(next),
+                    }
+                }
+                if char == '"' {
+                    tokens.push(Token::String(text.into()));
+                } else {
+                    tokens.push(Token::Atom(text.into()));
+                }
+            }
+
+            '$' => {
+                if at(position) == Some('\\') {
+                    position += 1;
+                }
+                position += 1;
+                tokens.push(Token::Char);
+            }
+
+            '-' if at(position) == Some('>') => {
+                position += 1;
+                tokens.push(Token::Arrow);
+            }
+
+            ':' if at(position) == Some(':') => {
+                position += 1;
+                tokens.push(Token::DoubleColon);
+            }
+
+            '.' if at(position) == Some('.') && at(position + 1) == Some('.') => {
+                position += 2;
+                tokens.push(Token::Ellipsis);
+            }
+
+            '.' if at(position) == Some('.') => {
+                position += 1;
+                tokens.push(Token::DoubleDot);
+            }
+
+            '.' if at(position).is_none_or(|char| char.is_whitespace() || char == '%') => {
+                tokens.push(Token::Dot);
+            }
+
+            _ if char.is_ascii_digit() => {
+                let start = position - 1;
+                while let Some(char) = at(position) {
+                    let fraction =
+                        char == '.' && at(position + 1).is_some_and(|c| c.is_ascii_digit());
+                    if char.is_ascii_alphanumeric() || char == '_' || char == '#' || fraction {
+                        position += 1;
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push(Token::Number(slice(start, position).into()));
+            }
+
+            _ if char.is_alphabetic() || char == '_' => {
+                let start = position - 1;
+                while at(position)
+                    .is_some_and(|char| char.is_alphanumeric() || char == '_' || char == '@')
+                {
+                    position += 1;
+                }
+                let name: EcoString = slice(start, position).into();
+                if char.is_lowercase() {
+                    tokens.push(Token::Atom(name));
+                } else {
+                    tokens.push(Token::Variable(name));
+                }
+            }
+
+            _ => tokens.push(Token::Punctuation(char)),
+        }
+    }
+
+    tokens
+}
+
+struct Form {
+    tokens: Vec<Token>,
+    /// The comment right before the form.
+    documentation: Option<EcoString>,
+}
+
+/// Splits tokens into the forms of a module, each ended by a dot.
+///
+fn forms(tokens: Vec<Token>) -> Vec<Form> {
+    let mut forms = vec![];
+    let mut form = vec![];
+    let mut comment = None;
+    for token in tokens {
+        match token {
+            Token::Comment(text) if form.is_empty() => comment = Some(text),
+            Token::Comment(_) => {}
+            Token::Dot => forms.push(Form {
+                tokens: std::mem::take(&mut form),
+                documentation: comment.take().and_then(|text| documentation_text(&text)),
+            }),
+            _ => form.push(token),
+        }
+    }
+    forms
+}
+
+/// The text of an EDoc comment, without the `@doc` tag and without the
+/// other tags such as `@param`.
+///
+fn documentation_text(comment: &str) -> Option<EcoString> {
+    let lines: Vec<&str> = comment
+        .lines()
+        .map(|line| {
+            let line = line.trim();
+            line.strip_prefix("@doc")
+                .map(str::trim_start)
+                .unwrap_or(line)
+        })
+        .take_while(|line| !line.starts_with('@'))
+        .collect();
+    let text = lines.join("\n");
+    let text = text.trim();
+    if text.is_empty() {
+        None
+    } else {
+        Some(text.into())
+    }
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn new(tokens: Vec<Token>) -> Self {
+        Self {
+            tokens,
+            position: 0,
+        }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn peek_nth(&self, n: usize) -> Option<&Token> {
+        self.tokens.get(self.position + n)
+    }
+
+    fn advance(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn at_punctuation(&self, char: char) -> bool {
+        self.peek() == Some(&Token::Punctuation(char))
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        let found = self.peek() == Some(token);
+        if found {
+            self.position += 1;
+        }
+        found
+    }
+
+    fn eat_punctuation(&mut self, char: char) -> bool {
+        self.eat(&Token::Punctuation(char))
+    }
+
+    fn eat_atom(&mut self, atom: &str) -> bool {
+        let found = matches!(self.peek(), Some(Token::Atom(found)) if found == atom);
+        if found {
+            self.position += 1;
+        }
+        found
+    }
+
+    fn atom(&mut self) -> Option<EcoString> {
+        match self.peek() {
+            Some(Token::Atom(atom)) => {
+                let atom = atom.clone();
+                self.position += 1;
+                Some(atom)
+            }
+            _ => None,
+        }
+    }
+
+    fn attribute(&mut self) -> Option<Attribute> {
+        if !self.eat_punctuation('-') {
+            return None;
+        }
+        let name = self.atom()?;
+        // Attributes can be written with or without parentheses:
+        // `-spec(...)` and `-spec ...`
+        let parenthesised = self.eat_punctuation('(');
+
+        match name.as_str() {
+            "export" => self.export().map(Attribute::Export),
+            "compile" => {
+                let export_all = self.tokens.contains(&Token::Atom("export_all".into()));
+                export_all.then_some(Attribute::ExportAll)
+            }
+            "doc" => match self.advance() {
+                Some(Token::String(text)) => Some(Attribute::Documentation(text)),
+                _ => None,
+            },
+            "type" | "opaque" => self.type_definition(name == "opaque", parenthesised),
+            "spec" => self.spec(),
+            _ => None,
+        }
+    }
+
+    fn export(&mut self) -> Option<Vec<(EcoString, usize)>> {
+        if !self.eat_punctuation('[') {
+            return None;
+        }
+        let mut functions = vec![];
+        while let Some(name) = self.atom() {
+            let _ = self.eat_punctuation('/');
+            if let Some(Token::Number(arity)) = self.advance() {
+                functions.push((name, arity.parse().ok()?));
+            }
+            if !self.eat_punctuation(',') {
+                break;
+            }
+        }
+        Some(functions)
+    }
+
+    fn type_definition(&mut self, opaque: bool, parenthesised: bool) -> Option<Attribute> {
+        let name = self.atom()?;
+        if !self.eat_punctuation('(') {
+            return None;
+        }
+        let mut parameters = vec![];
+        while let Some(Token::Variable(parameter)) = self.peek() {
+            parameters.push(parameter.clone());
+            self.position += 1;
+            let _ = self.eat_punctuation(',');
+        }
+        if !self.eat_punctuation(')') || !self.eat(&Token::DoubleColon) {
+            return None;
+        }
+        let definition = self.type_();
+        if parenthesised && !self.eat_punctuation(')') {
+            return None;
+        }
+        Some(Attribute::Type(TypeDefinition {
+            name,
+            parameters,
+            definition,
+            opaque,
+            documentation: None,
+        }))
+    }
+
+    fn spec(&mut self) -> Option<Attribute> {
+        let mut name = self.atom()?;
+        // Specs can be qualified with the name of the module
+        if self.eat_punctuation(':') {
+            name = self.atom()?;
+        }
+
+        // Only the first clause of a spec is used
+        if !self.eat_punctuation('(') {
+            return None;
+        }
+        let mut parameters = vec![];
+        while !self.eat_punctuation(')') {
+            let _ = self.peek()?;
+            parameters.push(self.parameter());
+            let _ = self.eat_punctuation(',');
+        }
+        if !self.eat(&Token::Arrow) {
+            return None;
+        }
+        let return_type = self.type_();
+
+        let mut constraints = HashMap::new();
+        if self.eat_atom("when") {
+            loop {
+                match (self.advance(), self.advance()) {
+                    (Some(Token::Variable(variable)), Some(Token::DoubleColon)) => {
+                        let _ = constraints.insert(variable, self.type_());
+                    }
+                    _ => break,
+                }
+                if !self.eat_punctuation(',') {
+                    break;
+                }
+            }
+        }
+
+        Some(Attribute::Spec(Spec {
+            name,
+            parameters,
+            return_type,
+            constraints,
+            documentation: None,
+        }))
+    }
+
+    fn parameter(&mut self) -> Parameter {
+        match self.peek() {
+            Some(Token::Variable(name)) if self.peek_nth(1) == Some(&Token::DoubleColon) => {
+                let name = name.clone();
+                self.position += 2;
+                Parameter {
+                    name: Some(name),
+                    type_: self.type_(),
+                }
+            }
+            Some(Token::Variable(name)) => Parameter {
+                name: Some(name.clone()),
+                type_: self.type_(),
+            },
+            _ => Parameter {
+                name: None,
+                type_: self.type_(),
+            },
+        }
+    }
+
+    fn type_(&mut self) -> ErlType {
+        let type_ = self.range_type();
+        if !self.at_punctuation('|') {
+            return type_;
+        }
+        let mut variants = vec![type_];
+        while self.eat_punctuation('|') {
+            variants.push(self.range_type());
+        }
+        ErlType::Union(variants)
+    }
+
+    fn range_type(&mut self) -> ErlType {
+        let type_ = self.primary_type();
+        if self.eat(&Token::DoubleDot) {
+            let _ = self.primary_type();
+            return ErlType::Integer;
+        }
+        type_
+    }
+
+    fn primary_type(&mut self) -> ErlType {
+        let Some(token) = self.advance() else {
+            return ErlType::Unknown;
+        };
+        match token {
+            Token::Variable(name) => {
+                // Annotated types, the name is only used for parameters
+                if self.eat(&Token::DoubleColon) {
+                    self.type_()
+                } else {
+                    ErlType::Variable(name)
+                }
+            }
+
+            Token::Number(_) | Token::Char => ErlType::Integer,
+            Token::Punctuation('-') => {
+                let _ = self.primary_type();
+                ErlType::Integer
+            }
+
+            Token::Atom(name) if name == "fun" && self.at_punctuation('(') => self.function_type(),
+
+            Token::Atom(module) if self.at_punctuation(':') => {
+                self.position += 1;
+                let name = self.atom().unwrap_or_default();
+                ErlType::Remote {
+                    module,
+                    name,
+                    arguments: self.arguments(),
+                }
+            }
+
+            Token::Atom(name) if self.at_punctuation('(') => ErlType::Local {
+                name,
+                arguments: self.arguments(),
+            },
+
+            Token::Atom(atom) => ErlType::Atom(atom),
+
+            Token::Punctuation('[') => {
+                if self.eat_punctuation(']') {
+                    return ErlType::List(Box::new(ErlType::Unknown));
+                }
+                let element = self.type_();
+                if self.eat_punctuation(',') {
+                    let _ = self.eat(&Token::Ellipsis);
+                }
+                let _ = self.eat_punctuation(']');
+                ErlType::List(Box::new(element))
+            }
+
+            Token::Punctuation('{') => {
+                let mut elements = vec![];
+                while !self.eat_punctuation('}') {
+                    if self.peek().is_none() {
+                        break;
+                    }
+                    elements.push(self.type_());
+                    let _ = self.eat_punctuation(',');
+                }
+                ErlType::Tuple(elements)
+            }
+
+            Token::Punctuation('(') => {
+                let type_ = self.type_();
+                let _ = self.eat_punctuation(')');
+                type_
+            }
+
+            Token::Punctuation('<') if self.at_punctuation('<') => {
+                while let Some(token) = self.advance() {
+                    if token == Token::Punctuation('>') && self.eat_punctuation('>') {
+                        break;
+                    }
+                }
+                ErlType::Binary
+            }
+
+            // Maps and records
+            Token::Punctuation('#') => {
+                let _ = self.atom();
+                self.skip_balanced();
+                ErlType::Unknown
+            }
+
+            // Macros
+            Token::Punctuation('?') => {
+                let _ = self.advance();
+                if self.at_punctuation('(') {
+                    self.skip_balanced();
+                }
+                ErlType::Unknown
+            }
+
+            _ => ErlType::Unknown,
+        }
+    }
+
+    fn arguments(&mut self) -> Vec<ErlType> {
+        let mut arguments = vec![];
+        if !self.eat_punctuation('(') {
+            return arguments;
+        }
+        while !self.eat_punctuation(')') {
+            if self.peek().is_none() {
+                break;
+            }
+            arguments.push(self.type_());
+            let _ = self.eat_punctuation(',');
+        }
+        arguments
+    }
+
+    /// A `fun(...)` type, the `fun` keyword having already been consumed.
+    ///
+    fn function_type(&mut self) -> ErlType {
+        let _ = self.eat_punctuation('(');
+        // `fun()`, any function
+        if self.eat_punctuation(')') {
+            return ErlType::Unknown;
+        }
+
+        let parameters = if self.at_punctuation('(') && self.peek_nth(1) == Some(&Token::Ellipsis) {
+            // `fun((...) -> Type)`, a function of any arity
+            self.position += 3;
+            None
+        } else {
+            Some(self.arguments())
+        };
+        let _ = self.eat(&Token::Arrow);
+        let return_ = self.type_();
+        let _ = self.eat_punctuation(')');
+
+        match parameters {
+            Some(parameters) => ErlType::Function {
+                parameters,
+                return_: Box::new(return_),
+            },
+            None => ErlType::Unknown,
+        }
+    }
+
+    /// Skips over a parenthesised, bracketed or braced group of tokens.
+    ///
+    fn skip_balanced(&mut self) {
+        let mut depth = 0;
+        while let Some(token) = self.advance() {
+            match token {
+                Token::Punctuation('(' | '[' | '{') => depth += 1,
+                Token::Punctuation(')' | ']' | '}') => depth -= 1,
+                _ => {}
+            }
+            if depth <= 0 {
+                break;
+            }
+        }
+    }
+}
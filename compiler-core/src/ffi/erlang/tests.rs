@@ -0,0 +1,242 @@
+macro_rules! assert_bindings {
+    ($src:expr $(,)?) => {
+        let output = super::generate($src, "wibble", "wibble.erl").unwrap();
+        insta::assert_snapshot!(insta::internals::AutoName, output, $src);
+    };
+}
+
+#[test]
+fn primitive_types() {
+    assert_bindings!(
+        r#"
+-module(wibble).
+-export([wibble/3, wobble/1]).
+
+-spec wibble(integer(), float(), boolean()) -> binary().
+wibble(A, B, C) -> <<>>.
+
+-spec wobble(unicode:unicode_binary()) -> nil.
+wobble(_) -> nil.
+"#
+    );
+}
+
+#[test]
+fn named_parameters() {
+    assert_bindings!(
+        r#"
+-module(wibble).
+-export([wibble/2]).
+
+-spec wibble(Name :: binary(), Count) -> 1..10 when Count :: non_neg_integer().
+wibble(Name, Count) -> 1.
+"#
+    );
+}
+
+#[test]
+fn lists_tuples_and_functions() {
+    assert_bindings!(
+        r#"
+-module(wibble).
+-export([wibble/2]).
+
+-spec wibble([integer()], fun((integer()) -> float())) -> {list(float()), [binary(), ...]}.
+wibble(List, Fun) -> {[], []}.
+"#
+    );
+}
+
+#[test]
+fn results() {
+    assert_bindings!(
+        r#"
+-module(wibble).
+-export([wibble/1]).
+
+-spec wibble(binary()) -> {ok, integer()} | {error, nil}.
+wibble(_) -> {error, nil}.
+"#
+    );
+}
+
+#[test]
+fn type_variables() {
+    assert_bindings!(
+        r#"
+-module(wibble).
+-export([map/2]).
+
+-spec map(list(Element), fun((Element) -> Result)) -> list(Result).
+map(List, Fun) -> lists:map(Fun, List).
+"#
+    );
+}
+
+#[test]
+fn unknown_types_are_dynamic() {
+    assert_bindings!(
+        r#"
+-module(wibble).
+-export([wibble/4]).
+
+-spec wibble(term(), #{atom() => term()}, ok | error, pid()) -> string().
+wibble(_, _, _, _) -> "".
+"#
+    );
+}
+
+#[test]
+fn declared_types() {
+    assert_bindings!(
+        r#"
+-module(wibble).
+-export([new/0, get/2]).
+-export_type([id/0, table/1, shape/0]).
+
+-type id() :: integer().
+-type pair(A, B) :: {A, B}.
+-type unused(A) :: integer().
+-type shape() :: #{width := float()}.
+-type tree() :: {node, tree(), tree()} | leaf.
+-opaque table(Value) :: #{id() => Value}.
+
+-spec new() -> table(_).
+new() -> #{}.
+
+-spec get(table(Value), id()) -> Value.
+get(Table, Id) -> maps:get(Id, Table).
+"#
+    );
+}
+
+#[test]
+fn documentation_is_kept() {
+    assert_bindings!(
+        r#"
+-module(wibble).
+-export([add/2, subtract/2]).
+
+%% @doc Adds two numbers.
+%%
+%% Really useful.
+%% @param A the first number
+-spec add(integer(), integer()) -> integer().
+add(A, B) -> A + B.
+
+-doc "Subtracts two numbers.".
+-spec subtract(integer(), integer()) -> integer().
+subtract(A, B) -> A - B.
+"#
+    );
+}
+
+#[test]
+fn only_exported_functions_are_bound() {
+    assert_bindings!(
+        r#"
+-module(wibble).
+-export([wibble/0]).
+
+-spec wibble() -> integer().
+wibble() -> wobble().
+
+-spec wobble() -> integer().
+wobble() -> 1.
+"#
+    );
+}
+
+#[test]
+fn export_all() {
+    assert_bindings!(
+        r#"
+-module(wibble).
+-compile(export_all).
+
+-spec wibble() -> integer().
+wibble() -> wobble().
+
+-spec wobble() -> integer().
+wobble() -> 1.
+"#
+    );
+}
+
+#[test]
+fn unbindable_functions_are_listed() {
+    assert_bindings!(
+        r#"
+-module(wibble).
+-export([wibble/0, wibble/1, wobble/0]).
+
+-spec wibble() -> integer().
+wibble() -> 1.
+
+-spec wibble(integer()) -> integer().
+wibble(X) -> X.
+
+wobble() -> 1.
+"#
+    );
+}
+
+#[test]
+fn multiple_spec_clauses_only_bind_the_first() {
+    assert_bindings!(
+        r#"
+-module(wibble).
+-export([wibble/1]).
+
+-spec wibble(integer()) -> integer(); (float()) -> float().
+wibble(X) -> X.
+"#
+    );
+}
+
+#[test]
+fn keywords_and_quoted_atoms() {
+    assert_bindings!(
+        r#"
+-module(wibble).
+-export(['case'/1, 'Wobble'/1]).
+
+-spec 'case'(Type :: integer()) -> integer().
+'case'(X) -> X.
+
+-spec 'Wobble'(integer()) -> integer().
+'Wobble'(X) -> X.
+"#
+    );
+}
+
+#[test]
+fn debug_info_attributes() {
+    // The format printed for compiled modules
+    assert_bindings!(
+        r#"
+-file("src/wibble.erl", 1).
+-module(wibble).
+-export([wibble/1]).
+-spec wibble(binary()) -> {ok, integer()} | {error, nil}.
+"#
+    );
+}
+
+#[test]
+fn triple_quoted_documentation() {
+    assert_bindings!(
+        r#"
+-module(wibble).
+-export([wibble/0]).
+
+-doc """
+Returns one.
+
+Always.
+""".
+-spec wibble() -> integer().
+wibble() -> 1.
+"#
+    );
+}
@@ -0,0 +1,832 @@
+//! Reading TypeScript declaration files (`.d.ts`) into Gleam bindings.
+//!
+//! This is not a full TypeScript parser: it understands the exported
+//! functions, interfaces, classes, enums and type aliases found in declaration
+//! files and skips over anything else.
+//!
+//! TypeScript types are translated as follows:
+//!
+//! | TypeScript                   | Gleam                     |
+//! | ---------------------------- | ------------------------- |
+//! | `number`                     | `Float`                   |
+//! | `string`                     | `String`                  |
+//! | `boolean`                    | `Bool`                    |
+//! | `void`, `undefined`, `null`  | `Nil`                     |
+//! | `[A, B]`                     | `#(A, B)`                 |
+//! | `(a: A) => B`                | `fn(A) -> B`              |
+//! | Types declared in the file   | External types            |
+//! | Anything else                | `dynamic.Dynamic`         |
+//!
+//! Numbers are bound as `Float` as TypeScript doesn't tell integers apart, a
+//! binding can be changed to use `Int` by hand when appropriate.
+
+#[cfg(test)]
+mod tests;
+
+use std::collections::{HashMap, HashSet};
+
+use ecow::{eco_format, EcoString};
+
+use crate::{build::Target, Result};
+
+use super::{
+    parameter_names, type_name, value_name, Bindings, ExternalFunction, ExternalType, GleamType,
+};
+
+/// Generates a Gleam module binding to the JavaScript module `module`, whose
+/// interface is described by the given TypeScript declarations.
+///
+/// `source` is a description of where the declarations come from, usually the
+/// path of the declaration file.
+///
+pub fn generate(declarations: &str, module: &str, source: &str) -> Result<String> {
+    let statements = Parser::new(tokenise(declarations)).statements();
+    let mut bindings = Bindings::new(Target::JavaScript, module.into(), source.into());
+
+    let declared_types: HashMap<EcoString, EcoString> = statements
+        .iter()
+        .filter_map(|statement| match statement {
+            Statement::Type { name, .. } => Some((name.clone(), type_name(name)?)),
+            Statement::Function { .. } | Statement::Skipped(_) => None,
+        })
+        .collect();
+
+    let mut functions = HashSet::new();
+    for statement in statements {
+        match statement {
+            Statement::Skipped(declaration) => bindings.skip(declaration),
+
+            Statement::Type {
+                name,
+                parameters,
+                alias,
+                documentation,
+            } => {
+                let Some(gleam_name) = declared_types.get(&name) else {
+                    bindings.skip(eco_format!("type {name}"));
+                    continue;
+                };
+                let converter = Converter::new(&declared_types, &parameters);
+                bindings.add_type(ExternalType {
+                    name: gleam_name.clone(),
+                    parameters: converter.variables(&parameters),
+                    documentation,
+                    alias: alias.map(|alias| converter.convert(&alias)),
+                });
+            }
+
+            Statement::Function {
+                name,
+                generics,
+                parameters,
+                return_type,
+                documentation,
+            } => {
+                // Only the first of a function's overloads is bound
+                if !functions.insert(name.clone()) {
+                    continue;
+                }
+                let Some(gleam_name) = value_name(&name) else {
+                    bindings.skip(name);
+                    continue;
+                };
+                let converter = Converter::new(&declared_types, &generics);
+                let names = parameter_names(
+                    parameters
+                        .iter()
+                        .map(|parameter| parameter.name.as_deref().and_then(value_name)),
+                );
+                let parameters = names
+                    .into_iter()
+                    .zip(parameters.iter())
+                    .map(|(name, parameter)| (name, converter.convert(&parameter.type_)))
+                    .collect();
+                bindings.add_function(ExternalFunction {
+                    name: gleam_name,
+                    external_name: name,
+                    parameters,
+                    return_type: converter.convert(&return_type),
+                    documentation,
+                });
+            }
+        }
+    }
+
+    bindings.to_gleam()
+}
+
+/// Translates TypeScript types into Gleam ones.
+///
+struct Converter<'a> {
+    declared_types: &'a HashMap<EcoString, EcoString>,
+    /// The type parameters in scope
+    generics: &'a [EcoString],
+}
+
+impl<'a> Converter<'a> {
+    fn new(declared_types: &'a HashMap<EcoString, EcoString>, generics: &'a [EcoString]) -> Self {
+        Self {
+            declared_types,
+            generics,
+        }
+    }
+
+    fn variables(&self, generics: &[EcoString]) -> Vec<EcoString> {
+        generics.iter().map(|name| self.variable(name)).collect()
+    }
+
+    fn variable(&self, name: &str) -> EcoString {
+        value_name(name).unwrap_or_else(|| "a".into())
+    }
+
+    fn convert(&self, type_: &TsType) -> GleamType {
+        match type_ {
+            TsType::Number => GleamType::Float,
+            TsType::String => GleamType::String,
+            TsType::Boolean => GleamType::Bool,
+            TsType::Void => GleamType::Nil,
+            TsType::Unknown => GleamType::Dynamic,
+            TsType::Tuple(elements) => {
+                GleamType::Tuple(elements.iter().map(|type_| self.convert(type_)).collect())
+            }
+            TsType::Function {
+                parameters,
+                return_,
+            } => GleamType::Function {
+                arguments: parameters.iter().map(|type_| self.convert(type_)).collect(),
+                return_: Box::new(self.convert(return_)),
+            },
+            TsType::Reference { name, arguments } => {
+                if arguments.is_empty() && self.generics.contains(name) {
+                    return GleamType::Variable(self.variable(name));
+                }
+                match self.declared_types.get(name) {
+                    Some(gleam_name) => GleamType::Named {
+                        name: gleam_name.clone(),
+                        arguments: arguments.iter().map(|type_| self.convert(type_)).collect(),
+                    },
+                    None => GleamType::Dynamic,
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Statement {
+    Function {
+        name: EcoString,
+        generics: Vec<EcoString>,
+        parameters: Vec<Parameter>,
+        return_type: TsType,
+        documentation: Option<EcoString>,
+    },
+    /// An interface, class, enum or type alias.
+    Type {
+        name: EcoString,
+        parameters: Vec<EcoString>,
+        alias: Option<TsType>,
+        documentation: Option<EcoString>,
+    },
+    /// An exported declaration that can't be bound.
+    Skipped(EcoString),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Parameter {
+    name: Option<EcoString>,
+    type_: TsType,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TsType {
+    Number,
+    String,
+    Boolean,
+    Void,
+    Tuple(Vec<TsType>),
+    Function {
+        parameters: Vec<TsType>,
+        return_: Box<TsType>,
+    },
+    Reference {
+        name: EcoString,
+        arguments: Vec<TsType>,
+    },
+    /// A type that has no Gleam equivalent.
+    Unknown,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Name(EcoString),
+    /// A string, number or template literal.
+    Literal,
+    Punctuation(char),
+    Arrow,
+    Ellipsis,
+    Documentation(EcoString),
+}
+
+fn tokenise(src: &str) -> Vec<Token> {
+    let mut tokens = vec![];
+    let mut chars = src.chars().peekable();
+
+    while let Some(char) = chars.next() {
+        match char {
+            _ if char.is_whitespace() => {}
+
+            '/' if chars.peek() == Some(&'/') => {
+                for char in chars.by_ref() {
+                    if char == '\n' {
+                        break;
+                    }
+                }
+            }
+
+            '/' if chars.peek() == Some(&'*') => {
+                let _ = chars.next();
+                let mut comment = String::new();
+                let mut previous = ' ';
+                for char in chars.by_ref() {
+                    if previous == '*' && char == '/' {
+                        let _ = comment.pop();
+                        break;
+                    }
+                    comment.push(char);
+                    previous = char;
+                }
+                if let Some(documentation) = comment.strip_prefix('*') {
+                    tokens.push(Token::Documentation(documentation_text(documentation)));
+                }
+            }
+
+            '"' | '\'' | '`' => {
+                let mut escaped = false;
+                for next in chars.by_ref() {
+                    if next == char && !escaped {
+                        break;
+                    }
+                    escaped = next == '\\' && !escaped;
+                }
+                tokens.push(Token::Literal);
+            }
+
+            '=' if chars.peek() == Some(&'>') => {
+                let _ = chars.next();
+                tokens.push(Token::Arrow);
+            }
+
+            '.' if chars.peek() == Some(&'.') => {
+                while chars.peek() == Some(&'.') {
+                    let _ = chars.next();
+                }
+                tokens.push(Token::Ellipsis);
+            }
+
+            _ if char.is_ascii_digit() => {
+                while chars.peek().is_some_and(|char| {
+                    char.is_ascii_alphanumeric() || *char == '.' || *char == '_'
+                }) {
+                    let _ = chars.next();
+                }
+                tokens.push(Token::Literal);
+            }
+
+            _ if char.is_alphabetic() || char == '_' || char == '$' => {
+                let mut name = String::from(char);
+                while let Some(char) = chars
+                    .peek()
+                    .filter(|char| char.is_alphanumeric() || **char == '_' || **char == '$')
+                {
+                    name.push(*char);
+                    let _ = chars.next();
+                }
+                tokens.push(Token::Name(name.into()));
+            }
+
+            _ => tokens.push(Token::Punctuation(char)),
+        }
+    }
+
+    tokens
+}
+
+/// The text of a JSDoc comment, without the leading `*` of each line and
+/// without the block tags such as `@param`.
+///
+fn documentation_text(comment: &str) -> EcoString {
+    let lines: Vec<&str> = comment
+        .lines()
+        .map(|line| {
+            let line = line.trim();
+            let line = line.strip_prefix('*').unwrap_or(line);
+            line.strip_prefix(' ').unwrap_or(line)
+        })
+        .take_while(|line| !line.starts_with('@'))
+        .collect();
+    lines.join("\n").trim().into()
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn new(tokens: Vec<Token>) -> Self {
+        Self {
+            tokens,
+            position: 0,
+        }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn peek_nth(&self, n: usize) -> Option<&Token> {
+        self.tokens.get(self.position + n)
+    }
+
+    fn advance(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn at_punctuation(&self, char: char) -> bool {
+        self.peek() == Some(&Token::Punctuation(char))
+    }
+
+    fn at_name(&self, name: &str) -> bool {
+        matches!(self.peek(), Some(Token::Name(found)) if found == name)
+    }
+
+    fn eat_punctuation(&mut self, char: char) -> bool {
+        let found = self.at_punctuation(char);
+        if found {
+            self.position += 1;
+        }
+        found
+    }
+
+    fn eat_name(&mut self, name: &str) -> bool {
+        let found = self.at_name(name);
+        if found {
+            self.position += 1;
+        }
+        found
+    }
+
+    fn name(&mut self) -> Option<EcoString> {
+        match self.peek() {
+            Some(Token::Name(name)) => {
+                let name = name.clone();
+                self.position += 1;
+                Some(name)
+            }
+            _ => None,
+        }
+    }
+
+    fn statements(mut self) -> Vec<Statement> {
+        let mut statements = vec![];
+        let mut documentation = None;
+
+        while let Some(token) = self.peek() {
+            if let Token::Documentation(text) = token {
+                documentation = Some(text.clone()).filter(|text| !text.is_empty());
+                self.position += 1;
+                continue;
+            }
+
+            let start = self.position;
+            let statement = self.statement(documentation.take());
+            if let Some(statement) = statement {
+                statements.push(statement);
+            }
+            // Make sure we always make progress, even on unexpected input.
+            if self.position == start {
+                self.position += 1;
+            }
+        }
+
+        statements
+    }
+
+    fn statement(&mut self, documentation: Option<EcoString>) -> Option<Statement> {
+        if !self.eat_name("export") {
+            self.skip_statement();
+            return None;
+        }
+
+        if self.eat_name("default") || self.at_punctuation('{') || self.at_punctuation('*') {
+            self.skip_statement();
+            return None;
+        }
+        let _ = self.eat_name("declare");
+
+        let Some(Token::Name(keyword)) = self.advance() else {
+            self.skip_statement();
+            return None;
+        };
+
+        match keyword.as_str() {
+            "function" => self.function(documentation),
+
+            "interface" | "class" | "enum" => self.declared_type(documentation),
+            "abstract" if self.eat_name("class") => self.declared_type(documentation),
+            "const" if self.eat_name("enum") => self.declared_type(documentation),
+
+            "type" => self.type_alias(documentation),
+
+            "const" | "let" | "var" | "namespace" | "module" => {
+                let name = self.name().unwrap_or_default();
+                self.skip_statement();
+                Some(Statement::Skipped(eco_format!("{keyword} {name}")))
+            }
+
+            _ => {
+                self.skip_statement();
+                None
+            }
+        }
+    }
+
+    fn function(&mut self, documentation: Option<EcoString>) -> Option<Statement> {
+        let Some(name) = self.name() else {
+            self.skip_statement();
+            return None;
+        };
+        let generics = self.generics();
+
+        let parameters = self.parameters();
+        let return_type = if self.eat_punctuation(':') {
+            self.return_type()
+        } else {
+            TsType::Unknown
+        };
+        let _ = self.eat_punctuation(';');
+
+        match parameters {
+            Some(parameters) => Some(Statement::Function {
+                name,
+                generics,
+                parameters,
+                return_type,
+                documentation,
+            }),
+            None => Some(Statement::Skipped(name)),
+        }
+    }
+
+    /// The parameters of a function. Returns `None` if the function can't be
+    /// bound, which is the case for functions with rest parameters.
+    fn parameters(&mut self) -> Option<Vec<Parameter>> {
+        if !self.eat_punctuation('(') {
+            return None;
+        }
+
+        let mut parameters = vec![];
+        let mut bindable = true;
+        while !self.eat_punctuation(')') {
+            let _ = self.peek()?;
+
+            if self.peek() == Some(&Token::Ellipsis) {
+                bindable = false;
+                self.position += 1;
+            }
+
+            let name = match self.peek() {
+                Some(Token::Name(_)) => self.name(),
+                _ => {
+                    // Destructured parameters
+                    self.skip_balanced();
+                    None
+                }
+            };
+            let _ = self.eat_punctuation('?');
+            let type_ = if self.eat_punctuation(':') {
+                self.type_()
+            } else {
+                TsType::Unknown
+            };
+            let _ = self.eat_punctuation(',');
+
+            // The type of `this` can be annotated, it is not an actual
+            // parameter.
+            if name.as_deref() != Some("this") {
+                parameters.push(Parameter { name, type_ });
+            }
+        }
+
+        bindable.then_some(parameters)
+    }
+
+    fn return_type(&mut self) -> TsType {
+        // Type predicates, such as `value is string`.
+        if matches!(self.peek(), Some(Token::Name(_)))
+            && matches!(self.peek_nth(1), Some(Token::Name(is)) if is == "is")
+        {
+            self.position += 2;
+            let _ = self.type_();
+            return TsType::Boolean;
+        }
+        if self.eat_name("asserts") {
+            let _ = self.type_();
+            return TsType::Void;
+        }
+        self.type_()
+    }
+
+    fn declared_type(&mut self, documentation: Option<EcoString>) -> Option<Statement> {
+        let name = self.name()?;
+        let parameters = self.generics();
+        self.skip_statement();
+        Some(Statement::Type {
+            name,
+            parameters,
+            alias: None,
+            documentation,
+        })
+    }
+
+    fn type_alias(&mut self, documentation: Option<EcoString>) -> Option<Statement> {
+        let name = self.name()?;
+        let parameters = self.generics();
+        if !self.eat_punctuation('=') {
+            self.skip_statement();
+            return None;
+        }
+        let alias = self.type_();
+        let _ = self.eat_punctuation(';');
+        Some(Statement::Type {
+            name,
+            parameters,
+            alias: Some(alias),
+            documentation,
+        })
+    }
+
+    /// The names of the type parameters of a declaration, ignoring their
+    /// constraints and defaults.
+    fn generics(&mut self) -> Vec<EcoString> {
+        let mut generics = vec![];
+        if !self.eat_punctuation('<') {
+            return generics;
+        }
+
+        let mut expecting_name = true;
+        let mut depth = 0;
+        while let Some(token) = self.advance() {
+            match token {
+                Token::Punctuation('>') if depth == 0 => break,
+                Token::Punctuation('<' | '(' | '[' | '{') => depth += 1,
+                Token::Punctuation('>' | ')' | ']' | '}') => depth -= 1,
+                Token::Punctuation(',') if depth == 0 => expecting_name = true,
+                Token::Name(name) if expecting_name && depth == 0 => {
+                    generics.push(name);
+                    expecting_name = false;
+                }
+                _ => {}
+            }
+        }
+        generics
+    }
+
+    fn type_(&mut self) -> TsType {
+        let _ = self.eat_punctuation('|');
+        let _ = self.eat_punctuation('&');
+        let mut type_ = self.postfix_type();
+        while self.eat_punctuation('|') || self.eat_punctuation('&') {
+            let _ = self.postfix_type();
+            type_ = TsType::Unknown;
+        }
+
+        // Conditional types
+        if self.eat_name("extends") {
+            let _ = self.type_();
+            if self.eat_punctuation('?') {
+                let _ = self.type_();
+                let _ = self.eat_punctuation(':');
+                let _ = self.type_();
+            }
+            type_ = TsType::Unknown;
+        }
+
+        type_
+    }
+
+    fn postfix_type(&mut self) -> TsType {
+        let mut type_ = self.primary_type();
+        // Arrays and indexed access types
+        while self.at_punctuation('[') {
+            self.skip_balanced();
+            type_ = TsType::Unknown;
+        }
+        type_
+    }
+
+    fn primary_type(&mut self) -> TsType {
+        match self.peek().cloned() {
+            Some(Token::Punctuation('(')) if self.is_function_type() => self.function_type(),
+
+            Some(Token::Punctuation('(')) => {
+                self.position += 1;
+                let type_ = self.type_();
+                let _ = self.eat_punctuation(')');
+                type_
+            }
+
+            Some(Token::Punctuation('[')) => self.tuple_type(),
+
+            Some(Token::Punctuation('{' | '<')) => {
+                self.skip_balanced();
+                if self.at_punctuation('(') {
+                    // A generic function type
+                    let _ = self.function_type();
+                }
+                TsType::Unknown
+            }
+
+            Some(Token::Punctuation('-') | Token::Literal) => {
+                let _ = self.eat_punctuation('-');
+                self.position += 1;
+                TsType::Unknown
+            }
+
+            Some(Token::Name(name)) => {
+                self.position += 1;
+                match name.as_str() {
+                    "number" | "Number" => TsType::Number,
+                    "string" | "String" => TsType::String,
+                    "boolean" | "Boolean" | "true" | "false" => TsType::Boolean,
+                    "void" | "undefined" | "null" => TsType::Void,
+                    "readonly" | "unique" => self.postfix_type(),
+                    "typeof" | "keyof" | "infer" => {
+                        let _ = self.postfix_type();
+                        TsType::Unknown
+                    }
+                    "new" | "abstract" => {
+                        let _ = self.eat_name("new");
+                        let _ = self.function_type();
+                        TsType::Unknown
+                    }
+                    _ => self.type_reference(name),
+                }
+            }
+
+            _ => {
+                self.position += 1;
+                TsType::Unknown
+            }
+        }
+    }
+
+    fn type_reference(&mut self, name: EcoString) -> TsType {
+        let mut name = name;
+        let mut qualified = false;
+        while self.eat_punctuation('.') {
+            qualified = true;
+            if let Some(next) = self.name() {
+                name = next;
+            }
+        }
+
+        let mut arguments = vec![];
+        if self.eat_punctuation('<') {
+            loop {
+                arguments.push(self.type_());
+                if !self.eat_punctuation(',') {
+                    break;
+                }
+            }
+            let _ = self.eat_punctuation('>');
+        }
+
+        if qualified {
+            TsType::Unknown
+        } else {
+            TsType::Reference { name, arguments }
+        }
+    }
+
+    fn tuple_type(&mut self) -> TsType {
+        let _ = self.eat_punctuation('[');
+        let mut elements = vec![];
+        let mut bindable = true;
+        while !self.eat_punctuation(']') {
+            if self.peek().is_none() {
+                return TsType::Unknown;
+            }
+            if self.peek() == Some(&Token::Ellipsis) {
+                bindable = false;
+                self.position += 1;
+            }
+            // Labelled elements, such as `[start: number, end: number]`.
+            if matches!(self.peek(), Some(Token::Name(_)))
+                && matches!(self.peek_nth(1), Some(Token::Punctuation(':' | '?')))
+            {
+                self.position += 1;
+                bindable &= !self.eat_punctuation('?');
+                let _ = self.eat_punctuation(':');
+            }
+            elements.push(self.type_());
+            bindable &= !self.eat_punctuation('?');
+            let _ = self.eat_punctuation(',');
+        }
+
+        if bindable {
+            TsType::Tuple(elements)
+        } else {
+            TsType::Unknown
+        }
+    }
+
+    fn is_function_type(&self) -> bool {
+        let mut depth = 0;
+        for (index, token) in self.tokens.iter().enumerate().skip(self.position) {
+            match token {
+                Token::Punctuation('(' | '[' | '{' | '<') => depth += 1,
+                Token::Punctuation(')' | ']' | '}' | '>') => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return self.tokens.get(index + 1) == Some(&Token::Arrow);
+                    }
+                }
+                _ => {}
+            }
+        }
+        false
+    }
+
+    fn function_type(&mut self) -> TsType {
+        let parameters = self.parameters();
+        if !matches!(self.advance(), Some(Token::Arrow)) {
+            return TsType::Unknown;
+        }
+        let return_ = self.return_type();
+        match parameters {
+            Some(parameters) => TsType::Function {
+                parameters: parameters
+                    .into_iter()
+                    .map(|parameter| parameter.type_)
+                    .collect(),
+                return_: Box::new(return_),
+            },
+            None => TsType::Unknown,
+        }
+    }
+
+    /// Skips a bracketed group of tokens, if the parser is at one.
+    fn skip_balanced(&mut self) {
+        let mut depth = 0;
+        while let Some(token) = self.advance() {
+            match token {
+                Token::Punctuation('(' | '[' | '{' | '<') => depth += 1,
+                Token::Punctuation(')' | ']' | '}' | '>') => depth -= 1,
+                _ => {}
+            }
+            if depth <= 0 {
+                break;
+            }
+        }
+    }
+
+    /// Skips to the end of the current statement, which is either a `;` or
+    /// the end of a block.
+    fn skip_statement(&mut self) {
+        let mut depth = 0;
+        while let Some(token) = self.peek() {
+            match token {
+                Token::Punctuation(';') if depth == 0 => {
+                    self.position += 1;
+                    return;
+                }
+                Token::Punctuation('(' | '[' | '{') => depth += 1,
+                Token::Punctuation(')' | ']') => depth -= 1,
+                Token::Punctuation('}') => {
+                    depth -= 1;
+                    if depth <= 0 {
+                        self.position += 1;
+                        let _ = self.eat_punctuation(';');
+                        return;
+                    }
+                }
+                // Declarations without a trailing semicolon end where the
+                // next one starts.
+                Token::Name(name)
+                    if depth == 0
+                        && self.position > 0
+                        && (name == "export" || name == "declare") =>
+                {
+                    return
+                }
+                Token::Documentation(_) if depth == 0 => return,
+                _ => {}
+            }
+            self.position += 1;
+        }
+    }
+}
@@ -0,0 +1,127 @@
+macro_rules! assert_bindings {
+    ($src:expr $(,)?) => {
+        let output = super::generate($src, "./wibble.mjs", "wibble.d.ts").unwrap();
+        insta::assert_snapshot!(insta::internals::AutoName, output, $src);
+    };
+}
+
+#[test]
+fn primitive_types() {
+    assert_bindings!(
+        "
+export declare function wibble(a: number, b: string, c: boolean): void;
+export function wobble(): undefined;
+export function wubble(a: null): Number;
+"
+    );
+}
+
+#[test]
+fn names_are_converted_to_snake_case() {
+    assert_bindings!("export function parseJSONValue(inputString: string, type: string): string;");
+}
+
+#[test]
+fn tuples_and_functions() {
+    assert_bindings!(
+        "export function wibble(pair: [number, string], callback: (value: number) => void): [start: number, end: number];"
+    );
+}
+
+#[test]
+fn unknown_types_are_dynamic() {
+    assert_bindings!(
+        "
+export function wibble(a: any, b: string[], c: Promise<number>, d: string | undefined): unknown;
+"
+    );
+}
+
+#[test]
+fn declared_types() {
+    assert_bindings!(
+        "
+export interface Options {
+  verbose: boolean;
+  retries?: number;
+}
+
+export declare class Client<T> {
+  constructor(options: Options);
+  get(key: string): T;
+}
+
+export function connect<T>(options: Options): Client<T>;
+"
+    );
+}
+
+#[test]
+fn type_aliases() {
+    assert_bindings!(
+        "
+export type Id = string;
+export type Callback<T> = (value: T) => void;
+export type Unused<T> = number;
+export type Shape = { width: number; height: number };
+export function wibble(id: Id, callback: Callback<Id>, shape: Shape): void;
+"
+    );
+}
+
+#[test]
+fn generic_functions() {
+    assert_bindings!("export function identity<T extends object = {}>(value: T): T;");
+}
+
+#[test]
+fn documentation_is_kept() {
+    assert_bindings!(
+        "
+/**
+ * Adds two numbers.
+ *
+ * Really useful.
+ * @param a the first number
+ */
+export function add(a: number, b: number): number;
+"
+    );
+}
+
+#[test]
+fn overloads_only_bind_the_first_signature() {
+    assert_bindings!(
+        "
+export function wibble(a: string): string;
+export function wibble(a: number): number;
+"
+    );
+}
+
+#[test]
+fn unbindable_declarations_are_listed() {
+    assert_bindings!(
+        "
+export const version: string;
+export function wibble(...values: number[]): void;
+export namespace Wobble {
+  function wubble(): void;
+}
+"
+    );
+}
+
+#[test]
+fn non_exported_declarations_are_ignored() {
+    assert_bindings!(
+        "
+import { Thing } from './thing';
+declare function internal(): void;
+interface Hidden {}
+export function isString(value: unknown): value is string;
+export { internal };
+export default internal;
+"
+    );
+}
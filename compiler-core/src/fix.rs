@@ -1,30 +1,133 @@
+//! Automatic fixes for Gleam code, applied by `gleam fix` and offered as
+//! code actions by the language server.
+
+mod deprecation;
+mod syntax;
+
+pub(crate) use deprecation::Replacement;
+pub use deprecation::{
+    deprecated_references, import_edits, replace_deprecated, DeprecatedReference,
+};
+pub use syntax::deprecated_syntax_edits;
+
 use crate::{
-    format::{Formatter, Intermediate},
-    warning::WarningEmitter,
-    Error, Result,
+    ast::{SrcSpan, TypedModule},
+    type_::ModuleInterface,
 };
-use camino::Utf8Path;
 use ecow::EcoString;
+use strum::{Display, EnumIter, EnumString, IntoEnumIterator, VariantNames};
+
+/// The fixes `gleam fix` can apply, each of which can be selected by name
+/// with `gleam fix --only <fix>`.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString, VariantNames, EnumIter)]
+#[strum(serialize_all = "kebab-case")]
+pub enum Fix {
+    /// References to deprecated values with a replacement are rewritten to
+    /// use the replacement.
+    DeprecatedValues,
+    /// `[1, 2..rest]` becomes `[1, 2, ..rest]`.
+    ListPrepend,
+    /// `[first..rest]` patterns become `[first, ..rest]`.
+    ListPattern,
+    /// `[..]` patterns become `_`.
+    ListCatchAllPattern,
+    /// `Wibble(a ..)` patterns become `Wibble(a, ..)`.
+    RecordSpreadPattern,
+    /// `@target(erl)` and `@target(js)` become `@target(erlang)` and
+    /// `@target(javascript)`.
+    TargetShorthand,
+    /// The `gleam` version constraint in `gleam.toml` is raised to the lowest
+    /// version supporting all the features used by the package. This fix is
+    /// applied to the package rather than to its modules.
+    GleamVersion,
+}
+
+impl Fix {
+    pub fn description(&self) -> &'static str {
+        match self {
+            Fix::DeprecatedValues => "Replace deprecated values with their replacement",
+            Fix::ListPrepend => "Add the missing comma before the tail of a list",
+            Fix::ListPattern => "Add the missing comma before the tail of a list pattern",
+            Fix::ListCatchAllPattern => "Replace `[..]` patterns with `_`",
+            Fix::RecordSpreadPattern => "Add the missing comma before `..` in record patterns",
+            Fix::TargetShorthand => "Replace the `erl` and `js` target shorthands",
+            Fix::GleamVersion => "Raise the Gleam version required by `gleam.toml`",
+        }
+    }
+
+    pub fn all() -> Vec<Fix> {
+        Fix::iter().collect()
+    }
+}
+
+/// The edits made by each of the given fixes to a module, leaving out the
+/// fixes that don't change it.
+///
+pub fn fix_module(
+    fixes: &[Fix],
+    module: &TypedModule,
+    src: &str,
+    modules: &im::HashMap<EcoString, ModuleInterface>,
+) -> Vec<(Fix, Vec<Edit>)> {
+    let syntax_edits = deprecated_syntax_edits(src);
+
+    fixes
+        .iter()
+        .filter_map(|fix| {
+            let edits = match fix {
+                Fix::DeprecatedValues => replace_deprecated(module, src, modules),
+                Fix::GleamVersion => vec![],
+                Fix::ListPrepend
+                | Fix::ListPattern
+                | Fix::ListCatchAllPattern
+                | Fix::RecordSpreadPattern
+                | Fix::TargetShorthand => syntax_edits
+                    .iter()
+                    .filter(|(syntax_fix, _)| syntax_fix == fix)
+                    .map(|(_, edit)| edit.clone())
+                    .collect(),
+            };
+            (!edits.is_empty()).then_some((*fix, edits))
+        })
+        .collect()
+}
+
+/// A change to the source code of a module: the code at the given location is
+/// replaced by the new text.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Edit {
+    pub location: SrcSpan,
+    pub new_text: EcoString,
+}
+
+/// Applies the given edits to the source code of a module. Edits overlapping
+/// ones that come before them are ignored.
+///
+pub fn apply_edits(src: &str, edits: &[Edit]) -> String {
+    apply_edits_from(src, 0, edits)
+}
+
+/// Applies edits to a slice of a module's source code starting at the given
+/// byte offset.
+///
+fn apply_edits_from(src: &str, offset: u32, edits: &[Edit]) -> String {
+    let mut edits = edits.iter().collect::<Vec<_>>();
+    edits.sort_by_key(|edit| (edit.location.start, edit.location.end));
 
-pub fn parse_fix_and_format(src: &EcoString, path: &Utf8Path) -> Result<String> {
-    // Parse
-    let parsed = crate::parse::parse_module(path.to_owned(), src, &WarningEmitter::null())
-        .map_err(|error| Error::Parse {
-            path: path.to_path_buf(),
-            src: src.clone(),
-            error,
-        })?;
-    let intermediate = Intermediate::from_extra(&parsed.extra, src);
-    let module = parsed.module;
-
-    // Fix
-    // let module = some_fixer_module::Fixer::fix(module);
-
-    // Format
-    let mut buffer = String::new();
-    Formatter::with_comments(&intermediate)
-        .module(&module)
-        .pretty_print(80, &mut buffer)?;
-
-    Ok(buffer)
+    let mut result = String::with_capacity(src.len());
+    let mut position = 0;
+    for edit in edits {
+        let start = (edit.location.start - offset) as usize;
+        let end = (edit.location.end - offset) as usize;
+        if start < position {
+            continue;
+        }
+        result.push_str(src.get(position..start).unwrap_or_default());
+        result.push_str(&edit.new_text);
+        position = end;
+    }
+    result.push_str(src.get(position..).unwrap_or_default());
+    result
 }
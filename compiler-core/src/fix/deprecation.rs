@@ -0,0 +1,611 @@
+//! Rewriting references to deprecated values that have a replacement:
+//!
+//! ```gleam
+//! @deprecated("Use new_map instead", replace: "gleam/dict.new_map(_2, _1)")
+//! pub fn old_map(a, b) { ... }
+//! ```
+//!
+//! The replacement is the name of the value to use instead, optionally
+//! qualified with its module. If it's not qualified it is taken to be in the
+//! same module as the deprecated value. It can be followed by the arguments
+//! the new function is called with, where `_1` is the first argument of the
+//! deprecated function, `_2` the second and so on, so that arguments can be
+//! reordered or dropped.
+//!
+//! When a replaced value was imported unqualified, it's referred to through
+//! its module instead, and it's removed from the import once none of its uses
+//! is left. A module that needs to be imported for the replacement is given
+//! an alias if the last segment of its path is already taken.
+//!
+//! Only calls with explicit arguments can be rewritten with a replacement that
+//! moves arguments around: references to the deprecated value that are piped
+//! into, used with `use`, or not called at all are left as they are.
+//!
+
+#[cfg(test)]
+mod tests;
+
+use std::collections::{HashMap, HashSet};
+
+use ecow::{eco_format, EcoString};
+use itertools::Itertools;
+
+use crate::{
+    ast::{
+        visit::{self, Visit},
+        Definition, Import, SrcSpan, TypedExpr, TypedModule,
+    },
+    reference::{name_is_taken, typed_module_references, Reference, ReferenceKind, Symbol},
+    type_::{Deprecation, ModuleInterface, Type, TypedCallArg, ValueConstructorVariant},
+};
+
+use super::{apply_edits_from, Edit};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Replacement {
+    pub module: Option<EcoString>,
+    pub name: EcoString,
+    /// The arguments the replacement is called with, as the 1-based positions
+    /// of the arguments of the deprecated function.
+    pub arguments: Option<Vec<usize>>,
+}
+
+impl Replacement {
+    /// Parses a replacement such as `gleam/dict.new_map(_2, _1)`, returning
+    /// `None` if it's not valid.
+    ///
+    pub fn parse(text: &str) -> Option<Self> {
+        let (reference, arguments) = match text.split_once('(') {
+            None => (text, None),
+            Some((reference, arguments)) => {
+                let arguments = arguments.strip_suffix(')')?;
+                let arguments = if arguments.trim().is_empty() {
+                    vec![]
+                } else {
+                    arguments
+                        .split(',')
+                        .map(|argument| {
+                            let position = argument.trim().strip_prefix('_')?.parse().ok()?;
+                            (position > 0).then_some(position)
+                        })
+                        .collect::<Option<Vec<usize>>>()?
+                };
+                (reference, Some(arguments))
+            }
+        };
+
+        let (module, name) = match reference.rsplit_once('.') {
+            Some((module, name)) => (Some(module), name),
+            None => (None, reference),
+        };
+        if !is_value_name(name) && !is_constructor_name(name) {
+            return None;
+        }
+        if let Some(module) = module {
+            if !module.split('/').all(is_value_name) {
+                return None;
+            }
+        }
+
+        Some(Self {
+            module: module.map(EcoString::from),
+            name: name.into(),
+            arguments,
+        })
+    }
+}
+
+fn is_value_name(name: &str) -> bool {
+    name.starts_with(|char: char| char.is_ascii_lowercase())
+        && name
+            .chars()
+            .all(|char| char.is_ascii_lowercase() || char.is_ascii_digit() || char == '_')
+}
+
+fn is_constructor_name(name: &str) -> bool {
+    name.starts_with(|char: char| char.is_ascii_uppercase())
+        && name.chars().all(|char| char.is_ascii_alphanumeric())
+}
+
+/// A reference to a deprecated value, rewritten to use its replacement.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeprecatedReference {
+    pub edit: Edit,
+    /// The modules that are not imported yet but are used by the new code,
+    /// as written after `import`: `gleam/dict` or `gleam/dict as gleam_dict`.
+    pub imports: Vec<EcoString>,
+    /// The modules already imported that the new code refers to.
+    pub imported_modules: Vec<EcoString>,
+    /// The uses of values imported unqualified that are replaced.
+    pub unqualified_uses: Vec<SrcSpan>,
+}
+
+/// Finds all the references to deprecated values of other modules that can
+/// be replaced in the given module.
+///
+pub fn deprecated_references(
+    module: &TypedModule,
+    src: &str,
+    modules: &im::HashMap<EcoString, ModuleInterface>,
+) -> Vec<DeprecatedReference> {
+    let mut replacer = Replacer {
+        module,
+        src,
+        modules,
+        symbols: typed_module_references(module, src),
+        new_imports: HashMap::new(),
+        nesting: 0,
+        edits: vec![],
+        imports: vec![],
+        imported_modules: vec![],
+        unqualified_uses: vec![],
+        references: vec![],
+    };
+    replacer.visit_typed_module(module);
+    replacer.references
+}
+
+/// The edits to the imports of a module needed by the given references: the
+/// modules they use are imported, and the values imported unqualified that
+/// are no longer used are removed from their import.
+///
+pub fn import_edits(
+    module: &TypedModule,
+    src: &str,
+    references: &[DeprecatedReference],
+) -> Vec<Edit> {
+    let imports = references
+        .iter()
+        .flat_map(|reference| &reference.imports)
+        .unique()
+        .collect_vec();
+    let mut edits = vec![];
+
+    // New imports go before the existing ones, or at the top of the module
+    // if there's none.
+    let first_import = module
+        .definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            Definition::Import(import) => Some(import.location.start),
+            _ => None,
+        })
+        .min();
+    if !imports.is_empty() {
+        let mut new_text = imports
+            .iter()
+            .map(|module| eco_format!("import {module}\n"))
+            .join("");
+        let position = match first_import {
+            Some(position) => position,
+            None => {
+                new_text.push('\n');
+                0
+            }
+        };
+        edits.push(Edit {
+            location: SrcSpan::new(position, position),
+            new_text: new_text.into(),
+        });
+    }
+
+    let replaced_uses = references
+        .iter()
+        .flat_map(|reference| &reference.unqualified_uses)
+        .map(|location| location.start)
+        .collect::<HashSet<_>>();
+    if replaced_uses.is_empty() {
+        return edits;
+    }
+    let symbols = typed_module_references(module, src);
+    let imports = module
+        .definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            Definition::Import(import) => Some(import),
+            _ => None,
+        });
+    for import in imports {
+        let unused = import
+            .unqualified_values
+            .iter()
+            .filter(|value| {
+                let symbol = Symbol::ModuleValue {
+                    module: import.module.clone(),
+                    name: value.name.clone(),
+                };
+                let mut uses = symbols
+                    .iter()
+                    .filter(|reference| {
+                        reference.symbol == symbol
+                            && reference.kind == ReferenceKind::Usage
+                            && !import.location.contains(reference.location.start)
+                            && !is_qualified(src, reference.location)
+                    })
+                    .peekable();
+                uses.peek().is_some()
+                    && uses.all(|reference| replaced_uses.contains(&reference.location.start))
+            })
+            .map(|value| value.location)
+            .collect_vec();
+        if unused.is_empty() {
+            continue;
+        }
+
+        let is_used_qualified = references
+            .iter()
+            .any(|reference| reference.imported_modules.contains(&import.module))
+            || symbols.iter().any(|reference| {
+                reference.kind == ReferenceKind::Usage
+                    && reference.symbol
+                        == (Symbol::ImportedModule {
+                            module: import.module.clone(),
+                        })
+                    && !references
+                        .iter()
+                        .any(|replaced| replaced.edit.location.contains(reference.location.start))
+            });
+        edits.extend(unqualified_removal_edits(
+            import,
+            src,
+            &unused,
+            is_used_qualified,
+        ));
+    }
+    edits
+}
+
+/// Whether the reference at the given location is qualified with a module,
+/// as `old` in `wibble.old`.
+///
+fn is_qualified(src: &str, location: SrcSpan) -> bool {
+    src.get(..location.start as usize)
+        .is_some_and(|before| before.trim_end().ends_with('.'))
+}
+
+/// The edits removing the unqualified imports at the given locations from an
+/// import, leaving the other ones and the formatting of the list as they are.
+/// If none are left the list is removed, along with the whole import if the
+/// module isn't used qualified either.
+///
+fn unqualified_removal_edits(
+    import: &Import<EcoString>,
+    src: &str,
+    removed: &[SrcSpan],
+    is_used_qualified: bool,
+) -> Vec<Edit> {
+    let mut entries = import
+        .unqualified_values
+        .iter()
+        .map(|value| value.location)
+        .chain(import.unqualified_types.iter().map(|type_| type_.location))
+        .collect_vec();
+    entries.sort_by_key(|location| location.start);
+
+    let is_removed = |location: &SrcSpan| removed.contains(location);
+    if entries.iter().all(is_removed) {
+        let location = if is_used_qualified {
+            // `import wibble.{old}` becomes `import wibble`.
+            let start = import.module_location(src).end;
+            let list_end = entries.last().map_or(start, |last| last.end) as usize;
+            let end = src
+                .get(list_end..)
+                .and_then(|after| after.find('}'))
+                .map_or(list_end, |offset| list_end + offset + 1);
+            SrcSpan::new(start, end as u32)
+        } else {
+            // The whole import goes, up to the end of its line.
+            let end = import.location.end as usize;
+            let end = src
+                .get(end..)
+                .and_then(|after| after.find('\n'))
+                .map_or(src.len(), |offset| end + offset + 1);
+            SrcSpan::new(import.location.start, end as u32)
+        };
+        return vec![Edit {
+            location,
+            new_text: "".into(),
+        }];
+    }
+
+    // Each run of removed entries is removed along with the separator up to
+    // the next entry left, or from the previous entry left if it's the last.
+    let removal = |start, end| Edit {
+        location: SrcSpan::new(start, end),
+        new_text: "".into(),
+    };
+    let mut edits = vec![];
+    let mut run: Option<SrcSpan> = None;
+    let mut previous_kept: Option<SrcSpan> = None;
+    for entry in &entries {
+        if is_removed(entry) {
+            run = Some(run.map_or(*entry, |run| SrcSpan::new(run.start, entry.end)));
+        } else {
+            if let Some(run) = run.take() {
+                edits.push(removal(run.start, entry.start));
+            }
+            previous_kept = Some(*entry);
+        }
+    }
+    if let (Some(run), Some(previous)) = (run, previous_kept) {
+        edits.push(removal(previous.end, run.end));
+    }
+    edits
+}
+
+/// All the edits replacing the deprecated values used by a module.
+///
+pub fn replace_deprecated(
+    module: &TypedModule,
+    src: &str,
+    modules: &im::HashMap<EcoString, ModuleInterface>,
+) -> Vec<Edit> {
+    let references = deprecated_references(module, src, modules);
+    import_edits(module, src, &references)
+        .into_iter()
+        .chain(references.into_iter().map(|reference| reference.edit))
+        .collect()
+}
+
+struct Deprecated<'a> {
+    /// The location of the reference to the deprecated value.
+    location: SrcSpan,
+    /// The module defining the deprecated value.
+    module: &'a EcoString,
+    /// The name the module is referred to with, if the value is qualified.
+    /// Otherwise it's imported unqualified.
+    module_alias: Option<&'a EcoString>,
+    replacement: Replacement,
+}
+
+struct Replacer<'a> {
+    module: &'a TypedModule,
+    src: &'a str,
+    modules: &'a im::HashMap<EcoString, ModuleInterface>,
+    /// All the references to symbols in the module.
+    symbols: Vec<Reference>,
+    /// The modules imported for the replacements, with the name they are
+    /// referred to with and the text of their import.
+    new_imports: HashMap<EcoString, (EcoString, EcoString)>,
+    /// How many replaced calls the visitor currently is in the arguments of.
+    nesting: usize,
+    /// The edits made to the arguments of the replaced call being visited.
+    edits: Vec<Edit>,
+    imports: Vec<EcoString>,
+    imported_modules: Vec<EcoString>,
+    unqualified_uses: Vec<SrcSpan>,
+    references: Vec<DeprecatedReference>,
+}
+
+impl<'a> Replacer<'a> {
+    fn deprecated(&self, expression: &'a TypedExpr) -> Option<Deprecated<'a>> {
+        let (location, module, module_alias, deprecation) = match expression {
+            TypedExpr::Var {
+                location,
+                constructor,
+                ..
+            } => match &constructor.variant {
+                ValueConstructorVariant::ModuleConstant { module, .. }
+                | ValueConstructorVariant::ModuleFn { module, .. }
+                | ValueConstructorVariant::Record { module, .. } => {
+                    (*location, module, None, &constructor.deprecation)
+                }
+                ValueConstructorVariant::LocalVariable { .. }
+                | ValueConstructorVariant::LocalConstant { .. } => return None,
+            },
+            TypedExpr::ModuleSelect {
+                location,
+                label,
+                module_name,
+                module_alias,
+                ..
+            } => {
+                let value = self.modules.get(module_name)?.values.get(label)?;
+                // The location of a module select only covers the `.label`
+                // part, the reference starts with the module alias.
+                let before = self.src.get(..location.start as usize)?.trim_end();
+                let start = before.strip_suffix(module_alias.as_str())?.len() as u32;
+                (
+                    SrcSpan::new(start, location.end),
+                    module_name,
+                    Some(module_alias),
+                    &value.deprecation,
+                )
+            }
+            _ => return None,
+        };
+
+        // Values deprecated by the module itself are left alone, they may well
+        // be used by the definition of their own replacement.
+        if *module == self.module.name {
+            return None;
+        }
+        let Deprecation::Deprecated {
+            replacement: Some(replacement),
+            ..
+        } = deprecation
+        else {
+            return None;
+        };
+
+        Some(Deprecated {
+            location,
+            module,
+            module_alias,
+            replacement: Replacement::parse(replacement)?,
+        })
+    }
+
+    /// The name the replacement is referred to with in the module, adding
+    /// the import needed to use it if there's none.
+    ///
+    fn replacement_name(&mut self, deprecated: &Deprecated<'_>) -> Option<EcoString> {
+        let name = &deprecated.replacement.name;
+        let module = deprecated
+            .replacement
+            .module
+            .as_ref()
+            .unwrap_or(deprecated.module);
+        if *module == self.module.name {
+            return Some(name.clone());
+        }
+
+        let qualifier = match deprecated.module_alias {
+            Some(alias) if module == deprecated.module => {
+                self.imported_modules.push(module.clone());
+                alias.clone()
+            }
+            _ => {
+                let import =
+                    self.module
+                        .definitions
+                        .iter()
+                        .find_map(|definition| match definition {
+                            Definition::Import(import) if import.module == *module => Some(import),
+                            _ => None,
+                        });
+                match import {
+                    Some(import) => {
+                        let name = import.used_name()?;
+                        self.imported_modules.push(module.clone());
+                        name
+                    }
+                    None => self.new_import(module)?,
+                }
+            }
+        };
+        if deprecated.module_alias.is_none() {
+            self.unqualified_uses.push(deprecated.location);
+        }
+        Some(eco_format!("{qualifier}.{name}"))
+    }
+
+    /// Imports a module for a replacement, returning the name it's referred
+    /// to with. That's the last segment of its path, or its whole path joined
+    /// with underscores if that's already taken.
+    ///
+    fn new_import(&mut self, module: &EcoString) -> Option<EcoString> {
+        if let Some((name, import)) = self.new_imports.get(module) {
+            self.imports.push(import.clone());
+            return Some(name.clone());
+        }
+
+        let is_taken = |name: &str| {
+            name_is_taken(self.module, self.src, &self.symbols, name, None)
+                || self.new_imports.values().any(|(taken, _)| taken == name)
+        };
+        let last_segment = module.rsplit('/').next()?;
+        let (name, import) = if !is_taken(last_segment) {
+            (last_segment.into(), module.clone())
+        } else {
+            let alias = module.replace("/", "_");
+            if is_taken(&alias) {
+                return None;
+            }
+            let import = eco_format!("{module} as {alias}");
+            (alias, import)
+        };
+        self.imports.push(import.clone());
+        let _ = self
+            .new_imports
+            .insert(module.clone(), (name.clone(), import));
+        Some(name)
+    }
+
+    fn rename(&mut self, deprecated: &Deprecated<'_>) {
+        if let Some(name) = self.replacement_name(deprecated) {
+            self.push(Edit {
+                location: deprecated.location,
+                new_text: name,
+            });
+        }
+    }
+
+    fn push(&mut self, edit: Edit) {
+        if self.nesting > 0 {
+            self.edits.push(edit);
+        } else {
+            self.references.push(DeprecatedReference {
+                edit,
+                imports: std::mem::take(&mut self.imports),
+                imported_modules: std::mem::take(&mut self.imported_modules),
+                unqualified_uses: std::mem::take(&mut self.unqualified_uses),
+            });
+        }
+    }
+
+    /// The source code of an expression, with its own deprecated references
+    /// replaced.
+    ///
+    fn replaced_source(&mut self, expression: &'a TypedExpr) -> String {
+        let location = expression.location();
+        let outer_edits = std::mem::take(&mut self.edits);
+        self.nesting += 1;
+        self.visit_typed_expr(expression);
+        self.nesting -= 1;
+        let edits = std::mem::replace(&mut self.edits, outer_edits);
+
+        let src = self
+            .src
+            .get(location.start as usize..location.end as usize)
+            .unwrap_or_default();
+        apply_edits_from(src, location.start, &edits)
+    }
+}
+
+impl<'a> Visit<'a> for Replacer<'a> {
+    fn visit_typed_expr(&mut self, expression: &'a TypedExpr) {
+        match self.deprecated(expression) {
+            Some(deprecated) if deprecated.replacement.arguments.is_none() => {
+                self.rename(&deprecated)
+            }
+            _ => visit::visit_typed_expr(self, expression),
+        }
+    }
+
+    fn visit_typed_expr_call(
+        &mut self,
+        location: &'a SrcSpan,
+        type_: &'a std::sync::Arc<Type>,
+        fun: &'a TypedExpr,
+        args: &'a [TypedCallArg],
+    ) {
+        let Some(deprecated) = self.deprecated(fun) else {
+            return visit::visit_typed_expr_call(self, location, type_, fun, args);
+        };
+        let Some(arguments) = deprecated.replacement.arguments.clone() else {
+            return visit::visit_typed_expr_call(self, location, type_, fun, args);
+        };
+
+        let keeps_arguments = arguments.len() == args.len()
+            && arguments
+                .iter()
+                .enumerate()
+                .all(|(index, position)| *position == index + 1);
+        let can_move_arguments = args.iter().all(|arg| arg.implicit.is_none())
+            && arguments.iter().all(|position| *position <= args.len());
+
+        if keeps_arguments {
+            // The call stays the same, only the function changes.
+            self.rename(&deprecated);
+        } else if can_move_arguments {
+            if let Some(name) = self.replacement_name(&deprecated) {
+                let arguments = arguments
+                    .iter()
+                    .filter_map(|position| args.get(position - 1))
+                    .map(|arg| self.replaced_source(&arg.value))
+                    .join(", ");
+                self.push(Edit {
+                    location: *location,
+                    new_text: eco_format!("{name}({arguments})"),
+                });
+                return;
+            }
+        }
+
+        for arg in args {
+            self.visit_typed_call_arg(arg);
+        }
+    }
+}
@@ -0,0 +1,465 @@
+use std::collections::HashMap;
+
+use camino::Utf8PathBuf;
+
+use crate::{
+    analyse::TargetSupport,
+    build::{Origin, Target},
+    config::PackageConfig,
+    line_numbers::LineNumbers,
+    type_::{build_prelude, PRELUDE_MODULE_NAME},
+    uid::UniqueIdGenerator,
+    warning::{TypeWarningEmitter, WarningEmitter},
+};
+
+use super::Replacement;
+
+macro_rules! assert_replace {
+    ($(($name:expr, $module_src:expr)),+ $(,)?; $src:expr $(,)?) => {
+        let output = $crate::fix::deprecation::tests::replace(&[$(($name, $module_src)),+], $src);
+        insta::assert_snapshot!(insta::internals::AutoName, output, $src);
+    };
+}
+
+fn replace(dependencies: &[(&str, &str)], src: &str) -> String {
+    let ids = UniqueIdGenerator::new();
+    let mut modules = im::HashMap::new();
+    let _ = modules.insert(PRELUDE_MODULE_NAME.into(), build_prelude(&ids));
+    let mut config = PackageConfig::default();
+    config.name = "thepackage".into();
+
+    let infer = |name: &str, src: &str, modules: &im::HashMap<_, _>| {
+        let parsed = crate::parse::parse_module(
+            Utf8PathBuf::from("test/path"),
+            src,
+            &WarningEmitter::null(),
+        )
+        .expect("syntax error");
+        let mut ast = parsed.module;
+        ast.name = name.into();
+        crate::analyse::ModuleAnalyzerConstructor::<()> {
+            target: Target::Erlang,
+            ids: &ids,
+            origin: Origin::Src,
+            importable_modules: modules,
+            warnings: &TypeWarningEmitter::null(),
+            direct_dependencies: &HashMap::new(),
+            target_support: TargetSupport::NotEnforced,
+            package_config: &config,
+        }
+        .infer_module(ast, LineNumbers::new(src), "".into())
+        .expect("should successfully infer")
+    };
+
+    for (name, module_src) in dependencies {
+        let module = infer(name, module_src, &modules);
+        let _ = modules.insert((*name).into(), module.type_info);
+    }
+    let module = infer("app", src, &modules);
+
+    let edits = super::replace_deprecated(&module, src, &modules);
+    let output = crate::fix::apply_edits(src, &edits);
+    format!("----- BEFORE\n{src}\n\n----- AFTER\n{output}")
+}
+
+#[test]
+fn parse_replacement() {
+    assert_eq!(
+        Replacement::parse("gleam/dict.new_map(_2, _1)"),
+        Some(Replacement {
+            module: Some("gleam/dict".into()),
+            name: "new_map".into(),
+            arguments: Some(vec![2, 1]),
+        })
+    );
+    assert_eq!(
+        Replacement::parse("Wobble"),
+        Some(Replacement {
+            module: None,
+            name: "Wobble".into(),
+            arguments: None,
+        })
+    );
+    assert_eq!(
+        Replacement::parse("wibble()"),
+        Some(Replacement {
+            module: None,
+            name: "wibble".into(),
+            arguments: Some(vec![]),
+        })
+    );
+}
+
+#[test]
+fn parse_invalid_replacement() {
+    assert_eq!(Replacement::parse(""), None);
+    assert_eq!(Replacement::parse("wibble.Wobble.wubble"), None);
+    assert_eq!(Replacement::parse("wibble(_0)"), None);
+    assert_eq!(Replacement::parse("wibble(_1"), None);
+    assert_eq!(Replacement::parse("wibble(1, _2)"), None);
+    assert_eq!(Replacement::parse("1 + 1"), None);
+}
+
+#[test]
+fn qualified_function() {
+    assert_replace!(
+        ("wibble", r#"
+@deprecated("Use new instead", replace: "new")
+pub fn old(a) { a }
+pub fn new(a) { a }
+"#);
+        r#"import wibble
+
+pub fn main() {
+  wibble.old(1)
+}
+"#
+    );
+}
+
+#[test]
+fn aliased_module() {
+    assert_replace!(
+        ("wibble", r#"
+@deprecated("Use new instead", replace: "new")
+pub fn old(a) { a }
+pub fn new(a) { a }
+"#);
+        r#"import wibble as w
+
+pub fn main() {
+  w.old(1)
+}
+"#
+    );
+}
+
+#[test]
+fn unqualified_function() {
+    assert_replace!(
+        ("wibble", r#"
+@deprecated("Use new instead", replace: "new")
+pub fn old(a) { a }
+pub fn new(a) { a }
+"#);
+        r#"import wibble.{old}
+
+pub fn main() {
+  old(1)
+}
+"#
+    );
+}
+
+#[test]
+fn reference_without_call() {
+    assert_replace!(
+        ("wibble", r#"
+@deprecated("Use new instead", replace: "new")
+pub fn old(a) { a }
+pub fn new(a) { a }
+"#);
+        r#"import wibble
+
+pub fn main() {
+  let f = wibble.old
+  f(1)
+}
+"#
+    );
+}
+
+#[test]
+fn reordered_arguments() {
+    assert_replace!(
+        ("wibble", r#"
+@deprecated("Use new instead", replace: "new(_2, _1)")
+pub fn old(a: Int, b: String) { #(a, b) }
+pub fn new(b: String, a: Int) { #(a, b) }
+"#);
+        r#"import wibble
+
+pub fn main() {
+  wibble.old(1, "a")
+}
+"#
+    );
+}
+
+#[test]
+fn reordered_labelled_arguments() {
+    assert_replace!(
+        ("wibble", r#"
+@deprecated("Use new instead", replace: "new(_2, _1)")
+pub fn old(a a: Int, b b: String) { #(a, b) }
+pub fn new(b: String, a: Int) { #(a, b) }
+"#);
+        r#"import wibble
+
+pub fn main() {
+  wibble.old(b: "a", a: 1)
+}
+"#
+    );
+}
+
+#[test]
+fn dropped_argument() {
+    assert_replace!(
+        ("wibble", r#"
+@deprecated("Use new instead", replace: "new(_1)")
+pub fn old(a: Int, _b: Int) { a }
+pub fn new(a: Int) { a }
+"#);
+        r#"import wibble
+
+pub fn main() {
+  wibble.old(1, 2)
+}
+"#
+    );
+}
+
+#[test]
+fn nested_calls() {
+    assert_replace!(
+        ("wibble", r#"
+@deprecated("Use new instead", replace: "new(_2, _1)")
+pub fn old(a: Int, b: Int) { a + b }
+pub fn new(b: Int, a: Int) { a + b }
+"#);
+        r#"import wibble
+
+pub fn main() {
+  wibble.old(wibble.old(1, 2), 3)
+}
+"#
+    );
+}
+
+#[test]
+fn replacement_in_other_module() {
+    assert_replace!(
+        ("wobble", "pub fn new(b: Int, a: Int) { a + b }"),
+        ("wibble", r#"
+@deprecated("Use wobble.new instead", replace: "wobble.new(_2, _1)")
+pub fn old(a: Int, b: Int) { a + b }
+"#);
+        r#"import wibble
+
+pub fn main() {
+  wibble.old(1, 2)
+}
+"#
+    );
+}
+
+#[test]
+fn replacement_in_imported_module() {
+    assert_replace!(
+        ("wobble/wubble", "pub fn new(b: Int, a: Int) { a + b }"),
+        ("wibble", r#"
+@deprecated("Use wubble.new instead", replace: "wobble/wubble.new(_2, _1)")
+pub fn old(a: Int, b: Int) { a + b }
+"#);
+        r#"import wibble
+import wobble/wubble as w
+
+pub fn main() {
+  w.new(wibble.old(1, 2), 3)
+}
+"#
+    );
+}
+
+#[test]
+fn new_import_without_other_imports() {
+    assert_replace!(
+        ("wobble", "pub fn new(a: Int) { a }"),
+        ("wibble", r#"
+@deprecated("Use wobble.new instead", replace: "wobble.new")
+pub fn old(a: Int) { a }
+"#);
+        r#"import wibble.{old}
+
+pub fn main() {
+  old(1)
+}
+"#
+    );
+}
+
+#[test]
+fn pipe_with_reordered_arguments_is_left_alone() {
+    assert_replace!(
+        ("wibble", r#"
+@deprecated("Use new instead", replace: "new(_2, _1)")
+pub fn old(a: Int, b: Int) { a + b }
+pub fn new(b: Int, a: Int) { a + b }
+"#);
+        r#"import wibble
+
+pub fn main() {
+  1 |> wibble.old(2)
+}
+"#
+    );
+}
+
+#[test]
+fn pipe_with_same_arguments() {
+    assert_replace!(
+        ("wibble", r#"
+@deprecated("Use new instead", replace: "new(_1, _2)")
+pub fn old(a: Int, b: Int) { a + b }
+pub fn new(a: Int, b: Int) { a + b }
+"#);
+        r#"import wibble
+
+pub fn main() {
+  1 |> wibble.old(2)
+}
+"#
+    );
+}
+
+#[test]
+fn deprecation_without_replacement_is_left_alone() {
+    assert_replace!(
+        ("wibble", r#"
+@deprecated("Use new instead")
+pub fn old(a) { a }
+"#);
+        r#"import wibble
+
+pub fn main() {
+  wibble.old(1)
+}
+"#
+    );
+}
+
+#[test]
+fn deprecated_constant() {
+    assert_replace!(
+        ("wibble", r#"
+@deprecated("Use new instead", replace: "new")
+pub const old = 1
+pub const new = 1
+"#);
+        r#"import wibble
+
+pub fn main() {
+  wibble.old
+}
+"#
+    );
+}
+
+#[test]
+fn unqualified_import_kept_when_still_used() {
+    assert_replace!(
+        ("wibble", r#"
+@deprecated("Use new instead", replace: "new(_2, _1)")
+pub fn old(a: Int, b: Int) { a + b }
+pub fn new(b: Int, a: Int) { a + b }
+"#);
+        r#"import wibble.{old}
+
+pub fn main() {
+  old(1, 2)
+  1 |> old(2)
+}
+"#
+    );
+}
+
+#[test]
+fn unqualified_import_removed_among_others() {
+    assert_replace!(
+        ("wibble", r#"
+pub type Wibble
+@deprecated("Use new instead", replace: "new")
+pub fn old(a: Int) { a }
+pub fn new(a: Int) { a }
+pub fn other() { 1 }
+"#);
+        r#"import wibble.{type Wibble, old, other}
+
+pub fn main() {
+  old(other())
+}
+"#
+    );
+}
+
+#[test]
+fn last_unqualified_import_removed() {
+    assert_replace!(
+        ("wibble", r#"
+@deprecated("Use new instead", replace: "new")
+pub fn old(a: Int) { a }
+pub fn new(a: Int) { a }
+pub fn other() { 1 }
+"#);
+        r#"import wibble.{other, old}
+
+pub fn main() {
+  old(other())
+}
+"#
+    );
+}
+
+#[test]
+fn aliased_unqualified_import_removed() {
+    assert_replace!(
+        ("wibble", r#"
+@deprecated("Use new instead", replace: "new")
+pub fn old(a: Int) { a }
+pub fn new(a: Int) { a }
+"#);
+        r#"import wibble.{old as wobble}
+
+pub fn main() {
+  wobble(1)
+}
+"#
+    );
+}
+
+#[test]
+fn unqualified_import_of_module_used_qualified() {
+    assert_replace!(
+        ("wobble", "pub fn new(a: Int) { a }"),
+        ("wibble", r#"
+@deprecated("Use wobble.new instead", replace: "wobble.new")
+pub fn old(a: Int) { a }
+pub fn other() { 1 }
+"#);
+        r#"import wibble.{old}
+
+pub fn main() {
+  old(wibble.other())
+}
+"#
+    );
+}
+
+#[test]
+fn new_import_with_taken_name() {
+    assert_replace!(
+        ("wobble/wubble", "pub fn new(a: Int) { a }"),
+        ("wibble", r#"
+@deprecated("Use wubble.new instead", replace: "wobble/wubble.new")
+pub fn old(a: Int) { a }
+"#);
+        r#"import wibble
+
+pub fn main() {
+  let wubble = 1
+  wibble.old(wubble)
+}
+"#
+    );
+}
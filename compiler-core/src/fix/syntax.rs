@@ -0,0 +1,113 @@
+//! Rewriting the deprecated syntax the parser warns about to its current
+//! form.
+//!
+
+#[cfg(test)]
+mod tests;
+
+use std::rc::Rc;
+
+use camino::Utf8PathBuf;
+
+use crate::{
+    ast::SrcSpan,
+    build::Target,
+    warning::{DeprecatedSyntaxWarning, VectorWarningEmitterIO, Warning, WarningEmitter},
+};
+
+use super::{Edit, Fix};
+
+/// All the edits replacing the deprecated syntax used in the given source
+/// code, along with the fix each of them belongs to. Code that can't be
+/// parsed has nothing to fix.
+///
+pub fn deprecated_syntax_edits(src: &str) -> Vec<(Fix, Edit)> {
+    let warnings = Rc::new(VectorWarningEmitterIO::new());
+    let emitter = WarningEmitter::new(warnings.clone());
+    let Ok(parsed) = crate::parse::parse_module(Utf8PathBuf::new(), src, &emitter) else {
+        return vec![];
+    };
+    let comments = &parsed.extra.comments;
+
+    warnings
+        .take()
+        .into_iter()
+        .filter_map(|warning| match warning {
+            Warning::DeprecatedSyntax { warning, .. } => edit(src, comments, warning),
+            _ => None,
+        })
+        .collect()
+}
+
+fn edit(src: &str, comments: &[SrcSpan], warning: DeprecatedSyntaxWarning) -> Option<(Fix, Edit)> {
+    match warning {
+        DeprecatedSyntaxWarning::DeprecatedListPrepend { location } => {
+            Some((Fix::ListPrepend, missing_comma(src, comments, location)?))
+        }
+        DeprecatedSyntaxWarning::DeprecatedListPattern { location } => {
+            Some((Fix::ListPattern, missing_comma(src, comments, location)?))
+        }
+        DeprecatedSyntaxWarning::DeprecatedRecordSpreadPattern { location } => Some((
+            Fix::RecordSpreadPattern,
+            missing_comma(src, comments, location)?,
+        )),
+        DeprecatedSyntaxWarning::DeprecatedListCatchAllPattern { location } => Some((
+            Fix::ListCatchAllPattern,
+            Edit {
+                location,
+                new_text: "_".into(),
+            },
+        )),
+        DeprecatedSyntaxWarning::DeprecatedTargetShorthand { target, location } => {
+            let new_text = match target {
+                Target::Erlang => "erlang",
+                Target::JavaScript => "javascript",
+            };
+            Some((
+                Fix::TargetShorthand,
+                Edit {
+                    location,
+                    new_text: new_text.into(),
+                },
+            ))
+        }
+    }
+}
+
+/// The edit adding the missing comma before the `..` at the given location.
+///
+fn missing_comma(src: &str, comments: &[SrcSpan], dot_dot: SrcSpan) -> Option<Edit> {
+    let before = src.get(..dot_dot.start as usize)?;
+
+    // The comma goes right after the element before the `..`, skipping the
+    // comments that could be between them.
+    let mut end = before.trim_end().len() as u32;
+    while let Some(comment) = comments
+        .iter()
+        .find(|comment| comment.start <= end && end <= comment.end)
+    {
+        // Comment spans don't include the `//` they start with.
+        let code = before.get(..comment.start.checked_sub(2)? as usize)?;
+        end = code.trim_end().len() as u32;
+    }
+
+    let code = before.get(..end as usize)?;
+    if code.ends_with(['[', '(', ',']) {
+        return None;
+    }
+
+    // If the `..` is on the same line as the element the space between them is
+    // replaced, otherwise the comma is added at the end of the element's line.
+    let whitespace = before.get(code.len()..)?;
+    if whitespace.contains('\n') {
+        Some(Edit {
+            location: SrcSpan::new(end, end),
+            new_text: ",".into(),
+        })
+    } else {
+        Some(Edit {
+            location: SrcSpan::new(end, dot_dot.start),
+            new_text: ", ".into(),
+        })
+    }
+}
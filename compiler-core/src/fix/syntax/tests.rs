@@ -0,0 +1,150 @@
+use crate::fix::{apply_edits, Fix};
+
+macro_rules! assert_fix {
+    ($src:expr $(,)?) => {
+        let output = $crate::fix::syntax::tests::fix($src);
+        insta::assert_snapshot!(insta::internals::AutoName, output, $src);
+    };
+}
+
+fn fix(src: &str) -> String {
+    let edits = super::deprecated_syntax_edits(src)
+        .into_iter()
+        .map(|(_, edit)| edit)
+        .collect::<Vec<_>>();
+    let output = apply_edits(src, &edits);
+    format!("----- BEFORE\n{src}\n\n----- AFTER\n{output}")
+}
+
+fn fixes(src: &str) -> Vec<Fix> {
+    super::deprecated_syntax_edits(src)
+        .into_iter()
+        .map(|(fix, _)| fix)
+        .collect()
+}
+
+#[test]
+fn list_prepend() {
+    assert_fix!(
+        r#"pub fn main(x, y, xs) {
+  [x, y..xs]
+}
+"#
+    );
+}
+
+#[test]
+fn list_prepend_with_space() {
+    assert_fix!(
+        r#"pub fn main(x, xs) {
+  [x ..xs]
+}
+"#
+    );
+}
+
+#[test]
+fn list_prepend_on_its_own_line() {
+    assert_fix!(
+        r#"pub fn main(xs) {
+  [
+    1
+    // the rest
+    ..xs
+  ]
+}
+"#
+    );
+}
+
+#[test]
+fn list_prepend_after_a_comment_on_the_same_line() {
+    assert_fix!(
+        r#"pub fn main(xs) {
+  [
+    1 // one
+    ..xs
+  ]
+}
+"#
+    );
+}
+
+#[test]
+fn list_pattern() {
+    assert_fix!(
+        r#"pub fn main(xs) {
+  case xs {
+    [first..rest] -> rest
+    [] -> []
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn list_catch_all_pattern() {
+    assert_fix!(
+        r#"pub fn main(xs) {
+  case xs {
+    [..] -> 1
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn record_spread_pattern() {
+    assert_fix!(
+        r#"pub type Wibble {
+  Wibble(a: Int, b: Int)
+}
+
+pub fn main(wibble) {
+  let Wibble(a: a ..) = wibble
+  a
+}
+"#
+    );
+}
+
+#[test]
+fn target_shorthands() {
+    assert_fix!(
+        r#"@target(erl)
+pub fn wibble() { 1 }
+
+@target(js)
+pub fn wibble() { 2 }
+"#
+    );
+}
+
+#[test]
+fn fixes_are_named() {
+    assert_eq!(
+        fixes(
+            r#"@target(erl)
+pub fn main(x, xs) {
+  case [x..xs] {
+    [a ..] -> a
+    [..] -> 1
+  }
+}
+"#
+        ),
+        vec![
+            Fix::TargetShorthand,
+            Fix::ListPrepend,
+            Fix::ListPattern,
+            Fix::ListCatchAllPattern,
+        ]
+    );
+}
+
+#[test]
+fn nothing_to_fix_in_invalid_code() {
+    assert_eq!(fixes("pub fn main( { [1..xs] }"), vec![]);
+}
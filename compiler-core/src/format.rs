@@ -1,6 +1,8 @@
 #[cfg(test)]
 mod tests;
 
+pub mod imports;
+
 use crate::{
     ast::{
         CustomType, Import, ModuleConstant, TypeAlias, TypeAstConstructor, TypeAstFn, TypeAstHole,
@@ -10,7 +12,7 @@ use crate::{
     docvec,
     io::Utf8Writer,
     parse::extra::{Comment, ModuleExtra},
-    parse::SpannedString,
+    parse::{Parsed, SpannedString},
     pretty::{self, *},
     type_::{self, Type},
     warning::WarningEmitter,
@@ -27,18 +29,23 @@ use camino::Utf8Path;
 const INDENT: isize = 2;
 
 pub fn pretty(writer: &mut impl Utf8Writer, src: &EcoString, path: &Utf8Path) -> Result<()> {
-    let parsed = crate::parse::parse_module(path.to_owned(), src, &WarningEmitter::null())
-        .map_err(|error| Error::Parse {
-            path: path.to_path_buf(),
-            src: src.clone(),
-            error,
-        })?;
+    let parsed = parse(src, path)?;
     let intermediate = Intermediate::from_extra(&parsed.extra, src);
     Formatter::with_comments(&intermediate)
         .module(&parsed.module)
         .pretty_print(80, writer)
 }
 
+fn parse(src: &EcoString, path: &Utf8Path) -> Result<Parsed> {
+    crate::parse::parse_module(path.to_owned(), src, &WarningEmitter::null()).map_err(|error| {
+        Error::Parse {
+            path: path.to_path_buf(),
+            src: src.clone(),
+            error,
+        }
+    })
+}
+
 pub(crate) struct Intermediate<'a> {
     comments: Vec<Comment<'a>>,
     doc_comments: Vec<Comment<'a>>,
@@ -181,6 +188,7 @@ impl<'comments> Formatter<'comments> {
 
     fn targeted_definition<'a>(&mut self, definition: &'a TargetedDefinition) -> Document<'a> {
         let target = definition.target;
+        let allowed_warnings = &definition.allowed_warnings;
         let definition = &definition.definition;
         let start = definition.location().start;
 
@@ -192,6 +200,17 @@ impl<'comments> Formatter<'comments> {
             Some(Target::Erlang) => docvec!["@target(erlang)", line(), document],
             Some(Target::JavaScript) => docvec!["@target(javascript)", line(), document],
         };
+        let document = if allowed_warnings.is_empty() {
+            document
+        } else {
+            docvec![
+                "@allow(",
+                EcoString::from(allowed_warnings.iter().join(", ")),
+                ")",
+                line(),
+                document
+            ]
+        };
 
         comments.to_doc().append(document.group())
     }
@@ -330,6 +349,17 @@ impl<'comments> Formatter<'comments> {
         Itertools::intersperse(import_groups_docs.into_iter(), lines(2)).collect_vec()
     }
 
+    /// Prints only the given imports, along with any comment left after the
+    /// last one of them.
+    ///
+    fn imports_only<'a>(&mut self, imports: &'a [TargetedDefinition]) -> Document<'a> {
+        let imports = self.imports(imports.iter().collect_vec());
+        match printed_comments(self.pop_comments(u32::MAX), false) {
+            Some(comments) => docvec![imports, line(), comments],
+            None => imports.to_doc(),
+        }
+    }
+
     /// Prints the imports as a single sorted group of import statements.
     ///
     fn sorted_import_group<'a>(&mut self, imports: &[&'a TargetedDefinition]) -> Document<'a> {
@@ -3072,8 +3102,22 @@ impl<'a> Documentable<'a> for AttributesPrinter<'a> {
         let mut attributes = vec![];
 
         // @deprecated attribute
-        if let Deprecation::Deprecated { message } = self.deprecation {
-            attributes.push(docvec!["@deprecated(\"", message, "\")"])
+        match self.deprecation {
+            Deprecation::NotDeprecated => {}
+            Deprecation::Deprecated {
+                message,
+                replacement: None,
+            } => attributes.push(docvec!["@deprecated(\"", message, "\")"]),
+            Deprecation::Deprecated {
+                message,
+                replacement: Some(replacement),
+            } => attributes.push(docvec![
+                "@deprecated(\"",
+                message,
+                "\", replace: \"",
+                replacement,
+                "\")"
+            ]),
         };
 
         // @external attributes
@@ -0,0 +1,226 @@
+//! Organizing of a module's imports, as done by `gleam format
+//! --organize-imports` and by the language server's "Organize imports" code
+//! action.
+//!
+//! The formatter already takes care of sorting modules and unqualified items,
+//! here we merge duplicate imports and remove the unused ones.
+
+use std::collections::{HashMap, HashSet};
+
+use camino::Utf8Path;
+use ecow::EcoString;
+use itertools::Itertools;
+
+use crate::{
+    ast::{AssignName, Definition, Import, SrcSpan, TargetedDefinition, UntypedModule},
+    build::Target,
+    io::Utf8Writer,
+    parse::{extra::ModuleExtra, lexer, token::Token},
+    Result,
+};
+
+use super::{Formatter, Intermediate};
+
+/// Pretty prints a module like `format::pretty` would, organizing its imports
+/// first. See [`organize_imports`] for the meaning of `unused`.
+///
+pub fn pretty(
+    writer: &mut impl Utf8Writer,
+    src: &EcoString,
+    path: &Utf8Path,
+    unused: &HashSet<SrcSpan>,
+) -> Result<()> {
+    let mut parsed = super::parse(src, path)?;
+    organize_imports(src, &mut parsed.module, unused);
+    let intermediate = Intermediate::from_extra(&parsed.extra, src);
+    Formatter::with_comments(&intermediate)
+        .module(&parsed.module)
+        .pretty_print(80, writer)
+}
+
+/// Rewrites the imports of a module:
+/// - Imports of the same module with the same alias are merged into the first
+///   one of them.
+/// - Imports, aliases and unqualified values and types whose location is in
+///   `unused` are removed. These locations usually come from the unused import
+///   warnings the type checker produced for this same source.
+/// - If all the unqualified items of an import are removed and the module is
+///   never used in a qualified way, the whole import is removed.
+///
+/// Both modules and unqualified items are then sorted by the formatter when
+/// printing the module.
+///
+pub fn organize_imports(src: &str, module: &mut UntypedModule, unused: &HashSet<SrcSpan>) {
+    let import_locations = module
+        .definitions
+        .iter()
+        .filter(|definition| definition.definition.is_import())
+        .map(|definition| definition.definition.location())
+        .collect_vec();
+    let qualifiers = qualifying_names(src, &import_locations);
+
+    let mut definitions: Vec<TargetedDefinition> = Vec::with_capacity(module.definitions.len());
+    let mut merged_imports: HashMap<ImportKey, usize> = HashMap::new();
+
+    for definition in std::mem::take(&mut module.definitions) {
+        let TargetedDefinition {
+            definition: Definition::Import(mut import),
+            target,
+            allowed_warnings,
+        } = definition
+        else {
+            definitions.push(definition);
+            continue;
+        };
+
+        if unused.contains(&import.location) {
+            continue;
+        }
+
+        let used_name = import.used_name();
+        let had_unqualified_items =
+            !import.unqualified_values.is_empty() || !import.unqualified_types.is_empty();
+        import
+            .unqualified_values
+            .retain(|value| !unused.contains(&value.location));
+        import
+            .unqualified_types
+            .retain(|type_| !unused.contains(&type_.location));
+        if import
+            .alias_location()
+            .is_some_and(|location| unused.contains(&location))
+        {
+            import.as_name = None;
+        }
+
+        // If all the unqualified items were unused the module might have been
+        // imported just for those, in which case it can go as well.
+        let lost_all_unqualified_items = had_unqualified_items
+            && import.unqualified_values.is_empty()
+            && import.unqualified_types.is_empty();
+        if lost_all_unqualified_items && !used_name.is_some_and(|name| qualifiers.contains(&name)) {
+            continue;
+        }
+
+        let key = ImportKey::new(&import, target);
+        match merged_imports.get(&key) {
+            Some(index) => match definitions.get_mut(*index) {
+                Some(TargetedDefinition {
+                    definition: Definition::Import(merged),
+                    ..
+                }) => merge_into(merged, import),
+                _ => panic!("merged import index pointing to a non import definition"),
+            },
+            None => {
+                let _ = merged_imports.insert(key, definitions.len());
+                definitions.push(TargetedDefinition {
+                    definition: Definition::Import(import),
+                    target,
+                    allowed_warnings,
+                });
+            }
+        }
+    }
+
+    module.definitions = definitions;
+}
+
+/// Prints the given imports sorted and grouped like the formatter would, only
+/// taking into account the comments and empty lines that appear in `span`.
+///
+/// This is used to rewrite just the imports of a module without touching the
+/// rest of its source code.
+///
+pub(crate) fn pretty_imports(
+    src: &EcoString,
+    extra: &ModuleExtra,
+    imports: &[TargetedDefinition],
+    span: SrcSpan,
+) -> String {
+    let mut intermediate = Intermediate::from_extra(extra, src);
+    intermediate
+        .comments
+        .retain(|comment| span.contains(comment.start));
+    intermediate.doc_comments.clear();
+    intermediate.module_comments.clear();
+    let from = intermediate
+        .empty_lines
+        .partition_point(|line| *line < span.start);
+    let to = intermediate
+        .empty_lines
+        .partition_point(|line| *line <= span.end);
+    intermediate.empty_lines = intermediate.empty_lines.get(from..to).unwrap_or_default();
+
+    Formatter::with_comments(&intermediate)
+        .imports_only(imports)
+        .to_pretty_string(80)
+}
+
+/// Two imports can be merged together if they import the same module with the
+/// same alias, for the same target.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct ImportKey {
+    module: EcoString,
+    alias: Option<EcoString>,
+    target: Option<Target>,
+}
+
+impl ImportKey {
+    fn new(import: &Import<()>, target: Option<Target>) -> Self {
+        Self {
+            module: import.module.clone(),
+            alias: import.as_name.as_ref().map(|(name, _)| match name {
+                AssignName::Variable(name) => name.clone(),
+                AssignName::Discard(name) => EcoString::from("_") + name.as_str(),
+            }),
+            target,
+        }
+    }
+}
+
+fn merge_into(import: &mut Import<()>, other: Import<()>) {
+    for value in other.unqualified_values {
+        let already_imported = import
+            .unqualified_values
+            .iter()
+            .any(|existing| existing.name == value.name && existing.as_name == value.as_name);
+        if !already_imported {
+            import.unqualified_values.push(value);
+        }
+    }
+
+    for type_ in other.unqualified_types {
+        let already_imported = import
+            .unqualified_types
+            .iter()
+            .any(|existing| existing.name == type_.name && existing.as_name == type_.as_name);
+        if !already_imported {
+            import.unqualified_types.push(type_);
+        }
+    }
+}
+
+/// Returns all the names that are used to qualify something outside of the
+/// given imports: for example `list` in `list.map` or in `list.List(a)`.
+///
+/// Since this only looks at tokens it might find names that are not modules
+/// (like a record `user.name`), that's fine as it is only used to err on the
+/// side of caution and keep imports around.
+///
+fn qualifying_names(src: &str, imports: &[SrcSpan]) -> HashSet<EcoString> {
+    lexer::make_tokenizer(src)
+        .map_while(|token| token.ok())
+        .tuple_windows()
+        .filter_map(
+            |((start, first, _), (_, second, _))| match (first, second) {
+                (Token::Name { name }, Token::Dot)
+                    if !imports.iter().any(|import| import.contains(start)) =>
+                {
+                    Some(name)
+                }
+                _ => None,
+            },
+        )
+        .collect()
+}
@@ -5177,6 +5177,65 @@ pub type Tiger =
     );
 }
 
+#[test]
+fn deprecated_function_with_replacement() {
+    assert_format!(
+        r#"@deprecated("Use new instead", replace: "wibble.new(_2, _1)")
+pub fn old(a, b) {
+  Nil
+}
+"#
+    );
+}
+
+#[test]
+fn deprecated_function_with_replacement_trailing_comma() {
+    assert_format_rewrite!(
+        r#"@deprecated("Use new instead", replace: "new",)
+pub fn old() {
+  Nil
+}
+"#,
+        r#"@deprecated("Use new instead", replace: "new")
+pub fn old() {
+  Nil
+}
+"#
+    );
+}
+
+#[test]
+fn allow_attribute() {
+    assert_format!(
+        r#"@allow(unused_variable, todo)
+pub fn main() {
+  let x = 1
+  todo
+}
+"#
+    );
+}
+
+#[test]
+fn allow_attribute_with_target() {
+    assert_format_rewrite!(
+        r#"@target(erlang)
+@allow(todo,)
+@deprecated("Use new instead")
+pub fn main() {
+  todo
+}
+"#,
+        r#"@allow(todo)
+@target(erlang)
+@deprecated("Use new instead")
+pub fn main() {
+  todo
+}
+"#
+    );
+}
+
 // https://github.com/gleam-lang/gleam/issues/2423
 #[test]
 fn prefix_as() {
@@ -218,3 +218,72 @@ import gleam/list
 "#
     );
 }
+
+macro_rules! assert_organize_imports_rewrite {
+    ($src:expr, $expected:expr  $(,)?) => {
+        let mut writer = String::new();
+        $crate::format::imports::pretty(
+            &mut writer,
+            &$src.into(),
+            camino::Utf8Path::new("<stdin>"),
+            &std::collections::HashSet::new(),
+        )
+        .unwrap();
+        assert_eq!(writer, $expected);
+    };
+}
+
+#[test]
+fn organize_imports_merges_duplicate_imports() {
+    assert_organize_imports_rewrite!(
+        "import wibble.{b}
+import wobble
+import wibble.{type B, a}
+import wibble.{a}
+",
+        "import wibble.{type B, a, b}
+import wobble
+"
+    );
+}
+
+#[test]
+fn organize_imports_does_not_merge_imports_with_different_aliases() {
+    assert_organize_imports_rewrite!(
+        "import wibble.{b} as wobble
+import wibble.{a}
+",
+        "import wibble.{b} as wobble
+import wibble.{a}
+"
+    );
+}
+
+#[test]
+fn organize_imports_merges_imports_across_groups() {
+    assert_organize_imports_rewrite!(
+        "import wibble.{b}
+
+// Comment
+import wobble
+import wibble.{a}
+
+pub fn main() {
+  Nil
+}
+
+import wubble
+",
+        "import wibble.{a, b}
+
+// Comment
+import wobble
+
+pub fn main() {
+  Nil
+}
+
+import wubble
+"
+    );
+}
@@ -1,3 +1,6 @@
+#[cfg(test)]
+mod tests;
+
 use camino::Utf8Path;
 use debug_ignore::DebugIgnore;
 use flate2::read::GzDecoder;
@@ -9,6 +12,7 @@ use crate::{
     io::{FileSystemReader, FileSystemWriter, HttpClient, TarUnpacker},
     manifest::{ManifestPackage, ManifestPackageSource},
     paths::{self, ProjectPaths},
+    version::COMPILER_VERSION,
     Error, Result,
 };
 
@@ -23,8 +27,11 @@ J1i2xWFndWa6nfFnRxZmCStCOZWYYPlaxr+FZceFbpMwzTNs4g3d4tLNUcbKAIH4
 -----END PUBLIC KEY-----
 ";
 
-fn key_name(hostname: &str) -> String {
-    format!("gleam-{hostname}")
+/// The name a Hex API key created for this computer has in the Hex API, given
+/// the name it's stored with.
+///
+pub fn key_name(stored_name: &str) -> String {
+    format!("gleam-{stored_name}")
 }
 
 pub async fn publish_package<Http: HttpClient>(
@@ -114,18 +121,221 @@ pub async fn create_api_key<Http: HttpClient>(
     hexpm::create_api_key_response(response).map_err(Error::hex)
 }
 
+/// Revoke the Hex API key with the given name, which can be any key of the
+/// user rather than only the one created for this computer.
+///
 pub async fn remove_api_key<Http: HttpClient>(
-    hostname: &str,
+    name: &str,
     config: &hexpm::Config,
     auth_key: &str,
     http: &Http,
 ) -> Result<()> {
-    tracing::info!("Deleting API key from Hex");
-    let request = hexpm::remove_api_key_request(&key_name(hostname), auth_key, config);
+    tracing::info!(name = name, "removing_hex_api_key");
+    let request = hexpm::remove_api_key_request(name, auth_key, config);
     let response = http.send(request).await?;
     hexpm::remove_api_key_response(response).map_err(Error::hex)
 }
 
+/// A Hex API key of the user, without its secret.
+///
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+pub struct ApiKey {
+    pub name: String,
+    #[serde(default)]
+    pub permissions: Vec<ApiKeyPermission>,
+    /// When the key was created, as an ISO 8601 timestamp.
+    #[serde(default)]
+    pub inserted_at: Option<String>,
+    #[serde(default)]
+    pub last_use: Option<ApiKeyUse>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+pub struct ApiKeyPermission {
+    pub domain: String,
+    #[serde(default)]
+    pub resource: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+pub struct ApiKeyUse {
+    #[serde(default)]
+    pub used_at: Option<String>,
+}
+
+pub async fn list_api_keys<Http: HttpClient>(
+    config: &hexpm::Config,
+    api_key: &str,
+    http: &Http,
+) -> Result<Vec<ApiKey>> {
+    tracing::info!("listing_hex_api_keys");
+    let request = api_request(config, http::Method::GET, &["keys"], api_key, vec![])?;
+    let response = http.send(request).await?;
+    json_response(response)
+}
+
+/// How much a package owner can do: maintainers can publish and retire
+/// releases, while full owners can also manage the other owners.
+///
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    strum::Display,
+    strum::EnumString,
+    strum::VariantNames,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+#[strum(serialize_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum OwnerLevel {
+    Maintainer,
+    Full,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+pub struct PackageOwner {
+    pub username: String,
+    #[serde(default)]
+    pub email: Option<String>,
+    pub level: OwnerLevel,
+}
+
+pub async fn list_package_owners<Http: HttpClient>(
+    package: &str,
+    config: &hexpm::Config,
+    api_key: &str,
+    http: &Http,
+) -> Result<Vec<PackageOwner>> {
+    tracing::info!(package = package, "listing_hex_package_owners");
+    let path = ["packages", package, "owners"];
+    let request = api_request(config, http::Method::GET, &path, api_key, vec![])?;
+    let response = http.send(request).await?;
+    json_response(response)
+}
+
+/// Add an owner to a package, given by their username or email address. If
+/// they are already an owner their level is changed.
+///
+pub async fn add_package_owner<Http: HttpClient>(
+    package: &str,
+    owner: &str,
+    level: OwnerLevel,
+    config: &hexpm::Config,
+    api_key: &str,
+    http: &Http,
+) -> Result<()> {
+    tracing::info!(package = package, owner = owner, "adding_hex_package_owner");
+    let path = ["packages", package, "owners", owner];
+    let body = serde_json::json!({ "level": level, "transfer": false });
+    let request = api_request(
+        config,
+        http::Method::PUT,
+        &path,
+        api_key,
+        body.to_string().into_bytes(),
+    )?;
+    let response = http.send(request).await?;
+    empty_response(response)
+}
+
+pub async fn remove_package_owner<Http: HttpClient>(
+    package: &str,
+    owner: &str,
+    config: &hexpm::Config,
+    api_key: &str,
+    http: &Http,
+) -> Result<()> {
+    tracing::info!(
+        package = package,
+        owner = owner,
+        "removing_hex_package_owner"
+    );
+    let path = ["packages", package, "owners", owner];
+    let request = api_request(config, http::Method::DELETE, &path, api_key, vec![])?;
+    let response = http.send(request).await?;
+    empty_response(response)
+}
+
+/// The characters of a path segment that are percent-encoded: all but the
+/// unreserved ones and `@`, which is allowed in a path and is part of email
+/// addresses.
+///
+const PATH_SEGMENT: &percent_encoding::AsciiSet = &percent_encoding::NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~')
+    .remove(b'@');
+
+/// The `hexpm` crate doesn't have requests for managing owners and keys, so
+/// they are made here in the same way. Each segment of the path is
+/// percent-encoded, as they can come from the command line.
+///
+fn api_request(
+    config: &hexpm::Config,
+    method: http::Method,
+    path: &[&str],
+    api_key: &str,
+    body: Vec<u8>,
+) -> Result<http::Request<Vec<u8>>> {
+    let mut parts = config.api_base.clone().into_parts();
+    let base_path = parts
+        .path_and_query
+        .as_ref()
+        .map(|path| path.path())
+        .unwrap_or("/");
+    let path = path
+        .iter()
+        .map(|segment| percent_encoding::utf8_percent_encode(segment, PATH_SEGMENT).to_string())
+        .collect::<Vec<_>>()
+        .join("/");
+    parts.path_and_query = Some(
+        format!("{base_path}{path}")
+            .try_into()
+            .map_err(Error::http)?,
+    );
+    http::Request::builder()
+        .method(method)
+        .uri(http::Uri::from_parts(parts).map_err(Error::http)?)
+        .header("user-agent", format!("gleam/{COMPILER_VERSION}"))
+        .header("content-type", "application/json")
+        .header("accept", "application/json")
+        .header("authorization", api_key)
+        .body(body)
+        .map_err(Error::http)
+}
+
+fn json_response<T: serde::de::DeserializeOwned>(response: http::Response<Vec<u8>>) -> Result<T> {
+    let (parts, body) = response.into_parts();
+    if parts.status == http::StatusCode::OK {
+        serde_json::from_slice(&body).map_err(|error| Error::hex(ApiError::Json(error)))
+    } else {
+        Err(Error::hex(api_error(parts.status, body)))
+    }
+}
+
+fn empty_response(response: http::Response<Vec<u8>>) -> Result<()> {
+    let (parts, body) = response.into_parts();
+    match parts.status {
+        http::StatusCode::OK | http::StatusCode::NO_CONTENT => Ok(()),
+        status => Err(Error::hex(api_error(status, body))),
+    }
+}
+
+fn api_error(status: http::StatusCode, body: Vec<u8>) -> ApiError {
+    match status {
+        http::StatusCode::NOT_FOUND => ApiError::NotFound,
+        http::StatusCode::TOO_MANY_REQUESTS => ApiError::RateLimited,
+        http::StatusCode::UNAUTHORIZED => ApiError::InvalidApiKey,
+        http::StatusCode::FORBIDDEN => ApiError::Forbidden,
+        status => ApiError::UnexpectedResponse(status, String::from_utf8_lossy(&body).into()),
+    }
+}
+
 #[derive(Debug)]
 pub struct Downloader {
     fs_reader: DebugIgnore<Box<dyn FileSystemReader>>,
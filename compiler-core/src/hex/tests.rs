@@ -0,0 +1,285 @@
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use futures::executor::block_on;
+
+use super::{
+    add_package_owner, list_api_keys, list_package_owners, remove_api_key, remove_package_owner,
+    ApiKey, ApiKeyPermission, ApiKeyUse, OwnerLevel, PackageOwner,
+};
+use crate::{io::HttpClient, Error, Result};
+
+/// Stands in for the Hex API, recording the requests sent to it and replying
+/// to each with the same response.
+///
+struct StandInHex {
+    status: u16,
+    body: &'static str,
+    requests: Mutex<Vec<http::Request<Vec<u8>>>>,
+}
+
+impl StandInHex {
+    fn new(status: u16, body: &'static str) -> Self {
+        Self {
+            status,
+            body,
+            requests: Mutex::new(vec![]),
+        }
+    }
+
+    /// The method, URI, authorization, and body of the one request sent.
+    ///
+    fn request(&self) -> (String, String, String, String) {
+        let requests = self.requests.lock().unwrap();
+        let [request] = requests.as_slice() else {
+            panic!("Expected one request, got {}", requests.len());
+        };
+        let authorization = request
+            .headers()
+            .get("authorization")
+            .map(|value| value.to_str().unwrap().to_string())
+            .unwrap_or_default();
+        (
+            request.method().to_string(),
+            request.uri().to_string(),
+            authorization,
+            String::from_utf8(request.body().clone()).unwrap(),
+        )
+    }
+}
+
+#[async_trait]
+impl HttpClient for StandInHex {
+    async fn send(&self, request: http::Request<Vec<u8>>) -> Result<http::Response<Vec<u8>>> {
+        self.requests.lock().unwrap().push(request);
+        Ok(http::Response::builder()
+            .status(self.status)
+            .body(self.body.as_bytes().to_vec())
+            .unwrap())
+    }
+}
+
+fn config() -> hexpm::Config {
+    hexpm::Config {
+        api_base: http::Uri::from_static("http://localhost:4000/api/"),
+        ..hexpm::Config::new()
+    }
+}
+
+#[test]
+fn list_owners() {
+    let hex = StandInHex::new(
+        200,
+        r#"[
+  {"username": "lpil", "email": "louis@example.com", "level": "full"},
+  {"username": "wibble", "level": "maintainer", "url": "https://hex.pm/api/users/wibble"}
+]"#,
+    );
+    let owners = block_on(list_package_owners("gleam_stdlib", &config(), "key", &hex)).unwrap();
+    assert_eq!(
+        owners,
+        vec![
+            PackageOwner {
+                username: "lpil".into(),
+                email: Some("louis@example.com".into()),
+                level: OwnerLevel::Full,
+            },
+            PackageOwner {
+                username: "wibble".into(),
+                email: None,
+                level: OwnerLevel::Maintainer,
+            },
+        ]
+    );
+    assert_eq!(
+        hex.request(),
+        (
+            "GET".into(),
+            "http://localhost:4000/api/packages/gleam_stdlib/owners".into(),
+            "key".into(),
+            "".into()
+        )
+    );
+}
+
+#[test]
+fn add_owner() {
+    let hex = StandInHex::new(204, "");
+    block_on(add_package_owner(
+        "gleam_stdlib",
+        "wibble@example.com",
+        OwnerLevel::Maintainer,
+        &config(),
+        "key",
+        &hex,
+    ))
+    .unwrap();
+    assert_eq!(
+        hex.request(),
+        (
+            "PUT".into(),
+            "http://localhost:4000/api/packages/gleam_stdlib/owners/wibble@example.com".into(),
+            "key".into(),
+            r#"{"level":"maintainer","transfer":false}"#.into()
+        )
+    );
+}
+
+#[test]
+fn add_owner_with_characters_to_encode() {
+    let hex = StandInHex::new(204, "");
+    block_on(add_package_owner(
+        "gleam_stdlib",
+        "wibble+wobble@example.com",
+        OwnerLevel::Full,
+        &config(),
+        "key",
+        &hex,
+    ))
+    .unwrap();
+    assert_eq!(
+        hex.request().1,
+        "http://localhost:4000/api/packages/gleam_stdlib/owners/wibble%2Bwobble@example.com"
+    );
+}
+
+#[test]
+fn remove_owner_with_characters_to_encode() {
+    let hex = StandInHex::new(204, "");
+    block_on(remove_package_owner(
+        "gleam_stdlib",
+        "Wibble Wobble?#/",
+        &config(),
+        "key",
+        &hex,
+    ))
+    .unwrap();
+    assert_eq!(
+        hex.request().1,
+        "http://localhost:4000/api/packages/gleam_stdlib/owners/Wibble%20Wobble%3F%23%2F"
+    );
+}
+
+#[test]
+fn remove_owner() {
+    let hex = StandInHex::new(204, "");
+    block_on(remove_package_owner(
+        "gleam_stdlib",
+        "wibble",
+        &config(),
+        "key",
+        &hex,
+    ))
+    .unwrap();
+    assert_eq!(
+        hex.request(),
+        (
+            "DELETE".into(),
+            "http://localhost:4000/api/packages/gleam_stdlib/owners/wibble".into(),
+            "key".into(),
+            "".into()
+        )
+    );
+}
+
+#[test]
+fn owners_of_unknown_package() {
+    let hex = StandInHex::new(404, "");
+    assert_eq!(
+        block_on(list_package_owners("wobble", &config(), "key", &hex)),
+        Err(Error::Hex("resource was not found".into()))
+    );
+}
+
+#[test]
+fn owner_changes_not_allowed() {
+    let hex = StandInHex::new(403, "");
+    assert_eq!(
+        block_on(remove_package_owner(
+            "gleam_stdlib",
+            "lpil",
+            &config(),
+            "key",
+            &hex
+        )),
+        Err(Error::Hex(
+            "this account is not authorized for this action".into()
+        ))
+    );
+}
+
+#[test]
+fn list_keys() {
+    let hex = StandInHex::new(
+        200,
+        r#"[
+  {
+    "name": "gleam-laptop",
+    "permissions": [{"domain": "api", "resource": "write"}],
+    "authing_key": true,
+    "inserted_at": "2024-01-02T03:04:05Z",
+    "last_use": {"used_at": "2024-02-03T04:05:06Z", "ip": "127.0.0.1"}
+  },
+  {"name": "ci", "permissions": [{"domain": "repositories"}], "last_use": null}
+]"#,
+    );
+    let keys = block_on(list_api_keys(&config(), "key", &hex)).unwrap();
+    assert_eq!(
+        keys,
+        vec![
+            ApiKey {
+                name: "gleam-laptop".into(),
+                permissions: vec![ApiKeyPermission {
+                    domain: "api".into(),
+                    resource: Some("write".into()),
+                }],
+                inserted_at: Some("2024-01-02T03:04:05Z".into()),
+                last_use: Some(ApiKeyUse {
+                    used_at: Some("2024-02-03T04:05:06Z".into()),
+                }),
+            },
+            ApiKey {
+                name: "ci".into(),
+                permissions: vec![ApiKeyPermission {
+                    domain: "repositories".into(),
+                    resource: None,
+                }],
+                inserted_at: None,
+                last_use: None,
+            },
+        ]
+    );
+    assert_eq!(
+        hex.request(),
+        (
+            "GET".into(),
+            "http://localhost:4000/api/keys".into(),
+            "key".into(),
+            "".into()
+        )
+    );
+}
+
+#[test]
+fn revoke_key() {
+    let hex = StandInHex::new(204, "");
+    block_on(remove_api_key("ci", &config(), "key", &hex)).unwrap();
+    assert_eq!(
+        hex.request(),
+        (
+            "DELETE".into(),
+            "http://localhost:4000/api/keys/ci".into(),
+            "key".into(),
+            "".into()
+        )
+    );
+}
+
+#[test]
+fn invalid_api_key() {
+    let hex = StandInHex::new(401, "");
+    assert_eq!(
+        block_on(list_api_keys(&config(), "key", &hex)),
+        Err(Error::Hex("the given API key was not valid".into()))
+    );
+}
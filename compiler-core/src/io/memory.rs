@@ -149,6 +149,30 @@ impl FileSystemWriter for InMemoryFileSystem {
         panic!("unimplemented") // TODO
     }
 
+    fn rename(&self, from: &Utf8Path, to: &Utf8Path) -> Result<(), Error> {
+        let mut files = self.files.deref().borrow_mut();
+        if !files.contains_key(from) || files.contains_key(to) {
+            return Err(Error::FileIo {
+                kind: FileKind::File,
+                action: FileIoAction::Rename,
+                path: from.to_path_buf(),
+                err: None,
+            });
+        }
+
+        let moved = files
+            .keys()
+            .filter(|path| path.starts_with(from))
+            .cloned()
+            .collect::<Vec<_>>();
+        for path in moved {
+            let file = files.remove(&path).expect("renamed file");
+            let relative = path.strip_prefix(from).expect("renamed file prefix");
+            let _ = files.insert(to.join(relative), file);
+        }
+        Ok(())
+    }
+
     fn mkdir(&self, path: &Utf8Path) -> Result<(), Error> {
         // Traverse ancestors from parent to root.
         // Create each missing ancestor.
@@ -1,27 +1,34 @@
+mod call_hierarchy;
 mod code_action;
 mod compiler;
 mod completer;
 mod edits;
 mod engine;
+mod externals;
 mod feedback;
 mod files;
+mod folding_range;
 mod messages;
 mod progress;
 mod rename;
 mod router;
+mod selection_range;
 mod server;
 mod signature_help;
 
 #[cfg(test)]
 mod tests;
 
-pub use server::LanguageServer;
+pub use engine::{Compilation, LanguageServerEngine, Response};
+pub use files::FileSystemProxy;
+pub use progress::ProgressReporter;
+pub use server::{diagnostic_to_lsp, LanguageServer};
 
 use crate::{
     ast::SrcSpan, build::Target, line_numbers::LineNumbers, manifest::Manifest,
     paths::ProjectPaths, Result,
 };
-use camino::Utf8PathBuf;
+use camino::{Utf8Path, Utf8PathBuf};
 use lsp_types::{Position, Range, TextEdit, Url};
 use std::any::Any;
 
@@ -93,6 +100,20 @@ impl<'a> TextEdits<'a> {
     }
 }
 
+/// Formats a module, returning the edit that replaces its source with the
+/// formatted code.
+///
+pub fn format(src: &str, path: &Utf8Path) -> Result<Vec<TextEdit>> {
+    let mut new_text = String::new();
+    crate::format::pretty(&mut new_text, &src.into(), path)?;
+
+    let line_count = src.lines().count() as u32;
+    Ok(vec![TextEdit {
+        range: Range::new(Position::new(0, 0), Position::new(line_count, 0)),
+        new_text,
+    }])
+}
+
 fn path(uri: &Url) -> Utf8PathBuf {
     // The to_file_path method is available on these platforms
     #[cfg(any(unix, windows, target_os = "redox", target_os = "wasi"))]
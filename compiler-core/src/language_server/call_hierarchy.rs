@@ -0,0 +1,254 @@
+use camino::{Utf8Path, Utf8PathBuf};
+use ecow::EcoString;
+use lsp_types::{CallHierarchyItem, SymbolKind, SymbolTag, Url};
+
+use crate::{
+    ast::{self, visit::Visit, Definition, SrcSpan, TypedExpr, TypedFunction},
+    build::{Located, Module, UnqualifiedImport},
+    type_::{
+        Deprecation, ModuleInterface, ModuleValueConstructor, Type, ValueConstructor,
+        ValueConstructorVariant,
+    },
+};
+
+use super::{compiler::ModuleSourceInformation, src_span_to_lsp_range};
+
+/// A module function, identified by the name of its module and its own name.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionName {
+    pub module: EcoString,
+    pub name: EcoString,
+}
+
+/// A reference to a module function from within the body of another
+/// function. Functions are most often referenced to be called, but they can
+/// also be passed around as values.
+///
+#[derive(Debug)]
+pub struct FunctionReference {
+    pub function: FunctionName,
+    pub location: SrcSpan,
+}
+
+/// Returns the module function at the given node, which can be its
+/// definition or a local, qualified or unqualified reference to it.
+///
+pub fn function_at(
+    module: &Module,
+    located: &Located<'_>,
+    importable_modules: &im::HashMap<EcoString, ModuleInterface>,
+) -> Option<FunctionName> {
+    match located {
+        Located::ModuleStatement(Definition::Function(function)) => Some(FunctionName {
+            module: module.name.clone(),
+            name: function.name.as_ref()?.1.clone(),
+        }),
+
+        Located::Expression(
+            TypedExpr::Var {
+                constructor:
+                    ValueConstructor {
+                        variant: ValueConstructorVariant::ModuleFn { module, name, .. },
+                        ..
+                    },
+                ..
+            }
+            | TypedExpr::ModuleSelect {
+                constructor: ModuleValueConstructor::Fn { module, name, .. },
+                ..
+            },
+        ) => Some(FunctionName {
+            module: module.clone(),
+            name: name.clone(),
+        }),
+
+        Located::UnqualifiedImport(UnqualifiedImport {
+            module,
+            name,
+            is_type: false,
+            ..
+        }) => {
+            let value = importable_modules.get(*module)?.values.get(*name)?;
+            match &value.variant {
+                ValueConstructorVariant::ModuleFn { module, name, .. } => Some(FunctionName {
+                    module: module.clone(),
+                    name: name.clone(),
+                }),
+                _ => None,
+            }
+        }
+
+        _ => None,
+    }
+}
+
+/// Returns the definition of a function in one of the modules of the root
+/// package.
+///
+pub fn find_function<'a>(module: &'a Module, name: &str) -> Option<&'a TypedFunction> {
+    module
+        .ast
+        .definitions
+        .iter()
+        .find_map(|definition| match definition {
+            Definition::Function(function)
+                if function.name.as_ref().is_some_and(|(_, n)| n == name) =>
+            {
+                Some(function)
+            }
+            _ => None,
+        })
+}
+
+/// Builds the call hierarchy item for a function defined in a module of the
+/// root package, using its full definition as the range of the item.
+///
+pub fn function_item(
+    project: &Utf8Path,
+    module: &Module,
+    function: &TypedFunction,
+    source: &ModuleSourceInformation,
+) -> Option<CallHierarchyItem> {
+    let (name_location, name) = function.name.as_ref()?;
+    Some(item(
+        project,
+        FunctionName {
+            module: module.name.clone(),
+            name: name.clone(),
+        },
+        source,
+        function.full_location(),
+        *name_location,
+        &function.deprecation,
+    ))
+}
+
+/// Builds the call hierarchy item for a function of a dependency, of which
+/// only the location of the head is known.
+///
+pub fn dependency_function_item(
+    project: &Utf8Path,
+    function: FunctionName,
+    interface: &ModuleInterface,
+    source: &ModuleSourceInformation,
+) -> Option<CallHierarchyItem> {
+    let value = interface.values.get(&function.name)?;
+    let location = value.definition_location().span;
+    Some(item(
+        project,
+        function,
+        source,
+        location,
+        location,
+        &value.deprecation,
+    ))
+}
+
+fn item(
+    project: &Utf8Path,
+    function: FunctionName,
+    source: &ModuleSourceInformation,
+    location: SrcSpan,
+    name_location: SrcSpan,
+    deprecation: &Deprecation,
+) -> CallHierarchyItem {
+    CallHierarchyItem {
+        name: function.name.to_string(),
+        kind: SymbolKind::FUNCTION,
+        tags: deprecation
+            .is_deprecated()
+            .then(|| vec![SymbolTag::DEPRECATED]),
+        detail: Some(function.module.to_string()),
+        uri: Url::parse(&format!("file:///{}", source.path)).expect("call hierarchy URL parse"),
+        range: src_span_to_lsp_range(location, &source.line_numbers),
+        selection_range: src_span_to_lsp_range(name_location, &source.line_numbers),
+        // The module is kept so the function can be found again when the
+        // client asks for its incoming or outgoing calls, and the project so
+        // that the request goes to it even when the function is defined in
+        // one of its dependencies.
+        data: Some(serde_json::json!({
+            "module": function.module,
+            "project": project,
+        })),
+    }
+}
+
+/// Returns the module function an item given back by the client refers to.
+///
+pub fn item_function(item: &CallHierarchyItem) -> Option<FunctionName> {
+    let module = item.data.as_ref()?.get("module")?.as_str()?;
+    Some(FunctionName {
+        module: module.into(),
+        name: item.name.as_str().into(),
+    })
+}
+
+/// Returns the root of the project an item given back by the client was
+/// created by.
+///
+pub fn item_project(item: &CallHierarchyItem) -> Option<Utf8PathBuf> {
+    let project = item.data.as_ref()?.get("project")?.as_str()?;
+    Some(project.into())
+}
+
+/// Returns all the references to module functions in the body of a function,
+/// in the order they appear in.
+///
+pub fn function_references(function: &TypedFunction) -> Vec<FunctionReference> {
+    let mut finder = FunctionReferences { references: vec![] };
+    finder.visit_typed_function(function);
+    finder.references
+}
+
+struct FunctionReferences {
+    references: Vec<FunctionReference>,
+}
+
+impl<'ast> Visit<'ast> for FunctionReferences {
+    fn visit_typed_expr_var(
+        &mut self,
+        location: &'ast SrcSpan,
+        constructor: &'ast ValueConstructor,
+        _name: &'ast EcoString,
+    ) {
+        if let ValueConstructorVariant::ModuleFn { module, name, .. } = &constructor.variant {
+            self.references.push(FunctionReference {
+                function: FunctionName {
+                    module: module.clone(),
+                    name: name.clone(),
+                },
+                location: *location,
+            });
+        }
+    }
+
+    fn visit_typed_expr_module_select(
+        &mut self,
+        location: &'ast SrcSpan,
+        type_: &'ast std::sync::Arc<Type>,
+        label: &'ast EcoString,
+        module_name: &'ast EcoString,
+        module_alias: &'ast EcoString,
+        constructor: &'ast ModuleValueConstructor,
+    ) {
+        if let ModuleValueConstructor::Fn { module, name, .. } = constructor {
+            self.references.push(FunctionReference {
+                function: FunctionName {
+                    module: module.clone(),
+                    name: name.clone(),
+                },
+                location: *location,
+            });
+        }
+        ast::visit::visit_typed_expr_module_select(
+            self,
+            location,
+            type_,
+            label,
+            module_name,
+            module_alias,
+            constructor,
+        );
+    }
+}
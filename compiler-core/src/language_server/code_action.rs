@@ -9,6 +9,8 @@ use crate::{
         TypedStatement, TypedUse,
     },
     build::{Located, Module},
+    fix,
+    format::imports,
     io::{BeamCompiler, CommandExecutor, FileSystemReader, FileSystemWriter},
     line_numbers::LineNumbers,
     parse::{extra::ModuleExtra, lexer::str_to_keyword},
@@ -18,6 +20,7 @@ use crate::{
         printer::{Names, Printer},
         FieldMap, ModuleValueConstructor, Type, TypeVar, TypedCallArg, ValueConstructor,
     },
+    warning::WarningEmitter,
     Error, STDLIB_PACKAGE_NAME,
 };
 use ecow::{eco_format, EcoString};
@@ -3025,40 +3028,25 @@ impl<'ast> ast::visit::Visit<'ast> for GenerateDynamicDecoder<'ast> {
             return;
         }
 
-        // For now, we only generate dynamic decoders for types with one variant.
-        let constructor = match custom_type.constructors.as_slice() {
-            [constructor] => constructor,
-            _ => return,
+        let Some(variants) = labelled_variants(custom_type) else {
+            return;
         };
 
         let name = eco_format!("{}_decoder", custom_type.name.to_snake_case());
 
-        let Some(fields): Option<Vec<_>> = constructor
-            .arguments
-            .iter()
-            .map(|argument| {
-                Some(RecordField {
-                    label: RecordLabel::Labeled(
-                        argument.label.as_ref().map(|(_, name)| name.as_str())?,
-                    ),
-                    type_: &argument.type_,
-                })
-            })
-            .collect()
-        else {
-            return;
-        };
-
         let mut decoder_printer = DecoderPrinter::new(
             &self.module.ast.names,
             custom_type.name.clone(),
             self.module.name.clone(),
+            module_function_names(self.module),
         );
 
-        let decoders = fields
-            .iter()
-            .map(|field| decoder_printer.decode_field(field, 2))
-            .join("\n");
+        let body = match variants.as_slice() {
+            // There's nothing we could decode a type with no constructors into.
+            [] => return,
+            [(constructor, fields)] => decoder_printer.decode_variant(constructor, fields, 2),
+            variants => decoder_printer.decode_tagged_variants(&custom_type.name, variants),
+        };
 
         let decoder_type = self.printer.print_type(&Type::Named {
             publicity: ast::Publicity::Public,
@@ -3068,31 +3056,15 @@ impl<'ast> ast::visit::Visit<'ast> for GenerateDynamicDecoder<'ast> {
             args: vec![],
             inferred_variant: None,
         });
-        let decode_module = self.printer.print_module(DECODE_MODULE);
-
-        let mut field_names = fields.iter().map(|field| field.label.variable_name());
-        let parameters = match custom_type.parameters.len() {
-            0 => EcoString::new(),
-            _ => eco_format!(
-                "({})",
-                custom_type
-                    .parameters
-                    .iter()
-                    .map(|(_, name)| name)
-                    .join(", ")
-            ),
-        };
 
         let function = format!(
             "
 
 fn {name}() -> {decoder_type}({type_name}{parameters}) {{
-{decoders}
-  {decode_module}.success({constructor_name}({fields}:))
+{body}
 }}",
             type_name = custom_type.name,
-            constructor_name = constructor.name,
-            fields = field_names.join(":, ")
+            parameters = type_parameters(custom_type),
         );
 
         self.edits.insert(custom_type.end_position, function);
@@ -3109,6 +3081,73 @@ fn {name}() -> {decoder_type}({type_name}{parameters}) {{
     }
 }
 
+/// The name of the field used to tell apart the variants of a custom type with
+/// multiple constructors when it is encoded as an object, for example:
+/// `{ "type": "wibble", "field": 1 }`.
+///
+const VARIANT_TAG_FIELD: &str = "type";
+
+/// Returns each constructor of a custom type along with its fields, or `None`
+/// if any of those fields has no label.
+///
+fn labelled_variants(
+    custom_type: &ast::TypedCustomType,
+) -> Option<Vec<(&ast::TypedRecordConstructor, Vec<RecordField<'_>>)>> {
+    custom_type
+        .constructors
+        .iter()
+        .map(|constructor| {
+            let fields = constructor
+                .arguments
+                .iter()
+                .map(|argument| {
+                    Some(RecordField {
+                        label: RecordLabel::Labeled(
+                            argument.label.as_ref().map(|(_, name)| name.as_str())?,
+                        ),
+                        type_: &argument.type_,
+                    })
+                })
+                .collect::<Option<Vec<_>>>()?;
+            Some((constructor, fields))
+        })
+        .collect()
+}
+
+/// The names of the functions defined in the given module, used to call the
+/// decoders and encoders it already has for other types.
+///
+fn module_function_names(module: &Module) -> HashSet<EcoString> {
+    module
+        .ast
+        .definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            ast::Definition::Function(function) => {
+                function.name.as_ref().map(|(_, name)| name.clone())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// The type parameters of a custom type as they would appear after its name,
+/// for example `(key, value)`.
+///
+fn type_parameters(custom_type: &ast::TypedCustomType) -> EcoString {
+    match custom_type.parameters.len() {
+        0 => EcoString::new(),
+        _ => eco_format!(
+            "({})",
+            custom_type
+                .parameters
+                .iter()
+                .map(|(_, name)| name)
+                .join(", ")
+        ),
+    }
+}
+
 /// If `module_name` is not already imported inside `module`, adds an edit to
 /// add that import.
 /// This function also makes sure not to import a module in itself.
@@ -3141,6 +3180,9 @@ struct DecoderPrinter<'a> {
     type_name: EcoString,
     /// The module name of the root type we are printing a decoder for
     type_module: EcoString,
+    /// The functions defined in the same module as the root type, so that
+    /// the decoders it already has for other types can be called
+    module_functions: HashSet<EcoString>,
 }
 
 struct RecordField<'a> {
@@ -3187,14 +3229,91 @@ impl RecordLabel<'_> {
 }
 
 impl<'a> DecoderPrinter<'a> {
-    fn new(names: &'a Names, type_name: EcoString, type_module: EcoString) -> Self {
+    fn new(
+        names: &'a Names,
+        type_name: EcoString,
+        type_module: EcoString,
+        module_functions: HashSet<EcoString>,
+    ) -> Self {
         Self {
             type_name,
             type_module,
+            module_functions,
             printer: Printer::new(names),
         }
     }
 
+    /// Prints the decoding of all the fields of a constructor, followed by
+    /// its construction.
+    ///
+    fn decode_variant(
+        &mut self,
+        constructor: &ast::TypedRecordConstructor,
+        fields: &[RecordField<'_>],
+        indent: usize,
+    ) -> EcoString {
+        let module = self.printer.print_module(DECODE_MODULE);
+        let constructed = if fields.is_empty() {
+            constructor.name.clone()
+        } else {
+            let fields = fields
+                .iter()
+                .map(|field| eco_format!("{}:", field.label.variable_name()))
+                .join(", ");
+            eco_format!("{}({fields})", constructor.name)
+        };
+
+        fields
+            .iter()
+            .map(|field| self.decode_field(field, indent))
+            .chain(iter::once(eco_format!(
+                "{indent}{module}.success({constructed})",
+                indent = " ".repeat(indent)
+            )))
+            .join("\n")
+            .into()
+    }
+
+    /// Prints a decoder that picks which constructor to decode based on the
+    /// value of the tag field.
+    ///
+    fn decode_tagged_variants(
+        &mut self,
+        type_name: &EcoString,
+        variants: &[(&ast::TypedRecordConstructor, Vec<RecordField<'_>>)],
+    ) -> EcoString {
+        let module = self.printer.print_module(DECODE_MODULE);
+        let branches = variants
+            .iter()
+            .map(|(constructor, fields)| {
+                let tag = constructor.name.to_snake_case();
+                if fields.is_empty() {
+                    let decoder = self.decode_variant(constructor, fields, 0);
+                    eco_format!("    \"{tag}\" -> {decoder}")
+                } else {
+                    let decoder = self.decode_variant(constructor, fields, 6);
+                    eco_format!("    \"{tag}\" -> {{\n{decoder}\n    }}")
+                }
+            })
+            .join("\n");
+
+        // If a constructor has no fields we can use it as the placeholder value
+        // for a failed decoding, otherwise it's up to the user to pick one.
+        let zero_value = variants
+            .iter()
+            .find(|(_, fields)| fields.is_empty())
+            .map(|(constructor, _)| constructor.name.clone())
+            .unwrap_or_else(|| eco_format!(r#"todo as "Zero value for {type_name}""#));
+
+        eco_format!(
+            r#"  use variant <- {module}.field("{VARIANT_TAG_FIELD}", {module}.string)
+  case variant {{
+{branches}
+    _ -> {module}.failure({zero_value}, "{type_name}")
+  }}"#
+        )
+    }
+
     fn decoder_for(&mut self, type_: &Type, indent: usize) -> EcoString {
         let module_name = self.printer.print_module(DECODE_MODULE);
         if type_.is_bit_array() {
@@ -3255,6 +3374,14 @@ impl<'a> DecoderPrinter<'a> {
                 Some((module, name, _)) if module == self.type_module && name == self.type_name => {
                     eco_format!("{}_decoder()", name.to_snake_case())
                 }
+                Some((module, name, _))
+                    if module == self.type_module
+                        && self
+                            .module_functions
+                            .contains(&eco_format!("{}_decoder", name.to_snake_case())) =>
+                {
+                    eco_format!("{}_decoder()", name.to_snake_case())
+                }
                 _ => eco_format!(
                     r#"todo as "Decoder for {}""#,
                     self.printer.print_type(type_)
@@ -3276,6 +3403,309 @@ impl<'a> DecoderPrinter<'a> {
     }
 }
 
+/// Builder for code action to generate a function encoding a custom type to
+/// JSON using the `gleam/json` package. The produced JSON has the same shape the
+/// generated dynamic decoder expects:
+///
+/// ```gleam
+/// pub type Wibble {
+///   Wibble(name: String, wobbles: List(Int))
+/// }
+///
+/// // Generates
+///
+/// fn wibble_to_json(wibble: Wibble) -> json.Json {
+///   let Wibble(name:, wobbles:) = wibble
+///   json.object([
+///     #("name", json.string(name)),
+///     #("wobbles", json.array(wobbles, json.int)),
+///   ])
+/// }
+/// ```
+///
+/// Types with multiple constructors are encoded as objects with an additional
+/// `"type"` field holding the name of the constructor.
+///
+pub struct GenerateJsonEncoder<'a> {
+    module: &'a Module,
+    params: &'a CodeActionParams,
+    edits: TextEdits<'a>,
+    printer: Printer<'a>,
+    actions: &'a mut Vec<CodeAction>,
+}
+
+const JSON_MODULE: &str = "gleam/json";
+const JSON_PACKAGE_NAME: &str = "gleam_json";
+
+impl<'a> GenerateJsonEncoder<'a> {
+    pub fn new(
+        module: &'a Module,
+        line_numbers: &'a LineNumbers,
+        params: &'a CodeActionParams,
+        actions: &'a mut Vec<CodeAction>,
+    ) -> Self {
+        let printer = Printer::new(&module.ast.names);
+        Self {
+            module,
+            params,
+            edits: TextEdits::new(line_numbers),
+            printer,
+            actions,
+        }
+    }
+
+    pub fn code_actions(&mut self) {
+        self.visit_typed_module(&self.module.ast);
+    }
+}
+
+impl<'ast> ast::visit::Visit<'ast> for GenerateJsonEncoder<'ast> {
+    fn visit_typed_custom_type(&mut self, custom_type: &'ast ast::TypedCustomType) {
+        let range = self.edits.src_span_to_lsp_range(custom_type.location);
+        if !overlaps(self.params.range, range) {
+            return;
+        }
+
+        let Some(variants) = labelled_variants(custom_type) else {
+            return;
+        };
+
+        let type_name = &custom_type.name;
+        let name = eco_format!("{}_to_json", type_name.to_snake_case());
+        let argument = EcoString::from(type_name.to_snake_case());
+
+        let mut encoder_printer = EncoderPrinter::new(
+            &self.module.ast.names,
+            type_name.clone(),
+            self.module.name.clone(),
+            module_function_names(self.module),
+        );
+
+        let (argument, body) = match variants.as_slice() {
+            [] => return,
+            [(_, fields)] if fields.is_empty() => {
+                let object = encoder_printer.encode_object(None, fields, 2);
+                (eco_format!("_{argument}"), eco_format!("  {object}"))
+            }
+            [(constructor, fields)] => {
+                let pattern = constructor_pattern(constructor, fields);
+                let object = encoder_printer.encode_object(None, fields, 2);
+                let body = eco_format!("  let {pattern} = {argument}\n  {object}");
+                (argument, body)
+            }
+            variants => {
+                let branches = variants
+                    .iter()
+                    .map(|(constructor, fields)| {
+                        let pattern = constructor_pattern(constructor, fields);
+                        let tag = constructor.name.to_snake_case();
+                        let object = encoder_printer.encode_object(Some(&tag), fields, 4);
+                        eco_format!("    {pattern} -> {object}")
+                    })
+                    .join("\n");
+                let body = eco_format!("  case {argument} {{\n{branches}\n  }}");
+                (argument, body)
+            }
+        };
+
+        let json_type = self.printer.print_type(&Type::Named {
+            publicity: ast::Publicity::Public,
+            package: JSON_PACKAGE_NAME.into(),
+            module: JSON_MODULE.into(),
+            name: "Json".into(),
+            args: vec![],
+            inferred_variant: None,
+        });
+
+        let function = format!(
+            "
+
+fn {name}({argument}: {type_name}{parameters}) -> {json_type} {{
+{body}
+}}",
+            parameters = type_parameters(custom_type),
+        );
+
+        self.edits.insert(custom_type.end_position, function);
+        maybe_import(&mut self.edits, self.module, JSON_MODULE);
+
+        CodeActionBuilder::new("Generate JSON encoder")
+            .kind(CodeActionKind::REFACTOR)
+            .preferred(false)
+            .changes(
+                self.params.text_document.uri.clone(),
+                std::mem::take(&mut self.edits.edits),
+            )
+            .push_to(self.actions);
+    }
+}
+
+/// A pattern binding all the labelled fields of a constructor, using the
+/// label shorthand syntax: `Wibble(wibble:, wobble:)`.
+///
+fn constructor_pattern(
+    constructor: &ast::TypedRecordConstructor,
+    fields: &[RecordField<'_>],
+) -> EcoString {
+    if fields.is_empty() {
+        return constructor.name.clone();
+    }
+    let fields = fields
+        .iter()
+        .map(|field| eco_format!("{}:", field.label.variable_name()))
+        .join(", ");
+    eco_format!("{}({fields})", constructor.name)
+}
+
+struct EncoderPrinter<'a> {
+    printer: Printer<'a>,
+    /// The name of the root type we are printing an encoder for
+    type_name: EcoString,
+    /// The module name of the root type we are printing an encoder for
+    type_module: EcoString,
+    /// The functions defined in the same module as the root type, so that
+    /// the encoders it already has for other types can be called
+    module_functions: HashSet<EcoString>,
+}
+
+impl<'a> EncoderPrinter<'a> {
+    fn new(
+        names: &'a Names,
+        type_name: EcoString,
+        type_module: EcoString,
+        module_functions: HashSet<EcoString>,
+    ) -> Self {
+        Self {
+            type_name,
+            type_module,
+            module_functions,
+            printer: Printer::new(names),
+        }
+    }
+
+    /// Prints a JSON object with a key for each of the given fields, preceded
+    /// by the tag field if the constructor needs one.
+    ///
+    fn encode_object(
+        &mut self,
+        tag: Option<&str>,
+        fields: &[RecordField<'_>],
+        indent: usize,
+    ) -> EcoString {
+        let module = self.printer.print_module(JSON_MODULE);
+        let indent = " ".repeat(indent);
+        let tag =
+            tag.map(|tag| eco_format!(r#"#("{VARIANT_TAG_FIELD}", {module}.string("{tag}"))"#));
+        let entries = tag
+            .into_iter()
+            .chain(fields.iter().map(|field| {
+                let variable = field.label.variable_name();
+                let encoder = self.encoder_for(field.type_, &variable);
+                eco_format!("#({}, {encoder})", field.label.field_key())
+            }))
+            .map(|entry| eco_format!("{indent}  {entry},"))
+            .collect_vec();
+
+        if entries.is_empty() {
+            eco_format!("{module}.object([])")
+        } else {
+            eco_format!("{module}.object([\n{}\n{indent}])", entries.join("\n"))
+        }
+    }
+
+    /// Prints an expression encoding the given value of the given type.
+    ///
+    fn encoder_for(&mut self, type_: &Type, value: &str) -> EcoString {
+        let module = self.printer.print_module(JSON_MODULE);
+        if let Some(function) = self.encoder_function_name(type_) {
+            return eco_format!("{function}({value})");
+        }
+
+        if type_.is_nil() {
+            return eco_format!("{module}.null()");
+        }
+
+        if let Some(types) = type_.tuple_types() {
+            let elements = types
+                .iter()
+                .enumerate()
+                .map(|(index, type_)| self.encoder_for(type_, &format!("{value}.{index}")))
+                .join(", ");
+            return eco_format!("{module}.preprocessed_array([{elements}])");
+        }
+
+        let type_information = type_.named_type_information();
+        let type_information = type_information.as_ref().map(|(module, name, arguments)| {
+            (module.as_str(), name.as_str(), arguments.as_slice())
+        });
+
+        match type_information {
+            Some(("gleam", "List", [element])) => {
+                eco_format!(
+                    "{module}.array({value}, {})",
+                    self.encoder_function(element)
+                )
+            }
+            Some(("gleam/option", "Option", [some])) => {
+                eco_format!(
+                    "{module}.nullable({value}, {})",
+                    self.encoder_function(some)
+                )
+            }
+            Some(("gleam/dict", "Dict", [key, value_type])) if key.is_string() => eco_format!(
+                "{module}.dict({value}, fn(string) {{ string }}, {})",
+                self.encoder_function(value_type)
+            ),
+            _ => self.todo(type_),
+        }
+    }
+
+    /// Prints a function that can be used to encode values of the given type,
+    /// for example to be passed to `json.array`.
+    ///
+    fn encoder_function(&mut self, type_: &Type) -> EcoString {
+        match self.encoder_function_name(type_) {
+            Some(function) => function,
+            None => match self.encoder_for(type_, "value") {
+                todo if todo.starts_with("todo") => todo,
+                encoder => eco_format!("fn(value) {{ {encoder} }}"),
+            },
+        }
+    }
+
+    /// If values of the given type can be encoded by calling a single
+    /// function, returns its name.
+    ///
+    fn encoder_function_name(&mut self, type_: &Type) -> Option<EcoString> {
+        let module = self.printer.print_module(JSON_MODULE);
+        if type_.is_bool() {
+            Some(eco_format!("{module}.bool"))
+        } else if type_.is_float() {
+            Some(eco_format!("{module}.float"))
+        } else if type_.is_int() {
+            Some(eco_format!("{module}.int"))
+        } else if type_.is_string() {
+            Some(eco_format!("{module}.string"))
+        } else {
+            match type_.named_type_information() {
+                Some((module, name, _)) if module == self.type_module => {
+                    let function = eco_format!("{}_to_json", name.to_snake_case());
+                    (name == self.type_name || self.module_functions.contains(&function))
+                        .then_some(function)
+                }
+                _ => None,
+            }
+        }
+    }
+
+    fn todo(&mut self, type_: &Type) -> EcoString {
+        eco_format!(
+            r#"todo as "Encoder for {}""#,
+            self.printer.print_type(type_)
+        )
+    }
+}
+
 /// Builder for code action to pattern match on things like (anonymous) function
 /// arguments or variables.
 /// For example:
@@ -4009,3 +4439,212 @@ fn is_valid_lowercase_name(name: &str) -> bool {
 
     str_to_keyword(name).is_none()
 }
+
+/// Builder for code action to organize the imports of a module: duplicate
+/// imports of the same module are merged, unused imports, aliases and
+/// unqualified items are removed and the remaining ones are sorted.
+///
+/// ```gleam
+/// import gleam/list.{map}
+/// import gleam/io
+/// import gleam/int
+/// import gleam/list.{filter, fold}
+/// // ^ `int` and `fold` are never used
+/// ```
+///
+/// Becomes:
+///
+/// ```gleam
+/// import gleam/io
+/// import gleam/list.{filter, map}
+/// ```
+///
+pub struct OrganizeImports<'a> {
+    module: &'a Module,
+    params: &'a CodeActionParams,
+    edits: TextEdits<'a>,
+}
+
+impl<'a> OrganizeImports<'a> {
+    pub fn new(
+        module: &'a Module,
+        line_numbers: &'a LineNumbers,
+        params: &'a CodeActionParams,
+    ) -> Self {
+        Self {
+            module,
+            params,
+            edits: TextEdits::new(line_numbers),
+        }
+    }
+
+    pub fn code_actions(mut self) -> Vec<CodeAction> {
+        let code = &self.module.code;
+        let Ok(parsed) = crate::parse::parse_module(
+            self.module.input_path.clone(),
+            code,
+            &WarningEmitter::null(),
+        ) else {
+            return vec![];
+        };
+        let mut ast = parsed.module;
+
+        // Imports marked with a `@target` attribute are deprecated and we would
+        // need to move their attribute around as well, so we leave those alone.
+        let imports = ast
+            .definitions
+            .iter()
+            .filter(|definition| definition.definition.is_import())
+            .collect_vec();
+        if imports.is_empty() || imports.iter().any(|import| import.target.is_some()) {
+            return vec![];
+        }
+
+        // All the imports are going to end up in the first group of
+        // consecutive imports, any import that comes after that is removed.
+        let first_import_group = ast
+            .definitions
+            .iter()
+            .skip_while(|definition| !definition.definition.is_import())
+            .take_while(|definition| definition.definition.is_import())
+            .map(|definition| definition.definition.location())
+            .reduce(|one, other| one.merge(&other))
+            .expect("at least one import");
+        let later_imports = imports
+            .iter()
+            .map(|import| import.definition.location())
+            .filter(|location| location.start > first_import_group.end)
+            .collect_vec();
+
+        let unused = self
+            .module
+            .ast
+            .type_info
+            .warnings
+            .iter()
+            .filter_map(|warning| warning.unused_import_location())
+            .collect();
+        imports::organize_imports(code, &mut ast, &unused);
+        let organized = ast
+            .definitions
+            .into_iter()
+            .filter(|definition| definition.definition.is_import())
+            .collect_vec();
+        let new_imports =
+            imports::pretty_imports(code, &parsed.extra, &organized, first_import_group);
+
+        let old_imports = code
+            .get(first_import_group.start as usize..first_import_group.end as usize)
+            .unwrap_or_default();
+        if later_imports.is_empty() && new_imports.trim_end() == old_imports {
+            return vec![];
+        }
+
+        if organized.is_empty() {
+            let end = first_import_group.end + whitespace_after(code, first_import_group.end);
+            self.edits
+                .delete(SrcSpan::new(first_import_group.start, end));
+        } else {
+            self.edits
+                .replace(first_import_group, new_imports.trim_end().to_string());
+        }
+        for import in later_imports {
+            let end = match code.get(import.end as usize..) {
+                Some(rest) if rest.starts_with('\n') => import.end + 1,
+                _ => import.end,
+            };
+            self.edits.delete(SrcSpan::new(import.start, end));
+        }
+
+        let mut action = Vec::with_capacity(1);
+        CodeActionBuilder::new("Organize imports")
+            .kind(CodeActionKind::SOURCE_ORGANIZE_IMPORTS)
+            .changes(self.params.text_document.uri.clone(), self.edits.edits)
+            .preferred(false)
+            .push_to(&mut action);
+        action
+    }
+}
+
+/// The number of whitespace bytes that follow the given position.
+///
+fn whitespace_after(code: &str, position: u32) -> u32 {
+    code.get(position as usize..)
+        .unwrap_or_default()
+        .chars()
+        .take_while(|char| char.is_whitespace())
+        .map(|char| char.len_utf8() as u32)
+        .sum()
+}
+
+/// Builder for code action to replace the deprecated values used in the
+/// selection, if their deprecation gives a replacement that can be applied
+/// automatically.
+///
+/// ```gleam
+/// // In the `wibble` module
+/// @deprecated("Use new instead", replace: "new(_2, _1)")
+/// pub fn old(a, b) { ... }
+///
+/// // In the selected code
+/// wibble.old(1, 2)
+/// ```
+///
+/// Becomes:
+///
+/// ```gleam
+/// wibble.new(2, 1)
+/// ```
+///
+pub struct ReplaceDeprecated<'a> {
+    module: &'a Module,
+    params: &'a CodeActionParams,
+    modules: &'a HashMap<EcoString, type_::ModuleInterface>,
+    edits: TextEdits<'a>,
+}
+
+impl<'a> ReplaceDeprecated<'a> {
+    pub fn new(
+        module: &'a Module,
+        line_numbers: &'a LineNumbers,
+        params: &'a CodeActionParams,
+        modules: &'a HashMap<EcoString, type_::ModuleInterface>,
+    ) -> Self {
+        Self {
+            module,
+            params,
+            modules,
+            edits: TextEdits::new(line_numbers),
+        }
+    }
+
+    pub fn code_actions(mut self) -> Vec<CodeAction> {
+        let references =
+            fix::deprecated_references(&self.module.ast, &self.module.code, self.modules)
+                .into_iter()
+                .filter(|reference| {
+                    let range = self.edits.src_span_to_lsp_range(reference.edit.location);
+                    overlaps(self.params.range, range)
+                })
+                .collect_vec();
+        if references.is_empty() {
+            return vec![];
+        }
+
+        let imports = fix::import_edits(&self.module.ast, &self.module.code, &references);
+        for edit in imports
+            .into_iter()
+            .chain(references.into_iter().map(|reference| reference.edit))
+        {
+            self.edits.replace(edit.location, edit.new_text.into());
+        }
+
+        let mut action = Vec::with_capacity(1);
+        CodeActionBuilder::new("Replace deprecated value")
+            .kind(CodeActionKind::QUICKFIX)
+            .changes(self.params.text_document.uri.clone(), self.edits.edits)
+            .preferred(true)
+            .push_to(&mut action);
+        action
+    }
+}
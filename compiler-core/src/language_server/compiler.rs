@@ -66,12 +66,14 @@ where
 
         let options = build::Options {
             warnings_as_errors: false,
+            warning_levels: HashMap::new(),
             mode: Mode::Lsp,
             target: None,
             codegen: build::Codegen::None,
             compile: build::Compile::All,
             root_target_support: TargetSupport::Enforced,
             no_print_progress: false,
+            tooling: false,
         };
         let mut project_compiler = ProjectCompiler::new(
             config,
@@ -11,11 +11,15 @@ use crate::{
         compiler::LspProjectCompiler, files::FileSystemProxy, progress::ProgressReporter,
     },
     line_numbers::LineNumbers,
+    manifest::ManifestPackageSource,
+    move_module,
     paths::ProjectPaths,
+    reference::{self, LabelOwner, ReferenceKind, Symbol},
     type_::{
         self, error::VariableOrigin, printer::Printer, Deprecation, ModuleInterface, Type,
         TypeConstructor, ValueConstructor, ValueConstructorVariant,
     },
+    uid::UniqueIdGenerator,
     Error, Result, Warning,
 };
 use camino::Utf8PathBuf;
@@ -30,17 +34,21 @@ use lsp_types::{
 use std::sync::Arc;
 
 use super::{
+    call_hierarchy,
     code_action::{
         code_action_add_missing_patterns, code_action_convert_qualified_constructor_to_unqualified,
         code_action_convert_unqualified_constructor_to_qualified, code_action_import_module,
         code_action_inexhaustive_let_to_case, AddAnnotations, CodeActionBuilder, DesugarUse,
         ExpandFunctionCapture, ExtractVariable, FillInMissingLabelledArgs, GenerateDynamicDecoder,
-        GenerateFunction, LetAssertToCase, PatternMatchOnValue, RedundantTupleInCaseSubject,
-        TurnIntoUse, UseLabelShorthandSyntax,
+        GenerateFunction, GenerateJsonEncoder, LetAssertToCase, OrganizeImports,
+        PatternMatchOnValue, RedundantTupleInCaseSubject, ReplaceDeprecated, TurnIntoUse,
+        UseLabelShorthandSyntax,
     },
     completer::Completer,
-    rename::{rename_local_variable, VariableRenameKind},
-    signature_help, src_span_to_lsp_range, DownloadDependencies, MakeLocker,
+    externals, folding_range,
+    rename::{rename_local_variable, rename_module_symbol, VariableRenameKind},
+    selection_range, signature_help, src_span_to_lsp_range, DownloadDependencies, MakeLocker,
+    TextEdits,
 };
 
 #[derive(Debug, PartialEq, Eq)]
@@ -119,9 +127,7 @@ where
             .packages
             .iter()
             .flat_map(|(k, v)| match &v.source {
-                crate::manifest::ManifestPackageSource::Hex { .. } => {
-                    Some(EcoString::from(k.as_str()))
-                }
+                ManifestPackageSource::Hex { .. } => Some(EcoString::from(k.as_str())),
 
                 _ => None,
             })
@@ -138,6 +144,15 @@ where
         })
     }
 
+    /// Makes modules compiled elsewhere importable by the project, see
+    /// `ProjectCompiler::load_importable_modules`.
+    pub fn load_importable_modules(
+        &mut self,
+        load: impl FnOnce(&UniqueIdGenerator) -> Result<Vec<ModuleInterface>>,
+    ) -> Result<()> {
+        self.compiler.project_compiler.load_importable_modules(load)
+    }
+
     pub fn compile_please(&mut self) -> Response<()> {
         self.respond(Self::compile)
     }
@@ -181,6 +196,12 @@ where
                 None => return Ok(None),
             };
 
+            // The definition of an external function is its native
+            // implementation, in an Erlang or JavaScript file.
+            if let Some(location) = this.native_definition(&params) {
+                return Ok(Some(location));
+            }
+
             let location = match node
                 .definition_location(this.compiler.project_compiler.get_importable_modules())
             {
@@ -206,6 +227,110 @@ where
         })
     }
 
+    pub fn goto_type_definition(
+        &mut self,
+        params: lsp::GotoDefinitionParams,
+    ) -> Response<Option<lsp::GotoDefinitionResponse>> {
+        self.respond(|this| {
+            let params = params.text_document_position_params;
+            let Some((_, node)) = this.node_at_position(&params) else {
+                return Ok(None);
+            };
+            let Some(type_) = located_type(&node) else {
+                return Ok(None);
+            };
+
+            let importable_modules = this.compiler.project_compiler.get_importable_modules();
+            let mut locations = vec![];
+            for (module, name) in named_types(type_) {
+                let Some(type_) = importable_modules
+                    .get(&module)
+                    .and_then(|interface| interface.types.get(&name))
+                else {
+                    continue;
+                };
+                let Some(source) = this.compiler.get_source(&module) else {
+                    continue;
+                };
+                let uri = Url::parse(&format!("file:///{}", &source.path))
+                    .expect("goto type definition URL parse");
+                let range = src_span_to_lsp_range(type_.origin, &source.line_numbers);
+                locations.push(lsp::Location { uri, range });
+            }
+
+            // When the type is made of several types, such as a list or a
+            // result, the client lets the programmer choose which to go to.
+            Ok(match locations.len() {
+                0 => None,
+                1 => locations.pop().map(lsp::GotoDefinitionResponse::Scalar),
+                _ => Some(lsp::GotoDefinitionResponse::Array(locations)),
+            })
+        })
+    }
+
+    /// Returns the location of the native implementation of the external
+    /// function whose name is at the given position.
+    fn native_definition(&self, params: &lsp::TextDocumentPositionParams) -> Option<lsp::Location> {
+        let module = self.module_for_uri(&params.text_document.uri)?;
+        let byte_index = LineNumbers::new(&module.code)
+            .byte_index(params.position.line, params.position.character);
+        // Functions without a body have a placeholder one spanning their head,
+        // so the function is looked for directly rather than by node. Only
+        // its name leads to the native code, its argument and return
+        // annotations lead to the definitions of their types.
+        let function = module
+            .ast
+            .definitions
+            .iter()
+            .find_map(|definition| match definition {
+                Definition::Function(function)
+                    if (function.external_erlang.is_some()
+                        || function.external_javascript.is_some())
+                        && function
+                            .name
+                            .as_ref()
+                            .is_some_and(|(location, _)| location.contains(byte_index)) =>
+                {
+                    Some(function)
+                }
+                _ => None,
+            })?;
+        let location = externals::native_location(
+            &self.compiler.project_compiler.io,
+            function,
+            &module.input_path,
+            &self.package_roots(),
+            self.compiler.project_compiler.config.target,
+        )?;
+        let uri =
+            Url::parse(&format!("file:///{}", location.path)).expect("goto definition URL parse");
+        let range = src_span_to_lsp_range(location.span, &LineNumbers::new(&location.src));
+        Some(lsp::Location { uri, range })
+    }
+
+    /// The root directories of the project and of all its dependencies, in
+    /// the order their native files are looked for in.
+    fn package_roots(&self) -> Vec<Utf8PathBuf> {
+        let dependencies = self
+            .compiler
+            .project_compiler
+            .packages
+            .values()
+            .sorted_by(|a, b| a.name.cmp(&b.name))
+            .map(|package| match &package.source {
+                ManifestPackageSource::Local { path } if path.is_relative() => {
+                    self.paths.manifest_directory().join(path)
+                }
+                ManifestPackageSource::Local { path } => path.clone(),
+                ManifestPackageSource::Git { .. } | ManifestPackageSource::Hex { .. } => {
+                    self.paths.build_packages_package(&package.name)
+                }
+            });
+        std::iter::once(self.paths.root().to_path_buf())
+            .chain(dependencies)
+            .collect()
+    }
+
     pub fn completion(
         &mut self,
         params: lsp::TextDocumentPositionParams,
@@ -328,6 +453,15 @@ where
                 &this.error,
                 &mut actions,
             );
+            actions.extend(
+                ReplaceDeprecated::new(
+                    module,
+                    &lines,
+                    &params,
+                    this.compiler.project_compiler.get_importable_modules(),
+                )
+                .code_actions(),
+            );
             actions.extend(LetAssertToCase::new(module, &lines, &params).code_actions());
             actions
                 .extend(RedundantTupleInCaseSubject::new(module, &lines, &params).code_actions());
@@ -342,7 +476,9 @@ where
                 PatternMatchOnValue::new(module, &lines, &params, &this.compiler).code_actions(),
             );
             GenerateDynamicDecoder::new(module, &lines, &params, &mut actions).code_actions();
+            GenerateJsonEncoder::new(module, &lines, &params, &mut actions).code_actions();
             AddAnnotations::new(module, &lines, &params).code_action(&mut actions);
+            actions.extend(OrganizeImports::new(module, &lines, &params).code_actions());
             Ok(if actions.is_empty() {
                 None
             } else {
@@ -496,6 +632,170 @@ where
         })
     }
 
+    pub fn folding_range(
+        &mut self,
+        params: lsp::FoldingRangeParams,
+    ) -> Response<Vec<lsp::FoldingRange>> {
+        self.respond(|this| {
+            let Some(module) = this.module_for_uri(&params.text_document.uri) else {
+                return Ok(vec![]);
+            };
+            Ok(folding_range::folding_ranges(module))
+        })
+    }
+
+    pub fn document_highlight(
+        &mut self,
+        params: lsp::DocumentHighlightParams,
+    ) -> Response<Option<Vec<lsp::DocumentHighlight>>> {
+        self.respond(|this| {
+            let position = &params.text_document_position_params;
+            let Some(module) = this.module_for_uri(&position.text_document.uri) else {
+                return Ok(None);
+            };
+            let line_numbers = LineNumbers::new(&module.code);
+            let byte_index =
+                line_numbers.byte_index(position.position.line, position.position.character);
+
+            let references = reference::module_references(module);
+            let Some(symbol) = reference::reference_at(&references, byte_index)
+                .map(|reference| reference.symbol.clone())
+            else {
+                return Ok(None);
+            };
+
+            let highlights = references
+                .into_iter()
+                .filter(|reference| reference.symbol == symbol)
+                .map(|reference| lsp::DocumentHighlight {
+                    range: src_span_to_lsp_range(reference.location, &line_numbers),
+                    kind: Some(match reference.kind {
+                        ReferenceKind::Definition => lsp::DocumentHighlightKind::WRITE,
+                        ReferenceKind::Usage | ReferenceKind::LabelShorthand => {
+                            lsp::DocumentHighlightKind::READ
+                        }
+                    }),
+                })
+                .collect();
+            Ok(Some(highlights))
+        })
+    }
+
+    /// The edits to make to the code of the project when the client renames
+    /// Gleam files. The native files of the renamed modules are moved along
+    /// with them only if the client can rename files itself.
+    ///
+    pub fn will_rename_files(
+        &mut self,
+        params: lsp::RenameFilesParams,
+        can_rename_files: bool,
+    ) -> Response<Option<WorkspaceEdit>> {
+        self.respond(|this| {
+            let moves = params
+                .files
+                .iter()
+                .filter_map(|file| {
+                    let old_uri = Url::parse(&file.old_uri).ok()?;
+                    let new_uri = Url::parse(&file.new_uri).ok()?;
+                    let old = this.module_name_for_uri(&old_uri)?;
+                    let new = this.module_name_for_uri(&new_uri)?;
+                    this.compiler
+                        .modules
+                        .contains_key(&old)
+                        .then_some((old_uri, new_uri, old, new))
+                })
+                .collect_vec();
+            if moves.is_empty() {
+                return Ok(None);
+            }
+
+            let mut operations = vec![];
+            let modules = this
+                .compiler
+                .modules
+                .values()
+                .sorted_by_key(|module| &module.name);
+            for module in modules {
+                let edits = moves
+                    .iter()
+                    .flat_map(|(_, _, old, new)| move_module::module_move_edits(module, old, new))
+                    .collect_vec();
+                let Some(source) = this.compiler.get_source(&module.name) else {
+                    continue;
+                };
+                if edits.is_empty() {
+                    continue;
+                }
+                let uri = Url::parse(&format!("file:///{}", &source.path))
+                    .expect("rename files URL parse");
+                let mut text_edits = TextEdits::new(&source.line_numbers);
+                for edit in edits {
+                    text_edits.replace(edit.location, edit.new_text.into());
+                }
+                operations.push(lsp::DocumentChangeOperation::Edit(lsp::TextDocumentEdit {
+                    text_document: lsp::OptionalVersionedTextDocumentIdentifier {
+                        uri,
+                        version: None,
+                    },
+                    edits: text_edits.edits.into_iter().map(lsp::OneOf::Left).collect(),
+                }));
+            }
+
+            // The client only moves the Gleam files, so the native files
+            // implementing their external functions are moved along with them.
+            let io = &this.compiler.project_compiler.io;
+            let native_moves = if can_rename_files {
+                moves.as_slice()
+            } else {
+                tracing::warn!("lsp_client_cannot_rename_native_files");
+                &[]
+            };
+            for (old_uri, new_uri, _, _) in native_moves {
+                let native_files = move_module::native_file_moves(
+                    io,
+                    &super::path(old_uri),
+                    &super::path(new_uri),
+                );
+                for (old_path, new_path) in native_files {
+                    let file_uri = |path: Utf8PathBuf| {
+                        Url::parse(&format!("file:///{path}")).expect("rename files URL parse")
+                    };
+                    operations.push(lsp::DocumentChangeOperation::Op(lsp::ResourceOp::Rename(
+                        lsp::RenameFile {
+                            old_uri: file_uri(old_path),
+                            new_uri: file_uri(new_path),
+                            options: None,
+                            annotation_id: None,
+                        },
+                    )));
+                }
+            }
+
+            Ok(Some(WorkspaceEdit {
+                changes: None,
+                document_changes: Some(lsp::DocumentChanges::Operations(operations)),
+                change_annotations: None,
+            }))
+        })
+    }
+
+    pub fn selection_range(
+        &mut self,
+        params: lsp::SelectionRangeParams,
+    ) -> Response<Vec<lsp::SelectionRange>> {
+        self.respond(|this| {
+            let Some(module) = this.module_for_uri(&params.text_document.uri) else {
+                return Ok(vec![]);
+            };
+            let line_numbers = LineNumbers::new(&module.code);
+            Ok(params
+                .positions
+                .into_iter()
+                .map(|position| selection_range::selection_range(module, &line_numbers, position))
+                .collect())
+        })
+    }
+
     pub fn prepare_rename(
         &mut self,
         params: lsp::TextDocumentPositionParams,
@@ -510,6 +810,10 @@ where
                 default_behavior: true,
             });
 
+            if this.module_symbol_at(&params).is_some() {
+                return Ok(success_response);
+            }
+
             Ok(match found {
                 Located::Expression(TypedExpr::Var {
                     constructor:
@@ -550,6 +854,16 @@ where
         self.respond(|this| {
             let position = &params.text_document_position;
 
+            if let Some(symbol) = this.module_symbol_at(position) {
+                let modules = this.compiler.modules.values().filter_map(|module| {
+                    let source = this.compiler.get_source(&module.name)?;
+                    let uri =
+                        Url::parse(&format!("file:///{}", &source.path)).expect("rename URL parse");
+                    Some((uri, module))
+                });
+                return Ok(rename_module_symbol(modules, &symbol, &params.new_name));
+            }
+
             let (lines, found) = match this.node_at_position(position) {
                 Some(value) => value,
                 None => return Ok(None),
@@ -640,6 +954,137 @@ where
         })
     }
 
+    pub fn prepare_call_hierarchy(
+        &mut self,
+        params: lsp::CallHierarchyPrepareParams,
+    ) -> Response<Option<Vec<lsp::CallHierarchyItem>>> {
+        self.respond(|this| {
+            let params = params.text_document_position_params;
+            let Some(module) = this.module_for_uri(&params.text_document.uri) else {
+                return Ok(None);
+            };
+            let Some((_, found)) = this.module_node_at_position(&params, module) else {
+                return Ok(None);
+            };
+            let importable_modules = this.compiler.project_compiler.get_importable_modules();
+            Ok(
+                call_hierarchy::function_at(module, &found, importable_modules)
+                    .and_then(|function| this.call_hierarchy_item(function))
+                    .map(|item| vec![item]),
+            )
+        })
+    }
+
+    pub fn incoming_calls(
+        &mut self,
+        params: lsp::CallHierarchyIncomingCallsParams,
+    ) -> Response<Option<Vec<lsp::CallHierarchyIncomingCall>>> {
+        self.respond(|this| {
+            let Some(target) = call_hierarchy::item_function(&params.item) else {
+                return Ok(None);
+            };
+
+            let mut calls = vec![];
+            for (name, module) in this
+                .compiler
+                .modules
+                .iter()
+                .sorted_by_key(|(name, _)| *name)
+            {
+                let Some(source) = this.compiler.get_source(name) else {
+                    continue;
+                };
+                for definition in &module.ast.definitions {
+                    let Definition::Function(function) = definition else {
+                        continue;
+                    };
+                    let from_ranges = call_hierarchy::function_references(function)
+                        .into_iter()
+                        .filter(|reference| reference.function == target)
+                        .map(|reference| {
+                            src_span_to_lsp_range(reference.location, &source.line_numbers)
+                        })
+                        .collect_vec();
+                    if from_ranges.is_empty() {
+                        continue;
+                    }
+                    if let Some(from) =
+                        call_hierarchy::function_item(this.paths.root(), module, function, source)
+                    {
+                        calls.push(lsp::CallHierarchyIncomingCall { from, from_ranges });
+                    }
+                }
+            }
+            Ok(Some(calls))
+        })
+    }
+
+    pub fn outgoing_calls(
+        &mut self,
+        params: lsp::CallHierarchyOutgoingCallsParams,
+    ) -> Response<Option<Vec<lsp::CallHierarchyOutgoingCall>>> {
+        self.respond(|this| {
+            // Only the functions of the root package have a body we can look
+            // into, the ones of dependencies are only known by their interface.
+            let Some(caller) = call_hierarchy::item_function(&params.item) else {
+                return Ok(None);
+            };
+            let Some(module) = this.compiler.modules.get(&caller.module) else {
+                return Ok(None);
+            };
+            let Some(function) = call_hierarchy::find_function(module, &caller.name) else {
+                return Ok(None);
+            };
+            let Some(source) = this.compiler.get_source(&caller.module) else {
+                return Ok(None);
+            };
+
+            let mut calls: Vec<(call_hierarchy::FunctionName, Vec<Range>)> = vec![];
+            for reference in call_hierarchy::function_references(function) {
+                let range = src_span_to_lsp_range(reference.location, &source.line_numbers);
+                match calls
+                    .iter_mut()
+                    .find(|(function, _)| *function == reference.function)
+                {
+                    Some((_, ranges)) => ranges.push(range),
+                    None => calls.push((reference.function, vec![range])),
+                }
+            }
+
+            Ok(Some(
+                calls
+                    .into_iter()
+                    .filter_map(|(function, from_ranges)| {
+                        let to = this.call_hierarchy_item(function)?;
+                        Some(lsp::CallHierarchyOutgoingCall { to, from_ranges })
+                    })
+                    .collect(),
+            ))
+        })
+    }
+
+    fn call_hierarchy_item(
+        &self,
+        function: call_hierarchy::FunctionName,
+    ) -> Option<lsp::CallHierarchyItem> {
+        let source = self.compiler.get_source(&function.module)?;
+        match self.compiler.modules.get(&function.module) {
+            Some(module) => {
+                let definition = call_hierarchy::find_function(module, &function.name)?;
+                call_hierarchy::function_item(self.paths.root(), module, definition, source)
+            }
+            None => {
+                let interface = self.compiler.get_module_interface(&function.module)?;
+                call_hierarchy::dependency_function_item(
+                    self.paths.root(),
+                    function,
+                    interface,
+                    source,
+                )
+            }
+        }
+    }
+
     fn respond<T>(&mut self, handler: impl FnOnce(&mut Self) -> Result<T>) -> Response<T> {
         let result = handler(self);
         let warnings = self.take_warnings();
@@ -817,7 +1262,40 @@ Unused labelled fields:
         self.module_node_at_position(params, module)
     }
 
+    /// Returns the function, constant, type, record constructor or label at
+    /// the given position, if it is defined in one of the modules of the root
+    /// package.
+    ///
+    fn module_symbol_at(&self, params: &lsp::TextDocumentPositionParams) -> Option<Symbol> {
+        let module = self.module_for_uri(&params.text_document.uri)?;
+        let byte_index = LineNumbers::new(&module.code)
+            .byte_index(params.position.line, params.position.character);
+        let references = reference::module_references(module);
+        let symbol = &reference::reference_at(&references, byte_index)?.symbol;
+        let defining_module = match symbol {
+            Symbol::ModuleValue { module, .. }
+            | Symbol::ModuleType { module, .. }
+            | Symbol::Label {
+                owner: LabelOwner::Type { module, .. } | LabelOwner::Function { module, .. },
+                ..
+            } => module,
+            Symbol::LocalVariable { .. } | Symbol::ImportedModule { .. } => return None,
+        };
+        self.compiler
+            .modules
+            .contains_key(defining_module)
+            .then(|| symbol.clone())
+    }
+
     fn module_for_uri(&self, uri: &Url) -> Option<&Module> {
+        let module_name = self.module_name_for_uri(uri)?;
+        self.compiler.modules.get(&module_name)
+    }
+
+    /// Returns the name of the module defined by a file of the project,
+    /// whether the module exists or not.
+    ///
+    fn module_name_for_uri(&self, uri: &Url) -> Option<EcoString> {
         // The to_file_path method is available on these platforms
         #[cfg(any(unix, windows, target_os = "redox", target_os = "wasi"))]
         let path = uri.to_file_path().expect("URL file");
@@ -831,12 +1309,11 @@ Unused labelled fields:
             .components()
             .skip(1)
             .map(|c| c.as_os_str().to_string_lossy());
-        let module_name: EcoString = Itertools::intersperse(components, "/".into())
+        let module_name = Itertools::intersperse(components, "/".into())
             .collect::<String>()
             .strip_suffix(".gleam")?
             .into();
-
-        self.compiler.modules.get(&module_name)
+        Some(module_name)
     }
 }
 
@@ -1136,6 +1613,59 @@ fn hover_for_imported_value(
     }
 }
 
+/// The type of the value, pattern or annotation at a node.
+///
+fn located_type(located: &Located<'_>) -> Option<Arc<Type>> {
+    match located {
+        Located::Expression(expression) => Some(expression.type_()),
+        Located::Pattern(pattern) => Some(pattern.type_()),
+        Located::Statement(statement) => Some(statement.type_()),
+        Located::Arg(arg) => Some(arg.type_.clone()),
+        Located::Annotation(_, type_) | Located::Label(_, type_) => Some(type_.clone()),
+        Located::PatternSpread { .. }
+        | Located::ModuleStatement(_)
+        | Located::FunctionBody(_)
+        | Located::UnqualifiedImport(_) => None,
+    }
+}
+
+/// The module and name of the named types a type is made of, starting with
+/// the type itself and followed by its arguments.
+///
+fn named_types(type_: Arc<Type>) -> Vec<(EcoString, EcoString)> {
+    fn collect(type_: Arc<Type>, names: &mut Vec<(EcoString, EcoString)>) {
+        match type_::collapse_links(type_).as_ref() {
+            Type::Named {
+                module, name, args, ..
+            } => {
+                let named = (module.clone(), name.clone());
+                if !names.contains(&named) {
+                    names.push(named);
+                }
+                for arg in args {
+                    collect(arg.clone(), names);
+                }
+            }
+            Type::Fn { args, retrn } => {
+                for arg in args {
+                    collect(arg.clone(), names);
+                }
+                collect(retrn.clone(), names);
+            }
+            Type::Tuple { elems } => {
+                for elem in elems {
+                    collect(elem.clone(), names);
+                }
+            }
+            Type::Var { .. } => (),
+        }
+    }
+
+    let mut names = vec![];
+    collect(type_, &mut names);
+    names
+}
+
 // Returns true if any part of either range overlaps with the other.
 pub fn overlaps(a: Range, b: Range) -> bool {
     position_within(a.start, b)
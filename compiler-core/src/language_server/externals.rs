@@ -0,0 +1,141 @@
+use camino::{Utf8Path, Utf8PathBuf};
+use ecow::EcoString;
+
+use crate::{
+    ast::{SrcSpan, TypedFunction},
+    build::Target,
+    io::{DirWalker, FileSystemReader},
+    paths::normalise,
+};
+
+/// The place where the native implementation of an external function is
+/// defined.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NativeLocation {
+    pub path: Utf8PathBuf,
+    pub src: EcoString,
+    pub span: SrcSpan,
+}
+
+/// Finds the native implementation of an external function, preferring the
+/// one for the given target.
+///
+/// - Erlang modules live in a single namespace, so the `.erl` file is looked
+///   for in the `src` and `test` directories of each of the given packages, in
+///   order, the same way they are found when copying native files.
+/// - JavaScript modules are referenced by a path relative to the Gleam module
+///   defining the function.
+///
+/// When the file is found but the function isn't, the start of the file is
+/// returned.
+///
+pub fn native_location(
+    io: &impl FileSystemReader,
+    function: &TypedFunction,
+    gleam_module_path: &Utf8Path,
+    package_roots: &[Utf8PathBuf],
+    target: Target,
+) -> Option<NativeLocation> {
+    let erlang = || {
+        let (module, name, _) = function.external_erlang.as_ref()?;
+        erlang_location(io, package_roots, module, name)
+    };
+    let javascript = || {
+        let (path, name, _) = function.external_javascript.as_ref()?;
+        javascript_location(io, gleam_module_path, path, name)
+    };
+    match target {
+        Target::Erlang => erlang().or_else(javascript),
+        Target::JavaScript => javascript().or_else(erlang),
+    }
+}
+
+fn erlang_location(
+    io: &impl FileSystemReader,
+    package_roots: &[Utf8PathBuf],
+    module: &str,
+    function: &str,
+) -> Option<NativeLocation> {
+    let file_name = format!("{module}.erl");
+    let path = package_roots
+        .iter()
+        .flat_map(|root| [root.join("src"), root.join("test")])
+        .filter(|directory| io.is_directory(directory))
+        .find_map(|directory| {
+            DirWalker::new(directory)
+                .into_file_iter(io)
+                .filter_map(Result::ok)
+                .find(|path| path.file_name() == Some(file_name.as_str()))
+        })?;
+    let src: EcoString = io.read(&path).ok()?.into();
+    let span = find_erlang_function(&src, function).unwrap_or_default();
+    Some(NativeLocation { path, src, span })
+}
+
+fn javascript_location(
+    io: &impl FileSystemReader,
+    gleam_module_path: &Utf8Path,
+    path: &str,
+    function: &str,
+) -> Option<NativeLocation> {
+    // Only relative paths point to files of the package, others are packages
+    // or modules of the runtime.
+    if !path.starts_with("./") && !path.starts_with("../") {
+        return None;
+    }
+    let path = normalise(&gleam_module_path.parent()?.join(path));
+    if !io.is_file(&path) {
+        return None;
+    }
+    let src: EcoString = io.read(&path).ok()?.into();
+    let span = find_javascript_function(&src, function).unwrap_or_default();
+    Some(NativeLocation { path, src, span })
+}
+
+/// Finds the first clause of an Erlang function, which starts at the beginning
+/// of a line with the name of the function, possibly quoted.
+///
+fn find_erlang_function(src: &str, name: &str) -> Option<SrcSpan> {
+    let quoted = format!("'{name}'");
+    lines_with_offsets(src).find_map(|(offset, line)| {
+        [name, quoted.as_str()].into_iter().find_map(|candidate| {
+            let rest = line.strip_prefix(candidate)?;
+            rest.trim_start()
+                .starts_with('(')
+                .then(|| span(offset, candidate))
+        })
+    })
+}
+
+/// Finds an exported JavaScript or TypeScript function, defined either with
+/// `function` or as a variable.
+///
+fn find_javascript_function(src: &str, name: &str) -> Option<SrcSpan> {
+    lines_with_offsets(src).find_map(|(offset, line)| {
+        let declaration = line.trim_start().strip_prefix("export ")?;
+        let declaration = declaration.strip_prefix("async ").unwrap_or(declaration);
+        let declaration = ["function* ", "function ", "const ", "let ", "var "]
+            .into_iter()
+            .find_map(|keyword| declaration.strip_prefix(keyword))?
+            .trim_start();
+        let rest = declaration.strip_prefix(name)?;
+        if rest.starts_with(|c: char| c.is_alphanumeric() || c == '_' || c == '$') {
+            return None;
+        }
+        let start = offset + (line.len() - declaration.len());
+        Some(span(start, name))
+    })
+}
+
+fn lines_with_offsets(src: &str) -> impl Iterator<Item = (usize, &str)> {
+    src.split_inclusive('\n').scan(0, |offset, line| {
+        let start = *offset;
+        *offset += line.len();
+        Some((start, line))
+    })
+}
+
+fn span(start: usize, name: &str) -> SrcSpan {
+    SrcSpan::new(start as u32, (start + name.len()) as u32)
+}
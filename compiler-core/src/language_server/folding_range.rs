@@ -0,0 +1,218 @@
+use std::sync::Arc;
+
+use itertools::Itertools;
+use lsp_types::{FoldingRange, FoldingRangeKind};
+use vec1::Vec1;
+
+use crate::{
+    ast::{
+        self, visit::Visit, FunctionLiteralKind, SrcSpan, TypeAst, TypedArg, TypedClause,
+        TypedCustomType, TypedDefinition, TypedExpr, TypedFunction, TypedStatement,
+    },
+    build::Module,
+    line_numbers::LineNumbers,
+    type_::{Type, TypedCallArg},
+};
+
+/// Returns the ranges of a module that can be folded, sorted by the line
+/// they start on:
+///
+/// - Functions, custom types, blocks and anonymous functions.
+/// - Case expressions and each of their clauses.
+/// - List literals and records spanning multiple lines.
+/// - Groups of consecutive imports.
+/// - Runs of consecutive documentation comments.
+///
+/// Only one range is returned for each line, the outermost one, as editors
+/// can only show a single folding marker per line.
+///
+pub fn folding_ranges(module: &Module) -> Vec<FoldingRange> {
+    let line_numbers = LineNumbers::new(&module.code);
+    let mut folder = Folder {
+        line_numbers: &line_numbers,
+        ranges: vec![],
+    };
+
+    folder.visit_typed_module(&module.ast);
+    folder.fold_imports(&module.ast.definitions);
+    folder.fold_comments(&module.extra.module_comments);
+    folder.fold_comments(&module.extra.doc_comments);
+
+    let mut ranges = folder.ranges;
+    // Sorting is stable, so for ranges starting on the same line the first
+    // one found, which is the outermost, is kept.
+    ranges.sort_by_key(|range| range.start_line);
+    ranges.dedup_by_key(|range| range.start_line);
+    ranges
+}
+
+struct Folder<'a> {
+    line_numbers: &'a LineNumbers,
+    ranges: Vec<FoldingRange>,
+}
+
+impl Folder<'_> {
+    fn line(&self, byte_index: u32) -> u32 {
+        self.line_numbers.line_number(byte_index) - 1
+    }
+
+    /// Folds all the lines spanned by a piece of code.
+    ///
+    fn fold(&mut self, location: SrcSpan, kind: Option<FoldingRangeKind>) {
+        let start_line = self.line(location.start);
+        let end_line = self.line(location.end);
+        self.push(start_line, end_line, kind);
+    }
+
+    /// Folds a piece of code ending with a closing bracket, leaving the line
+    /// of the bracket visible so that a folded `{ ... }` still shows its `}`.
+    ///
+    fn fold_delimited(&mut self, location: SrcSpan) {
+        let start_line = self.line(location.start);
+        let end_line = self.line(location.end).saturating_sub(1);
+        self.push(start_line, end_line, None);
+    }
+
+    fn push(&mut self, start_line: u32, end_line: u32, kind: Option<FoldingRangeKind>) {
+        if end_line <= start_line {
+            return;
+        }
+        self.ranges.push(FoldingRange {
+            start_line,
+            end_line,
+            kind,
+            ..Default::default()
+        });
+    }
+
+    /// Folds groups of imports with no other definition in between.
+    ///
+    fn fold_imports(&mut self, definitions: &[TypedDefinition]) {
+        // Imports are analysed before the other definitions, so they are
+        // sorted back in the order they appear in the source.
+        let definitions = definitions
+            .iter()
+            .map(|definition| (definition.location(), definition.is_import()))
+            .sorted_by_key(|(location, _)| location.start);
+
+        let mut group: Option<SrcSpan> = None;
+        for (location, is_import) in definitions {
+            if is_import {
+                group = Some(match group {
+                    Some(group) => group.merge(&location),
+                    None => location,
+                });
+            } else if let Some(group) = group.take() {
+                self.fold(group, Some(FoldingRangeKind::Imports));
+            }
+        }
+        if let Some(group) = group {
+            self.fold(group, Some(FoldingRangeKind::Imports));
+        }
+    }
+
+    /// Folds runs of comments on consecutive lines.
+    ///
+    fn fold_comments(&mut self, comments: &[SrcSpan]) {
+        let mut run: Option<SrcSpan> = None;
+        for comment in comments {
+            match &mut run {
+                Some(run) if self.line(comment.start) == self.line(run.end) + 1 => {
+                    run.end = comment.end;
+                }
+                _ => {
+                    if let Some(run) = run.replace(*comment) {
+                        self.fold(run, Some(FoldingRangeKind::Comment));
+                    }
+                }
+            }
+        }
+        if let Some(run) = run {
+            self.fold(run, Some(FoldingRangeKind::Comment));
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for Folder<'_> {
+    fn visit_typed_function(&mut self, function: &'ast TypedFunction) {
+        self.fold_delimited(SrcSpan::new(function.location.start, function.end_position));
+        ast::visit::visit_typed_function(self, function);
+    }
+
+    fn visit_typed_custom_type(&mut self, custom_type: &'ast TypedCustomType) {
+        self.fold_delimited(SrcSpan::new(
+            custom_type.location.start,
+            custom_type.end_position,
+        ));
+        ast::visit::visit_typed_custom_type(self, custom_type);
+    }
+
+    fn visit_typed_expr_block(
+        &mut self,
+        location: &'ast SrcSpan,
+        statements: &'ast [TypedStatement],
+    ) {
+        self.fold_delimited(*location);
+        ast::visit::visit_typed_expr_block(self, location, statements);
+    }
+
+    fn visit_typed_expr_fn(
+        &mut self,
+        location: &'ast SrcSpan,
+        type_: &'ast Arc<Type>,
+        kind: &'ast FunctionLiteralKind,
+        args: &'ast [TypedArg],
+        body: &'ast Vec1<TypedStatement>,
+        return_annotation: &'ast Option<TypeAst>,
+    ) {
+        // The callback of a `use` spans the rest of the enclosing block, so
+        // there's nothing to fold.
+        if let FunctionLiteralKind::Anonymous { .. } = kind {
+            self.fold_delimited(*location);
+        }
+        ast::visit::visit_typed_expr_fn(self, location, type_, kind, args, body, return_annotation);
+    }
+
+    fn visit_typed_expr_list(
+        &mut self,
+        location: &'ast SrcSpan,
+        type_: &'ast Arc<Type>,
+        elements: &'ast [TypedExpr],
+        tail: &'ast Option<Box<TypedExpr>>,
+    ) {
+        self.fold_delimited(*location);
+        ast::visit::visit_typed_expr_list(self, location, type_, elements, tail);
+    }
+
+    fn visit_typed_expr_call(
+        &mut self,
+        location: &'ast SrcSpan,
+        type_: &'ast Arc<Type>,
+        fun: &'ast TypedExpr,
+        args: &'ast [TypedCallArg],
+    ) {
+        if fun.is_record_builder() {
+            self.fold_delimited(*location);
+        }
+        ast::visit::visit_typed_expr_call(self, location, type_, fun, args);
+    }
+
+    fn visit_typed_expr_case(
+        &mut self,
+        location: &'ast SrcSpan,
+        type_: &'ast Arc<Type>,
+        subjects: &'ast [TypedExpr],
+        clauses: &'ast [TypedClause],
+    ) {
+        self.fold_delimited(*location);
+        ast::visit::visit_typed_expr_case(self, location, type_, subjects, clauses);
+    }
+
+    fn visit_typed_clause(&mut self, clause: &'ast TypedClause) {
+        // A clause whose body is a block is folded by the block itself.
+        if !matches!(clause.then, TypedExpr::Block { .. }) {
+            self.fold(clause.location, None);
+        }
+        ast::visit::visit_typed_clause(self, clause);
+    }
+}
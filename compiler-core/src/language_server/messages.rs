@@ -1,14 +1,16 @@
 use camino::Utf8PathBuf;
 use lsp::{
     notification::{DidChangeWatchedFiles, DidOpenTextDocument},
-    request::GotoDefinition,
+    request::{GotoDefinition, GotoTypeDefinition, WillRenameFiles},
 };
 use lsp_types::{
     self as lsp,
     notification::{DidChangeTextDocument, DidCloseTextDocument, DidSaveTextDocument},
     request::{
-        CodeActionRequest, Completion, DocumentSymbolRequest, Formatting, HoverRequest,
-        PrepareRenameRequest, Rename, SignatureHelpRequest,
+        CallHierarchyIncomingCalls, CallHierarchyOutgoingCalls, CallHierarchyPrepare,
+        CodeActionRequest, Completion, DocumentHighlightRequest, DocumentSymbolRequest,
+        FoldingRangeRequest, Formatting, HoverRequest, PrepareRenameRequest, Rename,
+        SelectionRangeRequest, SignatureHelpRequest,
     },
 };
 use std::time::Duration;
@@ -24,12 +26,20 @@ pub enum Request {
     Format(lsp::DocumentFormattingParams),
     Hover(lsp::HoverParams),
     GoToDefinition(lsp::GotoDefinitionParams),
+    GoToTypeDefinition(lsp::GotoDefinitionParams),
     Completion(lsp::CompletionParams),
     CodeAction(lsp::CodeActionParams),
     SignatureHelp(lsp::SignatureHelpParams),
     DocumentSymbol(lsp::DocumentSymbolParams),
+    DocumentHighlight(lsp::DocumentHighlightParams),
+    FoldingRange(lsp::FoldingRangeParams),
+    SelectionRange(lsp::SelectionRangeParams),
     PrepareRename(lsp::TextDocumentPositionParams),
     Rename(lsp::RenameParams),
+    WillRenameFiles(lsp::RenameFilesParams),
+    PrepareCallHierarchy(lsp::CallHierarchyPrepareParams),
+    IncomingCalls(Box<lsp::CallHierarchyIncomingCallsParams>),
+    OutgoingCalls(Box<lsp::CallHierarchyOutgoingCallsParams>),
 }
 
 impl Request {
@@ -48,6 +58,10 @@ impl Request {
                 let params = cast_request::<GotoDefinition>(request);
                 Some(Message::Request(id, Request::GoToDefinition(params)))
             }
+            "textDocument/typeDefinition" => {
+                let params = cast_request::<GotoTypeDefinition>(request);
+                Some(Message::Request(id, Request::GoToTypeDefinition(params)))
+            }
             "textDocument/completion" => {
                 let params = cast_request::<Completion>(request);
                 Some(Message::Request(id, Request::Completion(params)))
@@ -64,14 +78,48 @@ impl Request {
                 let params = cast_request::<DocumentSymbolRequest>(request);
                 Some(Message::Request(id, Request::DocumentSymbol(params)))
             }
+            "textDocument/documentHighlight" => {
+                let params = cast_request::<DocumentHighlightRequest>(request);
+                Some(Message::Request(id, Request::DocumentHighlight(params)))
+            }
+            "textDocument/foldingRange" => {
+                let params = cast_request::<FoldingRangeRequest>(request);
+                Some(Message::Request(id, Request::FoldingRange(params)))
+            }
+            "textDocument/selectionRange" => {
+                let params = cast_request::<SelectionRangeRequest>(request);
+                Some(Message::Request(id, Request::SelectionRange(params)))
+            }
             "textDocument/rename" => {
                 let params = cast_request::<Rename>(request);
                 Some(Message::Request(id, Request::Rename(params)))
             }
+            "workspace/willRenameFiles" => {
+                let params = cast_request::<WillRenameFiles>(request);
+                Some(Message::Request(id, Request::WillRenameFiles(params)))
+            }
             "textDocument/prepareRename" => {
                 let params = cast_request::<PrepareRenameRequest>(request);
                 Some(Message::Request(id, Request::PrepareRename(params)))
             }
+            "textDocument/prepareCallHierarchy" => {
+                let params = cast_request::<CallHierarchyPrepare>(request);
+                Some(Message::Request(id, Request::PrepareCallHierarchy(params)))
+            }
+            "callHierarchy/incomingCalls" => {
+                let params = cast_request::<CallHierarchyIncomingCalls>(request);
+                Some(Message::Request(
+                    id,
+                    Request::IncomingCalls(Box::new(params)),
+                ))
+            }
+            "callHierarchy/outgoingCalls" => {
+                let params = cast_request::<CallHierarchyOutgoingCalls>(request);
+                Some(Message::Request(
+                    id,
+                    Request::OutgoingCalls(Box::new(params)),
+                ))
+            }
             _ => None,
         }
     }
@@ -1,14 +1,14 @@
 use std::collections::HashMap;
 
-use ecow::EcoString;
 use lsp_types::{RenameParams, TextEdit, Url, WorkspaceEdit};
 
 use crate::{
     analyse::name,
-    ast::{self, visit::Visit, SrcSpan, TypedModule},
+    ast::SrcSpan,
     build::Module,
     line_numbers::LineNumbers,
-    type_::{error::Named, ValueConstructor, ValueConstructorVariant},
+    reference::{find_variable_references, module_references, ReferenceKind, Symbol},
+    type_::error::Named,
 };
 
 use super::TextEdits;
@@ -49,7 +49,7 @@ pub fn rename_local_variable(
     let uri = params.text_document_position.text_document.uri.clone();
     let mut edits = TextEdits::new(line_numbers);
 
-    let references = RenameLocalVariable::new(definition_location).references(&module.ast);
+    let references = find_variable_references(module, definition_location);
 
     match kind {
         VariableRenameKind::Variable => edits.replace(definition_location, params.new_name.clone()),
@@ -65,78 +65,65 @@ pub fn rename_local_variable(
     Some(workspace_edit(uri, edits.edits))
 }
 
-struct RenameLocalVariable {
-    definition_location: SrcSpan,
-    references: Vec<SrcSpan>,
-}
-
-impl RenameLocalVariable {
-    fn new(definition_location: SrcSpan) -> Self {
-        Self {
-            references: Vec::new(),
-            definition_location,
-        }
-    }
-
-    fn references(mut self, ast: &TypedModule) -> Vec<SrcSpan> {
-        self.visit_typed_module(ast);
-        self.references
-    }
-}
-
-impl<'ast> Visit<'ast> for RenameLocalVariable {
-    fn visit_typed_function(&mut self, fun: &'ast ast::TypedFunction) {
-        if fun.full_location().contains(self.definition_location.start) {
-            ast::visit::visit_typed_function(self, fun);
+/// Renames a function, constant, type, record constructor or label in all
+/// the given modules, along with its definition.
+///
+/// Only the references using the original name are renamed: when a value or
+/// type is imported unqualified with an alias, the name is only changed in the
+/// import and the code using the alias is left as it is.
+///
+pub fn rename_module_symbol<'a>(
+    modules: impl IntoIterator<Item = (Url, &'a Module)>,
+    symbol: &Symbol,
+    new_name: &str,
+) -> Option<WorkspaceEdit> {
+    let (old_name, kind) = match symbol {
+        Symbol::ModuleValue { name, .. } if name.starts_with(char::is_uppercase) => {
+            (name, Named::CustomTypeVariant)
         }
+        Symbol::ModuleValue { name, .. } => (name, Named::Function),
+        Symbol::ModuleType { name, .. } => (name, Named::Type),
+        Symbol::Label { label, .. } => (label, Named::Label),
+        Symbol::LocalVariable { .. } | Symbol::ImportedModule { .. } => return None,
+    };
+    if name::check_name_case(Default::default(), &new_name.into(), kind).is_err() {
+        return None;
     }
 
-    fn visit_typed_expr_var(
-        &mut self,
-        location: &'ast SrcSpan,
-        constructor: &'ast ValueConstructor,
-        _name: &'ast EcoString,
-    ) {
-        match constructor.variant {
-            ValueConstructorVariant::LocalVariable {
-                location: definition_location,
-                ..
-            } if definition_location == self.definition_location => self.references.push(*location),
-            _ => {}
+    let mut changes = HashMap::new();
+    for (uri, module) in modules {
+        let line_numbers = LineNumbers::new(&module.code);
+        let mut edits = TextEdits::new(&line_numbers);
+        for reference in module_references(module) {
+            let location = reference.location;
+            if &reference.symbol != symbol
+                || module
+                    .code
+                    .get(location.start as usize..location.end as usize)
+                    != Some(old_name.as_str())
+            {
+                continue;
+            }
+            match reference.kind {
+                ReferenceKind::Definition | ReferenceKind::Usage => {
+                    edits.replace(location, new_name.into())
+                }
+                // The variable keeps its name, so it has to be written out
+                // after the colon following the label.
+                ReferenceKind::LabelShorthand => {
+                    edits.replace(location, new_name.into());
+                    edits.insert(location.end + 1, format!(" {old_name}"));
+                }
+            }
         }
-    }
-
-    fn visit_typed_clause_guard_var(
-        &mut self,
-        location: &'ast SrcSpan,
-        _name: &'ast EcoString,
-        _type_: &'ast std::sync::Arc<crate::type_::Type>,
-        definition_location: &'ast SrcSpan,
-    ) {
-        if *definition_location == self.definition_location {
-            self.references.push(*location)
+        if !edits.edits.is_empty() {
+            let _ = changes.insert(uri, edits.edits);
         }
     }
 
-    fn visit_typed_pattern_var_usage(
-        &mut self,
-        location: &'ast SrcSpan,
-        _name: &'ast EcoString,
-        constructor: &'ast Option<ValueConstructor>,
-        _type_: &'ast std::sync::Arc<crate::type_::Type>,
-    ) {
-        let variant = match constructor {
-            Some(constructor) => &constructor.variant,
-            None => return,
-        };
-        match variant {
-            ValueConstructorVariant::LocalVariable {
-                location: definition_location,
-                ..
-            } if *definition_location == self.definition_location => {
-                self.references.push(*location)
-            }
-            _ => {}
-        }
-    }
+    Some(WorkspaceEdit {
+        changes: Some(changes),
+        document_changes: None,
+        change_annotations: None,
+    })
 }
@@ -126,7 +126,14 @@ where
         progress_reporter: Reporter,
     ) -> Result<Project<IO, Reporter>, Error> {
         tracing::info!(?path, "creating_new_language_server_engine");
-        let paths = ProjectPaths::new(path);
+        // The members of a workspace use its manifest and build directory.
+        // TODO: route all the members to a single engine for the workspace.
+        // For now each member has its own engine, which compiles the members
+        // it depends on again as path dependencies.
+        let paths = match crate::workspace::find_root(&io, &path) {
+            Some(workspace_root) => ProjectPaths::in_workspace(path, workspace_root),
+            None => ProjectPaths::new(path),
+        };
         let config_path = paths.root_config();
         let modification_time = io.modification_time(&config_path)?;
         let toml = io.read(&config_path)?;
@@ -0,0 +1,206 @@
+use std::sync::Arc;
+
+use lsp_types::{Position, Range, SelectionRange};
+use vec1::Vec1;
+
+use crate::{
+    ast::{
+        self, visit::Visit, Definition, FunctionLiteralKind, SrcSpan, TypeAst, TypedArg,
+        TypedClause, TypedCustomType, TypedDefinition, TypedExpr, TypedFunction, TypedPattern,
+        TypedStatement,
+    },
+    build::{Located, Module},
+    line_numbers::LineNumbers,
+    type_::{Type, TypedCallArg},
+};
+
+use super::src_span_to_lsp_range;
+
+/// Returns the ranges to select, from the innermost to the outermost, when
+/// repeatedly expanding the selection from the given position. Each range is
+/// the span of one of the nodes of the typed AST enclosing the position.
+///
+pub fn selection_range(
+    module: &Module,
+    line_numbers: &LineNumbers,
+    position: Position,
+) -> SelectionRange {
+    let byte_index = line_numbers.byte_index(position.line, position.character);
+
+    let mut finder = EnclosingSpans {
+        byte_index,
+        spans: vec![],
+    };
+    finder.visit_typed_module(&module.ast);
+    let mut spans = finder.spans;
+    // The node found at the position can be more precise than the ones found
+    // by visiting the AST, for example a label or an unqualified import.
+    spans.extend(
+        module
+            .find_node(byte_index)
+            .and_then(|node| located_span(&node)),
+    );
+
+    // Spans are sorted from the outermost to the innermost, and a span is only
+    // kept if it is within the previous one, as clients expect each range to
+    // contain the ones it is the parent of.
+    spans.sort_by_key(|span| (span.start, std::cmp::Reverse(span.end)));
+    let mut selection: Option<SelectionRange> = None;
+    let mut outer: Option<SrcSpan> = None;
+    for span in spans {
+        if outer
+            .is_some_and(|outer| outer == span || span.start < outer.start || span.end > outer.end)
+        {
+            continue;
+        }
+        outer = Some(span);
+        selection = Some(SelectionRange {
+            range: src_span_to_lsp_range(span, line_numbers),
+            parent: selection.map(Box::new),
+        });
+    }
+
+    selection.unwrap_or_else(|| SelectionRange {
+        range: Range::new(position, position),
+        parent: None,
+    })
+}
+
+fn located_span(located: &Located<'_>) -> Option<SrcSpan> {
+    match located {
+        Located::Pattern(pattern) => Some(pattern.location()),
+        Located::PatternSpread {
+            spread_location, ..
+        } => Some(*spread_location),
+        Located::Statement(statement) => Some(statement.location()),
+        Located::Expression(expression) => Some(expression.location()),
+        Located::Arg(arg) => Some(arg.location),
+        Located::Annotation(location, _) | Located::Label(location, _) => Some(*location),
+        Located::UnqualifiedImport(import) => Some(*import.location),
+        Located::ModuleStatement(_) | Located::FunctionBody(_) => None,
+    }
+}
+
+/// Collects the spans of all the nodes containing a byte index.
+///
+struct EnclosingSpans {
+    byte_index: u32,
+    spans: Vec<SrcSpan>,
+}
+
+impl EnclosingSpans {
+    /// Records a span if it contains the byte index, returning whether it
+    /// does.
+    ///
+    fn enclose(&mut self, span: SrcSpan) -> bool {
+        let contains = span.contains(self.byte_index);
+        if contains {
+            self.spans.push(span);
+        }
+        contains
+    }
+
+    fn visit_args(&mut self, args: &[TypedArg]) {
+        for arg in args {
+            if self.enclose(arg.location) {
+                if let Some(annotation) = &arg.annotation {
+                    self.visit_type_ast(annotation);
+                }
+            }
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for EnclosingSpans {
+    fn visit_typed_definition(&mut self, definition: &'ast TypedDefinition) {
+        let location = match definition {
+            Definition::Function(function) => {
+                SrcSpan::new(function.location.start, function.end_position)
+            }
+            Definition::CustomType(custom_type) => {
+                SrcSpan::new(custom_type.location.start, custom_type.end_position)
+            }
+            Definition::TypeAlias(_) | Definition::Import(_) | Definition::ModuleConstant(_) => {
+                definition.location()
+            }
+        };
+        if self.enclose(location) {
+            ast::visit::visit_typed_definition(self, definition);
+        }
+    }
+
+    fn visit_typed_function(&mut self, function: &'ast TypedFunction) {
+        if self.enclose(function.location) {
+            self.visit_args(&function.arguments);
+            if let Some(annotation) = &function.return_annotation {
+                self.visit_type_ast(annotation);
+            }
+        }
+        ast::visit::visit_typed_function(self, function);
+    }
+
+    fn visit_typed_custom_type(&mut self, custom_type: &'ast TypedCustomType) {
+        for constructor in &custom_type.constructors {
+            if !self.enclose(constructor.location) {
+                continue;
+            }
+            for argument in &constructor.arguments {
+                if self.enclose(argument.location) {
+                    self.visit_type_ast(&argument.ast);
+                }
+            }
+        }
+    }
+
+    fn visit_typed_statement(&mut self, statement: &'ast TypedStatement) {
+        if self.enclose(statement.location()) {
+            ast::visit::visit_typed_statement(self, statement);
+        }
+    }
+
+    fn visit_typed_expr(&mut self, expression: &'ast TypedExpr) {
+        if self.enclose(expression.location()) {
+            ast::visit::visit_typed_expr(self, expression);
+        }
+    }
+
+    fn visit_typed_expr_fn(
+        &mut self,
+        location: &'ast SrcSpan,
+        type_: &'ast Arc<Type>,
+        kind: &'ast FunctionLiteralKind,
+        args: &'ast [TypedArg],
+        body: &'ast Vec1<TypedStatement>,
+        return_annotation: &'ast Option<TypeAst>,
+    ) {
+        self.visit_args(args);
+        if let Some(annotation) = return_annotation {
+            self.visit_type_ast(annotation);
+        }
+        ast::visit::visit_typed_expr_fn(self, location, type_, kind, args, body, return_annotation);
+    }
+
+    fn visit_typed_call_arg(&mut self, arg: &'ast TypedCallArg) {
+        if self.enclose(arg.location) {
+            ast::visit::visit_typed_call_arg(self, arg);
+        }
+    }
+
+    fn visit_typed_clause(&mut self, clause: &'ast TypedClause) {
+        if self.enclose(clause.location) {
+            ast::visit::visit_typed_clause(self, clause);
+        }
+    }
+
+    fn visit_typed_pattern(&mut self, pattern: &'ast TypedPattern) {
+        if self.enclose(pattern.location()) {
+            ast::visit::visit_typed_pattern(self, pattern);
+        }
+    }
+
+    fn visit_type_ast(&mut self, node: &'ast TypeAst) {
+        if self.enclose(node.location()) {
+            ast::visit::visit_type_ast(self, node);
+        }
+    }
+}
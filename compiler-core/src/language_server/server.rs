@@ -19,8 +19,8 @@ use camino::{Utf8Path, Utf8PathBuf};
 use debug_ignore::DebugIgnore;
 use itertools::Itertools;
 use lsp_types::{
-    self as lsp, HoverProviderCapability, InitializeParams, Position, PublishDiagnosticsParams,
-    Range, RenameOptions, TextEdit, Url,
+    self as lsp, HoverProviderCapability, InitializeParams, PublishDiagnosticsParams,
+    RenameOptions, Url,
 };
 use serde_json::Value as Json;
 use std::collections::{HashMap, HashSet};
@@ -101,12 +101,20 @@ where
             Request::Format(param) => self.format(param),
             Request::Hover(param) => self.hover(param),
             Request::GoToDefinition(param) => self.goto_definition(param),
+            Request::GoToTypeDefinition(param) => self.goto_type_definition(param),
             Request::Completion(param) => self.completion(param),
             Request::CodeAction(param) => self.code_action(param),
             Request::SignatureHelp(param) => self.signature_help(param),
             Request::DocumentSymbol(param) => self.document_symbol(param),
+            Request::DocumentHighlight(param) => self.document_highlight(param),
+            Request::FoldingRange(param) => self.folding_range(param),
+            Request::SelectionRange(param) => self.selection_range(param),
             Request::PrepareRename(param) => self.prepare_rename(param),
             Request::Rename(param) => self.rename(param),
+            Request::WillRenameFiles(param) => self.will_rename_files(param),
+            Request::PrepareCallHierarchy(param) => self.prepare_call_hierarchy(param),
+            Request::IncomingCalls(param) => self.incoming_calls(*param),
+            Request::OutgoingCalls(param) => self.outgoing_calls(*param),
         };
 
         self.publish_feedback(feedback);
@@ -276,26 +284,19 @@ where
 
     fn format(&mut self, params: lsp::DocumentFormattingParams) -> (Json, Feedback) {
         let path = super::path(&params.text_document.uri);
-        let mut new_text = String::new();
 
         let src = match self.io.read(&path) {
-            Ok(src) => src.into(),
+            Ok(src) => src,
             Err(error) => return self.path_error_response(path, error),
         };
 
-        if let Err(error) = crate::format::pretty(&mut new_text, &src, &path) {
-            return self.path_error_response(path, error);
+        match super::format(&src, &path) {
+            Ok(edits) => {
+                let json = serde_json::to_value(edits).expect("to JSON value");
+                (json, Feedback::default())
+            }
+            Err(error) => self.path_error_response(path, error),
         }
-
-        let line_count = src.lines().count() as u32;
-
-        let edit = TextEdit {
-            range: Range::new(Position::new(0, 0), Position::new(line_count, 0)),
-            new_text,
-        };
-        let json = serde_json::to_value(vec![edit]).expect("to JSON value");
-
-        (json, Feedback::default())
     }
 
     fn hover(&mut self, params: lsp::HoverParams) -> (Json, Feedback) {
@@ -308,6 +309,11 @@ where
         self.respond_with_engine(path, |engine| engine.goto_definition(params))
     }
 
+    fn goto_type_definition(&mut self, params: lsp::GotoDefinitionParams) -> (Json, Feedback) {
+        let path = super::path(&params.text_document_position_params.text_document.uri);
+        self.respond_with_engine(path, |engine| engine.goto_type_definition(params))
+    }
+
     fn completion(&mut self, params: lsp::CompletionParams) -> (Json, Feedback) {
         let path = super::path(&params.text_document_position.text_document.uri);
 
@@ -335,6 +341,42 @@ where
         self.respond_with_engine(path, |engine| engine.document_symbol(params))
     }
 
+    fn document_highlight(&mut self, params: lsp::DocumentHighlightParams) -> (Json, Feedback) {
+        let path = super::path(&params.text_document_position_params.text_document.uri);
+        self.respond_with_engine(path, |engine| engine.document_highlight(params))
+    }
+
+    fn folding_range(&mut self, params: lsp::FoldingRangeParams) -> (Json, Feedback) {
+        let path = super::path(&params.text_document.uri);
+        self.respond_with_engine(path, |engine| engine.folding_range(params))
+    }
+
+    fn selection_range(&mut self, params: lsp::SelectionRangeParams) -> (Json, Feedback) {
+        let path = super::path(&params.text_document.uri);
+        self.respond_with_engine(path, |engine| engine.selection_range(params))
+    }
+
+    fn will_rename_files(&mut self, params: lsp::RenameFilesParams) -> (Json, Feedback) {
+        let Some(file) = params.files.first() else {
+            return (Json::Null, Feedback::default());
+        };
+        let Ok(uri) = Url::parse(&file.old_uri) else {
+            return (Json::Null, Feedback::default());
+        };
+        let can_rename_files = self
+            .initialise_params
+            .capabilities
+            .workspace
+            .as_ref()
+            .and_then(|workspace| workspace.workspace_edit.as_ref())
+            .and_then(|edit| edit.resource_operations.as_ref())
+            .is_some_and(|operations| operations.contains(&lsp::ResourceOperationKind::Rename));
+        let path = super::path(&uri);
+        self.respond_with_engine(path, |engine| {
+            engine.will_rename_files(params, can_rename_files)
+        })
+    }
+
     fn prepare_rename(&mut self, params: lsp::TextDocumentPositionParams) -> (Json, Feedback) {
         let path = super::path(&params.text_document.uri);
         self.respond_with_engine(path, |engine| engine.prepare_rename(params))
@@ -345,6 +387,30 @@ where
         self.respond_with_engine(path, |engine| engine.rename(params))
     }
 
+    fn prepare_call_hierarchy(
+        &mut self,
+        params: lsp::CallHierarchyPrepareParams,
+    ) -> (Json, Feedback) {
+        let path = super::path(&params.text_document_position_params.text_document.uri);
+        self.respond_with_engine(path, |engine| engine.prepare_call_hierarchy(params))
+    }
+
+    fn incoming_calls(
+        &mut self,
+        params: lsp::CallHierarchyIncomingCallsParams,
+    ) -> (Json, Feedback) {
+        let path = call_hierarchy_item_path(&params.item);
+        self.respond_with_engine(path, |engine| engine.incoming_calls(params))
+    }
+
+    fn outgoing_calls(
+        &mut self,
+        params: lsp::CallHierarchyOutgoingCallsParams,
+    ) -> (Json, Feedback) {
+        let path = call_hierarchy_item_path(&params.item);
+        self.respond_with_engine(path, |engine| engine.outgoing_calls(params))
+    }
+
     fn cache_file_in_memory(&mut self, path: Utf8PathBuf, text: String) -> Feedback {
         self.project_changed(&path);
         if let Err(error) = self.io.write_mem_cache(&path, &text) {
@@ -399,7 +465,7 @@ fn initialisation_handshake(connection: &lsp_server::Connection) -> InitializePa
                 )),
             },
         )),
-        selection_range_provider: None,
+        selection_range_provider: Some(lsp::SelectionRangeProviderCapability::Simple(true)),
         hover_provider: Some(HoverProviderCapability::Simple(true)),
         completion_provider: Some(lsp::CompletionOptions {
             resolve_provider: None,
@@ -418,10 +484,10 @@ fn initialisation_handshake(connection: &lsp_server::Connection) -> InitializePa
             },
         }),
         definition_provider: Some(lsp::OneOf::Left(true)),
-        type_definition_provider: None,
+        type_definition_provider: Some(lsp::TypeDefinitionProviderCapability::Simple(true)),
         implementation_provider: None,
         references_provider: None,
-        document_highlight_provider: None,
+        document_highlight_provider: Some(lsp::OneOf::Left(true)),
         document_symbol_provider: Some(lsp::OneOf::Left(true)),
         workspace_symbol_provider: None,
         code_action_provider: Some(lsp::CodeActionProviderCapability::Simple(true)),
@@ -437,11 +503,26 @@ fn initialisation_handshake(connection: &lsp_server::Connection) -> InitializePa
         })),
         document_link_provider: None,
         color_provider: None,
-        folding_range_provider: None,
+        folding_range_provider: Some(lsp::FoldingRangeProviderCapability::Simple(true)),
         declaration_provider: None,
         execute_command_provider: None,
-        workspace: None,
-        call_hierarchy_provider: None,
+        workspace: Some(lsp::WorkspaceServerCapabilities {
+            workspace_folders: None,
+            file_operations: Some(lsp::WorkspaceFileOperationsServerCapabilities {
+                will_rename: Some(lsp::FileOperationRegistrationOptions {
+                    filters: vec![lsp::FileOperationFilter {
+                        scheme: Some("file".into()),
+                        pattern: lsp::FileOperationPattern {
+                            glob: "**/*.gleam".into(),
+                            matches: Some(lsp::FileOperationPatternKind::File),
+                            options: None,
+                        },
+                    }],
+                }),
+                ..Default::default()
+            }),
+        }),
+        call_hierarchy_provider: Some(lsp::CallHierarchyServerCapability::Simple(true)),
         semantic_tokens_provider: None,
         moniker_provider: None,
         linked_editing_range_provider: None,
@@ -461,7 +542,7 @@ fn initialisation_handshake(connection: &lsp_server::Connection) -> InitializePa
     initialise_params
 }
 
-fn diagnostic_to_lsp(diagnostic: Diagnostic) -> Vec<lsp::Diagnostic> {
+pub fn diagnostic_to_lsp(diagnostic: Diagnostic) -> Vec<lsp::Diagnostic> {
     let severity = match diagnostic.level {
         Level::Error => lsp::DiagnosticSeverity::ERROR,
         Level::Warning => lsp::DiagnosticSeverity::WARNING,
@@ -519,7 +600,9 @@ fn diagnostic_to_lsp(diagnostic: Diagnostic) -> Vec<lsp::Diagnostic> {
     let main = lsp::Diagnostic {
         range,
         severity: Some(severity),
-        code: None,
+        code: diagnostic
+            .code
+            .map(|code| lsp::NumberOrString::String(code.into())),
         code_description: None,
         source: None,
         message: text,
@@ -545,6 +628,13 @@ fn diagnostic_to_lsp(diagnostic: Diagnostic) -> Vec<lsp::Diagnostic> {
     }
 }
 
+/// The items of a call hierarchy can be functions defined in dependencies, so
+/// the project that created them is used rather than the path of the item.
+///
+fn call_hierarchy_item_path(item: &lsp::CallHierarchyItem) -> Utf8PathBuf {
+    super::call_hierarchy::item_project(item).unwrap_or_else(|| super::path(&item.uri))
+}
+
 fn path_to_uri(path: Utf8PathBuf) -> Url {
     let mut file: String = "file://".into();
     file.push_str(&path.as_os_str().to_string_lossy());
@@ -1,11 +1,17 @@
 mod action;
+mod call_hierarchy;
 mod compilation;
 mod completion;
 mod definition;
+mod document_highlight;
 mod document_symbols;
+mod folding_range;
 mod hover;
 mod rename;
+mod rename_files;
+mod selection_range;
 mod signature_help;
+mod type_definition;
 
 use std::{
     collections::{HashMap, HashSet},
@@ -176,6 +182,10 @@ impl FileSystemWriter for LanguageServerTestIO {
         self.io.copy_dir(from, to)
     }
 
+    fn rename(&self, from: &Utf8Path, to: &Utf8Path) -> Result<()> {
+        self.io.rename(from, to)
+    }
+
     fn hardlink(&self, from: &Utf8Path, to: &Utf8Path) -> Result<()> {
         self.io.hardlink(from, to)
     }
@@ -384,6 +394,7 @@ struct TestProject<'a> {
     hex_modules: Vec<(&'a str, &'a str)>,
     dev_hex_modules: Vec<(&'a str, &'a str)>,
     indirect_hex_modules: Vec<(&'a str, &'a str)>,
+    native_files: Vec<(&'a str, &'a str)>,
 }
 
 impl<'a> TestProject<'a> {
@@ -396,13 +407,22 @@ impl<'a> TestProject<'a> {
             hex_modules: vec![],
             dev_hex_modules: vec![],
             indirect_hex_modules: vec![],
+            native_files: vec![],
         }
     }
 
     pub fn src_from_module_url(&self, url: &Url) -> Option<&str> {
+        if let Some((_, src)) = self
+            .native_files
+            .iter()
+            .find(|(path, _)| url.path().ends_with(path))
+        {
+            return Some(src);
+        }
+
         let module_name: EcoString = url
             .path_segments()?
-            .skip_while(|segment| *segment != "src")
+            .skip_while(|segment| *segment != "src" && *segment != "test")
             .skip(1)
             .join("/")
             .trim_end_matches(".gleam")
@@ -457,6 +477,13 @@ impl<'a> TestProject<'a> {
         self
     }
 
+    /// Adds an Erlang or JavaScript file, at a path relative to the root of
+    /// the project.
+    pub fn add_native_file(mut self, path: &'a str, src: &'a str) -> Self {
+        self.native_files.push((path, src));
+        self
+    }
+
     pub fn build_engine(
         &self,
         io: &mut LanguageServerTestIO,
@@ -491,6 +518,11 @@ impl<'a> TestProject<'a> {
         self.test_modules.iter().for_each(|(name, code)| {
             let _ = io.test_module(name, code);
         });
+
+        // Add all the native files
+        self.native_files.iter().for_each(|(path, code)| {
+            io.module(&io.paths.root().join(path), code);
+        });
         for package in &io.manifest.packages {
             let toml_path = engine.paths.build_packages_package_config(&package.name);
             add_package_from_manifest(&mut engine, toml_path, package.clone());
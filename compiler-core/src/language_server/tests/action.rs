@@ -71,9 +71,12 @@ const CONVERT_TO_USE: &str = "Convert to `use`";
 const EXTRACT_VARIABLE: &str = "Extract variable";
 const EXPAND_FUNCTION_CAPTURE: &str = "Expand function capture";
 const GENERATE_DYNAMIC_DECODER: &str = "Generate dynamic decoder";
+const GENERATE_JSON_ENCODER: &str = "Generate JSON encoder";
 const PATTERN_MATCH_ON_ARGUMENT: &str = "Pattern match on argument";
 const PATTERN_MATCH_ON_VARIABLE: &str = "Pattern match on variable";
 const GENERATE_FUNCTION: &str = "Generate function";
+const ORGANIZE_IMPORTS: &str = "Organize imports";
+const REPLACE_DEPRECATED_VALUE: &str = "Replace deprecated value";
 
 macro_rules! assert_code_action {
     ($title:expr, $code:literal, $range:expr $(,)?) => {
@@ -4402,8 +4405,65 @@ pub type LinkedList {
 }
 
 #[test]
-fn no_code_action_to_generate_dynamic_decoder_for_multi_variant_type() {
-    assert_no_code_actions!(
+fn generate_dynamic_decoder_other_custom_type() {
+    let src = "
+pub type Wobble {
+  Wobble(value: Int)
+}
+
+pub type Wibble {
+  Wibble(wobble: Wobble)
+}
+";
+    assert_code_action!(
+        GENERATE_DYNAMIC_DECODER,
+        TestProject::for_source(src),
+        find_position_of("type Wibble").to_selection()
+    );
+}
+
+#[test]
+fn generate_dynamic_decoder_other_custom_type_with_decoder() {
+    let src = "
+import gleam/dynamic/decode
+
+pub type Wobble {
+  Wobble(value: Int)
+}
+
+fn wobble_decoder() -> decode.Decoder(Wobble) {
+  todo
+}
+
+pub type Wibble {
+  Wibble(wobble: Wobble)
+}
+";
+    assert_code_action!(
+        GENERATE_DYNAMIC_DECODER,
+        TestProject::for_source(src).add_module("gleam/dynamic/decode", "pub type Decoder(a)"),
+        find_position_of("type Wibble").to_selection()
+    );
+}
+
+#[test]
+fn generate_dynamic_decoder_multi_variant_type() {
+    assert_code_action!(
+        GENERATE_DYNAMIC_DECODER,
+        "
+pub type Wibble {
+  Wibble(wibble: Int, next: Wibble)
+  Wobble(wobble: Float)
+  Wubble
+}
+",
+        find_position_of("type").to_selection()
+    );
+}
+
+#[test]
+fn generate_dynamic_decoder_multi_variant_type_without_zero_value() {
+    assert_code_action!(
         GENERATE_DYNAMIC_DECODER,
         "
 pub type Wibble {
@@ -4428,6 +4488,147 @@ pub type Wibble {
     );
 }
 
+#[test]
+fn generate_json_encoder() {
+    assert_code_action!(
+        GENERATE_JSON_ENCODER,
+        "
+pub type Person {
+  Person(name: String, age: Int, height: Float, is_cool: Bool, brain: BitArray)
+}
+",
+        find_position_of("type").to_selection()
+    );
+}
+
+#[test]
+fn generate_json_encoder_complex_types() {
+    let src = "
+import gleam/option
+import gleam/dict
+
+pub type Wibble(value) {
+  Wibble(
+    maybe: option.Option(Int),
+    map: dict.Dict(String, List(Float)),
+    other_map: dict.Dict(Int, String),
+    tuple: #(Int, #(String, Nil)),
+    unknown: List(value),
+  )
+}
+";
+
+    assert_code_action!(
+        GENERATE_JSON_ENCODER,
+        TestProject::for_source(src)
+            .add_module("gleam/option", "pub type Option(a)")
+            .add_module("gleam/dict", "pub type Dict(k, v)"),
+        find_position_of("type W").to_selection()
+    );
+}
+
+#[test]
+fn generate_json_encoder_already_imported_module() {
+    let src = "
+import gleam/json as j
+
+pub type Wibble {
+  Wibble(a: Int, b: Float, c: String)
+}
+";
+
+    assert_code_action!(
+        GENERATE_JSON_ENCODER,
+        TestProject::for_source(src).add_module("gleam/json", "pub type Json"),
+        find_position_of("type W").to_selection()
+    );
+}
+
+#[test]
+fn generate_json_encoder_nested_custom_type() {
+    let src = "
+import gleam/option
+
+pub type Wobble {
+  Wobble(value: Int)
+}
+
+pub type Wibble {
+  Wibble(wobbles: List(Wobble), next: option.Option(Wibble))
+}
+";
+    assert_code_action!(
+        GENERATE_JSON_ENCODER,
+        TestProject::for_source(src).add_module("gleam/option", "pub type Option(a)"),
+        find_position_of("type Wibble").to_selection()
+    );
+}
+
+#[test]
+fn generate_json_encoder_nested_custom_type_with_encoder() {
+    let src = "
+import gleam/json
+
+pub type Wobble {
+  Wobble(value: Int)
+}
+
+fn wobble_to_json(wobble: Wobble) -> json.Json {
+  todo
+}
+
+pub type Wibble {
+  Wibble(wobbles: List(Wobble))
+}
+";
+    assert_code_action!(
+        GENERATE_JSON_ENCODER,
+        TestProject::for_source(src).add_module("gleam/json", "pub type Json"),
+        find_position_of("type Wibble").to_selection()
+    );
+}
+
+#[test]
+fn generate_json_encoder_multi_variant_type() {
+    assert_code_action!(
+        GENERATE_JSON_ENCODER,
+        "
+pub type Wibble {
+  Wibble(wibble: Int, next: Wibble)
+  Wobble(wobble: Float)
+  Wubble
+}
+",
+        find_position_of("type").to_selection()
+    );
+}
+
+#[test]
+fn generate_json_encoder_type_without_fields() {
+    assert_code_action!(
+        GENERATE_JSON_ENCODER,
+        "
+pub type Wibble {
+  Wibble
+}
+",
+        find_position_of("type").to_selection()
+    );
+}
+
+#[test]
+fn no_code_action_to_generate_json_encoder_for_type_without_labels() {
+    assert_no_code_actions!(
+        GENERATE_JSON_ENCODER,
+        "
+pub type Wibble {
+  Wibble(Int, Int, String)
+}
+",
+        find_position_of("type").to_selection()
+    );
+}
+
 #[test]
 fn pattern_match_on_argument_empty_tuple() {
     assert_no_code_actions!(
@@ -4871,3 +5072,275 @@ pub type Names {
         find_position_of("[").to_selection()
     );
 }
+
+#[test]
+fn organize_imports_sorts_and_removes_unused_imports() {
+    let src = "import wobble.{wubble}
+import wibble.{Wibble, type Wibble, wibble}
+import unused
+import wibble_wobble as ww
+
+pub fn main() -> Wibble {
+  wibble
+  wobble.wobble()
+  Wibble
+}
+";
+
+    assert_code_action!(
+        ORGANIZE_IMPORTS,
+        TestProject::for_source(src)
+            .add_hex_module("wibble", "pub type Wibble { Wibble } pub const wibble = 1")
+            .add_hex_module("wobble", "pub fn wobble() { 1 } pub const wubble = 1")
+            .add_hex_module("unused", "")
+            .add_hex_module("wibble_wobble", "pub fn main() { 1 }"),
+        find_position_of("main").to_selection(),
+    );
+}
+
+#[test]
+fn organize_imports_keeps_comments() {
+    let src = "// Standard library
+import gleam/list
+import gleam/io
+
+// Local modules
+import wobble
+import wibble.{wibble}
+
+pub fn main() {
+  io.println(\"\")
+  list.map
+  wibble
+  wobble.wobble
+}
+";
+
+    assert_code_action!(
+        ORGANIZE_IMPORTS,
+        TestProject::for_source(src)
+            .add_hex_module("gleam/list", "pub fn map() { 1 }")
+            .add_hex_module("gleam/io", "pub fn println(x) { x }")
+            .add_hex_module("wibble", "pub const wibble = 1")
+            .add_hex_module("wobble", "pub const wobble = 1"),
+        find_position_of("main").to_selection(),
+    );
+}
+
+#[test]
+fn organize_imports_keeps_module_used_in_a_qualified_way() {
+    let src = "import wibble.{wubble}
+
+pub fn main() {
+  wibble.wobble
+}
+";
+
+    assert_code_action!(
+        ORGANIZE_IMPORTS,
+        TestProject::for_source(src)
+            .add_hex_module("wibble", "pub const wubble = 1 pub const wobble = 2"),
+        find_position_of("main").to_selection(),
+    );
+}
+
+#[test]
+fn organize_imports_removes_all_imports() {
+    let src = "import wibble.{wibble}
+import wobble
+
+pub fn main() {
+  1
+}
+";
+
+    assert_code_action!(
+        ORGANIZE_IMPORTS,
+        TestProject::for_source(src)
+            .add_hex_module("wibble", "pub const wibble = 1")
+            .add_hex_module("wobble", ""),
+        find_position_of("main").to_selection(),
+    );
+}
+
+#[test]
+fn organize_imports_moves_imports_to_the_top() {
+    let src = "import wobble
+
+pub fn main() {
+  wibble.wibble
+  wobble.wobble
+}
+
+import wibble
+";
+
+    assert_code_action!(
+        ORGANIZE_IMPORTS,
+        TestProject::for_source(src)
+            .add_hex_module("wibble", "pub const wibble = 1")
+            .add_hex_module("wobble", "pub const wobble = 1"),
+        find_position_of("main").to_selection(),
+    );
+}
+
+#[test]
+fn no_organize_imports_if_already_organized() {
+    let src = "import wibble.{wibble}
+import wobble
+
+pub fn main() {
+  wibble
+  wobble.wobble
+}
+";
+
+    assert_no_code_actions!(
+        ORGANIZE_IMPORTS,
+        TestProject::for_source(src)
+            .add_hex_module("wibble", "pub const wibble = 1")
+            .add_hex_module("wobble", "pub const wobble = 1"),
+        find_position_of("main").to_selection(),
+    );
+}
+
+#[test]
+fn replace_deprecated_function() {
+    let src = "import wibble
+
+pub fn main() {
+  wibble.old(1, 2)
+}
+";
+
+    assert_code_action!(
+        REPLACE_DEPRECATED_VALUE,
+        TestProject::for_source(src).add_hex_module(
+            "wibble",
+            r#"
+@deprecated("Use new instead", replace: "new(_2, _1)")
+pub fn old(a, b) { #(a, b) }
+pub fn new(b, a) { #(a, b) }
+"#
+        ),
+        find_position_of("old").to_selection(),
+    );
+}
+
+#[test]
+fn replace_deprecated_function_adds_import() {
+    let src = "import wibble
+
+pub fn main() {
+  wibble.old(1)
+}
+";
+
+    assert_code_action!(
+        REPLACE_DEPRECATED_VALUE,
+        TestProject::for_source(src)
+            .add_hex_module("wobble", "pub fn new(a) { a }")
+            .add_hex_module(
+                "wibble",
+                r#"
+@deprecated("Use wobble.new instead", replace: "wobble.new")
+pub fn old(a) { a }
+"#
+            ),
+        find_position_of("old").to_selection(),
+    );
+}
+
+#[test]
+fn replace_deprecated_function_only_in_selection() {
+    let src = "import wibble
+
+pub fn main() {
+  wibble.old(1)
+  wibble.old(2)
+}
+";
+
+    assert_code_action!(
+        REPLACE_DEPRECATED_VALUE,
+        TestProject::for_source(src).add_hex_module(
+            "wibble",
+            r#"
+@deprecated("Use new instead", replace: "new")
+pub fn old(a) { a }
+pub fn new(a) { a }
+"#
+        ),
+        find_position_of("old(2)").to_selection(),
+    );
+}
+
+#[test]
+fn replace_deprecated_unqualified_function_keeps_import_used_outside_selection() {
+    let src = "import wibble.{old}
+
+pub fn main() {
+  old(1)
+  old(2)
+}
+";
+
+    assert_code_action!(
+        REPLACE_DEPRECATED_VALUE,
+        TestProject::for_source(src).add_hex_module(
+            "wibble",
+            r#"
+@deprecated("Use new instead", replace: "new")
+pub fn old(a) { a }
+pub fn new(a) { a }
+"#
+        ),
+        find_position_of("old(2)").to_selection(),
+    );
+}
+
+#[test]
+fn replace_deprecated_unqualified_function_removes_import() {
+    let src = "import wibble.{old}
+
+pub fn main() {
+  old(1)
+}
+";
+
+    assert_code_action!(
+        REPLACE_DEPRECATED_VALUE,
+        TestProject::for_source(src).add_hex_module(
+            "wibble",
+            r#"
+@deprecated("Use new instead", replace: "new")
+pub fn old(a) { a }
+pub fn new(a) { a }
+"#
+        ),
+        find_position_of("old(1)").to_selection(),
+    );
+}
+
+#[test]
+fn no_replace_deprecated_function_without_replacement() {
+    assert_no_code_actions!(
+        REPLACE_DEPRECATED_VALUE,
+        TestProject::for_source(
+            "import wibble
+
+pub fn main() {
+  wibble.old(1)
+}
+"
+        )
+        .add_hex_module(
+            "wibble",
+            r#"
+@deprecated("Use new instead")
+pub fn old(a) { a }
+"#
+        ),
+        find_position_of("old").to_selection(),
+    );
+}
@@ -0,0 +1,438 @@
+use lsp_types::{
+    CallHierarchyIncomingCallsParams, CallHierarchyItem, CallHierarchyOutgoingCallsParams,
+    CallHierarchyPrepareParams, Position, Range,
+};
+
+use super::*;
+
+fn prepare(
+    engine: &mut LanguageServerEngine<LanguageServerTestIO, LanguageServerTestIO>,
+    params: TextDocumentPositionParams,
+) -> Option<CallHierarchyItem> {
+    let params = CallHierarchyPrepareParams {
+        text_document_position_params: params,
+        work_done_progress_params: Default::default(),
+    };
+    engine
+        .prepare_call_hierarchy(params)
+        .result
+        .unwrap()
+        .and_then(|items| items.into_iter().next())
+}
+
+/// Shows the given ranges of a module underlined.
+///
+fn show_ranges(src: &str, ranges: &[Range]) -> String {
+    let mut buffer = String::new();
+    for (line_number, line) in src.lines().enumerate() {
+        let mut underline = String::new();
+        for (column_number, _) in line.chars().enumerate() {
+            let position = Position::new(line_number as u32, column_number as u32);
+            let underlined = ranges
+                .iter()
+                .any(|range| range.start <= position && position < range.end);
+            underline.push(if underlined { '▔' } else { ' ' });
+        }
+        buffer.push_str(line);
+        if underline.contains('▔') {
+            buffer.push('\n');
+            buffer.push_str(underline.trim_end());
+        }
+        buffer.push('\n');
+    }
+    buffer
+}
+
+fn show_item(project: &TestProject<'_>, item: &CallHierarchyItem) -> String {
+    let src = project
+        .src_from_module_url(&item.uri)
+        .expect("a module for the item");
+    format!(
+        "{} in {}\n{}",
+        item.name,
+        item.detail.as_deref().unwrap_or_default(),
+        hover::show_hover(src, item.selection_range, item.selection_range.start)
+    )
+}
+
+fn pretty_incoming_calls(project: TestProject<'_>, position: PositionFinder) -> String {
+    let position = position.find_position(project.src);
+    project.at(position, |engine, params, _| {
+        let item = prepare(engine, params).expect("a call hierarchy item");
+        let params = CallHierarchyIncomingCallsParams {
+            item: item.clone(),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        };
+        let calls = engine.incoming_calls(params).result.unwrap().unwrap();
+
+        let mut output = format!("----- ITEM\n{}", show_item(&project, &item));
+        for call in calls {
+            let src = project
+                .src_from_module_url(&call.from.uri)
+                .expect("a module for the caller");
+            output.push_str(&format!(
+                "\n----- CALLED FROM {} in {}\n{}",
+                call.from.name,
+                call.from.detail.unwrap_or_default(),
+                show_ranges(src, &call.from_ranges)
+            ));
+        }
+        output
+    })
+}
+
+fn pretty_outgoing_calls(project: TestProject<'_>, position: PositionFinder) -> String {
+    let position = position.find_position(project.src);
+    project.at(position, |engine, params, _| {
+        let item = prepare(engine, params).expect("a call hierarchy item");
+        let params = CallHierarchyOutgoingCallsParams {
+            item: item.clone(),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        };
+        let calls = engine.outgoing_calls(params).result.unwrap().unwrap();
+        let src = project
+            .src_from_module_url(&item.uri)
+            .expect("a module for the item");
+
+        let mut output = format!("----- ITEM\n{}", show_item(&project, &item));
+        for call in calls {
+            output.push_str(&format!(
+                "\n----- CALLS {}\n{}",
+                show_item(&project, &call.to),
+                show_ranges(src, &call.from_ranges)
+            ));
+        }
+        output
+    })
+}
+
+macro_rules! assert_call_hierarchy_item {
+    ($project:expr, $position:expr $(,)?) => {
+        let project = $project;
+        let position = $position.find_position(project.src);
+        let output = project.at(position, |engine, params, _| {
+            let item = prepare(engine, params).expect("a call hierarchy item");
+            show_item(&project, &item)
+        });
+        insta::assert_snapshot!(insta::internals::AutoName, output, project.src);
+    };
+}
+
+macro_rules! assert_no_call_hierarchy_item {
+    ($project:expr, $position:expr $(,)?) => {
+        let project = $project;
+        let position = $position.find_position(project.src);
+        let item = project.at(position, |engine, params, _| prepare(engine, params));
+        assert_eq!(item, None);
+    };
+}
+
+macro_rules! assert_incoming_calls {
+    ($project:expr, $position:expr $(,)?) => {
+        let project = $project;
+        let src = project.src;
+        let output = pretty_incoming_calls(project, $position);
+        insta::assert_snapshot!(insta::internals::AutoName, output, src);
+    };
+}
+
+macro_rules! assert_outgoing_calls {
+    ($project:expr, $position:expr $(,)?) => {
+        let project = $project;
+        let src = project.src;
+        let output = pretty_outgoing_calls(project, $position);
+        insta::assert_snapshot!(insta::internals::AutoName, output, src);
+    };
+}
+
+#[test]
+fn call_hierarchy_item_for_function_definition() {
+    assert_call_hierarchy_item!(
+        TestProject::for_source(
+            "
+pub fn main() {
+  wibble()
+}
+
+fn wibble() {
+  Nil
+}
+"
+        ),
+        find_position_of("fn wibble").with_char_offset(4),
+    );
+}
+
+#[test]
+fn call_hierarchy_item_for_local_function_call() {
+    assert_call_hierarchy_item!(
+        TestProject::for_source(
+            "
+pub fn main() {
+  wibble()
+}
+
+fn wibble() {
+  Nil
+}
+"
+        ),
+        find_position_of("wibble()"),
+    );
+}
+
+#[test]
+fn call_hierarchy_item_for_qualified_function_call() {
+    assert_call_hierarchy_item!(
+        TestProject::for_source(
+            "
+import wibble
+
+pub fn main() {
+  wibble.wobble()
+}
+"
+        )
+        .add_module("wibble", "pub fn wobble() { Nil }"),
+        find_position_of("wobble()"),
+    );
+}
+
+#[test]
+fn call_hierarchy_item_for_unqualified_import() {
+    assert_call_hierarchy_item!(
+        TestProject::for_source(
+            "
+import wibble.{wobble}
+
+pub fn main() {
+  wobble()
+}
+"
+        )
+        .add_module("wibble", "pub fn wobble() { Nil }"),
+        find_position_of("wobble}"),
+    );
+}
+
+#[test]
+fn call_hierarchy_item_for_dependency_function() {
+    assert_call_hierarchy_item!(
+        TestProject::for_source(
+            "
+import wibble
+
+pub fn main() {
+  wibble.wobble()
+}
+"
+        )
+        .add_hex_module("wibble", "pub fn wobble() { Nil }"),
+        find_position_of("wobble()"),
+    );
+}
+
+#[test]
+fn no_call_hierarchy_item_for_variable() {
+    assert_no_call_hierarchy_item!(
+        TestProject::for_source(
+            "
+pub fn main() {
+  let wibble = 1
+  wibble
+}
+"
+        ),
+        find_position_of("wibble").nth_occurrence(2),
+    );
+}
+
+#[test]
+fn no_call_hierarchy_item_for_record_constructor() {
+    assert_no_call_hierarchy_item!(
+        TestProject::for_source(
+            "
+pub type Wibble {
+  Wibble
+}
+
+pub fn main() {
+  Wibble
+}
+"
+        ),
+        find_position_of("Wibble").nth_occurrence(3),
+    );
+}
+
+#[test]
+fn incoming_calls_from_same_module() {
+    assert_incoming_calls!(
+        TestProject::for_source(
+            "
+pub fn main() {
+  wibble()
+  wibble()
+}
+
+pub fn other() {
+  let f = fn() { wibble() }
+  f()
+}
+
+fn wibble() {
+  Nil
+}
+"
+        ),
+        find_position_of("fn wibble").with_char_offset(4),
+    );
+}
+
+#[test]
+fn incoming_calls_from_other_modules() {
+    assert_incoming_calls!(
+        TestProject::for_source(
+            "
+import wibble
+
+pub fn main() {
+  wibble.wobble()
+}
+"
+        )
+        .add_module("wibble", "pub fn wobble() { Nil }")
+        .add_module(
+            "wubble",
+            "import wibble.{wobble}
+
+pub fn wubble() {
+  wobble()
+}
+
+pub fn unrelated() {
+  Nil
+}
+"
+        ),
+        find_position_of("wobble()"),
+    );
+}
+
+#[test]
+fn incoming_calls_include_references() {
+    assert_incoming_calls!(
+        TestProject::for_source(
+            "
+pub fn main() {
+  apply(wibble)
+}
+
+fn apply(f) {
+  f()
+}
+
+fn wibble() {
+  Nil
+}
+"
+        ),
+        find_position_of("fn wibble").with_char_offset(4),
+    );
+}
+
+#[test]
+fn incoming_calls_to_dependency_function() {
+    assert_incoming_calls!(
+        TestProject::for_source(
+            "
+import wibble
+
+pub fn main() {
+  wibble.wobble()
+}
+"
+        )
+        .add_hex_module("wibble", "pub fn wobble() { Nil }"),
+        find_position_of("wobble()"),
+    );
+}
+
+#[test]
+fn outgoing_calls() {
+    assert_outgoing_calls!(
+        TestProject::for_source(
+            "
+import wibble
+import wobble.{wubble}
+
+pub fn main() {
+  local()
+  wibble.wibble()
+  wubble()
+  local()
+  [1, 2] |> wibble.map(local_with_argument)
+}
+
+fn local() {
+  Nil
+}
+
+fn local_with_argument(x) {
+  x
+}
+"
+        )
+        .add_module("wobble", "pub fn wubble() { Nil }")
+        .add_hex_module(
+            "wibble",
+            "pub fn wibble() { Nil }
+
+pub fn map(list: List(a), f: fn(a) -> b) -> List(b) { todo }
+"
+        ),
+        find_position_of("main"),
+    );
+}
+
+#[test]
+fn outgoing_calls_of_function_without_calls() {
+    assert_outgoing_calls!(
+        TestProject::for_source(
+            "
+pub fn main() {
+  let x = 1
+  x + 1
+}
+"
+        ),
+        find_position_of("main"),
+    );
+}
+
+#[test]
+fn no_outgoing_calls_for_dependency_function() {
+    let project = TestProject::for_source(
+        "
+import wibble
+
+pub fn main() {
+  wibble.wobble()
+}
+",
+    )
+    .add_hex_module("wibble", "pub fn wobble() { Nil }");
+    let position = find_position_of("wobble()").find_position(project.src);
+    let calls = project.at(position, |engine, params, _| {
+        let item = prepare(engine, params).expect("a call hierarchy item");
+        let params = CallHierarchyOutgoingCallsParams {
+            item,
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        };
+        engine.outgoing_calls(params).result.unwrap()
+    });
+    assert_eq!(calls, None);
+}
@@ -1,4 +1,7 @@
-use crate::language_server::engine::Compilation;
+use crate::{
+    language_server::engine::Compilation,
+    warning::{Warning, WarningKind, WarningLevel},
+};
 
 use super::*;
 
@@ -212,3 +215,37 @@ fn dep_compile_recompile() {
         ]
     )
 }
+
+#[test]
+fn compile_applies_the_warning_levels_of_the_package() {
+    let io = LanguageServerTestIO::new();
+    let mut engine = setup_engine(&io);
+    _ = engine
+        .compiler
+        .project_compiler
+        .config
+        .warnings
+        .insert(WarningKind::UnusedVariable, WarningLevel::Allow);
+
+    _ = io.src_module(
+        "app/unused",
+        "pub fn main() {
+  let x = 1
+  todo
+}",
+    );
+
+    let response = engine.compile_please();
+    assert!(response.result.is_ok());
+    assert_eq!(
+        response
+            .warnings
+            .iter()
+            .map(|warning| match warning {
+                Warning::Type { warning, .. } => warning.kind(),
+                _ => panic!("unexpected warning {warning:?}"),
+            })
+            .collect::<Vec<_>>(),
+        vec![WarningKind::Todo]
+    );
+}
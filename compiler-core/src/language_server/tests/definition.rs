@@ -626,3 +626,212 @@ pub fn main() {
         find_position_of("w: Wibble").under_char('i')
     );
 }
+
+#[test]
+fn goto_definition_of_external_function_opens_erlang_file() {
+    let code = "
+@external(erlang, \"app_ffi\", \"do_wibble\")
+pub fn wibble() -> Int
+";
+
+    assert_goto!(
+        TestProject::for_source(code).add_native_file(
+            "src/app_ffi.erl",
+            "-module(app_ffi).
+-export([do_wibble/0]).
+
+do_wibble() ->
+    1.
+"
+        ),
+        find_position_of("wibble()")
+    );
+}
+
+#[test]
+fn goto_definition_of_external_function_opens_nested_erlang_file() {
+    let code = "
+@external(erlang, \"app_ffi\", \"wibble\")
+pub fn wibble() -> Int
+";
+
+    assert_goto!(
+        TestProject::for_source(code).add_native_file(
+            "src/nested/app_ffi.erl",
+            "-module(app_ffi).
+-export([wibble/0]).
+
+wibble() ->
+    1.
+"
+        ),
+        find_position_of("wibble()")
+    );
+}
+
+#[test]
+fn goto_definition_of_external_function_opens_quoted_erlang_function() {
+    let code = "
+@external(erlang, \"app_ffi\", \"Wibble\")
+pub fn wibble() -> Int
+";
+
+    assert_goto!(
+        TestProject::for_source(code).add_native_file(
+            "src/app_ffi.erl",
+            "-module(app_ffi).
+-export(['Wibble'/0]).
+
+'Wibble'() ->
+    1.
+"
+        ),
+        find_position_of("wibble()")
+    );
+}
+
+#[test]
+fn goto_definition_of_external_function_opens_javascript_file() {
+    let code = "
+@external(javascript, \"./app_ffi.mjs\", \"wibble\")
+pub fn wibble() -> Int
+";
+
+    assert_goto!(
+        TestProject::for_source(code).add_native_file(
+            "src/app_ffi.mjs",
+            "import { Ok } from \"./gleam.mjs\";
+
+export function wobble() {
+  return 2;
+}
+
+export function wibble() {
+  return 1;
+}
+"
+        ),
+        find_position_of("wibble()")
+    );
+}
+
+#[test]
+fn goto_definition_of_external_function_opens_javascript_const() {
+    let code = "
+@external(javascript, \"./app_ffi.mjs\", \"wibble\")
+pub fn wibble() -> Int
+";
+
+    assert_goto!(
+        TestProject::for_source(code).add_native_file(
+            "src/app_ffi.mjs",
+            "export const wibble_wobble = () => 2;
+export const wibble = () => 1;
+"
+        ),
+        find_position_of("wibble()")
+    );
+}
+
+#[test]
+fn goto_definition_of_external_function_prefers_the_target() {
+    let code = "
+@external(erlang, \"app_ffi\", \"wibble\")
+@external(javascript, \"./app_ffi.mjs\", \"wibble\")
+pub fn wibble() -> Int
+";
+
+    assert_goto!(
+        TestProject::for_source(code)
+            .add_native_file("src/app_ffi.erl", "wibble() -> 1.\n")
+            .add_native_file(
+                "src/app_ffi.mjs",
+                "export function wibble() { return 1; }\n"
+            ),
+        find_position_of("wibble()")
+    );
+}
+
+#[test]
+fn goto_definition_of_external_function_in_dependency_erlang_file() {
+    let code = "
+@external(erlang, \"hex_ffi\", \"wibble\")
+pub fn wibble() -> Int
+";
+
+    assert_goto!(
+        TestProject::for_source(code)
+            .add_hex_module("hex_module", "pub fn wobble() { 1 }")
+            .add_native_file(
+                "build/packages/hex/src/hex_ffi.erl",
+                "-module(hex_ffi).\n\nwibble() -> 1.\n"
+            ),
+        find_position_of("wibble()")
+    );
+}
+
+#[test]
+fn goto_definition_of_external_function_return_annotation_opens_type() {
+    let code = "
+pub type Wibble {
+  Wibble
+}
+
+@external(erlang, \"app_ffi\", \"wibble\")
+pub fn wibble(wobble: Wibble) -> Wibble
+";
+
+    assert_goto!(
+        TestProject::for_source(code).add_native_file(
+            "src/app_ffi.erl",
+            "-module(app_ffi).\n\nwibble(Wobble) -> Wobble.\n"
+        ),
+        find_position_of("-> Wibble").under_char('W')
+    );
+}
+
+#[test]
+fn goto_definition_of_external_function_argument_annotation_opens_type() {
+    let code = "
+pub type Wibble {
+  Wibble
+}
+
+@external(erlang, \"app_ffi\", \"wibble\")
+pub fn wibble(wobble: Wibble) -> Wibble
+";
+
+    assert_goto!(
+        TestProject::for_source(code).add_native_file(
+            "src/app_ffi.erl",
+            "-module(app_ffi).\n\nwibble(Wobble) -> Wobble.\n"
+        ),
+        find_position_of("wobble: Wibble").under_char('W')
+    );
+}
+
+#[test]
+fn goto_definition_of_external_function_missing_from_native_file() {
+    let code = "
+@external(erlang, \"app_ffi\", \"wibble\")
+pub fn wibble() -> Int
+";
+
+    assert_goto!(
+        TestProject::for_source(code)
+            .add_native_file("src/app_ffi.erl", "-module(app_ffi).\n\nwobble() -> 1.\n"),
+        find_position_of("wibble()")
+    );
+}
+
+#[test]
+fn no_definition_for_external_function_without_native_file() {
+    let project = TestProject::for_source(
+        "
+@external(erlang, \"lists\", \"reverse\")
+pub fn reverse(list: List(a)) -> List(a)
+",
+    );
+    let position = find_position_of("reverse(").find_position(project.src);
+    assert_eq!(definition(&project, position), None);
+}
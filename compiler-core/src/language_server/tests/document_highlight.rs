@@ -0,0 +1,318 @@
+use lsp_types::{DocumentHighlight, DocumentHighlightKind, DocumentHighlightParams};
+
+use super::*;
+
+fn document_highlight(
+    project: &TestProject<'_>,
+    position: Position,
+) -> Option<Vec<DocumentHighlight>> {
+    project.at(position, |engine, param, _| {
+        let params = DocumentHighlightParams {
+            text_document_position_params: param,
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        };
+        engine.document_highlight(params).result.unwrap()
+    })
+}
+
+/// Underlines the written occurrences with `W`, the read ones with `R` and
+/// the position of the cursor with `↑`.
+///
+fn pretty_document_highlight(project: TestProject<'_>, position: PositionFinder) -> String {
+    let src = project.src;
+    let position = position.find_position(src);
+    let highlights = document_highlight(&project, position).expect("some highlights");
+
+    let mut buffer = String::new();
+    for (line_number, line) in src.lines().enumerate() {
+        let underline: String = (0..line.chars().count())
+            .map(|column_number| {
+                let current = Position::new(line_number as u32, column_number as u32);
+                if current == position {
+                    return '↑';
+                }
+                highlights
+                    .iter()
+                    .find(|highlight| {
+                        highlight.range.start <= current && current < highlight.range.end
+                    })
+                    .map_or(' ', |highlight| match highlight.kind {
+                        Some(DocumentHighlightKind::WRITE) => 'W',
+                        _ => 'R',
+                    })
+            })
+            .collect();
+
+        buffer.push_str(line);
+        if !underline.trim().is_empty() {
+            buffer.push('\n');
+            buffer.push_str(underline.trim_end());
+        }
+        buffer.push('\n');
+    }
+    buffer
+}
+
+macro_rules! assert_document_highlight {
+    ($src:literal, $position:expr $(,)?) => {
+        let project = TestProject::for_source($src);
+        assert_document_highlight!(project, $position);
+    };
+
+    ($project:expr, $position:expr $(,)?) => {
+        let src = $project.src;
+        let output = pretty_document_highlight($project, $position);
+        insta::assert_snapshot!(insta::internals::AutoName, output, src);
+    };
+}
+
+#[test]
+fn highlight_local_variable() {
+    assert_document_highlight!(
+        "
+pub fn main() {
+  let wibble = 1
+  let wobble = wibble + 2
+  wibble * wobble
+}
+",
+        find_position_of("wibble +"),
+    );
+}
+
+#[test]
+fn highlight_function_argument() {
+    assert_document_highlight!(
+        "
+pub fn add(x, y) {
+  x + y + x
+}
+",
+        find_position_of("x,"),
+    );
+}
+
+#[test]
+fn highlight_pattern_binding() {
+    assert_document_highlight!(
+        "
+pub fn main(result) {
+  case result {
+    Ok(value) if value > 1 -> value
+    Ok(_) | Error(_) -> 0
+  }
+}
+",
+        find_position_of("value)"),
+    );
+}
+
+#[test]
+fn highlight_assign_pattern() {
+    assert_document_highlight!(
+        "
+pub fn main(x) {
+  let [_, ..] as list = x
+  list
+}
+",
+        find_position_of("list").nth_occurrence(2),
+    );
+}
+
+#[test]
+fn highlight_record_label() {
+    assert_document_highlight!(
+        "
+pub type Wibble {
+  Wibble(wobble: Int, wubble: Int)
+}
+
+pub fn main() {
+  let wibble = Wibble(wobble: 1, wubble: 2)
+  let Wibble(wobble: w, ..) = wibble
+  Wibble(..wibble, wobble: w + 1).wobble
+}
+",
+        find_position_of("wobble:"),
+    );
+}
+
+#[test]
+fn highlight_function_label() {
+    assert_document_highlight!(
+        "
+pub fn greet(name name: String, greeting greeting: String) {
+  greeting <> name
+}
+
+pub fn main() {
+  greet(greeting: \"Hello\", name: \"Joe\")
+}
+",
+        find_position_of("greeting greeting"),
+    );
+}
+
+#[test]
+fn highlight_module_function() {
+    assert_document_highlight!(
+        "
+pub fn wibble() {
+  wibble
+}
+
+pub fn main() {
+  wibble()
+  [1] |> map(wibble)
+}
+
+fn map(_list, _fun) {
+  Nil
+}
+",
+        find_position_of("wibble()").nth_occurrence(2),
+    );
+}
+
+#[test]
+fn highlight_type() {
+    assert_document_highlight!(
+        "
+pub type Wibble {
+  Wibble
+}
+
+pub type Wobble =
+  List(Wibble)
+
+pub fn main(wibble: Wibble) -> Wibble {
+  let _: Wobble = [wibble]
+  wibble
+}
+",
+        find_position_of("Wibble {"),
+    );
+}
+
+#[test]
+fn highlight_record_constructor() {
+    assert_document_highlight!(
+        "
+pub type Wibble {
+  Wibble
+  Wobble
+}
+
+pub fn main(x) {
+  case x {
+    Wibble -> Wobble
+    Wobble -> Wibble
+  }
+}
+",
+        find_position_of("Wibble ->"),
+    );
+}
+
+#[test]
+fn highlight_import_alias() {
+    let src = "
+import wibble as w
+import wibble/wobble
+
+pub fn main() -> w.Wibble {
+  wobble.wobble()
+  w.wibble(w.Wibble)
+}
+";
+    assert_document_highlight!(
+        TestProject::for_source(src)
+            .add_module(
+                "wibble",
+                "pub type Wibble { Wibble }\npub fn wibble(x) { x }"
+            )
+            .add_module("wibble/wobble", "pub fn wobble() { 1 }"),
+        find_position_of("w.wibble"),
+    );
+}
+
+#[test]
+fn highlight_import_without_alias() {
+    let src = "
+import wibble/wobble
+
+pub fn main() {
+  wobble.wobble()
+  wobble.wobble
+}
+";
+    assert_document_highlight!(
+        TestProject::for_source(src).add_module("wibble/wobble", "pub fn wobble() { 1 }"),
+        find_position_of("wobble.").nth_occurrence(2),
+    );
+}
+
+#[test]
+fn highlight_unqualified_import() {
+    let src = "
+import wibble.{type Wibble, wibble}
+
+pub fn main(x: Wibble) {
+  wibble(x)
+}
+";
+    assert_document_highlight!(
+        TestProject::for_source(src).add_module(
+            "wibble",
+            "pub type Wibble { Wibble }\npub fn wibble(x) { x }"
+        ),
+        find_position_of("wibble(x)"),
+    );
+}
+
+#[test]
+fn highlight_constant() {
+    assert_document_highlight!(
+        "
+const wibble = 1
+
+const wobble = [wibble, 2]
+
+pub fn main() {
+  [wibble, ..wobble]
+}
+",
+        find_position_of("wibble").nth_occurrence(3),
+    );
+}
+
+#[test]
+fn highlight_use_callback_argument() {
+    assert_document_highlight!(
+        "
+pub fn main() {
+  use wibble <- apply(1)
+  wibble + wibble
+}
+
+fn apply(x, f) {
+  f(x)
+}
+",
+        find_position_of("wibble +"),
+    );
+}
+
+#[test]
+fn no_highlight_outside_of_symbols() {
+    let src = "
+pub fn main() {
+  1 + 2
+}
+";
+    let project = TestProject::for_source(src);
+    let highlights = document_highlight(&project, find_position_of("+").find_position(src));
+    assert_eq!(highlights, None);
+}
@@ -0,0 +1,204 @@
+use lsp_types::{FoldingRange, FoldingRangeParams};
+
+use super::*;
+
+fn folding_ranges(project: &TestProject<'_>) -> Vec<FoldingRange> {
+    project.at(Position::default(), |engine, param, _| {
+        let params = FoldingRangeParams {
+            text_document: param.text_document,
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        };
+        engine.folding_range(params).result.unwrap()
+    })
+}
+
+fn pretty_folding_ranges(project: TestProject<'_>) -> String {
+    let lines = project.src.lines().collect_vec();
+    let mut output = String::new();
+    for range in folding_ranges(&project) {
+        let kind = match range.kind {
+            Some(kind) => format!(" ({kind:?})"),
+            None => String::new(),
+        };
+        output.push_str(&format!(
+            "----- Lines {} to {}{kind}\n",
+            range.start_line, range.end_line
+        ));
+        for line in lines
+            .iter()
+            .skip(range.start_line as usize)
+            .take((range.end_line - range.start_line + 1) as usize)
+        {
+            output.push_str(line);
+            output.push('\n');
+        }
+    }
+    output
+}
+
+macro_rules! assert_folding_ranges {
+    ($src:literal $(,)?) => {
+        assert_folding_ranges!(TestProject::for_source($src));
+    };
+    ($project:expr $(,)?) => {
+        let project = $project;
+        let src = project.src;
+        let output = pretty_folding_ranges(project);
+        insta::assert_snapshot!(insta::internals::AutoName, output, src);
+    };
+}
+
+#[test]
+fn folding_functions() {
+    assert_folding_ranges!(
+        "
+pub fn main() {
+  let x = 1
+  x
+}
+
+pub fn one_liner() { 1 }
+
+fn wibble(
+  a: Int,
+  b: Int,
+) -> Int {
+  a + b
+}
+"
+    );
+}
+
+#[test]
+fn folding_custom_types() {
+    assert_folding_ranges!(
+        "
+pub type Wibble {
+  Wibble(a: Int)
+  Wobble
+}
+
+pub type Empty
+"
+    );
+}
+
+#[test]
+fn folding_case_clauses() {
+    assert_folding_ranges!(
+        "
+pub fn main(x) {
+  case x {
+    1 -> Nil
+    2 ->
+      Nil
+    _ -> {
+      let y = x
+      Nil
+    }
+  }
+}
+"
+    );
+}
+
+#[test]
+fn folding_blocks_and_anonymous_functions() {
+    assert_folding_ranges!(
+        "
+pub fn main() {
+  let x = {
+    let y = 1
+    y + 1
+  }
+  let f = fn(a) {
+    a + x
+  }
+  f
+}
+"
+    );
+}
+
+#[test]
+fn no_folding_for_use_callbacks() {
+    assert_folding_ranges!(
+        "
+pub fn main() {
+  use x <- apply(1)
+  let y = x
+  y
+}
+
+fn apply(x, f) {
+  f(x)
+}
+"
+    );
+}
+
+#[test]
+fn folding_lists_and_records() {
+    assert_folding_ranges!(
+        "
+pub type Wibble {
+  Wibble(a: Int, b: Int)
+}
+
+pub fn main() {
+  let short = [1, 2]
+  let long = [
+    1,
+    2,
+  ]
+  let record = Wibble(
+    a: 1,
+    b: 2,
+  )
+  #(short, long, record)
+}
+"
+    );
+}
+
+#[test]
+fn folding_import_groups() {
+    assert_folding_ranges!(TestProject::for_source(
+        "import wibble
+import wobble.{
+  type Wobble,
+}
+import wubble
+
+pub const x = 1
+
+import other
+"
+    )
+    .add_module("wibble", "")
+    .add_module("wobble", "pub type Wobble")
+    .add_module("wubble", "")
+    .add_module("other", ""));
+}
+
+#[test]
+fn folding_doc_comments() {
+    assert_folding_ranges!(
+        "//// The module
+//// documentation
+
+/// Some
+/// documentation
+/// for main
+pub fn main() {
+  Nil
+}
+
+/// A single line
+pub fn other() {
+  Nil
+}
+"
+    );
+}
@@ -1,13 +1,12 @@
-use std::collections::HashMap;
-
-use lsp_types::{Position, RenameParams, TextDocumentPositionParams, Url, WorkDoneProgressParams};
+use itertools::Itertools;
+use lsp_types::{Position, RenameParams, TextDocumentPositionParams, WorkDoneProgressParams};
 
 use crate::language_server::tests::{find_position_of, TestProject};
 
 use super::hover;
 
 fn rename(
-    tester: TestProject<'_>,
+    tester: &TestProject<'_>,
     new_name: &str,
     position: Position,
 ) -> Option<lsp_types::WorkspaceEdit> {
@@ -32,21 +31,38 @@ fn rename(
     })
 }
 
+/// Applies a rename, returning the new source of the module being edited
+/// followed by the one of each other module changed by the rename.
+///
 fn apply_rename(tester: TestProject<'_>, new_name: &str, position: Position) -> String {
     let src = tester.src;
-    let changes = rename(tester, new_name, position)
+    let uri = tester.build_path(position).text_document.uri;
+    let changes = rename(&tester, new_name, position)
         .expect("Rename failed")
         .changes
         .expect("No text edit found");
-    apply_code_edit(src, changes)
-}
 
-fn apply_code_edit(src: &str, changes: HashMap<Url, Vec<lsp_types::TextEdit>>) -> String {
-    let mut result = src.to_string();
-    for (_, change) in changes {
-        result = super::apply_code_edit(result.as_str(), change);
+    let mut output = changes.get(&uri).map_or(src.to_string(), |edits| {
+        super::apply_code_edit(src, edits.clone())
+    });
+    for (other_uri, edits) in changes
+        .into_iter()
+        .filter(|(other_uri, _)| *other_uri != uri)
+        .sorted_by_key(|(other_uri, _)| other_uri.to_string())
+    {
+        let other_src = tester
+            .src_from_module_url(&other_uri)
+            .expect("edited module source");
+        let module = other_uri
+            .path()
+            .rsplit_once("/src/")
+            .map_or(other_uri.path(), |(_, module)| module);
+        output.push_str(&format!(
+            "\n\n----- {module}\n{}",
+            super::apply_code_edit(other_src, edits)
+        ));
     }
-    result
+    output
 }
 
 macro_rules! assert_rename {
@@ -77,7 +93,7 @@ macro_rules! assert_no_rename {
     ($project:expr, $new_name:literal, $range:expr $(,)?) => {
         let src = $project.src;
         let range = $range.find_range(src);
-        let result = rename($project, $new_name, range.start);
+        let result = rename(&$project, $new_name, range.start);
         assert_eq!(result, None);
     };
 }
@@ -342,3 +358,215 @@ pub fn main() {
         find_position_of("wibble").nth_occurrence(2).to_selection()
     );
 }
+
+#[test]
+fn rename_function_across_modules() {
+    let src = "
+import wibble.{wobble}
+
+pub fn main() {
+  wibble.wobble()
+  wobble()
+}
+";
+    assert_rename!(
+        TestProject::for_source(src)
+            .add_module("wibble", "pub fn wobble() { wobble() }")
+            .add_module("wubble", "import wibble as w\npub fn main() { w.wobble }"),
+        "wabble",
+        find_position_of("wobble()").to_selection(),
+    );
+}
+
+#[test]
+fn rename_function_from_definition() {
+    let src = "
+pub fn wibble() {
+  wibble()
+}
+";
+    assert_rename!(
+        TestProject::for_source(src).add_module(
+            "wobble",
+            "import app.{wibble}\npub fn main() { app.wibble() wibble() }"
+        ),
+        "wobble",
+        find_position_of("wibble").to_selection(),
+    );
+}
+
+#[test]
+fn rename_function_imported_with_alias() {
+    let src = "
+import wibble.{wobble as wubble}
+
+pub fn main() {
+  wubble()
+}
+";
+    assert_rename!(
+        TestProject::for_source(src).add_module("wibble", "pub fn wobble() { 1 }"),
+        "wabble",
+        find_position_of("wobble").to_selection(),
+    );
+}
+
+#[test]
+fn rename_constant() {
+    let src = "
+import wibble.{wobble}
+
+const wubble = [wobble, wibble.wobble]
+
+pub fn main() {
+  wobble + wibble.wobble
+}
+";
+    assert_rename!(
+        TestProject::for_source(src).add_module("wibble", "pub const wobble = 1"),
+        "wabble",
+        find_position_of("wobble +").to_selection(),
+    );
+}
+
+#[test]
+fn rename_custom_type() {
+    let src = "
+import wibble.{type Wobble}
+
+pub fn main(x: Wobble) -> wibble.Wobble {
+  x
+}
+";
+    assert_rename!(
+        TestProject::for_source(src).add_module(
+            "wibble",
+            "pub type Wobble { Wobble }\npub type Wobbles = List(Wobble)"
+        ),
+        "Wubble",
+        find_position_of("Wobble)").to_selection(),
+    );
+}
+
+#[test]
+fn rename_type_alias() {
+    let src = "
+pub type Wibble =
+  List(Int)
+
+pub fn main(x: Wibble) -> Wibble {
+  x
+}
+";
+    assert_rename!(
+        TestProject::for_source(src)
+            .add_module("wobble", "import app\npub fn wobble(x: app.Wibble) { x }"),
+        "Wobble",
+        find_position_of("Wibble =").to_selection(),
+    );
+}
+
+#[test]
+fn rename_record_constructor() {
+    let src = "
+import wibble.{Wobble}
+
+pub fn main(x) {
+  case x {
+    Wobble(1) -> wibble.Wobble(2)
+    wibble.Wobble(_) -> Wobble(3)
+  }
+}
+";
+    assert_rename!(
+        TestProject::for_source(src).add_module(
+            "wibble",
+            "pub type Wibble { Wobble(Int) }\npub const wobble = Wobble(1)"
+        ),
+        "Wubble",
+        find_position_of("Wobble(1)").to_selection(),
+    );
+}
+
+#[test]
+fn rename_record_label() {
+    let src = "
+import wibble.{Wibble}
+
+pub fn main(x) {
+  let wobble = 1
+  let record = Wibble(wobble:)
+  let Wibble(wobble: y) = record
+  Wibble(..record, wobble: y).wobble
+}
+";
+    assert_rename!(
+        TestProject::for_source(src)
+            .add_module("wibble", "pub type Wibble { Wibble(wobble: Int) }"),
+        "wubble",
+        find_position_of("wobble: y").to_selection(),
+    );
+}
+
+#[test]
+fn rename_function_label() {
+    let src = "
+pub fn greet(name name: String) {
+  name
+}
+
+pub fn main() {
+  greet(name: \"Joe\")
+}
+";
+    assert_rename!(
+        TestProject::for_source(src).add_module(
+            "wibble",
+            "import app\npub fn main() { app.greet(name: \"Jo\") }"
+        ),
+        "person",
+        find_position_of("name name").to_selection(),
+    );
+}
+
+#[test]
+fn no_rename_function_from_dependency() {
+    let src = "
+import wibble
+
+pub fn main() {
+  wibble.wobble()
+}
+";
+    assert_no_rename!(
+        TestProject::for_source(src).add_hex_module("wibble", "pub fn wobble() { 1 }"),
+        "wubble",
+        find_position_of("wobble").to_selection(),
+    );
+}
+
+#[test]
+fn no_rename_prelude_type() {
+    assert_no_rename!(
+        "
+pub fn main(x: Int) {
+  x
+}
+",
+        "Integer",
+        find_position_of("Int").to_selection(),
+    );
+}
+
+#[test]
+fn no_rename_type_with_invalid_name() {
+    assert_no_rename!(
+        "
+pub type Wibble {
+  Wibble
+}
+",
+        "wobble",
+        find_position_of("Wibble").to_selection(),
+    );
+}
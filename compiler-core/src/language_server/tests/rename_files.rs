@@ -0,0 +1,261 @@
+use itertools::Itertools;
+use lsp_types::{
+    DocumentChangeOperation, DocumentChanges, FileRename, OneOf, RenameFilesParams, ResourceOp, Url,
+};
+
+use super::*;
+
+fn file_uri(path: &str) -> String {
+    let path = if cfg!(target_family = "windows") {
+        format!(r"\\?\C:{}", path.replace('/', r"\"))
+    } else {
+        path.to_string()
+    };
+    Url::from_file_path(path).unwrap().to_string()
+}
+
+fn pretty_path(uri: &Url) -> String {
+    uri.path_segments()
+        .expect("a file path")
+        // To make snapshots the same both on windows and unix systems we need
+        // to discard windows' `C:` path segment at the beginning of a uri.
+        .skip_while(|segment| segment.is_empty() || *segment == "C:")
+        .join("/")
+}
+
+fn will_rename_files(
+    project: &TestProject<'_>,
+    old_path: &str,
+    new_path: &str,
+    can_rename_files: bool,
+) -> Option<lsp_types::WorkspaceEdit> {
+    project.at(Position::default(), |engine, _, _| {
+        let params = RenameFilesParams {
+            files: vec![FileRename {
+                old_uri: file_uri(old_path),
+                new_uri: file_uri(new_path),
+            }],
+        };
+        engine
+            .will_rename_files(params, can_rename_files)
+            .result
+            .unwrap()
+    })
+}
+
+fn pretty_rename_files(project: TestProject<'_>, old_path: &str, new_path: &str) -> String {
+    let edit = will_rename_files(&project, old_path, new_path, true).expect("a workspace edit");
+    let Some(DocumentChanges::Operations(operations)) = edit.document_changes else {
+        panic!("no document changes");
+    };
+
+    let mut output = format!("----- MOVING {old_path} TO {new_path}\n");
+    for operation in operations {
+        match operation {
+            DocumentChangeOperation::Edit(edit) => {
+                let src = project
+                    .src_from_module_url(&edit.text_document.uri)
+                    .expect("edited module source");
+                let edits = edit
+                    .edits
+                    .into_iter()
+                    .map(|edit| match edit {
+                        OneOf::Left(edit) => edit,
+                        OneOf::Right(annotated) => annotated.text_edit,
+                    })
+                    .collect_vec();
+                output.push_str(&format!(
+                    "\n----- {}\n{}",
+                    pretty_path(&edit.text_document.uri),
+                    apply_code_edit(src, edits)
+                ));
+            }
+            DocumentChangeOperation::Op(ResourceOp::Rename(rename)) => {
+                output.push_str(&format!(
+                    "\n----- MOVED {} TO {}\n",
+                    pretty_path(&rename.old_uri),
+                    pretty_path(&rename.new_uri)
+                ));
+            }
+            DocumentChangeOperation::Op(operation) => panic!("unexpected {operation:?}"),
+        }
+    }
+    output
+}
+
+macro_rules! assert_rename_files {
+    ($project:expr, $old_path:literal, $new_path:literal $(,)?) => {
+        let output = pretty_rename_files($project, $old_path, $new_path);
+        insta::assert_snapshot!(insta::internals::AutoName, output);
+    };
+}
+
+#[test]
+fn moving_module_rewrites_imports() {
+    let src = "
+import wibble/wobble.{type Wobble, wobble}
+
+pub fn main() -> Wobble {
+  wobble()
+}
+";
+    assert_rename_files!(
+        TestProject::for_source(src).add_module(
+            "wibble/wobble",
+            "pub type Wobble { Wobble }\npub fn wobble() { Wobble }"
+        ),
+        "/src/wibble/wobble.gleam",
+        "/src/wubble/wobble.gleam",
+    );
+}
+
+#[test]
+fn moving_module_rewrites_qualified_references() {
+    let src = "
+import wibble
+
+pub fn main() -> wibble.Wibble {
+  case wibble.wibble() {
+    wibble.Wibble -> wibble.Wibble
+  }
+}
+";
+    assert_rename_files!(
+        TestProject::for_source(src).add_module(
+            "wibble",
+            "pub type Wibble { Wibble }\npub fn wibble() { Wibble }"
+        ),
+        "/src/wibble.gleam",
+        "/src/wobble.gleam",
+    );
+}
+
+#[test]
+fn moving_module_keeps_alias() {
+    let src = "
+import wibble as wubble
+
+pub fn main() {
+  wubble.wibble()
+}
+";
+    assert_rename_files!(
+        TestProject::for_source(src).add_module("wibble", "pub fn wibble() { 1 }"),
+        "/src/wibble.gleam",
+        "/src/wobble.gleam",
+    );
+}
+
+#[test]
+fn moving_module_keeps_name_already_in_use() {
+    let src = "
+import wibble
+
+pub fn main(wobble) {
+  wibble.wibble(wobble)
+}
+";
+    assert_rename_files!(
+        TestProject::for_source(src).add_module("wibble", "pub fn wibble(x) { x }"),
+        "/src/wibble.gleam",
+        "/src/wobble.gleam",
+    );
+}
+
+#[test]
+fn moving_module_keeps_name_of_unqualified_import() {
+    let src = "
+import wibble.{wibble}
+import wubble.{wobble}
+
+pub fn main() {
+  wibble.wibble(wibble(wobble))
+}
+";
+    assert_rename_files!(
+        TestProject::for_source(src)
+            .add_module("wibble", "pub fn wibble(x) { x }")
+            .add_module("wubble", "pub const wobble = 1"),
+        "/src/wibble.gleam",
+        "/src/wobble.gleam",
+    );
+}
+
+#[test]
+fn moving_module_updates_test_modules() {
+    let src = "
+import wibble
+
+pub fn main() {
+  wibble.wibble()
+}
+";
+    assert_rename_files!(
+        TestProject::for_source(src)
+            .add_module("wibble", "pub fn wibble() { 1 }")
+            .add_test_module(
+                "wibble_test",
+                "import wibble\npub fn wibble_test() { wibble.wibble() }"
+            ),
+        "/src/wibble.gleam",
+        "/src/accounts/wibble.gleam",
+    );
+}
+
+#[test]
+fn moving_module_moves_native_files() {
+    let src = "
+import wibble
+
+pub fn main() {
+  wibble.wibble()
+}
+";
+    assert_rename_files!(
+        TestProject::for_source(src)
+            .add_module(
+                "wibble",
+                "@external(erlang, \"wibble\", \"wibble\")\n@external(javascript, \"./wibble.mjs\", \"wibble\")\npub fn wibble() -> Int"
+            )
+            .add_native_file("src/wibble.erl", "-module(wibble).")
+            .add_native_file("src/wibble.mjs", "export function wibble() { return 1 }"),
+        "/src/wibble.gleam",
+        "/src/accounts/wibble.gleam",
+    );
+}
+
+#[test]
+fn native_files_are_not_moved_if_client_cannot_rename_files() {
+    let project = TestProject::for_source("import wibble\npub fn main() { wibble.wibble() }")
+        .add_module(
+            "wibble",
+            "@external(erlang, \"wibble\", \"wibble\")\npub fn wibble() -> Int",
+        )
+        .add_native_file("src/wibble.erl", "-module(wibble).");
+    let edit = will_rename_files(
+        &project,
+        "/src/wibble.gleam",
+        "/src/accounts/wibble.gleam",
+        false,
+    )
+    .expect("a workspace edit");
+    let Some(DocumentChanges::Operations(operations)) = edit.document_changes else {
+        panic!("no document changes");
+    };
+    assert!(operations
+        .iter()
+        .all(|operation| matches!(operation, DocumentChangeOperation::Edit(_))));
+    assert!(!operations.is_empty());
+}
+
+#[test]
+fn no_edit_when_moving_file_outside_of_project() {
+    let project = TestProject::for_source("pub fn main() { 1 }");
+    let edit = will_rename_files(
+        &project,
+        "/elsewhere/wibble.gleam",
+        "/src/wibble.gleam",
+        true,
+    );
+    assert_eq!(edit, None);
+}
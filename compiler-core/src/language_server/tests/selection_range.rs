@@ -0,0 +1,148 @@
+use lsp_types::{Range, SelectionRange, SelectionRangeParams};
+
+use super::*;
+
+fn selection_range(project: &TestProject<'_>, position: Position) -> SelectionRange {
+    project.at(position, |engine, param, _| {
+        let params = SelectionRangeParams {
+            text_document: param.text_document,
+            positions: vec![param.position],
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        };
+        engine
+            .selection_range(params)
+            .result
+            .unwrap()
+            .into_iter()
+            .next()
+            .expect("a selection range")
+    })
+}
+
+fn pretty_selection_range(src: &str, position: PositionFinder) -> String {
+    let project = TestProject::for_source(src);
+    let position = position.find_position(src);
+    let mut selection = Some(selection_range(&project, position));
+    let mut output = String::new();
+    while let Some(SelectionRange { range, parent }) = selection {
+        output.push_str("-----\n");
+        output.push_str(&hover::show_hover(src, range, position));
+        selection = parent.map(|parent| *parent);
+    }
+    output
+}
+
+macro_rules! assert_selection_range {
+    ($src:expr, $position:expr $(,)?) => {
+        let output = pretty_selection_range($src, $position);
+        insta::assert_snapshot!(insta::internals::AutoName, output, $src);
+    };
+}
+
+#[test]
+fn selection_range_in_expression() {
+    assert_selection_range!(
+        "
+pub fn main() {
+  let x = wibble(1, 2 + 3)
+  x
+}
+
+fn wibble(a, b) {
+  a + b
+}
+",
+        find_position_of("3"),
+    );
+}
+
+#[test]
+fn selection_range_in_case_clause() {
+    assert_selection_range!(
+        "
+pub fn main(x) {
+  case x {
+    Ok(value) -> value
+    Error(_) -> 0
+  }
+}
+",
+        find_position_of("value)"),
+    );
+}
+
+#[test]
+fn selection_range_in_argument_annotation() {
+    assert_selection_range!(
+        "
+pub fn main(x: List(Int)) -> Int {
+  0
+}
+",
+        find_position_of("Int"),
+    );
+}
+
+#[test]
+fn selection_range_in_anonymous_function() {
+    assert_selection_range!(
+        "
+pub fn main() {
+  fn(a: Int) { a + 1 }
+}
+",
+        find_position_of("1"),
+    );
+}
+
+#[test]
+fn selection_range_in_custom_type() {
+    assert_selection_range!(
+        "
+pub type Wibble {
+  Wibble(label: List(Int))
+}
+",
+        find_position_of("Int"),
+    );
+}
+
+#[test]
+fn selection_range_of_labelled_argument() {
+    assert_selection_range!(
+        "
+pub type Wibble {
+  Wibble(label: Int)
+}
+
+pub fn main() {
+  Wibble(label: 1)
+}
+",
+        find_position_of("label: 1"),
+    );
+}
+
+#[test]
+fn selection_range_of_unqualified_import() {
+    assert_selection_range!(
+        "
+import gleam.{type Int}
+",
+        find_position_of("Int"),
+    );
+}
+
+#[test]
+fn selection_range_outside_of_definitions() {
+    let project = TestProject::for_source("\n\npub fn main() { Nil }\n");
+    let position = Position::new(0, 0);
+    assert_eq!(
+        selection_range(&project, position),
+        SelectionRange {
+            range: Range::new(position, position),
+            parent: None,
+        }
+    );
+}
@@ -0,0 +1,240 @@
+use lsp_types::{GotoDefinitionParams, GotoDefinitionResponse, Location, Position, Range};
+
+use super::*;
+
+fn type_definition(tester: &TestProject<'_>, position: Position) -> Option<GotoDefinitionResponse> {
+    tester.at(position, |engine, param, _| {
+        let params = GotoDefinitionParams {
+            text_document_position_params: param,
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        };
+        engine.goto_type_definition(params).result.unwrap()
+    })
+}
+
+fn pretty_type_definition(project: TestProject<'_>, position_finder: PositionFinder) -> String {
+    let position = position_finder.find_position(project.src);
+    let locations = match type_definition(&project, position).expect("a location to jump to") {
+        GotoDefinitionResponse::Scalar(location) => vec![location],
+        GotoDefinitionResponse::Array(locations) => locations,
+        GotoDefinitionResponse::Link(_) => panic!("unexpected location links"),
+    };
+
+    let src = hover::show_hover(
+        project.src,
+        Range {
+            start: position,
+            end: position,
+        },
+        position,
+    );
+    let mut output = format!("----- Jumping from `src/app.gleam`\n{src}");
+
+    for Location { uri, range } in locations {
+        let destination = uri
+            .path_segments()
+            .expect("a location to jump to")
+            // To make snapshots the same both on windows and unix systems we need
+            // to discard windows' `C:` path segment at the beginning of a uri.
+            .skip_while(|segment| *segment == "C:")
+            .join("/");
+        let src = project
+            .src_from_module_url(&uri)
+            .expect("a module to jump to");
+        output.push_str(&format!(
+            "----- Jumped to `{destination}`\n{}",
+            hover::show_hover(src, range, range.start)
+        ));
+    }
+    output
+}
+
+macro_rules! assert_type_definition {
+    ($src:literal, $position:expr) => {
+        let project = TestProject::for_source($src);
+        assert_type_definition!(project, $position);
+    };
+    ($project:expr, $position:expr) => {
+        let output = pretty_type_definition($project, $position);
+        insta::assert_snapshot!(insta::internals::AutoName, output);
+    };
+}
+
+#[test]
+fn type_definition_of_local_variable() {
+    assert_type_definition!(
+        "
+pub type Wibble {
+  Wibble(Int)
+}
+
+pub fn main() {
+  let wibble = Wibble(1)
+  wibble
+}
+",
+        find_position_of("wibble").nth_occurrence(2)
+    );
+}
+
+#[test]
+fn type_definition_of_function_argument() {
+    assert_type_definition!(
+        "
+pub type Wibble {
+  Wibble(Int)
+}
+
+pub fn main(wibble: Wibble) {
+  wibble
+}
+",
+        find_position_of("wibble:")
+    );
+}
+
+#[test]
+fn type_definition_of_type_from_other_module() {
+    assert_type_definition!(
+        TestProject::for_source(
+            "
+import wibble
+
+pub fn main() {
+  let value = wibble.new()
+  value
+}
+"
+        )
+        .add_module(
+            "wibble",
+            "pub type Wibble {
+  Wibble
+}
+
+pub fn new() -> Wibble {
+  Wibble
+}
+"
+        ),
+        find_position_of("value").nth_occurrence(2)
+    );
+}
+
+#[test]
+fn type_definition_of_type_from_dependency() {
+    assert_type_definition!(
+        TestProject::for_source(
+            "
+import wibble
+
+pub fn main() {
+  wibble.new()
+}
+"
+        )
+        .add_hex_module(
+            "wibble",
+            "pub type Wibble {
+  Wibble
+}
+
+pub fn new() -> Wibble {
+  Wibble
+}
+"
+        ),
+        find_position_of("new")
+    );
+}
+
+#[test]
+fn type_definition_unwraps_list() {
+    assert_type_definition!(
+        "
+pub type Wibble {
+  Wibble
+}
+
+pub fn main() {
+  let wibbles = [Wibble]
+  wibbles
+}
+",
+        find_position_of("wibbles").nth_occurrence(2)
+    );
+}
+
+#[test]
+fn type_definition_of_result_offers_a_choice() {
+    assert_type_definition!(
+        "
+pub type Wibble {
+  Wibble
+}
+
+pub type Wobble {
+  Wobble
+}
+
+pub fn main(result: Result(Wibble, Wobble)) {
+  result
+}
+",
+        find_position_of("result").nth_occurrence(2)
+    );
+}
+
+#[test]
+fn type_definition_of_generic_custom_type_includes_arguments() {
+    assert_type_definition!(
+        "
+pub type Box(a) {
+  Box(a)
+}
+
+pub type Wibble {
+  Wibble
+}
+
+pub fn main() {
+  let box = Box(Wibble)
+  box
+}
+",
+        find_position_of("box").nth_occurrence(2)
+    );
+}
+
+#[test]
+fn type_definition_of_annotation() {
+    assert_type_definition!(
+        "
+pub type Wibble {
+  Wibble
+}
+
+pub fn main() -> Wibble {
+  Wibble
+}
+",
+        find_position_of("-> Wibble").under_char('W')
+    );
+}
+
+#[test]
+fn no_type_definition_for_prelude_type() {
+    let project = TestProject::for_source(
+        "
+pub fn main() {
+  let x = 1
+  x
+}
+",
+    );
+    let position = find_position_of("x")
+        .nth_occurrence(2)
+        .find_position(project.src);
+    assert_eq!(type_definition(&project, position), None);
+}
@@ -59,6 +59,7 @@ extern crate pretty_assertions;
 
 pub mod analyse;
 pub mod ast;
+pub mod audit;
 pub mod bit_array;
 pub mod build;
 pub mod codegen;
@@ -69,6 +70,8 @@ pub mod docs;
 pub mod encryption;
 pub mod erlang;
 pub mod error;
+pub mod explain;
+pub mod ffi;
 pub mod fix;
 pub mod format;
 pub mod hex;
@@ -78,22 +81,26 @@ pub mod language_server;
 pub mod line_numbers;
 pub mod manifest;
 pub mod metadata;
+pub mod move_module;
 pub mod package_interface;
 pub mod parse;
 pub mod paths;
 pub mod pretty;
 pub mod requirement;
+pub mod sbom;
 pub mod strings;
 pub mod type_;
 pub mod uid;
 pub mod version;
 pub mod warning;
+pub mod workspace;
 
 pub(crate) mod ast_folder;
 mod call_graph;
 mod dep_tree;
 mod exhaustiveness;
 pub(crate) mod graph;
+mod reference;
 
 pub use error::{Error, Result};
 pub use warning::Warning;
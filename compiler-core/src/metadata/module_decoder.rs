@@ -107,6 +107,7 @@ impl ModuleDecoder {
         } else {
             Deprecation::Deprecated {
                 message: self.string(deprecation)?,
+                replacement: None,
             }
         };
         Ok(TypeConstructor {
@@ -241,8 +242,14 @@ impl ModuleDecoder {
         let deprecation = if deprecation.is_empty() {
             Deprecation::NotDeprecated
         } else {
+            let replacement = reader.get_deprecation_replacement()?;
             Deprecation::Deprecated {
                 message: self.string(deprecation)?,
+                replacement: if replacement.is_empty() {
+                    None
+                } else {
+                    Some(self.string(replacement)?)
+                },
             }
         };
         Ok(ValueConstructor {
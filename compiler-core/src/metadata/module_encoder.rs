@@ -183,7 +183,7 @@ impl<'a> ModuleEncoder<'a> {
         builder.set_module(&constructor.module);
         builder.set_deprecated(match &constructor.deprecation {
             Deprecation::NotDeprecated => "",
-            Deprecation::Deprecated { message } => message,
+            Deprecation::Deprecated { message, .. } => message,
         });
         self.build_publicity(builder.reborrow().init_publicity(), constructor.publicity);
         let type_builder = builder.reborrow().init_type();
@@ -234,7 +234,17 @@ impl<'a> ModuleEncoder<'a> {
     ) {
         builder.set_deprecated(match &constructor.deprecation {
             Deprecation::NotDeprecated => "",
-            Deprecation::Deprecated { message } => message,
+            Deprecation::Deprecated { message, .. } => message,
+        });
+        builder.set_deprecation_replacement(match &constructor.deprecation {
+            Deprecation::Deprecated {
+                replacement: Some(replacement),
+                ..
+            } => replacement,
+            Deprecation::Deprecated {
+                replacement: None, ..
+            }
+            | Deprecation::NotDeprecated => "",
         });
 
         self.build_publicity(builder.reborrow().init_publicity(), constructor.publicity);
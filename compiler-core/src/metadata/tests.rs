@@ -496,6 +496,57 @@ fn deprecated_module_fn_value() {
                 publicity: Publicity::Public,
                 deprecation: Deprecation::Deprecated {
                     message: "wibble wobble".into(),
+                    replacement: None,
+                },
+                type_: type_::int(),
+                variant: ValueConstructorVariant::ModuleFn {
+                    documentation: Some("wobble!".into()),
+                    name: "one".into(),
+                    field_map: None,
+                    module: "a".into(),
+                    arity: 5,
+                    location: SrcSpan {
+                        start: 535,
+                        end: 1100,
+                    },
+                    external_erlang: None,
+                    external_javascript: None,
+                    implementations: Implementations {
+                        gleam: true,
+                        uses_erlang_externals: false,
+                        uses_javascript_externals: false,
+                        can_run_on_erlang: true,
+                        can_run_on_javascript: true,
+                    },
+                },
+            },
+        )]
+        .into(),
+        line_numbers: LineNumbers::new(""),
+        src_path: "some_path".into(),
+        minimum_required_version: Version::new(0, 1, 0),
+    };
+    assert_eq!(roundtrip(&module), module);
+}
+
+#[test]
+fn deprecated_module_fn_value_with_replacement() {
+    let module = ModuleInterface {
+        warnings: vec![],
+        is_internal: false,
+        package: "some_package".into(),
+        origin: Origin::Src,
+        name: "a".into(),
+        types: HashMap::new(),
+        types_value_constructors: HashMap::new(),
+        accessors: HashMap::new(),
+        values: [(
+            "one".into(),
+            ValueConstructor {
+                publicity: Publicity::Public,
+                deprecation: Deprecation::Deprecated {
+                    message: "wibble wobble".into(),
+                    replacement: Some("wobble.two(_2, _1)".into()),
                 },
                 type_: type_::int(),
                 variant: ValueConstructorVariant::ModuleFn {
@@ -1228,6 +1279,7 @@ fn deprecated_type() {
                 parameters: vec![],
                 deprecation: Deprecation::Deprecated {
                     message: "oh no".into(),
+                    replacement: None,
                 },
                 documentation: None,
             },
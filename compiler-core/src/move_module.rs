@@ -0,0 +1,115 @@
+//! Moving a module to a new path, as done by `gleam mv` and by the language
+//! server when a file is renamed in the editor.
+
+use camino::{Utf8Path, Utf8PathBuf};
+use ecow::{eco_format, EcoString};
+
+use crate::{
+    ast::{Definition, SrcSpan},
+    build::Module,
+    fix::Edit,
+    io::FileSystemReader,
+    reference::{module_references, name_is_taken, ReferenceKind, Symbol},
+};
+
+/// The extensions of the native files moved along with a module.
+///
+const NATIVE_FILE_EXTENSIONS: [&str; 2] = ["erl", "mjs"];
+
+/// The edits to make to a module of the package when the module `old` is
+/// moved to `new`, so that the code importing it keeps working.
+///
+/// The path of the import is changed, and when the module is imported without
+/// an alias and the last segment of its path changes, so does the name it is
+/// referred to with in qualified references. If the new name is already used
+/// in the module, the old one is kept instead with an alias:
+///
+/// ```gleam
+/// import app/accounts/member as user
+/// ```
+///
+pub fn module_move_edits(module: &Module, old: &str, new: &str) -> Vec<Edit> {
+    let Some(import) = module
+        .ast
+        .definitions
+        .iter()
+        .find_map(|definition| match definition {
+            Definition::Import(import) if import.module == old => Some(import),
+            _ => None,
+        })
+    else {
+        return vec![];
+    };
+
+    let mut edits = vec![Edit {
+        location: import.module_location(&module.code),
+        new_text: new.into(),
+    }];
+
+    let old_name = last_segment(old);
+    let new_name = last_segment(new);
+    if import.as_name.is_some() || old_name == new_name {
+        return edits;
+    }
+
+    let symbol = Symbol::ImportedModule { module: old.into() };
+    let references = module_references(module);
+    if name_is_taken(
+        &module.ast,
+        &module.code,
+        &references,
+        new_name,
+        Some(&symbol),
+    ) {
+        edits.push(Edit {
+            location: SrcSpan::new(import.location.end, import.location.end),
+            new_text: eco_format!(" as {old_name}"),
+        });
+    } else {
+        edits.extend(
+            references
+                .into_iter()
+                .filter(|reference| {
+                    reference.symbol == symbol && reference.kind == ReferenceKind::Usage
+                })
+                .map(|reference| Edit {
+                    location: reference.location,
+                    new_text: new_name.into(),
+                }),
+        );
+    }
+    edits
+}
+
+/// The native files moved along with a module: the Erlang and JavaScript
+/// files next to it with the same name. Each is returned with the path it is
+/// moved to.
+///
+pub fn native_file_moves(
+    io: &impl FileSystemReader,
+    old_path: &Utf8Path,
+    new_path: &Utf8Path,
+) -> Vec<(Utf8PathBuf, Utf8PathBuf)> {
+    NATIVE_FILE_EXTENSIONS
+        .iter()
+        .map(|extension| {
+            (
+                old_path.with_extension(extension),
+                new_path.with_extension(extension),
+            )
+        })
+        .filter(|(old_path, _)| io.is_file(old_path))
+        .collect()
+}
+
+/// The name of the module defined by a file, given its path relative to the
+/// `src` or `test` directory of its package.
+///
+pub fn module_name_from_path(path: &Utf8Path) -> Option<EcoString> {
+    let path = path.as_str().strip_suffix(".gleam")?;
+    Some(path.replace('\\', "/").into())
+}
+
+fn last_segment(module: &str) -> &str {
+    module.rsplit('/').next().unwrap_or(module)
+}
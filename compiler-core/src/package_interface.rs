@@ -284,7 +284,7 @@ impl DeprecationInterface {
     fn from_deprecation(deprecation: &Deprecation) -> Option<DeprecationInterface> {
         match deprecation {
             Deprecation::NotDeprecated => None,
-            Deprecation::Deprecated { message } => Some(DeprecationInterface {
+            Deprecation::Deprecated { message, .. } => Some(DeprecationInterface {
                 message: message.clone(),
             }),
         }
@@ -52,7 +52,7 @@
 pub mod error;
 pub mod extra;
 pub mod lexer;
-mod token;
+pub mod token;
 
 use crate::analyse::Inferred;
 use crate::ast::{
@@ -71,7 +71,7 @@ use crate::parse::extra::ModuleExtra;
 use crate::type_::error::VariableOrigin;
 use crate::type_::expression::Implementations;
 use crate::type_::Deprecation;
-use crate::warning::{DeprecatedSyntaxWarning, WarningEmitter};
+use crate::warning::{DeprecatedSyntaxWarning, WarningEmitter, WarningKind};
 use crate::Warning;
 use camino::Utf8PathBuf;
 use ecow::EcoString;
@@ -119,9 +119,12 @@ enum InternalAttribute {
 struct Attributes {
     target: Option<Target>,
     deprecated: Deprecation,
+    // The location of the `replace:` argument of a `@deprecated` attribute
+    deprecation_replacement: Option<SrcSpan>,
     external_erlang: Option<(EcoString, EcoString, SrcSpan)>,
     external_javascript: Option<(EcoString, EcoString, SrcSpan)>,
     internal: InternalAttribute,
+    allowed_warnings: Vec<WarningKind>,
 }
 
 impl Attributes {
@@ -142,6 +145,15 @@ impl Attributes {
             Target::JavaScript => self.external_javascript = ext,
         }
     }
+
+    // Replacements are only applied to the uses of deprecated functions and
+    // constants, so types and constructors can't have one.
+    fn ensure_no_deprecation_replacement(&self) -> Result<(), ParseError> {
+        match self.deprecation_replacement {
+            Some(location) => parse_error(ParseErrorType::DeprecationReplacementOnType, location),
+            None => Ok(()),
+        }
+    }
 }
 
 //
@@ -358,11 +370,13 @@ where
             (Some(definition), _) if definition.is_function() => Ok(Some(TargetedDefinition {
                 definition,
                 target: attributes.target,
+                allowed_warnings: attributes.allowed_warnings,
             })),
 
             (Some(definition), None) => Ok(Some(TargetedDefinition {
                 definition,
                 target: attributes.target,
+                allowed_warnings: attributes.allowed_warnings,
             })),
 
             (_, Some(location)) if attributes.has_function_only() => {
@@ -372,6 +386,7 @@ where
             (Some(definition), _) => Ok(Some(TargetedDefinition {
                 definition,
                 target: attributes.target,
+                allowed_warnings: attributes.allowed_warnings,
             })),
 
             (_, Some(location)) => parse_error(ParseErrorType::ExpectedDefinition, location),
@@ -2216,6 +2231,7 @@ where
         opaque: bool,
         attributes: &mut Attributes,
     ) -> Result<Option<UntypedDefinition>, ParseError> {
+        attributes.ensure_no_deprecation_replacement()?;
         let documentation = self.take_documentation(start);
         let (name_start, name, parameters, end, name_end) = self.expect_type_name()?;
         let name_location = SrcSpan::new(name_start, name_end);
@@ -2227,6 +2243,7 @@ where
                     // The only attribute supported on constructors is @deprecated
                     let mut attributes = Attributes::default();
                     let attr_loc = Parser::parse_attributes(p, &mut attributes)?;
+                    attributes.ensure_no_deprecation_replacement()?;
 
                     if let Some(attr_span) = attr_loc {
                         // Expecting all but the deprecated atterbutes to be default
@@ -2234,6 +2251,7 @@ where
                             || attributes.external_javascript.is_some()
                             || attributes.target.is_some()
                             || attributes.internal != InternalAttribute::Missing
+                            || !attributes.allowed_warnings.is_empty()
                         {
                             return parse_error(
                                 ParseErrorType::UnknownAttributeRecordVariant,
@@ -3663,6 +3681,10 @@ functions are declared separately from types.";
                 self.parse_deprecated_attribute(start, end, attributes)
             }
             "internal" => self.parse_internal_attribute(start, end, attributes),
+            "allow" => {
+                let _ = self.expect_one(&Token::LeftParen)?;
+                self.parse_allow_attribute(start, end, attributes)
+            }
             _ => parse_error(ParseErrorType::UnknownAttribute, SrcSpan { start, end }),
         }?;
 
@@ -3729,8 +3751,82 @@ functions are declared separately from types.";
             error: ParseErrorType::ExpectedDeprecationMessage,
             location: SrcSpan { start, end },
         })?;
+        let replacement = match self.maybe_one(&Token::Comma) {
+            Some(_) if self.maybe_one(&Token::RightParen).is_none() => {
+                let (location, replacement) = self.parse_deprecation_replacement()?;
+                let _ = self.maybe_one(&Token::Comma);
+                attributes.deprecation_replacement = Some(location);
+                Some(replacement)
+            }
+            _ => None,
+        };
+        let (_, end) = self.expect_one(&Token::RightParen)?;
+        attributes.deprecated = Deprecation::Deprecated {
+            message,
+            replacement,
+        };
+        Ok(end)
+    }
+
+    // The `replace: "module.new_function(_2, _1)"` argument of a
+    // `@deprecated` attribute
+    fn parse_deprecation_replacement(&mut self) -> Result<SpannedString, ParseError> {
+        let (start, label, end) = self.expect_name()?;
+        if label != "replace" {
+            return parse_error(
+                ParseErrorType::ExpectedDeprecationReplacement,
+                SrcSpan { start, end },
+            );
+        }
+        let _ = self.expect_one(&Token::Colon)?;
+        let (start, replacement, end) = self.expect_string().map_err(|_| ParseError {
+            error: ParseErrorType::ExpectedDeprecationReplacement,
+            location: SrcSpan { start, end },
+        })?;
+        if crate::fix::Replacement::parse(&replacement).is_none() {
+            return parse_error(
+                ParseErrorType::InvalidDeprecationReplacement,
+                SrcSpan { start, end },
+            );
+        }
+        Ok((SrcSpan { start, end }, replacement))
+    }
+
+    // The warnings silenced by an `@allow(unused_variable, todo)` attribute
+    fn parse_allow_attribute(
+        &mut self,
+        start: u32,
+        end: u32,
+        attributes: &mut Attributes,
+    ) -> Result<u32, ParseError> {
+        if !attributes.allowed_warnings.is_empty() {
+            return parse_error(ParseErrorType::DuplicateAttribute, SrcSpan { start, end });
+        }
+
+        loop {
+            let (start, name, end) = match self.next_tok() {
+                Some((start, Token::Name { name }, end)) => (start, name, end),
+                // `todo` is a keyword, but it's also the name of a warning
+                Some((start, Token::Todo, end)) => (start, "todo".into(), end),
+                Some((start, _, end)) => {
+                    return parse_error(ParseErrorType::UnknownWarning, SrcSpan { start, end })
+                }
+                None => return parse_error(ParseErrorType::UnexpectedEof, SrcSpan::new(end, end)),
+            };
+            let Ok(kind) = WarningKind::from_str(&name) else {
+                return parse_error(ParseErrorType::UnknownWarning, SrcSpan { start, end });
+            };
+            attributes.allowed_warnings.push(kind);
+
+            // A trailing comma is allowed
+            if self.maybe_one(&Token::Comma).is_none()
+                || matches!(self.tok0, Some((_, Token::RightParen, _)))
+            {
+                break;
+            }
+        }
+
         let (_, end) = self.expect_one(&Token::RightParen)?;
-        attributes.deprecated = Deprecation::Deprecated { message };
         Ok(end)
     }
 
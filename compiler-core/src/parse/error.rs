@@ -57,6 +57,25 @@ impl ParseError {
                 "A deprecation attribute must have a string message.",
                 vec![],
             ),
+            ParseErrorType::ExpectedDeprecationReplacement => (
+                "I was expecting a replacement here",
+                vec!["Hint: A replacement is given as `replace: \"module.new_function\"`.".into()],
+            ),
+            ParseErrorType::DeprecationReplacementOnType => (
+                "Types and constructors can't have a replacement",
+                vec![wrap(
+                    "Hint: Replacements are only applied to deprecated functions \
+and constants, the deprecation message can say what to use instead.",
+                )],
+            ),
+            ParseErrorType::InvalidDeprecationReplacement => (
+                "This is not a valid replacement",
+                vec![wrap(
+                    "Hint: A replacement is the name of a value, optionally qualified \
+with its module, and followed by the arguments of the deprecated function it \
+is called with. For example: `gleam/list.map(_2, _1)`.",
+                )],
+            ),
             ParseErrorType::ExpectedFunctionDefinition => {
                 ("I was expecting a function definition after this", vec![])
             }
@@ -250,6 +269,13 @@ utf16_codepoint, utf32_codepoint, signed, unsigned, big, little, native, size, u
                 "I don't recognise this target",
                 vec!["Try `erlang`, `javascript`.".into()],
             ),
+            ParseErrorType::UnknownWarning => (
+                "I don't recognise this warning",
+                vec![wrap(
+                    "Hint: Warnings are named in snake case, for example \
+`unused_variable` or `deprecated_item`.",
+                )],
+            ),
             ParseErrorType::ExpectedFunctionBody => ("This function does not have a body", vec![]),
             ParseErrorType::RedundantInternalAttribute => (
                 "Redundant internal attribute",
@@ -340,25 +366,27 @@ utf16_codepoint, utf32_codepoint, signed, unsigned, big, little, native, size, u
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ParseErrorType {
-    ExpectedEqual,              // expect "="
-    ExpectedExpr,               // after "->" in a case clause
-    ExpectedName,               // any token used when a Name was expected
-    ExpectedPattern,            // after ':' where a pattern is expected
-    ExpectedType,               // after ':' or '->' where a type annotation is expected
-    ExpectedUpName,             // any token used when a UpName was expected
-    ExpectedValue,              // no value after "="
-    ExpectedStatement,          // no statement after "@<name>"
-    ExpectedDefinition,         // after attributes
-    ExpectedDeprecationMessage, // after "deprecated"
-    ExpectedFunctionDefinition, // after function-only attributes
-    ExprLparStart,              // it seems "(" was used to start an expression
-    ExtraSeparator,             // #(1,,) <- the 2nd comma is an extra separator
-    IncorrectName,              // UpName or DiscardName used when Name was expected
-    IncorrectUpName,            // Name or DiscardName used when UpName was expected
-    InvalidBitArraySegment,     // <<7:hello>> `hello` is an invalid BitArray segment
-    InvalidBitArrayUnit,        // in <<1:unit(x)>> x must be 1 <= x <= 256
-    InvalidTailPattern,         // only name and _name are allowed after ".." in list pattern
-    InvalidTupleAccess,         // only positive int literals for tuple access
+    ExpectedEqual,                  // expect "="
+    ExpectedExpr,                   // after "->" in a case clause
+    ExpectedName,                   // any token used when a Name was expected
+    ExpectedPattern,                // after ':' where a pattern is expected
+    ExpectedType,                   // after ':' or '->' where a type annotation is expected
+    ExpectedUpName,                 // any token used when a UpName was expected
+    ExpectedValue,                  // no value after "="
+    ExpectedStatement,              // no statement after "@<name>"
+    ExpectedDefinition,             // after attributes
+    ExpectedDeprecationMessage,     // after "deprecated"
+    ExpectedDeprecationReplacement, // after the message of "deprecated"
+    InvalidDeprecationReplacement,  // @deprecated("", replace: "1 + 1")
+    ExpectedFunctionDefinition,     // after function-only attributes
+    ExprLparStart,                  // it seems "(" was used to start an expression
+    ExtraSeparator,                 // #(1,,) <- the 2nd comma is an extra separator
+    IncorrectName,                  // UpName or DiscardName used when Name was expected
+    IncorrectUpName,                // Name or DiscardName used when UpName was expected
+    InvalidBitArraySegment,         // <<7:hello>> `hello` is an invalid BitArray segment
+    InvalidBitArrayUnit,            // in <<1:unit(x)>> x must be 1 <= x <= 256
+    InvalidTailPattern,             // only name and _name are allowed after ".." in list pattern
+    InvalidTupleAccess,             // only positive int literals for tuple access
     LexError {
         error: LexicalError,
     },
@@ -373,6 +401,7 @@ pub enum ParseErrorType {
     DuplicateAttribute, // an attribute was used more than once
     UnknownAttribute, // an attribute was used that is not known
     UnknownTarget, // an unknown target was used
+    UnknownWarning, // an unknown warning was used in `@allow`
     ListSpreadWithoutElements, // Pointless spread: `[..xs]`
     ListSpreadFollowedByElements, // trying to append something after the spread: `[..xs, x]`
     LowcaseBooleanPattern, // most likely user meant True or False in patterns
@@ -406,6 +435,93 @@ pub enum ParseErrorType {
     TypeConstructorNoArguments,           // let a : Int()
     TypeDefinitionNoArguments,            // pub type Wibble() { ... }
     UnknownAttributeRecordVariant, // an attribute was used that is not know for a custom type variant
+    DeprecationReplacementOnType,  // @deprecated("", replace: "wobble") pub type Wibble
+}
+
+impl ParseErrorType {
+    /// The stable code identifying this kind of syntax error, which can be
+    /// looked up with `gleam explain`.
+    ///
+    pub fn code(&self) -> &'static str {
+        match self {
+            ParseErrorType::ExpectedEqual => "E0101",
+            ParseErrorType::ExpectedExpr => "E0102",
+            ParseErrorType::ExpectedName => "E0103",
+            ParseErrorType::ExpectedPattern => "E0104",
+            ParseErrorType::ExpectedType => "E0105",
+            ParseErrorType::ExpectedUpName => "E0106",
+            ParseErrorType::ExpectedValue => "E0107",
+            ParseErrorType::ExpectedStatement => "E0108",
+            ParseErrorType::ExpectedDefinition => "E0109",
+            ParseErrorType::ExpectedDeprecationMessage => "E0110",
+            ParseErrorType::ExpectedDeprecationReplacement => "E0111",
+            ParseErrorType::InvalidDeprecationReplacement => "E0112",
+            ParseErrorType::ExpectedFunctionDefinition => "E0113",
+            ParseErrorType::ExprLparStart => "E0114",
+            ParseErrorType::ExtraSeparator => "E0115",
+            ParseErrorType::IncorrectName => "E0116",
+            ParseErrorType::IncorrectUpName => "E0117",
+            ParseErrorType::InvalidBitArraySegment => "E0118",
+            ParseErrorType::InvalidBitArrayUnit => "E0119",
+            ParseErrorType::InvalidTailPattern => "E0120",
+            ParseErrorType::InvalidTupleAccess => "E0121",
+            ParseErrorType::LexError { error } => error.error.code(),
+            ParseErrorType::NestedBitArrayPattern => "E0122",
+            ParseErrorType::NoExpression => "E0123",
+            ParseErrorType::NoLetBinding => "E0124",
+            ParseErrorType::NoValueAfterEqual => "E0125",
+            ParseErrorType::NotConstType => "E0126",
+            ParseErrorType::OpNakedRight => "E0127",
+            ParseErrorType::OpaqueTypeAlias => "E0128",
+            ParseErrorType::TooManyArgHoles => "E0129",
+            ParseErrorType::DuplicateAttribute => "E0130",
+            ParseErrorType::UnknownAttribute => "E0131",
+            ParseErrorType::UnknownTarget => "E0132",
+            ParseErrorType::UnknownWarning => "E0133",
+            ParseErrorType::ListSpreadWithoutElements => "E0134",
+            ParseErrorType::ListSpreadFollowedByElements => "E0135",
+            ParseErrorType::LowcaseBooleanPattern => "E0136",
+            ParseErrorType::UnexpectedLabel => "E0137",
+            ParseErrorType::UnexpectedEof => "E0138",
+            ParseErrorType::UnexpectedReservedWord => "E0139",
+            ParseErrorType::UnexpectedToken { .. } => "E0140",
+            ParseErrorType::ExpectedBoolean => "E0141",
+            ParseErrorType::UnexpectedFunction => "E0142",
+            ParseErrorType::ConcatPatternVariableLeftHandSide => "E0143",
+            ParseErrorType::ListSpreadWithoutTail => "E0144",
+            ParseErrorType::ExpectedFunctionBody => "E0145",
+            ParseErrorType::RedundantInternalAttribute => "E0146",
+            ParseErrorType::InvalidModuleTypePattern => "E0147",
+            ParseErrorType::ListPatternSpreadFollowedByElements => "E0148",
+            ParseErrorType::ExpectedRecordConstructor { .. } => "E0149",
+            ParseErrorType::CallInClauseGuard => "E0150",
+            ParseErrorType::IfExpression => "E0151",
+            ParseErrorType::ConstantRecordConstructorNoArguments => "E0152",
+            ParseErrorType::TypeConstructorNoArguments => "E0153",
+            ParseErrorType::TypeDefinitionNoArguments => "E0154",
+            ParseErrorType::UnknownAttributeRecordVariant => "E0155",
+            ParseErrorType::DeprecationReplacementOnType => "E0156",
+        }
+    }
+}
+
+impl LexicalErrorType {
+    /// The stable code identifying this kind of syntax error, which can be
+    /// looked up with `gleam explain`.
+    ///
+    pub fn code(&self) -> &'static str {
+        match self {
+            LexicalErrorType::BadStringEscape => "E0181",
+            LexicalErrorType::InvalidUnicodeEscape(..) => "E0182",
+            LexicalErrorType::DigitOutOfRadix => "E0183",
+            LexicalErrorType::NumTrailingUnderscore => "E0184",
+            LexicalErrorType::RadixIntNoValue => "E0185",
+            LexicalErrorType::MissingExponent => "E0186",
+            LexicalErrorType::UnexpectedStringEnd => "E0187",
+            LexicalErrorType::UnrecognizedToken { .. } => "E0188",
+            LexicalErrorType::InvalidTripleEqual => "E0189",
+        }
+    }
 }
 
 impl LexicalError {
@@ -641,6 +641,115 @@ pub fn main() -> Nil {
     );
 }
 
+#[test]
+fn deprecation_with_unknown_argument() {
+    assert_module_error!(
+        r#"
+@deprecated("1", wibble: "new")
+pub fn main() -> Nil {
+  Nil
+}
+"#
+    );
+}
+
+#[test]
+fn deprecation_with_invalid_replacement() {
+    assert_module_error!(
+        r#"
+@deprecated("1", replace: "new(1, _2)")
+pub fn main() -> Nil {
+  Nil
+}
+"#
+    );
+}
+
+#[test]
+fn deprecation_with_replacement() {
+    assert_parse_module!(
+        r#"
+@deprecated("1", replace: "wibble.new(_2, _1)")
+pub fn main(a, b) -> Nil {
+  Nil
+}
+"#
+    );
+}
+
+#[test]
+fn deprecation_with_replacement_on_type() {
+    assert_module_error!(
+        r#"
+@deprecated("1", replace: "Wobble")
+pub type Wibble {
+  Wibble
+}
+"#
+    );
+}
+
+#[test]
+fn deprecation_with_replacement_on_type_alias() {
+    assert_module_error!(
+        r#"
+@deprecated("1", replace: "Int")
+pub type Wibble =
+  Int
+"#
+    );
+}
+
+#[test]
+fn deprecation_with_replacement_on_type_variant() {
+    assert_module_error!(
+        r#"
+pub type Wibble {
+  @deprecated("1", replace: "Wobble")
+  Wibble
+  Wobble
+}
+"#
+    );
+}
+
+#[test]
+fn allow_attribute_with_unknown_warning() {
+    assert_module_error!(
+        r#"
+@allow(unused_wibble)
+pub fn main() -> Nil {
+  Nil
+}
+"#
+    );
+}
+
+#[test]
+fn multiple_allow_attributes() {
+    assert_module_error!(
+        r#"
+@allow(todo)
+@allow(unused_variable)
+pub fn main() -> Nil {
+  todo
+}
+"#
+    );
+}
+
+#[test]
+fn allow_attribute_on_type_variant() {
+    assert_module_error!(
+        r#"
+pub type Wibble {
+  @allow(todo)
+  Wibble
+}
+"#
+    );
+}
+
 #[test]
 fn multiple_internal_attributes() {
     assert_module_error!(
@@ -1,17 +1,31 @@
 use crate::build::{Mode, Target};
 
-use camino::{Utf8Path, Utf8PathBuf};
+use camino::{Utf8Component, Utf8Path, Utf8PathBuf};
 
 pub const ARTEFACT_DIRECTORY_NAME: &str = "_gleam_artefacts";
 
 #[derive(Debug, Clone)]
 pub struct ProjectPaths {
     root: Utf8PathBuf,
+    workspace_root: Option<Utf8PathBuf>,
 }
 
 impl ProjectPaths {
     pub fn new(root: Utf8PathBuf) -> Self {
-        Self { root }
+        Self {
+            root,
+            workspace_root: None,
+        }
+    }
+
+    /// The paths of a package of a workspace, which keeps the manifest and
+    /// the build directory of all its members at its own root. The root of
+    /// the workspace itself is given as both.
+    pub fn in_workspace(root: Utf8PathBuf, workspace_root: Utf8PathBuf) -> Self {
+        Self {
+            root,
+            workspace_root: Some(workspace_root),
+        }
     }
 
     pub fn at_filesystem_root() -> Self {
@@ -28,6 +42,20 @@ impl ProjectPaths {
         &self.root
     }
 
+    pub fn workspace_root(&self) -> Option<&Utf8Path> {
+        self.workspace_root.as_deref()
+    }
+
+    pub fn is_workspace_root(&self) -> bool {
+        self.workspace_root() == Some(self.root())
+    }
+
+    /// The directory holding the manifest and the build directory, which
+    /// relative path dependencies are also relative to.
+    pub fn manifest_directory(&self) -> &Utf8Path {
+        self.workspace_root().unwrap_or(self.root())
+    }
+
     pub fn root_config(&self) -> Utf8PathBuf {
         self.root.join("gleam.toml")
     }
@@ -37,7 +65,7 @@ impl ProjectPaths {
     }
 
     pub fn manifest(&self) -> Utf8PathBuf {
-        self.root.join("manifest.toml")
+        self.manifest_directory().join("manifest.toml")
     }
 
     pub fn src_directory(&self) -> Utf8PathBuf {
@@ -49,7 +77,7 @@ impl ProjectPaths {
     }
 
     pub fn build_directory(&self) -> Utf8PathBuf {
-        self.root.join("build")
+        self.manifest_directory().join("build")
     }
 
     pub fn build_packages_directory(&self) -> Utf8PathBuf {
@@ -60,6 +88,10 @@ impl ProjectPaths {
         self.build_packages_directory().join("packages.toml")
     }
 
+    pub fn build_packages_licences_toml(&self) -> Utf8PathBuf {
+        self.build_packages_directory().join("licences.toml")
+    }
+
     pub fn build_packages_package(&self, package_name: &str) -> Utf8PathBuf {
         self.build_packages_directory().join(package_name)
     }
@@ -82,6 +114,16 @@ impl ProjectPaths {
         self.build_directory().join("erlang-shipment")
     }
 
+    pub fn erlang_release_directory(&self) -> Utf8PathBuf {
+        self.build_directory().join("erlang-release")
+    }
+
+    pub fn erlang_escript(&self, package_name: &str) -> Utf8PathBuf {
+        self.build_directory()
+            .join("erlang-escript")
+            .join(package_name)
+    }
+
     pub fn build_documentation_directory(&self, package: &str) -> Utf8PathBuf {
         self.build_directory_for_mode(Mode::Dev)
             .join("docs")
@@ -130,6 +172,12 @@ pub fn global_hexpm_credentials_path() -> Utf8PathBuf {
     global_hexpm_cache().join("credentials")
 }
 
+/// The directory holding the compiled Hex packages shared by all projects, see
+/// `build::global_build_cache` for details.
+pub fn global_build_cache() -> Utf8PathBuf {
+    default_global_gleam_cache().join("build")
+}
+
 fn global_hexpm_cache() -> Utf8PathBuf {
     default_global_gleam_cache().join("hex").join("hexpm")
 }
@@ -155,12 +203,39 @@ pub fn unnest(within: &Utf8Path) -> Utf8PathBuf {
     path
 }
 
+/// Removes the `.` and `..` components of a path without looking at the file
+/// system, so that paths to the same file can be compared. A `..` that can't
+/// be resolved is kept at the start of a relative path, and dropped after the
+/// root of an absolute one.
+///
+pub fn normalise(path: &Utf8Path) -> Utf8PathBuf {
+    let mut normalised = Utf8PathBuf::new();
+    for component in path.components() {
+        match component {
+            Utf8Component::CurDir => (),
+            Utf8Component::ParentDir => match normalised.components().next_back() {
+                Some(Utf8Component::Normal(_)) => _ = normalised.pop(),
+                Some(Utf8Component::RootDir | Utf8Component::Prefix(_)) => (),
+                Some(Utf8Component::ParentDir | Utf8Component::CurDir) | None => {
+                    normalised.push(component)
+                }
+            },
+            Utf8Component::Normal(_) | Utf8Component::RootDir | Utf8Component::Prefix(_) => {
+                normalised.push(component)
+            }
+        }
+    }
+    normalised
+}
+
 #[test]
 fn paths() {
     assert!(default_global_gleam_cache().ends_with("gleam"));
 
     assert!(global_packages_cache().ends_with("hex/hexpm/packages"));
 
+    assert!(global_build_cache().ends_with("gleam/build"));
+
     assert!(
         global_package_cache_package_tarball("gleam_stdlib", "0.17.1")
             .ends_with("hex/hexpm/packages/gleam_stdlib-0.17.1.tar")
@@ -169,3 +244,26 @@ fn paths() {
     assert!(global_package_cache_package_tarball("elli", "1.0.0")
         .ends_with("hex/hexpm/packages/elli-1.0.0.tar"));
 }
+
+#[test]
+fn workspace_member_paths() {
+    let paths = ProjectPaths::in_workspace("/ws/packages/app".into(), "/ws".into());
+    assert_eq!(paths.root_config(), "/ws/packages/app/gleam.toml");
+    assert_eq!(paths.src_directory(), "/ws/packages/app/src");
+    assert_eq!(paths.manifest(), "/ws/manifest.toml");
+    assert_eq!(paths.build_packages_directory(), "/ws/build/packages");
+    assert!(!paths.is_workspace_root());
+    assert!(ProjectPaths::in_workspace("/ws".into(), "/ws".into()).is_workspace_root());
+}
+
+#[test]
+fn normalise_paths() {
+    assert_eq!(
+        normalise("/ws/packages/./app/../lib".into()),
+        "/ws/packages/lib"
+    );
+    assert_eq!(normalise("src/../../wibble.mjs".into()), "../wibble.mjs");
+    assert_eq!(normalise("../../wibble".into()), "../../wibble");
+    assert_eq!(normalise("/../wibble".into()), "/wibble");
+    assert_eq!(normalise("./wibble/..".into()), "");
+}
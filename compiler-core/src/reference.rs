@@ -0,0 +1,857 @@
+use std::{collections::HashMap, sync::Arc};
+
+use ecow::EcoString;
+use vec1::Vec1;
+
+use crate::{
+    analyse::Inferred,
+    ast::{
+        self, visit::Visit, ArgNames, AssignName, CallArg, Constant, Definition,
+        FunctionLiteralKind, HasLocation, ImplicitCallArgOrigin, SrcSpan, TypeAst, TypedArg,
+        TypedAssignment, TypedConstant, TypedDefinition, TypedExpr, TypedModule, TypedPattern,
+        TypedStatement,
+    },
+    build::Module,
+    type_::{
+        self, error::VariableOrigin, prelude::PRELUDE_MODULE_NAME, ModuleValueConstructor,
+        PatternConstructor, Type, TypedCallArg, ValueConstructor, ValueConstructorVariant,
+    },
+};
+
+/// Something with a name that can be defined and referenced in many places.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Symbol {
+    /// A variable local to a function, identified by the place it is defined
+    /// in.
+    LocalVariable { definition: SrcSpan },
+    /// A function, constant or record constructor defined at the top level of
+    /// a module.
+    ModuleValue { module: EcoString, name: EcoString },
+    /// A custom type or a type alias.
+    ModuleType { module: EcoString, name: EcoString },
+    /// The label of a field of the records of a custom type, or of an argument
+    /// of a module function.
+    Label { owner: LabelOwner, label: EcoString },
+    /// A module, referenced through the name it is imported with.
+    ImportedModule { module: EcoString },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum LabelOwner {
+    Type { module: EcoString, name: EcoString },
+    Function { module: EcoString, name: EcoString },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReferenceKind {
+    /// The place where the symbol is defined, or bound in the case of
+    /// variables and imported modules.
+    Definition,
+    Usage,
+    /// A label that is also the name of a variable, as `wobble` in
+    /// `Wibble(wobble:)`.
+    LabelShorthand,
+}
+
+/// A place in the source of a module where a symbol's name appears.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reference {
+    pub symbol: Symbol,
+    pub location: SrcSpan,
+    pub kind: ReferenceKind,
+}
+
+/// Returns all the places where a symbol is defined or referenced in a
+/// module, in the order they are found in.
+///
+pub fn module_references(module: &Module) -> Vec<Reference> {
+    typed_module_references(&module.ast, &module.code)
+}
+
+/// Returns all the places where a symbol is defined or referenced in the
+/// given typed module and its source code.
+///
+pub fn typed_module_references(module: &TypedModule, code: &str) -> Vec<Reference> {
+    let mut finder = References::new(module, code);
+    finder.visit_typed_module(module);
+    finder.references
+}
+
+/// Whether a name is already given to a value or a module in a module, so
+/// that using it for something else would shadow it or clash with it. The
+/// definition of the `ignored` symbol doesn't count.
+///
+pub fn name_is_taken(
+    module: &TypedModule,
+    code: &str,
+    references: &[Reference],
+    name: &str,
+    ignored: Option<&Symbol>,
+) -> bool {
+    let code_at = |location: SrcSpan| code.get(location.start as usize..location.end as usize);
+    let is_defined = references.iter().any(|reference| {
+        reference.kind == ReferenceKind::Definition
+            && Some(&reference.symbol) != ignored
+            && code_at(reference.location) == Some(name)
+    });
+    is_defined
+        || module
+            .definitions
+            .iter()
+            .any(|definition| match definition {
+                Definition::Import(import) => import
+                    .unqualified_values
+                    .iter()
+                    .any(|value| value.used_name() == name),
+                _ => false,
+            })
+}
+
+/// Returns the reference at the given byte index. When references are nested,
+/// the innermost is picked, and a label shorthand is taken to refer to its
+/// variable rather than its label.
+///
+pub fn reference_at(references: &[Reference], byte_index: u32) -> Option<&Reference> {
+    references
+        .iter()
+        .rev()
+        .filter(|reference| reference.kind != ReferenceKind::LabelShorthand)
+        .filter(|reference| reference.location.contains(byte_index))
+        .min_by_key(|reference| reference.location.end - reference.location.start)
+}
+
+/// Returns all the places where the local variable defined at the given
+/// location is used.
+///
+pub fn find_variable_references(module: &Module, definition: SrcSpan) -> Vec<SrcSpan> {
+    let symbol = Symbol::LocalVariable { definition };
+    module_references(module)
+        .into_iter()
+        .filter(|reference| reference.symbol == symbol && reference.kind == ReferenceKind::Usage)
+        .map(|reference| reference.location)
+        .collect()
+}
+
+struct References<'a> {
+    module: &'a TypedModule,
+    code: &'a str,
+    /// The modules imported in the module, by the name they are used with.
+    module_aliases: HashMap<EcoString, EcoString>,
+    /// The types imported unqualified, by the name they are used with.
+    imported_types: HashMap<EcoString, (EcoString, EcoString)>,
+    /// The values imported unqualified, by their module and the name they are
+    /// used with.
+    imported_values: HashMap<(EcoString, EcoString), EcoString>,
+    references: Vec<Reference>,
+}
+
+impl<'a> References<'a> {
+    fn new(module: &'a TypedModule, code: &'a str) -> Self {
+        let mut module_aliases = HashMap::new();
+        let mut imported_types = HashMap::new();
+        let mut imported_values = HashMap::new();
+        for definition in &module.definitions {
+            let Definition::Import(import) = definition else {
+                continue;
+            };
+            if let Some(alias) = import.used_name() {
+                let _ = module_aliases.insert(alias, import.module.clone());
+            }
+            for type_ in &import.unqualified_types {
+                let _ = imported_types.insert(
+                    type_.used_name().clone(),
+                    (import.module.clone(), type_.name.clone()),
+                );
+            }
+            for value in &import.unqualified_values {
+                let _ = imported_values.insert(
+                    (import.module.clone(), value.used_name().clone()),
+                    value.name.clone(),
+                );
+            }
+        }
+
+        Self {
+            module,
+            code,
+            module_aliases,
+            imported_types,
+            imported_values,
+            references: vec![],
+        }
+    }
+
+    fn push(&mut self, symbol: Symbol, location: SrcSpan, kind: ReferenceKind) {
+        self.references.push(Reference {
+            symbol,
+            location,
+            kind,
+        });
+    }
+
+    /// The location of a name within a larger span, such as `Wibble` in
+    /// `type Wibble`.
+    ///
+    fn name_location(&self, location: SrcSpan, name: &str) -> SrcSpan {
+        let start = self
+            .code
+            .get(location.start as usize..location.end as usize)
+            .and_then(|code| code.find(name))
+            .map_or(location.start, |offset| location.start + offset as u32);
+        SrcSpan::new(start, start + name.len() as u32)
+    }
+
+    fn module_value(&self, module: &EcoString, name: &EcoString) -> Symbol {
+        Symbol::ModuleValue {
+            module: module.clone(),
+            name: name.clone(),
+        }
+    }
+
+    fn push_module_alias(&mut self, alias: &EcoString, location: SrcSpan) {
+        if let Some(module) = self.module_aliases.get(alias) {
+            let symbol = Symbol::ImportedModule {
+                module: module.clone(),
+            };
+            self.push(symbol, location, ReferenceKind::Usage);
+        }
+    }
+
+    /// Records a reference to a value by the name it is used with, which can
+    /// be an alias given to it when imported unqualified.
+    ///
+    fn push_value(&mut self, constructor: &ValueConstructor, name: &EcoString, location: SrcSpan) {
+        let symbol = match &constructor.variant {
+            ValueConstructorVariant::LocalVariable {
+                location: definition,
+                origin,
+            } => {
+                if let VariableOrigin::Generated = origin {
+                    return;
+                }
+                Symbol::LocalVariable {
+                    definition: *definition,
+                }
+            }
+            ValueConstructorVariant::ModuleFn { module, name, .. }
+            | ValueConstructorVariant::Record { module, name, .. } => {
+                self.module_value(module, name)
+            }
+            ValueConstructorVariant::ModuleConstant { module, .. } => {
+                let name = self
+                    .imported_values
+                    .get(&(module.clone(), name.clone()))
+                    .unwrap_or(name);
+                self.module_value(module, name)
+            }
+            ValueConstructorVariant::LocalConstant { .. } => return,
+        };
+        self.push(symbol, location, ReferenceKind::Usage);
+    }
+
+    fn push_definition_args(&mut self, owner: Option<&EcoString>, args: &[TypedArg]) {
+        for arg in args {
+            match &arg.names {
+                ArgNames::Named { location, .. } => self.push(
+                    Symbol::LocalVariable {
+                        definition: *location,
+                    },
+                    *location,
+                    ReferenceKind::Definition,
+                ),
+                ArgNames::NamedLabelled {
+                    label,
+                    label_location,
+                    name_location,
+                    ..
+                } => {
+                    self.push_function_label(owner, label, *label_location);
+                    self.push(
+                        Symbol::LocalVariable {
+                            definition: *name_location,
+                        },
+                        *name_location,
+                        ReferenceKind::Definition,
+                    );
+                }
+                ArgNames::LabelledDiscard {
+                    label,
+                    label_location,
+                    ..
+                } => self.push_function_label(owner, label, *label_location),
+                ArgNames::Discard { .. } => {}
+            }
+            if let Some(annotation) = &arg.annotation {
+                self.visit_type_ast(annotation);
+            }
+        }
+    }
+
+    fn push_function_label(
+        &mut self,
+        function: Option<&EcoString>,
+        label: &EcoString,
+        location: SrcSpan,
+    ) {
+        let Some(function) = function else {
+            return;
+        };
+        let owner = LabelOwner::Function {
+            module: self.module.name.clone(),
+            name: function.clone(),
+        };
+        let symbol = Symbol::Label {
+            owner,
+            label: label.clone(),
+        };
+        self.push(symbol, location, ReferenceKind::Definition);
+    }
+
+    /// Records the labels of the arguments of a call, if the function called
+    /// is a record constructor or a module function.
+    ///
+    fn push_call_labels<A: HasLocation>(&mut self, owner: Option<LabelOwner>, args: &[CallArg<A>]) {
+        let Some(owner) = owner else {
+            return;
+        };
+        for arg in args {
+            let Some(label) = &arg.label else {
+                continue;
+            };
+            if arg.implicit.is_some() {
+                continue;
+            }
+            let symbol = Symbol::Label {
+                owner: owner.clone(),
+                label: label.clone(),
+            };
+            let location =
+                SrcSpan::new(arg.location.start, arg.location.start + label.len() as u32);
+            let kind = if arg.uses_label_shorthand() {
+                ReferenceKind::LabelShorthand
+            } else {
+                ReferenceKind::Usage
+            };
+            self.push(symbol, location, kind);
+        }
+    }
+
+    fn visit_constant(&mut self, constant: &TypedConstant) {
+        match constant {
+            Constant::Int { .. }
+            | Constant::Float { .. }
+            | Constant::String { .. }
+            | Constant::Invalid { .. } => {}
+
+            Constant::Tuple { elements, .. } | Constant::List { elements, .. } => {
+                for element in elements {
+                    self.visit_constant(element);
+                }
+            }
+
+            Constant::Record {
+                location,
+                module,
+                name,
+                args,
+                type_,
+                ..
+            } => {
+                let name_start = match module {
+                    Some((alias, alias_location)) => {
+                        self.push_module_alias(alias, *alias_location);
+                        alias_location.end + 1
+                    }
+                    None => location.start,
+                };
+                if let Some((module, _)) = named_type(type_) {
+                    let symbol = self.module_value(&module, name);
+                    let location = SrcSpan::new(name_start, name_start + name.len() as u32);
+                    self.push(symbol, location, ReferenceKind::Usage);
+                }
+                self.push_call_labels(type_owner(type_), args);
+                for arg in args {
+                    self.visit_constant(&arg.value);
+                }
+            }
+
+            Constant::BitArray { segments, .. } => {
+                for segment in segments {
+                    self.visit_constant(&segment.value);
+                }
+            }
+
+            Constant::Var {
+                location,
+                module,
+                name,
+                constructor,
+                ..
+            } => {
+                let name_start = match module {
+                    Some((alias, alias_location)) => {
+                        self.push_module_alias(alias, *alias_location);
+                        alias_location.end + 1
+                    }
+                    None => location.start,
+                };
+                if let Some(constructor) = constructor {
+                    let location = SrcSpan::new(name_start, name_start + name.len() as u32);
+                    self.push_value(constructor, name, location);
+                }
+            }
+
+            Constant::StringConcatenation { left, right, .. } => {
+                self.visit_constant(left);
+                self.visit_constant(right);
+            }
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for References<'_> {
+    fn visit_typed_definition(&mut self, definition: &'ast TypedDefinition) {
+        let module = self.module.name.clone();
+        match definition {
+            Definition::Function(function) => {
+                let name = function.name.as_ref();
+                if let Some((location, name)) = name {
+                    let symbol = self.module_value(&module, name);
+                    self.push(symbol, *location, ReferenceKind::Definition);
+                }
+                self.push_definition_args(name.map(|(_, name)| name), &function.arguments);
+                if let Some(annotation) = &function.return_annotation {
+                    self.visit_type_ast(annotation);
+                }
+                self.visit_typed_function(function);
+            }
+
+            Definition::CustomType(custom_type) => {
+                let symbol = Symbol::ModuleType {
+                    module: module.clone(),
+                    name: custom_type.name.clone(),
+                };
+                self.push(symbol, custom_type.name_location, ReferenceKind::Definition);
+                let owner = LabelOwner::Type {
+                    module: module.clone(),
+                    name: custom_type.name.clone(),
+                };
+                for constructor in &custom_type.constructors {
+                    let symbol = self.module_value(&module, &constructor.name);
+                    self.push(symbol, constructor.name_location, ReferenceKind::Definition);
+                    for argument in &constructor.arguments {
+                        if let Some((location, label)) = &argument.label {
+                            let symbol = Symbol::Label {
+                                owner: owner.clone(),
+                                label: label.clone(),
+                            };
+                            self.push(symbol, *location, ReferenceKind::Definition);
+                        }
+                        self.visit_type_ast(&argument.ast);
+                    }
+                }
+            }
+
+            Definition::TypeAlias(alias) => {
+                let symbol = Symbol::ModuleType {
+                    module,
+                    name: alias.alias.clone(),
+                };
+                self.push(symbol, alias.name_location, ReferenceKind::Definition);
+                self.visit_type_ast(&alias.type_ast);
+            }
+
+            Definition::ModuleConstant(constant) => {
+                let symbol = self.module_value(&module, &constant.name);
+                self.push(symbol, constant.name_location, ReferenceKind::Definition);
+                if let Some(annotation) = &constant.annotation {
+                    self.visit_type_ast(annotation);
+                }
+                self.visit_constant(&constant.value);
+            }
+
+            Definition::Import(import) => {
+                let symbol = Symbol::ImportedModule {
+                    module: import.module.clone(),
+                };
+                match &import.as_name {
+                    Some((AssignName::Variable(name), location)) => {
+                        // The location of the alias includes the `as` keyword.
+                        let location = SrcSpan::new(location.end - name.len() as u32, location.end);
+                        self.push(symbol, location, ReferenceKind::Definition)
+                    }
+                    Some((AssignName::Discard(_), _)) => {}
+                    None => {
+                        // Without an alias a module is used with the last
+                        // segment of its path.
+                        let path = import.module_location(self.code);
+                        let name = import.module.rsplit('/').next().unwrap_or_default();
+                        let location = SrcSpan::new(path.end - name.len() as u32, path.end);
+                        self.push(symbol, location, ReferenceKind::Definition);
+                    }
+                }
+
+                for value in &import.unqualified_values {
+                    let symbol = self.module_value(&import.module, &value.name);
+                    let location = self.name_location(value.location, &value.name);
+                    self.push(symbol, location, ReferenceKind::Usage);
+                }
+                for type_ in &import.unqualified_types {
+                    let symbol = Symbol::ModuleType {
+                        module: import.module.clone(),
+                        name: type_.name.clone(),
+                    };
+                    let location = self.name_location(type_.location, &type_.name);
+                    self.push(symbol, location, ReferenceKind::Usage);
+                }
+            }
+        }
+    }
+
+    fn visit_typed_assignment(&mut self, assignment: &'ast TypedAssignment) {
+        if let Some(annotation) = &assignment.annotation {
+            self.visit_type_ast(annotation);
+        }
+        ast::visit::visit_typed_assignment(self, assignment);
+    }
+
+    fn visit_typed_expr_var(
+        &mut self,
+        location: &'ast SrcSpan,
+        constructor: &'ast ValueConstructor,
+        name: &'ast EcoString,
+    ) {
+        self.push_value(constructor, name, *location);
+    }
+
+    fn visit_typed_expr_fn(
+        &mut self,
+        location: &'ast SrcSpan,
+        type_: &'ast Arc<Type>,
+        kind: &'ast FunctionLiteralKind,
+        args: &'ast [TypedArg],
+        body: &'ast Vec1<TypedStatement>,
+        return_annotation: &'ast Option<TypeAst>,
+    ) {
+        self.push_definition_args(None, args);
+        if let Some(annotation) = return_annotation {
+            self.visit_type_ast(annotation);
+        }
+        ast::visit::visit_typed_expr_fn(self, location, type_, kind, args, body, return_annotation);
+    }
+
+    fn visit_typed_call_arg(&mut self, arg: &'ast TypedCallArg) {
+        // The fields copied over by a record update are accesses to the
+        // updated record generated by the compiler, which appear nowhere in
+        // the source.
+        if let Some(ImplicitCallArgOrigin::RecordUpdate) = arg.implicit {
+            return;
+        }
+        ast::visit::visit_typed_call_arg(self, arg);
+    }
+
+    fn visit_typed_expr_call(
+        &mut self,
+        location: &'ast SrcSpan,
+        type_: &'ast Arc<Type>,
+        fun: &'ast TypedExpr,
+        args: &'ast [TypedCallArg],
+    ) {
+        self.push_call_labels(callee_owner(fun), args);
+        ast::visit::visit_typed_expr_call(self, location, type_, fun, args);
+    }
+
+    fn visit_typed_expr_record_update(
+        &mut self,
+        location: &'ast SrcSpan,
+        type_: &'ast Arc<Type>,
+        record: &'ast TypedAssignment,
+        constructor: &'ast TypedExpr,
+        args: &'ast [TypedCallArg],
+    ) {
+        self.push_call_labels(type_owner(type_), args);
+        ast::visit::visit_typed_expr_record_update(
+            self,
+            location,
+            type_,
+            record,
+            constructor,
+            args,
+        );
+    }
+
+    fn visit_typed_expr_record_access(
+        &mut self,
+        location: &'ast SrcSpan,
+        type_: &'ast Arc<Type>,
+        label: &'ast EcoString,
+        index: &'ast u64,
+        record: &'ast TypedExpr,
+    ) {
+        if let Some(owner) = type_owner(&record.type_()) {
+            let symbol = Symbol::Label {
+                owner,
+                label: label.clone(),
+            };
+            let location = SrcSpan::new(location.end - label.len() as u32, location.end);
+            self.push(symbol, location, ReferenceKind::Usage);
+        }
+        ast::visit::visit_typed_expr_record_access(self, location, type_, label, index, record);
+    }
+
+    fn visit_typed_expr_module_select(
+        &mut self,
+        location: &'ast SrcSpan,
+        _type_: &'ast Arc<Type>,
+        label: &'ast EcoString,
+        module_name: &'ast EcoString,
+        module_alias: &'ast EcoString,
+        constructor: &'ast ModuleValueConstructor,
+    ) {
+        // The location of a module select starts at the dot following the
+        // module name.
+        let alias_location =
+            SrcSpan::new(location.start - module_alias.len() as u32, location.start);
+        self.push_module_alias(module_alias, alias_location);
+
+        let name = match constructor {
+            ModuleValueConstructor::Fn { name, .. }
+            | ModuleValueConstructor::Record { name, .. } => name,
+            ModuleValueConstructor::Constant { .. } => label,
+        };
+        let symbol = self.module_value(module_name, name);
+        let location = SrcSpan::new(location.end - label.len() as u32, location.end);
+        self.push(symbol, location, ReferenceKind::Usage);
+    }
+
+    fn visit_typed_clause_guard_var(
+        &mut self,
+        location: &'ast SrcSpan,
+        _name: &'ast EcoString,
+        _type_: &'ast Arc<Type>,
+        definition_location: &'ast SrcSpan,
+    ) {
+        let symbol = Symbol::LocalVariable {
+            definition: *definition_location,
+        };
+        self.push(symbol, *location, ReferenceKind::Usage);
+    }
+
+    fn visit_typed_clause_guard_module_select(
+        &mut self,
+        location: &'ast SrcSpan,
+        _type_: &'ast Arc<Type>,
+        label: &'ast EcoString,
+        module_name: &'ast EcoString,
+        module_alias: &'ast EcoString,
+        _literal: &'ast TypedConstant,
+    ) {
+        // In guards the location is the one of the module name, and the label
+        // follows the dot after it.
+        self.push_module_alias(module_alias, *location);
+        let symbol = self.module_value(module_name, label);
+        let start = location.end + 1;
+        let location = SrcSpan::new(start, start + label.len() as u32);
+        self.push(symbol, location, ReferenceKind::Usage);
+    }
+
+    fn visit_typed_pattern_variable(
+        &mut self,
+        location: &'ast SrcSpan,
+        _name: &'ast EcoString,
+        _type_: &'ast Arc<Type>,
+        origin: &'ast VariableOrigin,
+    ) {
+        if let VariableOrigin::Generated = origin {
+            return;
+        }
+        let symbol = Symbol::LocalVariable {
+            definition: *location,
+        };
+        self.push(symbol, *location, ReferenceKind::Definition);
+    }
+
+    fn visit_typed_pattern_var_usage(
+        &mut self,
+        location: &'ast SrcSpan,
+        name: &'ast EcoString,
+        constructor: &'ast Option<ValueConstructor>,
+        _type_: &'ast Arc<Type>,
+    ) {
+        if let Some(constructor) = constructor {
+            self.push_value(constructor, name, *location);
+        }
+    }
+
+    fn visit_typed_pattern_assign(
+        &mut self,
+        location: &'ast SrcSpan,
+        name: &'ast EcoString,
+        pattern: &'ast TypedPattern,
+    ) {
+        let symbol = Symbol::LocalVariable {
+            definition: *location,
+        };
+        self.push(symbol, *location, ReferenceKind::Definition);
+        ast::visit::visit_typed_pattern_assign(self, location, name, pattern);
+    }
+
+    fn visit_typed_pattern_constructor(
+        &mut self,
+        location: &'ast SrcSpan,
+        name: &'ast EcoString,
+        arguments: &'ast Vec<CallArg<TypedPattern>>,
+        module: &'ast Option<(EcoString, SrcSpan)>,
+        constructor: &'ast Inferred<PatternConstructor>,
+        spread: &'ast Option<SrcSpan>,
+        type_: &'ast Arc<Type>,
+    ) {
+        let name_start = match module {
+            Some((alias, alias_location)) => {
+                self.push_module_alias(alias, *alias_location);
+                alias_location.end + 1
+            }
+            None => location.start,
+        };
+        if let Inferred::Known(constructor) = constructor {
+            let symbol = self.module_value(&constructor.module, &constructor.name);
+            let location = SrcSpan::new(name_start, name_start + name.len() as u32);
+            self.push(symbol, location, ReferenceKind::Usage);
+        }
+        self.push_call_labels(type_owner(type_), arguments);
+        ast::visit::visit_typed_pattern_constructor(
+            self,
+            location,
+            name,
+            arguments,
+            module,
+            constructor,
+            spread,
+            type_,
+        );
+    }
+
+    fn visit_typed_pattern_string_prefix(
+        &mut self,
+        _location: &'ast SrcSpan,
+        _left_location: &'ast SrcSpan,
+        left_side_assignment: &'ast Option<(EcoString, SrcSpan)>,
+        right_location: &'ast SrcSpan,
+        _left_side_string: &'ast EcoString,
+        right_side_assignment: &'ast AssignName,
+    ) {
+        if let Some((_, location)) = left_side_assignment {
+            let symbol = Symbol::LocalVariable {
+                definition: *location,
+            };
+            self.push(symbol, *location, ReferenceKind::Definition);
+        }
+        if let AssignName::Variable(_) = right_side_assignment {
+            let symbol = Symbol::LocalVariable {
+                definition: *right_location,
+            };
+            self.push(symbol, *right_location, ReferenceKind::Definition);
+        }
+    }
+
+    fn visit_type_ast_constructor(
+        &mut self,
+        location: &'ast SrcSpan,
+        module: &'ast Option<(EcoString, SrcSpan)>,
+        name: &'ast EcoString,
+        arguments: &'ast Vec<TypeAst>,
+    ) {
+        let (name_start, type_module) = match module {
+            Some((alias, alias_location)) => {
+                self.push_module_alias(alias, *alias_location);
+                (
+                    alias_location.end + 1,
+                    self.module_aliases.get(alias).cloned(),
+                )
+            }
+            None => (location.start, None),
+        };
+        let symbol = match type_module {
+            Some(module) => Some(Symbol::ModuleType {
+                module,
+                name: name.clone(),
+            }),
+            None if module.is_some() => None,
+            None => Some(self.unqualified_type(name)),
+        };
+        if let Some(symbol) = symbol {
+            let location = SrcSpan::new(name_start, name_start + name.len() as u32);
+            self.push(symbol, location, ReferenceKind::Usage);
+        }
+        ast::visit::visit_type_ast_constructor(self, location, module, name, arguments);
+    }
+}
+
+impl References<'_> {
+    /// Resolves a type used without a module qualifier, which is either
+    /// defined in the module, imported unqualified or from the prelude.
+    ///
+    fn unqualified_type(&self, name: &EcoString) -> Symbol {
+        let is_local = self
+            .module
+            .definitions
+            .iter()
+            .any(|definition| match definition {
+                Definition::CustomType(custom_type) => &custom_type.name == name,
+                Definition::TypeAlias(alias) => &alias.alias == name,
+                Definition::Function(_) | Definition::Import(_) | Definition::ModuleConstant(_) => {
+                    false
+                }
+            });
+        let (module, name) = if is_local {
+            (self.module.name.clone(), name.clone())
+        } else if let Some((module, name)) = self.imported_types.get(name) {
+            (module.clone(), name.clone())
+        } else {
+            (PRELUDE_MODULE_NAME.into(), name.clone())
+        };
+        Symbol::ModuleType { module, name }
+    }
+}
+
+fn named_type(type_: &Arc<Type>) -> Option<(EcoString, EcoString)> {
+    match type_::collapse_links(type_.clone()).as_ref() {
+        Type::Named { module, name, .. } => Some((module.clone(), name.clone())),
+        Type::Fn { retrn, .. } => named_type(retrn),
+        Type::Var { .. } | Type::Tuple { .. } => None,
+    }
+}
+
+/// The owner of the labels of a record of the given type, or built by the
+/// given record constructor.
+///
+fn type_owner(type_: &Arc<Type>) -> Option<LabelOwner> {
+    let (module, name) = named_type(type_)?;
+    Some(LabelOwner::Type { module, name })
+}
+
+/// The owner of the labels used when calling a function.
+///
+fn callee_owner(fun: &TypedExpr) -> Option<LabelOwner> {
+    match fun {
+        TypedExpr::Var {
+            constructor:
+                ValueConstructor {
+                    variant: ValueConstructorVariant::ModuleFn { module, name, .. },
+                    ..
+                },
+            ..
+        }
+        | TypedExpr::ModuleSelect {
+            constructor: ModuleValueConstructor::Fn { module, name, .. },
+            ..
+        } => Some(LabelOwner::Function {
+            module: module.clone(),
+            name: name.clone(),
+        }),
+        _ if fun.is_record_builder() => type_owner(&fun.type_()),
+        _ => None,
+    }
+}
@@ -0,0 +1,616 @@
+//! Software bills of materials for a project, listing the packages it is made
+//! of in the formats written by `gleam export sbom`.
+
+#[cfg(test)]
+mod tests;
+
+use std::{
+    collections::{HashMap, HashSet},
+    time::SystemTime,
+};
+
+use ecow::EcoString;
+use itertools::Itertools;
+use serde::Serialize;
+use strum::{Display, EnumString, VariantNames};
+
+use crate::{
+    config::PackageConfig,
+    manifest::{Manifest, ManifestPackage, ManifestPackageSource},
+    version::COMPILER_VERSION,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, EnumString, VariantNames)]
+#[strum(serialize_all = "lowercase")]
+pub enum SbomFormat {
+    /// CycloneDX 1.5, as JSON.
+    CycloneDx,
+    /// SPDX 2.3, as JSON.
+    Spdx,
+}
+
+#[derive(Debug)]
+pub struct Sbom<'a> {
+    pub config: &'a PackageConfig,
+    pub manifest: &'a Manifest,
+    /// The licences of the packages of the manifest by name. Packages whose
+    /// licences are not known can be left out.
+    pub licences: &'a HashMap<EcoString, Vec<EcoString>>,
+    /// When the bill of materials was made, as an RFC 3339 timestamp.
+    pub created: &'a str,
+    pub include_dev_dependencies: bool,
+}
+
+/// A package of the manifest, along with whether it is only needed by the
+/// dev dependencies of the project.
+///
+#[derive(Debug, Clone, Copy)]
+struct Component<'a> {
+    package: &'a ManifestPackage,
+    dev: bool,
+}
+
+impl<'a> Sbom<'a> {
+    pub fn to_json(&self, format: SbomFormat) -> String {
+        let json = match format {
+            SbomFormat::CycloneDx => serde_json::to_string_pretty(&self.cyclonedx()),
+            SbomFormat::Spdx => serde_json::to_string_pretty(&self.spdx()),
+        };
+        json.expect("SBOM to JSON")
+    }
+
+    /// The packages of the manifest sorted by name, without the ones only
+    /// needed by the dev dependencies unless they are to be included.
+    ///
+    fn components(&self) -> Vec<Component<'a>> {
+        let runtime = self.runtime_packages();
+        self.manifest
+            .packages
+            .iter()
+            .sorted_by(|a, b| a.name.cmp(&b.name))
+            .map(|package| Component {
+                package,
+                dev: !runtime.contains(&package.name),
+            })
+            .filter(|component| self.include_dev_dependencies || !component.dev)
+            .collect()
+    }
+
+    /// The names of the packages reachable from the dependencies of the
+    /// project, rather than only from its dev dependencies.
+    ///
+    fn runtime_packages(&self) -> HashSet<EcoString> {
+        let packages: HashMap<_, _> = self
+            .manifest
+            .packages
+            .iter()
+            .map(|package| (&package.name, package))
+            .collect();
+        let mut reached = HashSet::new();
+        let mut names = self.config.dependencies.keys().cloned().collect_vec();
+        while let Some(name) = names.pop() {
+            if !reached.insert(name.clone()) {
+                continue;
+            }
+            if let Some(package) = packages.get(&name) {
+                names.extend(package.requirements.iter().cloned());
+            }
+        }
+        reached.retain(|name| packages.contains_key(name));
+        reached
+    }
+
+    /// The packages the project itself depends on, out of the given ones.
+    ///
+    fn root_requirements(&self, components: &[Component<'_>]) -> Vec<EcoString> {
+        components
+            .iter()
+            .map(|component| &component.package.name)
+            .filter(|name| {
+                self.config.dependencies.contains_key(*name)
+                    || self.config.dev_dependencies.contains_key(*name)
+            })
+            .cloned()
+            .collect()
+    }
+
+    fn package_licences(&self, name: &str) -> &[EcoString] {
+        self.licences.get(name).map_or(&[], Vec::as_slice)
+    }
+
+    fn root_licences(&self) -> Vec<EcoString> {
+        self.config
+            .licences
+            .iter()
+            .map(|licence| licence.to_string().into())
+            .collect()
+    }
+
+    fn cyclonedx(&self) -> CycloneDx<'_> {
+        let components = self.components();
+        let included: HashSet<_> = components.iter().map(|c| &c.package.name).collect();
+        let root = CycloneDxComponent {
+            type_: "application",
+            bom_ref: Some(bom_ref(&self.config.name, &self.config.version.to_string())),
+            name: self.config.name.clone(),
+            version: self.config.version.to_string(),
+            scope: None,
+            hashes: vec![],
+            licenses: cyclonedx_licences(&self.root_licences()),
+            purl: None,
+            external_references: vec![],
+            properties: vec![],
+        };
+
+        let dependencies = std::iter::once(CycloneDxDependency {
+            ref_: root.bom_ref.clone().unwrap_or_default(),
+            depends_on: self
+                .root_requirements(&components)
+                .iter()
+                .map(|name| self.component_bom_ref(name))
+                .collect(),
+        })
+        .chain(components.iter().map(|component| {
+            CycloneDxDependency {
+                ref_: self.component_bom_ref(&component.package.name),
+                depends_on: component
+                    .package
+                    .requirements
+                    .iter()
+                    .filter(|name| included.contains(name))
+                    .sorted()
+                    .map(|name| self.component_bom_ref(name))
+                    .collect(),
+            }
+        }))
+        .collect();
+
+        CycloneDx {
+            bom_format: "CycloneDX",
+            spec_version: "1.5",
+            version: 1,
+            metadata: CycloneDxMetadata {
+                timestamp: self.created,
+                tools: CycloneDxTools {
+                    components: vec![CycloneDxComponent {
+                        type_: "application",
+                        bom_ref: None,
+                        name: "gleam".into(),
+                        version: COMPILER_VERSION.into(),
+                        scope: None,
+                        hashes: vec![],
+                        licenses: vec![],
+                        purl: None,
+                        external_references: vec![],
+                        properties: vec![],
+                    }],
+                },
+                component: root,
+            },
+            components: components
+                .iter()
+                .map(|component| self.cyclonedx_component(*component))
+                .collect(),
+            dependencies,
+        }
+    }
+
+    fn component_bom_ref(&self, name: &str) -> String {
+        self.manifest
+            .packages
+            .iter()
+            .find(|package| package.name == name)
+            .map(|package| bom_ref(&package.name, &package.version.to_string()))
+            .unwrap_or_else(|| name.into())
+    }
+
+    fn cyclonedx_component(&self, component: Component<'_>) -> CycloneDxComponent {
+        let package = component.package;
+        let version = package.version.to_string();
+        let mut hashes = vec![];
+        let mut purl = None;
+        let mut external_references = vec![];
+        let mut properties = vec![CycloneDxProperty {
+            name: "gleam:source",
+            value: source_name(&package.source).into(),
+        }];
+        match &package.source {
+            ManifestPackageSource::Hex { outer_checksum } => {
+                hashes.push(CycloneDxHash {
+                    alg: "SHA-256",
+                    content: outer_checksum.to_string().to_lowercase(),
+                });
+                purl = Some(hex_purl(&package.name, &version));
+            }
+            ManifestPackageSource::Git { repo, commit } => {
+                external_references.push(CycloneDxReference {
+                    type_: "vcs",
+                    url: repo.to_string(),
+                });
+                properties.push(CycloneDxProperty {
+                    name: "gleam:commit",
+                    value: commit.to_string(),
+                });
+            }
+            ManifestPackageSource::Local { path } => properties.push(CycloneDxProperty {
+                name: "gleam:path",
+                value: path.to_string(),
+            }),
+        }
+
+        CycloneDxComponent {
+            type_: "library",
+            bom_ref: Some(bom_ref(&package.name, &version)),
+            name: package.name.clone(),
+            version,
+            // Packages that are not part of the project when it runs are
+            // "excluded" in CycloneDX terms.
+            scope: Some(if component.dev {
+                "excluded"
+            } else {
+                "required"
+            }),
+            hashes,
+            licenses: cyclonedx_licences(self.package_licences(&package.name)),
+            purl,
+            external_references,
+            properties,
+        }
+    }
+
+    fn spdx(&self) -> Spdx<'_> {
+        let components = self.components();
+        let included: HashSet<_> = components.iter().map(|c| &c.package.name).collect();
+        let root_id = spdx_id(&self.config.name);
+        let name = format!("{}-{}", self.config.name, self.config.version);
+
+        let root = SpdxPackage {
+            spdx_id: root_id.clone(),
+            name: self.config.name.clone(),
+            version_info: self.config.version.to_string(),
+            download_location: "NOASSERTION".into(),
+            files_analyzed: false,
+            checksums: vec![],
+            license_concluded: "NOASSERTION",
+            license_declared: spdx_licence_expression(&self.root_licences()),
+            external_refs: vec![],
+            comment: None,
+        };
+        let packages = std::iter::once(root)
+            .chain(
+                components
+                    .iter()
+                    .map(|component| self.spdx_package(*component)),
+            )
+            .collect();
+
+        let mut relationships = vec![SpdxRelationship {
+            spdx_element_id: "SPDXRef-DOCUMENT".into(),
+            relationship_type: "DESCRIBES",
+            related_spdx_element: root_id.clone(),
+        }];
+        for name in self.root_requirements(&components) {
+            relationships.push(if self.config.dependencies.contains_key(&name) {
+                SpdxRelationship {
+                    spdx_element_id: root_id.clone(),
+                    relationship_type: "DEPENDS_ON",
+                    related_spdx_element: spdx_id(&name),
+                }
+            } else {
+                SpdxRelationship {
+                    spdx_element_id: spdx_id(&name),
+                    relationship_type: "DEV_DEPENDENCY_OF",
+                    related_spdx_element: root_id.clone(),
+                }
+            });
+        }
+        for component in &components {
+            relationships.extend(
+                component
+                    .package
+                    .requirements
+                    .iter()
+                    .filter(|name| included.contains(name))
+                    .sorted()
+                    .map(|name| SpdxRelationship {
+                        spdx_element_id: spdx_id(&component.package.name),
+                        relationship_type: "DEPENDS_ON",
+                        related_spdx_element: spdx_id(name),
+                    }),
+            );
+        }
+
+        Spdx {
+            spdx_version: "SPDX-2.3",
+            data_license: "CC0-1.0",
+            spdx_id: "SPDXRef-DOCUMENT",
+            // The namespace only has to be unique to this document, so it is
+            // made from the project and the time the document was made.
+            document_namespace: format!("https://gleam.run/spdxdocs/{name}-{}", self.created),
+            name,
+            creation_info: SpdxCreationInfo {
+                created: self.created,
+                creators: vec![format!("Tool: gleam-{COMPILER_VERSION}")],
+            },
+            packages,
+            relationships,
+        }
+    }
+
+    fn spdx_package(&self, component: Component<'_>) -> SpdxPackage {
+        let package = component.package;
+        let version = package.version.to_string();
+        let (download_location, checksums, external_refs) = match &package.source {
+            ManifestPackageSource::Hex { outer_checksum } => (
+                format!(
+                    "https://repo.hex.pm/tarballs/{}-{version}.tar",
+                    package.name
+                ),
+                vec![SpdxChecksum {
+                    algorithm: "SHA256",
+                    checksum_value: outer_checksum.to_string().to_lowercase(),
+                }],
+                vec![SpdxExternalRef {
+                    reference_category: "PACKAGE-MANAGER",
+                    reference_type: "purl",
+                    reference_locator: hex_purl(&package.name, &version),
+                }],
+            ),
+            ManifestPackageSource::Git { repo, commit } => {
+                (format!("git+{repo}@{commit}"), vec![], vec![])
+            }
+            ManifestPackageSource::Local { .. } => ("NOASSERTION".into(), vec![], vec![]),
+        };
+
+        SpdxPackage {
+            spdx_id: spdx_id(&package.name),
+            name: package.name.clone(),
+            version_info: version,
+            download_location,
+            files_analyzed: false,
+            checksums,
+            license_concluded: "NOASSERTION",
+            license_declared: spdx_licence_expression(self.package_licences(&package.name)),
+            external_refs,
+            comment: component.dev.then_some("Only used by the dev dependencies"),
+        }
+    }
+}
+
+/// Formats a time as an RFC 3339 timestamp in UTC, such as
+/// `2024-01-02T03:04:05Z`.
+///
+pub fn timestamp(time: SystemTime) -> String {
+    let seconds = time
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs());
+    let (days, seconds) = (seconds / 86_400, seconds % 86_400);
+    let (hour, minute, second) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+
+    // Converts days since the epoch to a date in the proleptic Gregorian
+    // calendar, counting eras of 400 years starting from the 1st of March.
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+    format!("{year:04}-{month:02}-{day:02}T{hour:02}:{minute:02}:{second:02}Z")
+}
+
+fn source_name(source: &ManifestPackageSource) -> &'static str {
+    match source {
+        ManifestPackageSource::Hex { .. } => "hex",
+        ManifestPackageSource::Git { .. } => "git",
+        ManifestPackageSource::Local { .. } => "local",
+    }
+}
+
+fn bom_ref(name: &str, version: &str) -> String {
+    format!("{name}@{version}")
+}
+
+fn hex_purl(name: &str, version: &str) -> String {
+    format!("pkg:hex/{name}@{version}")
+}
+
+/// SPDX identifiers can't contain underscores and Gleam package names can't
+/// contain dashes, so one is swapped for the other.
+///
+fn spdx_id(name: &str) -> String {
+    format!("SPDXRef-Package-{}", name.replace('_', "-"))
+}
+
+/// Licences that are not on the SPDX list are given by name in CycloneDX.
+///
+fn cyclonedx_licences(licences: &[EcoString]) -> Vec<CycloneDxLicence> {
+    licences
+        .iter()
+        .map(|licence| {
+            let licence = if spdx::license_id(licence).is_some() {
+                CycloneDxLicenceId {
+                    id: Some(licence.clone()),
+                    name: None,
+                }
+            } else {
+                CycloneDxLicenceId {
+                    id: None,
+                    name: Some(licence.clone()),
+                }
+            };
+            CycloneDxLicence { license: licence }
+        })
+        .collect()
+}
+
+/// A package offered under several licences can be used under any one of
+/// them. SPDX has no way to declare licences that are not on its list without
+/// extra definitions, so those are not asserted.
+///
+fn spdx_licence_expression(licences: &[EcoString]) -> String {
+    if licences.is_empty()
+        || licences
+            .iter()
+            .any(|licence| spdx::license_id(licence).is_none())
+    {
+        return "NOASSERTION".into();
+    }
+    licences.join(" OR ")
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CycloneDx<'a> {
+    bom_format: &'static str,
+    spec_version: &'static str,
+    version: u32,
+    metadata: CycloneDxMetadata<'a>,
+    components: Vec<CycloneDxComponent>,
+    dependencies: Vec<CycloneDxDependency>,
+}
+
+#[derive(Debug, Serialize)]
+struct CycloneDxMetadata<'a> {
+    timestamp: &'a str,
+    tools: CycloneDxTools,
+    component: CycloneDxComponent,
+}
+
+#[derive(Debug, Serialize)]
+struct CycloneDxTools {
+    components: Vec<CycloneDxComponent>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CycloneDxComponent {
+    #[serde(rename = "type")]
+    type_: &'static str,
+    #[serde(rename = "bom-ref", skip_serializing_if = "Option::is_none")]
+    bom_ref: Option<String>,
+    name: EcoString,
+    version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scope: Option<&'static str>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    hashes: Vec<CycloneDxHash>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    licenses: Vec<CycloneDxLicence>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    purl: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    external_references: Vec<CycloneDxReference>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    properties: Vec<CycloneDxProperty>,
+}
+
+#[derive(Debug, Serialize)]
+struct CycloneDxHash {
+    alg: &'static str,
+    content: String,
+}
+
+#[derive(Debug, Serialize)]
+struct CycloneDxLicence {
+    license: CycloneDxLicenceId,
+}
+
+#[derive(Debug, Serialize)]
+struct CycloneDxLicenceId {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<EcoString>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<EcoString>,
+}
+
+#[derive(Debug, Serialize)]
+struct CycloneDxReference {
+    #[serde(rename = "type")]
+    type_: &'static str,
+    url: String,
+}
+
+#[derive(Debug, Serialize)]
+struct CycloneDxProperty {
+    name: &'static str,
+    value: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CycloneDxDependency {
+    #[serde(rename = "ref")]
+    ref_: String,
+    depends_on: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Spdx<'a> {
+    spdx_version: &'static str,
+    data_license: &'static str,
+    #[serde(rename = "SPDXID")]
+    spdx_id: &'static str,
+    name: String,
+    document_namespace: String,
+    creation_info: SpdxCreationInfo<'a>,
+    packages: Vec<SpdxPackage>,
+    relationships: Vec<SpdxRelationship>,
+}
+
+#[derive(Debug, Serialize)]
+struct SpdxCreationInfo<'a> {
+    created: &'a str,
+    creators: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SpdxPackage {
+    #[serde(rename = "SPDXID")]
+    spdx_id: String,
+    name: EcoString,
+    version_info: String,
+    download_location: String,
+    files_analyzed: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    checksums: Vec<SpdxChecksum>,
+    license_concluded: &'static str,
+    license_declared: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    external_refs: Vec<SpdxExternalRef>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    comment: Option<&'static str>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SpdxChecksum {
+    algorithm: &'static str,
+    checksum_value: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SpdxExternalRef {
+    reference_category: &'static str,
+    reference_type: &'static str,
+    reference_locator: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SpdxRelationship {
+    spdx_element_id: String,
+    relationship_type: &'static str,
+    related_spdx_element: String,
+}
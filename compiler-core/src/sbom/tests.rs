@@ -0,0 +1,130 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, SystemTime},
+};
+
+use camino::Utf8PathBuf;
+use ecow::EcoString;
+use hexpm::version::Version;
+
+use super::{timestamp, Sbom, SbomFormat};
+use crate::{
+    config::PackageConfig,
+    manifest::{Base16Checksum, Manifest, ManifestPackage, ManifestPackageSource},
+    requirement::Requirement,
+};
+
+fn package(name: &str, source: ManifestPackageSource, requirements: &[&str]) -> ManifestPackage {
+    ManifestPackage {
+        name: name.into(),
+        version: Version::new(1, 2, 3),
+        build_tools: vec!["gleam".into()],
+        otp_app: None,
+        requirements: requirements.iter().map(|name| (*name).into()).collect(),
+        source,
+    }
+}
+
+fn hex() -> ManifestPackageSource {
+    ManifestPackageSource::Hex {
+        outer_checksum: Base16Checksum(vec![0xAB, 0xCD]),
+    }
+}
+
+fn config() -> PackageConfig {
+    let mut config = PackageConfig {
+        name: "my_project".into(),
+        version: Version::new(0, 1, 0),
+        ..Default::default()
+    };
+    let _ = config
+        .dependencies
+        .insert("wibble".into(), Requirement::hex("~> 1.0"));
+    let _ = config
+        .dependencies
+        .insert("wobble".into(), Requirement::hex("~> 1.0"));
+    let _ = config
+        .dev_dependencies
+        .insert("test_runner".into(), Requirement::hex("~> 1.0"));
+    config
+}
+
+fn manifest() -> Manifest {
+    Manifest {
+        requirements: HashMap::new(),
+        packages: vec![
+            package("wibble", hex(), &["shared"]),
+            package(
+                "wobble",
+                ManifestPackageSource::Git {
+                    repo: "https://github.com/gleam-lang/wobble".into(),
+                    commit: "0123456789abcdef".into(),
+                },
+                &[],
+            ),
+            package("shared", hex(), &[]),
+            package("test_runner", hex(), &["shared", "test_helpers"]),
+            package(
+                "test_helpers",
+                ManifestPackageSource::Local {
+                    path: Utf8PathBuf::from("../test_helpers"),
+                },
+                &[],
+            ),
+        ],
+    }
+}
+
+fn sbom(format: SbomFormat, include_dev_dependencies: bool) -> String {
+    let config = config();
+    let manifest = manifest();
+    let licences: HashMap<EcoString, Vec<EcoString>> = HashMap::from([
+        ("wibble".into(), vec!["MIT".into(), "Apache-2.0".into()]),
+        ("shared".into(), vec!["Apache-2.0".into()]),
+        ("test_runner".into(), vec!["Custom licence".into()]),
+    ]);
+    Sbom {
+        config: &config,
+        manifest: &manifest,
+        licences: &licences,
+        created: "2024-01-02T03:04:05Z",
+        include_dev_dependencies,
+    }
+    .to_json(format)
+    .replace(crate::version::COMPILER_VERSION, "<version>")
+}
+
+#[test]
+fn cyclonedx() {
+    insta::assert_snapshot!(sbom(SbomFormat::CycloneDx, true));
+}
+
+#[test]
+fn cyclonedx_without_dev_dependencies() {
+    insta::assert_snapshot!(sbom(SbomFormat::CycloneDx, false));
+}
+
+#[test]
+fn spdx() {
+    insta::assert_snapshot!(sbom(SbomFormat::Spdx, true));
+}
+
+#[test]
+fn spdx_without_dev_dependencies() {
+    insta::assert_snapshot!(sbom(SbomFormat::Spdx, false));
+}
+
+#[test]
+fn format_names() {
+    assert_eq!(SbomFormat::CycloneDx.to_string(), "cyclonedx");
+    assert_eq!(SbomFormat::Spdx.to_string(), "spdx");
+}
+
+#[test]
+fn timestamps() {
+    let at = |seconds| timestamp(SystemTime::UNIX_EPOCH + Duration::from_secs(seconds));
+    assert_eq!(at(0), "1970-01-01T00:00:00Z");
+    assert_eq!(at(951_782_400), "2000-02-29T00:00:00Z");
+    assert_eq!(at(1_704_164_645), "2024-01-02T03:04:05Z");
+    assert_eq!(at(1_735_689_599), "2024-12-31T23:59:59Z");
+}
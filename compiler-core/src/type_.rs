@@ -1233,7 +1233,13 @@ pub struct ValueConstructor {
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub enum Deprecation {
     NotDeprecated,
-    Deprecated { message: EcoString },
+    Deprecated {
+        message: EcoString,
+        /// The value to use instead, given with the `replace` argument of the
+        /// `@deprecated` attribute. It can be applied automatically by
+        /// `gleam fix`, see [`crate::fix::Replacement`] for its syntax.
+        replacement: Option<EcoString>,
+    },
 }
 
 impl Deprecation {
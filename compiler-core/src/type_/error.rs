@@ -6,6 +6,7 @@ use crate::{
     ast::{BinOp, Layer, SrcSpan, TodoKind},
     build::Target,
     type_::Type,
+    warning::WarningKind,
 };
 
 use camino::Utf8PathBuf;
@@ -777,6 +778,8 @@ pub enum Warning {
         location: SrcSpan,
         message: EcoString,
         layer: Layer,
+        /// The value to use instead, if the deprecation gives one.
+        replacement: Option<EcoString>,
     },
 
     UnreachableCaseClause {
@@ -985,6 +988,75 @@ pub enum UnreachableCaseClauseReason {
 }
 
 impl Error {
+    /// The stable code identifying this kind of error, which can be looked
+    /// up with `gleam explain`.
+    ///
+    pub fn code(&self) -> &'static str {
+        match self {
+            Error::SrcImportingTest { .. } => "E0201",
+            Error::BitArraySegmentError { .. } => "E0202",
+            Error::UnknownLabels { .. } => "E0203",
+            Error::UnknownVariable { .. } => "E0204",
+            Error::UnknownType { .. } => "E0205",
+            Error::UnknownModule { .. } => "E0206",
+            Error::UnknownModuleType { .. } => "E0207",
+            Error::UnknownModuleValue { .. } => "E0208",
+            Error::ModuleAliasUsedAsName { .. } => "E0209",
+            Error::NotFn { .. } => "E0210",
+            Error::UnknownRecordField { .. } => "E0211",
+            Error::IncorrectArity { .. } => "E0212",
+            Error::UnsafeRecordUpdate { .. } => "E0213",
+            Error::UnnecessarySpreadOperator { .. } => "E0214",
+            Error::IncorrectTypeArity { .. } => "E0215",
+            Error::CouldNotUnify { .. } => "E0216",
+            Error::RecursiveType { .. } => "E0217",
+            Error::DuplicateName { .. } => "E0218",
+            Error::DuplicateImport { .. } => "E0219",
+            Error::DuplicateTypeName { .. } => "E0220",
+            Error::DuplicateArgument { .. } => "E0221",
+            Error::DuplicateField { .. } => "E0222",
+            Error::PrivateTypeLeak { .. } => "E0223",
+            Error::UnexpectedLabelledArg { .. } => "E0224",
+            Error::PositionalArgumentAfterLabelled { .. } => "E0225",
+            Error::IncorrectNumClausePatterns { .. } => "E0226",
+            Error::NonLocalClauseGuardVariable { .. } => "E0227",
+            Error::ExtraVarInAlternativePattern { .. } => "E0228",
+            Error::MissingVarInAlternativePattern { .. } => "E0229",
+            Error::DuplicateVarInPattern { .. } => "E0230",
+            Error::OutOfBoundsTupleIndex { .. } => "E0231",
+            Error::NotATuple { .. } => "E0232",
+            Error::NotATupleUnbound { .. } => "E0233",
+            Error::RecordAccessUnknownType { .. } => "E0234",
+            Error::RecordUpdateInvalidConstructor { .. } => "E0235",
+            Error::UnexpectedTypeHole { .. } => "E0236",
+            Error::ReservedModuleName { .. } => "E0237",
+            Error::KeywordInModuleName { .. } => "E0238",
+            Error::NotExhaustivePatternMatch { .. } => "E0239",
+            Error::ArgumentNameAlreadyUsed { .. } => "E0240",
+            Error::UnlabelledAfterlabelled { .. } => "E0241",
+            Error::RecursiveTypeAlias { .. } => "E0242",
+            Error::ExternalMissingAnnotation { .. } => "E0243",
+            Error::NoImplementation { .. } => "E0244",
+            Error::UnsupportedExpressionTarget { .. } => "E0245",
+            Error::InvalidExternalJavascriptModule { .. } => "E0246",
+            Error::InvalidExternalJavascriptFunction { .. } => "E0247",
+            Error::InexhaustiveCaseExpression { .. } => "E0248",
+            Error::MissingCaseBody { .. } => "E0249",
+            Error::InexhaustiveLetAssignment { .. } => "E0250",
+            Error::UnusedTypeAliasParameter { .. } => "E0251",
+            Error::DuplicateTypeParameter { .. } => "E0252",
+            Error::UnsupportedPublicFunctionTarget { .. } => "E0253",
+            Error::NotFnInUse { .. } => "E0254",
+            Error::UseFnIncorrectArity { .. } => "E0255",
+            Error::UseCallbackIncorrectArity { .. } => "E0256",
+            Error::UseFnDoesntTakeCallback { .. } => "E0257",
+            Error::BadName { .. } => "E0258",
+            Error::AllVariantsDeprecated { .. } => "E0259",
+            Error::DeprecatedVariantOnDeprecatedType { .. } => "E0260",
+            Error::ErlangFloatUnsafe { .. } => "E0261",
+        }
+    }
+
     // Location where the error started
     pub fn start_location(&self) -> u32 {
         match self {
@@ -1082,7 +1154,7 @@ impl Warning {
         }
     }
 
-    fn location(&self) -> SrcSpan {
+    pub(crate) fn location(&self) -> SrcSpan {
         match self {
             Warning::Todo { location, .. }
             | Warning::ImplicitlyDiscardedResult { location, .. }
@@ -1117,12 +1189,120 @@ impl Warning {
         }
     }
 
+    /// The stable code identifying this kind of warning, which can be looked
+    /// up with `gleam explain`.
+    ///
+    pub fn code(&self) -> &'static str {
+        match self {
+            Warning::Todo { .. } => "W0201",
+            Warning::ImplicitlyDiscardedResult { .. } => "W0202",
+            Warning::UnusedLiteral { .. } => "W0203",
+            Warning::UnusedValue { .. } => "W0204",
+            Warning::NoFieldsRecordUpdate { .. } => "W0205",
+            Warning::AllFieldsRecordUpdate { .. } => "W0206",
+            Warning::UnusedType { .. } => "W0207",
+            Warning::UnusedConstructor { .. } => "W0208",
+            Warning::UnusedImportedValue { .. } => "W0209",
+            Warning::UnusedImportedModule { .. } => "W0210",
+            Warning::UnusedImportedModuleAlias { .. } => "W0211",
+            Warning::UnusedPrivateModuleConstant { .. } => "W0212",
+            Warning::UnusedPrivateFunction { .. } => "W0213",
+            Warning::UnusedVariable { .. } => "W0214",
+            Warning::UnnecessaryDoubleIntNegation { .. } => "W0215",
+            Warning::UnnecessaryDoubleBoolNegation { .. } => "W0216",
+            Warning::InefficientEmptyListCheck { .. } => "W0217",
+            Warning::TransitiveDependencyImported { .. } => "W0218",
+            Warning::DeprecatedItem { .. } => "W0219",
+            Warning::UnreachableCaseClause { .. } => "W0220",
+            Warning::CaseMatchOnLiteralCollection { .. } => "W0221",
+            Warning::CaseMatchOnLiteralValue { .. } => "W0222",
+            Warning::OpaqueExternalType { .. } => "W0223",
+            Warning::InternalTypeLeak { .. } => "W0224",
+            Warning::RedundantAssertAssignment { .. } => "W0225",
+            Warning::TodoOrPanicUsedAsFunction { .. } => "W0226",
+            Warning::UnreachableCodeAfterPanic { .. } => "W0227",
+            Warning::RedundantPipeFunctionCapture { .. } => "W0228",
+            Warning::FeatureRequiresHigherGleamVersion { .. } => "W0229",
+            Warning::JavaScriptIntUnsafe { .. } => "W0230",
+        }
+    }
+
+    pub fn kind(&self) -> WarningKind {
+        match self {
+            Warning::Todo { .. } => WarningKind::Todo,
+            Warning::ImplicitlyDiscardedResult { .. } => WarningKind::ImplicitlyDiscardedResult,
+            Warning::UnusedLiteral { .. } => WarningKind::UnusedLiteral,
+            Warning::UnusedValue { .. } => WarningKind::UnusedValue,
+            Warning::NoFieldsRecordUpdate { .. } => WarningKind::NoFieldsRecordUpdate,
+            Warning::AllFieldsRecordUpdate { .. } => WarningKind::AllFieldsRecordUpdate,
+            Warning::UnusedType { .. } => WarningKind::UnusedType,
+            Warning::UnusedConstructor { .. } => WarningKind::UnusedConstructor,
+            Warning::UnusedImportedValue { .. } => WarningKind::UnusedImportedValue,
+            Warning::UnusedImportedModule { .. } => WarningKind::UnusedImportedModule,
+            Warning::UnusedImportedModuleAlias { .. } => WarningKind::UnusedImportedModuleAlias,
+            Warning::UnusedPrivateModuleConstant { .. } => WarningKind::UnusedPrivateModuleConstant,
+            Warning::UnusedPrivateFunction { .. } => WarningKind::UnusedPrivateFunction,
+            Warning::UnusedVariable { .. } => WarningKind::UnusedVariable,
+            Warning::UnnecessaryDoubleIntNegation { .. } => {
+                WarningKind::UnnecessaryDoubleIntNegation
+            }
+            Warning::UnnecessaryDoubleBoolNegation { .. } => {
+                WarningKind::UnnecessaryDoubleBoolNegation
+            }
+            Warning::InefficientEmptyListCheck { .. } => WarningKind::InefficientEmptyListCheck,
+            Warning::TransitiveDependencyImported { .. } => {
+                WarningKind::TransitiveDependencyImported
+            }
+            Warning::DeprecatedItem { .. } => WarningKind::DeprecatedItem,
+            Warning::UnreachableCaseClause { .. } => WarningKind::UnreachableCaseClause,
+            Warning::CaseMatchOnLiteralCollection { .. } => {
+                WarningKind::CaseMatchOnLiteralCollection
+            }
+            Warning::CaseMatchOnLiteralValue { .. } => WarningKind::CaseMatchOnLiteralValue,
+            Warning::OpaqueExternalType { .. } => WarningKind::OpaqueExternalType,
+            Warning::InternalTypeLeak { .. } => WarningKind::InternalTypeLeak,
+            Warning::RedundantAssertAssignment { .. } => WarningKind::RedundantAssertAssignment,
+            Warning::TodoOrPanicUsedAsFunction { .. } => WarningKind::TodoOrPanicUsedAsFunction,
+            Warning::UnreachableCodeAfterPanic { .. } => WarningKind::UnreachableCodeAfterPanic,
+            Warning::RedundantPipeFunctionCapture { .. } => {
+                WarningKind::RedundantPipeFunctionCapture
+            }
+            Warning::FeatureRequiresHigherGleamVersion { .. } => {
+                WarningKind::FeatureRequiresHigherGleamVersion
+            }
+            Warning::JavaScriptIntUnsafe { .. } => WarningKind::JavaScriptIntUnsafe,
+        }
+    }
+
     pub(crate) fn is_todo(&self) -> bool {
         match self {
             Self::Todo { .. } => true,
             _ => false,
         }
     }
+
+    /// If this warning is about something that was imported and never used,
+    /// returns the location of the import, import alias or unqualified item
+    /// that can be safely removed.
+    ///
+    pub fn unused_import_location(&self) -> Option<SrcSpan> {
+        match self {
+            Warning::UnusedImportedModule { location, .. }
+            | Warning::UnusedImportedModuleAlias { location, .. }
+            | Warning::UnusedImportedValue { location, .. }
+            | Warning::UnusedType {
+                location,
+                imported: true,
+                ..
+            }
+            | Warning::UnusedConstructor {
+                location,
+                imported: true,
+                ..
+            } => Some(*location),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -2246,11 +2246,16 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
                     })?;
 
             // Emit a warning if the value being used is deprecated.
-            if let Deprecation::Deprecated { message } = &constructor.deprecation {
+            if let Deprecation::Deprecated {
+                message,
+                replacement,
+            } = &constructor.deprecation
+            {
                 self.problems.warning(Warning::DeprecatedItem {
                     location: select_location,
                     message: message.clone(),
                     layer: Layer::Value,
+                    replacement: replacement.clone(),
                 })
             }
 
@@ -2819,11 +2824,16 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
         } = constructor;
 
         // Emit a warning if the value being used is deprecated.
-        if let Deprecation::Deprecated { message } = &deprecation {
+        if let Deprecation::Deprecated {
+            message,
+            replacement,
+        } = &deprecation
+        {
             self.problems.warning(Warning::DeprecatedItem {
                 location: *location,
                 message: message.clone(),
                 layer: Layer::Value,
+                replacement: replacement.clone(),
             })
         }
 
@@ -143,11 +143,12 @@ impl Hydrator {
 
                 match deprecation {
                     Deprecation::NotDeprecated => {}
-                    Deprecation::Deprecated { message } => {
+                    Deprecation::Deprecated { message, .. } => {
                         problems.warning(Warning::DeprecatedItem {
                             location: *location,
                             message: message.clone(),
                             layer: Layer::Type,
+                            replacement: None,
                         })
                     }
                 }
@@ -760,11 +760,12 @@ impl<'a, 'b> PatternTyper<'a, 'b> {
                 let constructor_deprecation = cons.deprecation.clone();
                 match constructor_deprecation {
                     Deprecation::NotDeprecated => {}
-                    Deprecation::Deprecated { message } => {
+                    Deprecation::Deprecated { message, .. } => {
                         self.problems.warning(Warning::DeprecatedItem {
                             location,
                             message: message.clone(),
                             layer: Layer::Value,
+                            replacement: None,
                         })
                     }
                 }
@@ -878,6 +878,24 @@ pub fn a() {
     );
 }
 
+#[test]
+fn deprecated_imported_function_with_replacement() {
+    assert_warning!(
+        (
+            "package",
+            "module",
+            r#"@deprecated("Use b instead", replace: "b(_2, _1)") pub fn a(x, y) { #(x, y) }"#
+        ),
+        r#"
+import module
+
+pub fn main() {
+  module.a(1, 2)
+}
+"#
+    );
+}
+
 #[test]
 fn deprecated_imported_call_function() {
     assert_warning!(
@@ -2989,3 +3007,71 @@ pub fn main() {
 "#
     );
 }
+
+#[test]
+fn allowed_warning_is_not_reported() {
+    assert_no_warnings!(
+        r#"
+@allow(unused_variable)
+pub fn main() {
+  let x = 1
+  Nil
+}
+"#
+    );
+}
+
+#[test]
+fn allowed_todo_is_not_reported() {
+    assert_no_warnings!(
+        r#"
+@allow(unused_variable, todo)
+pub fn main() {
+  let x = 1
+  todo
+}
+"#
+    );
+}
+
+#[test]
+fn allow_only_silences_the_warnings_it_lists() {
+    assert_warning!(
+        r#"
+@allow(todo)
+pub fn main() {
+  let x = 1
+  todo
+}
+"#
+    );
+}
+
+#[test]
+fn allow_only_applies_to_its_definition() {
+    assert_warning!(
+        r#"
+@allow(unused_variable)
+pub fn main() {
+  let x = 1
+  Nil
+}
+
+pub fn other() {
+  let y = 1
+  Nil
+}
+"#
+    );
+}
+
+#[test]
+fn allowed_unused_import_is_not_reported() {
+    assert_no_warnings!(
+        ("thepackage", "wibble", "pub fn wibble() { 1 }"),
+        r#"
+@allow(unused_imported_module)
+import wibble
+"#
+    );
+}
@@ -15,11 +15,14 @@ use crate::{
 use camino::Utf8PathBuf;
 use debug_ignore::DebugIgnore;
 use ecow::EcoString;
+use serde::Deserialize;
 use std::{
+    collections::HashMap,
     io::Write,
     sync::{atomic::Ordering, Arc},
 };
 use std::{rc::Rc, sync::atomic::AtomicUsize};
+use strum::{Display, EnumIter, EnumString, VariantNames};
 use termcolor::Buffer;
 
 pub trait WarningEmitterIO {
@@ -77,6 +80,10 @@ pub struct WarningEmitter {
     /// package only, the count is reset back to zero after the dependencies are
     /// compiled.
     count: Arc<AtomicUsize>,
+    /// The number of emitted warnings that are denied and should fail the
+    /// build. It is reset along with `count`.
+    denied_count: Arc<AtomicUsize>,
+    levels: Arc<WarningLevels>,
     emitter: DebugIgnore<Rc<dyn WarningEmitterIO>>,
 }
 
@@ -84,6 +91,8 @@ impl WarningEmitter {
     pub fn new(emitter: Rc<dyn WarningEmitterIO>) -> Self {
         Self {
             count: Arc::new(AtomicUsize::new(0)),
+            denied_count: Arc::new(AtomicUsize::new(0)),
+            levels: Arc::new(WarningLevels::default()),
             emitter: DebugIgnore(emitter),
         }
     }
@@ -94,13 +103,30 @@ impl WarningEmitter {
 
     pub fn reset_count(&self) {
         self.count.store(0, Ordering::Relaxed);
+        self.denied_count.store(0, Ordering::Relaxed);
     }
 
     pub fn count(&self) -> usize {
         self.count.load(Ordering::Relaxed)
     }
 
+    pub fn denied_count(&self) -> usize {
+        self.denied_count.load(Ordering::Relaxed)
+    }
+
+    /// Sets the levels of the warnings emitted from now on. Emitters cloned
+    /// before keep the levels they had.
+    ///
+    pub fn set_levels(&mut self, levels: WarningLevels) {
+        self.levels = Arc::new(levels);
+    }
+
     pub fn emit(&self, warning: Warning) {
+        match self.levels.level(&warning) {
+            WarningLevel::Allow => return,
+            WarningLevel::Warn => (),
+            WarningLevel::Deny => _ = self.denied_count.fetch_add(1, Ordering::Relaxed),
+        }
         _ = self.count.fetch_add(1, Ordering::Relaxed);
         self.emitter.emit_warning(warning);
     }
@@ -217,7 +243,151 @@ pub enum DeprecatedSyntaxWarning {
     },
 }
 
+/// The kinds of warnings the type checker can emit, as used to configure how
+/// they are reported with the `[warnings]` table of `gleam.toml` and with the
+/// `@allow` attribute.
+///
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    Display,
+    EnumString,
+    VariantNames,
+    EnumIter,
+)]
+#[strum(serialize_all = "snake_case")]
+pub enum WarningKind {
+    Todo,
+    ImplicitlyDiscardedResult,
+    UnusedLiteral,
+    UnusedValue,
+    NoFieldsRecordUpdate,
+    AllFieldsRecordUpdate,
+    UnusedType,
+    UnusedConstructor,
+    UnusedImportedValue,
+    UnusedImportedModule,
+    UnusedImportedModuleAlias,
+    UnusedPrivateModuleConstant,
+    UnusedPrivateFunction,
+    UnusedVariable,
+    UnnecessaryDoubleIntNegation,
+    UnnecessaryDoubleBoolNegation,
+    InefficientEmptyListCheck,
+    TransitiveDependencyImported,
+    DeprecatedItem,
+    UnreachableCaseClause,
+    CaseMatchOnLiteralCollection,
+    CaseMatchOnLiteralValue,
+    OpaqueExternalType,
+    InternalTypeLeak,
+    RedundantAssertAssignment,
+    TodoOrPanicUsedAsFunction,
+    UnreachableCodeAfterPanic,
+    RedundantPipeFunctionCapture,
+    FeatureRequiresHigherGleamVersion,
+    #[strum(serialize = "javascript_int_unsafe")]
+    JavaScriptIntUnsafe,
+}
+
+impl<'de> Deserialize<'de> for WarningKind {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let name = EcoString::deserialize(deserializer)?;
+        name.parse()
+            .map_err(|_| serde::de::Error::custom(format!("unknown warning `{name}`")))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, EnumString, VariantNames, Deserialize)]
+#[strum(serialize_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum WarningLevel {
+    /// The warning is not reported at all.
+    Allow,
+    /// The warning is reported, this is the default.
+    Warn,
+    /// The warning is reported and fails the build.
+    Deny,
+}
+
+/// The level each kind of warning is reported at.
+///
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WarningLevels {
+    levels: HashMap<WarningKind, WarningLevel>,
+    /// If true all warnings that are not allowed are denied.
+    warnings_as_errors: bool,
+}
+
+impl WarningLevels {
+    /// The levels set by a package's `gleam.toml`, with the given overrides
+    /// taking precedence over them.
+    ///
+    pub fn new(
+        config: &HashMap<WarningKind, WarningLevel>,
+        overrides: &HashMap<WarningKind, WarningLevel>,
+        warnings_as_errors: bool,
+    ) -> Self {
+        let mut levels = config.clone();
+        levels.extend(overrides);
+        Self {
+            levels,
+            warnings_as_errors,
+        }
+    }
+
+    pub fn level(&self, warning: &Warning) -> WarningLevel {
+        let level = match warning {
+            Warning::Type { warning, .. } => self
+                .levels
+                .get(&warning.kind())
+                .copied()
+                .unwrap_or(WarningLevel::Warn),
+            Warning::InvalidSource { .. } | Warning::DeprecatedSyntax { .. } => WarningLevel::Warn,
+        };
+        match level {
+            WarningLevel::Warn if self.warnings_as_errors => WarningLevel::Deny,
+            _ => level,
+        }
+    }
+}
+
+impl DeprecatedSyntaxWarning {
+    /// The stable code identifying this kind of warning, which can be looked
+    /// up with `gleam explain`.
+    ///
+    pub fn code(&self) -> &'static str {
+        match self {
+            DeprecatedSyntaxWarning::DeprecatedListPrepend { .. } => "W0101",
+            DeprecatedSyntaxWarning::DeprecatedListPattern { .. } => "W0102",
+            DeprecatedSyntaxWarning::DeprecatedListCatchAllPattern { .. } => "W0103",
+            DeprecatedSyntaxWarning::DeprecatedRecordSpreadPattern { .. } => "W0104",
+            DeprecatedSyntaxWarning::DeprecatedTargetShorthand { .. } => "W0105",
+        }
+    }
+}
+
 impl Warning {
+    /// The stable code identifying this kind of warning, which can be looked
+    /// up with `gleam explain`.
+    ///
+    pub fn code(&self) -> &'static str {
+        match self {
+            Warning::Type { warning, .. } => warning.code(),
+            Warning::InvalidSource { .. } => "W0001",
+            Warning::DeprecatedSyntax { warning, .. } => warning.code(),
+        }
+    }
+
     pub fn to_diagnostic(&self) -> Diagnostic {
         match self {
             Warning::InvalidSource { path } => Diagnostic {
@@ -227,6 +397,7 @@ Module names must begin with a lowercase letter and contain
 only lowercase alphanumeric characters or underscores."
                     .into(),
                 level: diagnostic::Level::Warning,
+                code: Some(self.code()),
                 location: None,
                 hint: Some(format!(
                     "Rename `{path}` to be valid, or remove this file from the project source."
@@ -247,6 +418,7 @@ like this: `[item, ..list]`.",
 
                 hint: None,
                 level: diagnostic::Level::Warning,
+                code: Some(self.code()),
                 location: Some(Location {
                     label: diagnostic::Label {
                         text: Some("This spread should be preceded by a comma".into()),
@@ -271,6 +443,7 @@ like this: `[item, ..list]`.",
                 ),
                 hint: None,
                 level: diagnostic::Level::Warning,
+                code: Some(self.code()),
                 location: Some(Location {
                     label: diagnostic::Label {
                         text: Some("This spread should be preceded by a comma".into()),
@@ -291,6 +464,7 @@ like this: `[item, ..list]`.",
                 text: wrap("This syntax for pattern matching on a record is deprecated."),
                 hint: None,
                 level: diagnostic::Level::Warning,
+                code: Some(self.code()),
                 location: Some(Location {
                     label: diagnostic::Label {
                         text: Some("This should be preceded by a comma".into()),
@@ -314,6 +488,7 @@ To match on all possible lists, use the `_` catch-all pattern instead.",
                 ),
                 hint: None,
                 level: diagnostic::Level::Warning,
+                code: Some(self.code()),
                 location: Some(Location {
                     label: diagnostic::Label {
                         text: Some("This can be replaced with `_`".into()),
@@ -342,6 +517,7 @@ To match on all possible lists, use the `_` catch-all pattern instead.",
                     )),
                     hint: None,
                     level: diagnostic::Level::Warning,
+                    code: Some(self.code()),
                     location: Some(Location {
                         label: diagnostic::Label {
                             text: Some(format!("This should be replaced with `{full_name}`")),
@@ -396,6 +572,7 @@ A use expression must always be followed by at least one expression.",
                         title,
                         text,
                         level: diagnostic::Level::Warning,
+                        code: Some(self.code()),
                         location: Some(Location {
                             path: path.to_path_buf(),
                             src: src.clone(),
@@ -416,6 +593,7 @@ A use expression must always be followed by at least one expression.",
                         "If you are sure you don't need it you can assign it to `_`.".into(),
                     ),
                     level: diagnostic::Level::Warning,
+                    code: Some(self.code()),
                     location: Some(Location {
                         path: path.to_path_buf(),
                         src: src.clone(),
@@ -432,6 +610,7 @@ A use expression must always be followed by at least one expression.",
                     text: "".into(),
                     hint: Some("You can safely remove it.".into()),
                     level: diagnostic::Level::Warning,
+                    code: Some(self.code()),
                     location: Some(Location {
                         path: path.to_path_buf(),
                         src: src.clone(),
@@ -450,6 +629,7 @@ A use expression must always be followed by at least one expression.",
                         "Add some fields to change or replace it with the record itself.".into(),
                     ),
                     level: diagnostic::Level::Warning,
+                    code: Some(self.code()),
                     location: Some(Location {
                         path: path.to_path_buf(),
                         src: src.clone(),
@@ -466,6 +646,7 @@ A use expression must always be followed by at least one expression.",
                     text: "".into(),
                     hint: Some("It is better style to use the record creation syntax.".into()),
                     level: diagnostic::Level::Warning,
+                    code: Some(self.code()),
                     location: Some(Location {
                         src: src.clone(),
                         path: path.to_path_buf(),
@@ -495,6 +676,7 @@ A use expression must always be followed by at least one expression.",
                         text: "".into(),
                         hint: Some("You can safely remove it.".into()),
                         level: diagnostic::Level::Warning,
+                        code: Some(self.code()),
                         location: Some(Location {
                             src: src.clone(),
                             path: path.to_path_buf(),
@@ -525,6 +707,7 @@ A use expression must always be followed by at least one expression.",
                         text: "".into(),
                         hint: Some("You can safely remove it.".into()),
                         level: diagnostic::Level::Warning,
+                        code: Some(self.code()),
                         location: Some(Location {
                             src: src.clone(),
                             path: path.to_path_buf(),
@@ -542,6 +725,7 @@ A use expression must always be followed by at least one expression.",
                     text: "".into(),
                     hint: Some("You can safely remove it.".into()),
                     level: diagnostic::Level::Warning,
+                    code: Some(self.code()),
                     location: Some(Location {
                         src: src.clone(),
                         path: path.to_path_buf(),
@@ -570,6 +754,7 @@ Hint: You can safely remove it.
                         text,
                         hint: None,
                         level: diagnostic::Level::Warning,
+                        code: Some(self.code()),
                         location: Some(Location {
                             src: src.clone(),
                             path: path.to_path_buf(),
@@ -587,6 +772,7 @@ Hint: You can safely remove it.
                     text: "".into(),
                     hint: Some("You can safely remove it.".into()),
                     level: diagnostic::Level::Warning,
+                    code: Some(self.code()),
                     location: Some(Location {
                         src: src.clone(),
                         path: path.to_path_buf(),
@@ -603,6 +789,7 @@ Hint: You can safely remove it.
                     text: "".into(),
                     hint: Some("You can safely remove it.".into()),
                     level: diagnostic::Level::Warning,
+                    code: Some(self.code()),
                     location: Some(Location {
                         src: src.clone(),
                         path: path.to_path_buf(),
@@ -619,6 +806,7 @@ Hint: You can safely remove it.
                     text: "".into(),
                     hint: Some("You can safely remove it.".into()),
                     level: diagnostic::Level::Warning,
+                    code: Some(self.code()),
                     location: Some(Location {
                         src: src.clone(),
                         path: path.to_path_buf(),
@@ -635,6 +823,7 @@ Hint: You can safely remove it.
                     text: "".into(),
                     hint: origin.how_to_ignore(),
                     level: diagnostic::Level::Warning,
+                    code: Some(self.code()),
                     location: Some(Location {
                         src: src.clone(),
                         path: path.to_path_buf(),
@@ -650,6 +839,7 @@ Hint: You can safely remove it.
                     text: "".into(),
                     hint: Some("You can safely remove this.".into()),
                     level: diagnostic::Level::Warning,
+                    code: Some(self.code()),
                     location: Some(Location {
                         src: src.clone(),
                         path: path.to_path_buf(),
@@ -665,6 +855,7 @@ Hint: You can safely remove it.
                     text: "".into(),
                     hint: Some("You can safely remove this.".into()),
                     level: diagnostic::Level::Warning,
+                    code: Some(self.code()),
                     location: Some(Location {
                         src: src.clone(),
                         path: path.to_path_buf(),
@@ -694,6 +885,7 @@ need to know if the list is empty or not.
                         text,
                         hint,
                         level: diagnostic::Level::Warning,
+                        code: Some(self.code()),
                         location: Some(Location {
                             src: src.clone(),
                             path: path.to_path_buf(),
@@ -727,6 +919,7 @@ Run this command to add it to your dependencies:
                         text,
                         hint: None,
                         level: diagnostic::Level::Warning,
+                        code: Some(self.code()),
                         location: Some(Location {
                             src: src.clone(),
                             path: path.to_path_buf(),
@@ -743,6 +936,7 @@ Run this command to add it to your dependencies:
                     location,
                     message,
                     layer,
+                    replacement,
                 } => {
                     let text = wrap(&format!("It was deprecated with this message: {message}"));
                     let (title, diagnostic_label_text) = if layer.is_value() {
@@ -757,11 +951,16 @@ Run this command to add it to your dependencies:
                         )
                     };
 
+                    let hint = replacement
+                        .as_ref()
+                        .map(|_| "Run `gleam fix` to replace it automatically.".into());
+
                     Diagnostic {
                         title,
                         text,
-                        hint: None,
+                        hint,
                         level: diagnostic::Level::Warning,
+                        code: Some(self.code()),
                         location: Some(Location {
                             src: src.clone(),
                             path: path.to_path_buf(),
@@ -790,6 +989,7 @@ on a variant of a type which is never present.\n",
                         text,
                         hint: Some("It can be safely removed.".into()),
                         level: diagnostic::Level::Warning,
+                        code: Some(self.code()),
                         location: Some(Location {
                             src: src.clone(),
                             path: path.to_path_buf(),
@@ -827,6 +1027,7 @@ See: https://tour.gleam.run/flow-control/multiple-subjects/"
                         text,
                         hint: None,
                         level: diagnostic::Level::Warning,
+                        code: Some(self.code()),
                         location: Some(Location {
                             src: src.clone(),
                             path: path.to_path_buf(),
@@ -847,6 +1048,7 @@ can already tell which branch is going to match with this value.",
                     ),
                     hint: None,
                     level: diagnostic::Level::Warning,
+                    code: Some(self.code()),
                     location: Some(Location {
                         src: src.clone(),
                         path: path.to_path_buf(),
@@ -863,6 +1065,7 @@ can already tell which branch is going to match with this value.",
                     text: "This type has no constructors so making it opaque is redundant.".into(),
                     hint: Some("Remove the `opaque` qualifier from the type definition.".into()),
                     level: diagnostic::Level::Warning,
+                    code: Some(self.code()),
                     location: Some(Location {
                         src: src.clone(),
                         path: path.to_path_buf(),
@@ -879,6 +1082,7 @@ can already tell which branch is going to match with this value.",
                     text: "".into(),
                     hint: None,
                     level: diagnostic::Level::Warning,
+                    code: Some(self.code()),
                     location: Some(Location {
                         path: path.to_path_buf(),
                         src: src.clone(),
@@ -912,6 +1116,7 @@ hidden from the package's documentation.",
                         text,
                         hint: None,
                         level: diagnostic::Level::Warning,
+                        code: Some(self.code()),
                         location: Some(Location {
                             label: diagnostic::Label {
                                 text: None,
@@ -929,6 +1134,7 @@ hidden from the package's documentation.",
                         .into(),
                     hint: None,
                     level: diagnostic::Level::Warning,
+                    code: Some(self.code()),
                     location: Some(Location {
                         label: diagnostic::Label {
                             text: Some("You can remove this".into()),
@@ -985,6 +1191,7 @@ See: https://tour.gleam.run/advanced-features/{name}/"
                         text: wrap(&text),
                         hint: None,
                         level: diagnostic::Level::Warning,
+                        code: Some(self.code()),
                         location: Some(Location {
                             label: diagnostic::Label {
                                 text: None,
@@ -1017,6 +1224,7 @@ Your code will crash before reaching this point.",
                         text: wrap(text),
                         hint: None,
                         level: diagnostic::Level::Warning,
+                        code: Some(self.code()),
                         location: Some(Location {
                             label: diagnostic::Label {
                                 text: None,
@@ -1039,6 +1247,7 @@ See: https://tour.gleam.run/functions/pipelines/",
                     ),
                     hint: None,
                     level: diagnostic::Level::Warning,
+                    code: Some(self.code()),
                     location: Some(Location {
                         label: diagnostic::Label {
                             text: Some("You can safely remove this".into()),
@@ -1098,6 +1307,7 @@ See: https://tour.gleam.run/functions/pipelines/",
     gleam = \">= {minimum_required_version}\""
                         )),
                         level: diagnostic::Level::Warning,
+                        code: Some(self.code()),
                         location: Some(Location {
                             label: diagnostic::Label {
                                 text: Some(format!(
@@ -1124,6 +1334,7 @@ information.",
                     ),
                     hint: None,
                     level: diagnostic::Level::Warning,
+                    code: Some(self.code()),
                     location: Some(Location {
                         path: path.to_path_buf(),
                         src: src.clone(),
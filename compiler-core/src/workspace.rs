@@ -0,0 +1,219 @@
+//! Workspaces of several packages developed together. The `gleam.toml` at the
+//! root of a workspace lists the directories of its member packages:
+//!
+//! ```toml
+//! [workspace]
+//! members = ["packages/app", "packages/lib"]
+//! ```
+//!
+//! The members share one manifest and one build directory at the root of the
+//! workspace. Their dependencies are resolved together, as the dependencies
+//! of a package standing for the whole workspace, which depends on each
+//! member by path.
+
+#[cfg(test)]
+mod tests;
+
+use std::collections::{HashMap, HashSet};
+
+use camino::{Utf8Path, Utf8PathBuf};
+use ecow::EcoString;
+use hexpm::version::Range;
+use serde::Deserialize;
+
+use crate::{
+    config::{Dependencies, PackageConfig},
+    error::{FileIoAction, FileKind},
+    io::FileSystemReader,
+    manifest::ManifestPackage,
+    paths::normalise,
+    requirement::Requirement,
+    Error, Result,
+};
+
+/// The name of the package standing for the whole workspace. It is not a
+/// valid package name, so it can't clash with a member or a dependency.
+///
+pub const WORKSPACE_PACKAGE_NAME: &str = "gleam workspace";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct WorkspaceConfig {
+    /// The directories of the members, relative to the root of the workspace.
+    #[serde(default)]
+    pub members: Vec<Utf8PathBuf>,
+}
+
+#[derive(Deserialize)]
+struct GleamToml {
+    #[serde(default)]
+    workspace: Option<WorkspaceConfig>,
+}
+
+impl WorkspaceConfig {
+    /// The `[workspace]` table of a `gleam.toml`, if it has one.
+    ///
+    pub fn parse(toml: &str) -> Result<Option<Self>, toml::de::Error> {
+        let file: GleamToml = toml::from_str(toml)?;
+        Ok(file.workspace)
+    }
+
+    pub fn read<FS: FileSystemReader>(path: &Utf8Path, fs: &FS) -> Result<Option<Self>> {
+        let toml = fs.read(path)?;
+        Self::parse(&toml).map_err(|e| Error::FileIo {
+            action: FileIoAction::Parse,
+            kind: FileKind::File,
+            path: path.to_path_buf(),
+            err: Some(e.to_string()),
+        })
+    }
+
+    /// The config of the package standing for the whole workspace, given the
+    /// configs of the members in the order they are listed in. It depends on
+    /// each member by path, and on all the dependencies and dev dependencies
+    /// of the members, so that a change to any of those is noticed when
+    /// checking if the manifest is up to date.
+    ///
+    pub fn package_config(&self, members: &[PackageConfig]) -> Result<PackageConfig> {
+        let mut dependencies = Requirements::default();
+        for (directory, member) in self.members.iter().zip(members) {
+            let path = normalise(directory);
+            dependencies.add(&member.name, "workspace", Requirement::Path { path })?;
+
+            for (name, requirement) in member.dependencies.iter().chain(&member.dev_dependencies) {
+                // Path dependencies are relative to the member, so they are
+                // made relative to the root of the workspace instead.
+                let requirement = match requirement {
+                    Requirement::Path { path } if path.is_relative() => Requirement::Path {
+                        path: normalise(&directory.join(path)),
+                    },
+                    Requirement::Hex { .. }
+                    | Requirement::Path { .. }
+                    | Requirement::Git { .. } => requirement.clone(),
+                };
+                dependencies.add(name, &member.name, requirement)?;
+            }
+        }
+
+        Ok(PackageConfig {
+            name: WORKSPACE_PACKAGE_NAME.into(),
+            dependencies: dependencies.into_dependencies(),
+            ..Default::default()
+        })
+    }
+}
+
+/// The requirements of the members, each with the member it first came from.
+///
+#[derive(Debug, Default)]
+struct Requirements {
+    requirements: HashMap<EcoString, (EcoString, Requirement)>,
+}
+
+impl Requirements {
+    fn add(&mut self, package: &EcoString, member: &str, requirement: Requirement) -> Result<()> {
+        let Some((first_member, existing)) = self.requirements.get_mut(package) else {
+            let _ = self
+                .requirements
+                .insert(package.clone(), (member.into(), requirement));
+            return Ok(());
+        };
+
+        match (&*existing, &requirement) {
+            (a, b) if a == b => Ok(()),
+
+            // Hex requirements are combined, but ranges using `or` can't be
+            // combined with `and` without changing their meaning.
+            (Requirement::Hex { version: a }, Requirement::Hex { version: b })
+                if !a.to_string().contains(" or ") && !b.to_string().contains(" or ") =>
+            {
+                *existing = Requirement::Hex {
+                    version: Range::new(format!("{a} and {b}")),
+                };
+                Ok(())
+            }
+
+            (_, _) => Err(Error::ConflictingWorkspaceRequirements {
+                package: package.clone(),
+                requirements: vec![
+                    (first_member.clone(), describe(existing)),
+                    (member.into(), describe(&requirement)),
+                ],
+            }),
+        }
+    }
+
+    fn into_dependencies(self) -> Dependencies {
+        self.requirements
+            .into_iter()
+            .map(|(package, (_, requirement))| (package, requirement))
+            .collect()
+    }
+}
+
+fn describe(requirement: &Requirement) -> EcoString {
+    match requirement {
+        Requirement::Hex { version } => version.to_string().into(),
+        Requirement::Path { path } => format!("path {path}").into(),
+        Requirement::Git { git } => format!("git {git}").into(),
+    }
+}
+
+/// The root of the workspace the package at the given root belongs to: the
+/// package's own root if it is the root of a workspace, or else the closest
+/// parent directory whose `gleam.toml` lists it as a member.
+///
+pub fn find_root<FS: FileSystemReader>(fs: &FS, package_root: &Utf8Path) -> Option<Utf8PathBuf> {
+    let package_root = normalise(package_root);
+    package_root
+        .ancestors()
+        .find(|directory| {
+            // A parent directory with a `gleam.toml` that can't be read isn't
+            // a workspace this package belongs to.
+            let Ok(Some(workspace)) = WorkspaceConfig::read(&directory.join("gleam.toml"), fs)
+            else {
+                return false;
+            };
+            *directory == package_root
+                || workspace
+                    .members
+                    .iter()
+                    .any(|member| normalise(&directory.join(member)) == package_root)
+        })
+        .map(Utf8Path::to_path_buf)
+}
+
+/// The packages of the manifest of a workspace a member needs: the ones it
+/// depends on, directly or not, in its dependencies and dev dependencies.
+/// The member itself is left out as it is compiled as the root package.
+///
+pub fn member_packages(
+    packages: &[ManifestPackage],
+    member: &PackageConfig,
+) -> Vec<ManifestPackage> {
+    let by_name: HashMap<_, _> = packages
+        .iter()
+        .map(|package| (package.name.clone(), package))
+        .collect();
+
+    let mut needed = HashSet::new();
+    let mut to_visit = member
+        .dependencies
+        .keys()
+        .chain(member.dev_dependencies.keys())
+        .cloned()
+        .collect::<Vec<_>>();
+    while let Some(name) = to_visit.pop() {
+        if name == member.name || !needed.insert(name.clone()) {
+            continue;
+        }
+        if let Some(package) = by_name.get(&name) {
+            to_visit.extend(package.requirements.iter().cloned());
+        }
+    }
+
+    packages
+        .iter()
+        .filter(|package| needed.contains(&package.name))
+        .cloned()
+        .collect()
+}
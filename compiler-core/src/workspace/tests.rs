@@ -0,0 +1,171 @@
+use hexpm::version::Version;
+
+use super::{find_root, member_packages, WorkspaceConfig, WORKSPACE_PACKAGE_NAME};
+use crate::{
+    config::PackageConfig,
+    io::{memory::InMemoryFileSystem, FileSystemWriter},
+    manifest::{Base16Checksum, ManifestPackage, ManifestPackageSource},
+    requirement::Requirement,
+    Error,
+};
+
+fn member(name: &str, dependencies: &[(&str, Requirement)]) -> PackageConfig {
+    PackageConfig {
+        name: name.into(),
+        dependencies: dependencies
+            .iter()
+            .map(|(name, requirement)| ((*name).into(), requirement.clone()))
+            .collect(),
+        ..Default::default()
+    }
+}
+
+fn workspace(members: &[&str]) -> WorkspaceConfig {
+    WorkspaceConfig {
+        members: members.iter().map(|member| (*member).into()).collect(),
+    }
+}
+
+fn package(name: &str, requirements: &[&str]) -> ManifestPackage {
+    ManifestPackage {
+        name: name.into(),
+        version: Version::new(1, 0, 0),
+        build_tools: vec!["gleam".into()],
+        otp_app: None,
+        requirements: requirements.iter().map(|name| (*name).into()).collect(),
+        source: ManifestPackageSource::Hex {
+            outer_checksum: Base16Checksum(vec![]),
+        },
+    }
+}
+
+#[test]
+fn parse_workspace_table() {
+    assert_eq!(
+        WorkspaceConfig::parse(
+            r#"
+[workspace]
+members = ["packages/app", "packages/lib"]
+"#
+        )
+        .unwrap(),
+        Some(workspace(&["packages/app", "packages/lib"]))
+    );
+}
+
+#[test]
+fn packages_are_not_workspaces() {
+    assert_eq!(
+        WorkspaceConfig::parse(
+            r#"
+name = "app"
+version = "1.0.0"
+"#
+        )
+        .unwrap(),
+        None
+    );
+}
+
+#[test]
+fn workspace_package_depends_on_the_members_and_their_dependencies() {
+    let mut app = member(
+        "app",
+        &[
+            ("lib", Requirement::path("../lib")),
+            ("gleam_stdlib", Requirement::hex(">= 0.34.0 and < 2.0.0")),
+        ],
+    );
+    app.dev_dependencies = [("gleeunit".into(), Requirement::hex(">= 1.0.0"))].into();
+    let lib = member(
+        "lib",
+        &[("gleam_stdlib", Requirement::hex(">= 0.34.0 and < 2.0.0"))],
+    );
+
+    let config = workspace(&["packages/app", "./packages/lib"])
+        .package_config(&[app, lib])
+        .unwrap();
+    assert_eq!(config.name, WORKSPACE_PACKAGE_NAME);
+    assert_eq!(
+        config.dependencies,
+        [
+            ("app".into(), Requirement::path("packages/app")),
+            ("lib".into(), Requirement::path("packages/lib")),
+            (
+                "gleam_stdlib".into(),
+                Requirement::hex(">= 0.34.0 and < 2.0.0")
+            ),
+            ("gleeunit".into(), Requirement::hex(">= 1.0.0")),
+        ]
+        .into()
+    );
+    assert!(config.dev_dependencies.is_empty());
+}
+
+#[test]
+fn different_hex_requirements_are_combined() {
+    let app = member("app", &[("wibble", Requirement::hex(">= 1.0.0"))]);
+    let lib = member("lib", &[("wibble", Requirement::hex("< 2.0.0"))]);
+    let config = workspace(&["app", "lib"])
+        .package_config(&[app, lib])
+        .unwrap();
+    assert_eq!(
+        config.dependencies.get("wibble"),
+        Some(&Requirement::hex(">= 1.0.0 and < 2.0.0"))
+    );
+}
+
+#[test]
+fn conflicting_requirements() {
+    let app = member("app", &[("wibble", Requirement::hex("~> 1.0 or ~> 2.0"))]);
+    let lib = member("lib", &[("wibble", Requirement::path("../wibble"))]);
+    assert_eq!(
+        workspace(&["app", "lib"]).package_config(&[app, lib]),
+        Err(Error::ConflictingWorkspaceRequirements {
+            package: "wibble".into(),
+            requirements: vec![
+                ("app".into(), "~> 1.0 or ~> 2.0".into()),
+                ("lib".into(), "path wibble".into()),
+            ],
+        })
+    );
+}
+
+#[test]
+fn find_root_of_members() {
+    let fs = InMemoryFileSystem::new();
+    fs.write(
+        "/ws/gleam.toml".into(),
+        "[workspace]\nmembers = [\"packages/app\"]\n",
+    )
+    .unwrap();
+    fs.write("/ws/packages/app/gleam.toml".into(), "name = \"app\"\n")
+        .unwrap();
+    fs.write("/ws/other/gleam.toml".into(), "name = \"other\"\n")
+        .unwrap();
+
+    assert_eq!(
+        find_root(&fs, "/ws/packages/app".into()),
+        Some("/ws".into())
+    );
+    assert_eq!(find_root(&fs, "/ws".into()), Some("/ws".into()));
+    assert_eq!(find_root(&fs, "/ws/other".into()), None);
+}
+
+#[test]
+fn member_packages_are_the_ones_it_needs() {
+    let mut app = member("app", &[("lib", Requirement::path("../lib"))]);
+    app.dev_dependencies = [("gleeunit".into(), Requirement::hex(">= 1.0.0"))].into();
+    let packages = vec![
+        package("app", &["lib"]),
+        package("lib", &["gleam_stdlib"]),
+        package("gleam_stdlib", &[]),
+        package("gleeunit", &["gleam_stdlib"]),
+        package("wisp", &["gleam_stdlib"]),
+    ];
+    let names = member_packages(&packages, &app)
+        .into_iter()
+        .map(|package| package.name)
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec!["lib", "gleam_stdlib", "gleeunit"]);
+}
@@ -0,0 +1,213 @@
+use camino::{Utf8Path, Utf8PathBuf};
+use gleam_core::{
+    build::Target,
+    config::PackageConfig,
+    diagnostic::{self, Level},
+    language_server::{self, FileSystemProxy, LanguageServerEngine, ProgressReporter, Response},
+    paths::ProjectPaths,
+    Error,
+};
+use lsp_types::{self as lsp, DiagnosticSeverity, Position, TextDocumentIdentifier, Url};
+use serde::Serialize;
+use std::{cell::RefCell, collections::HashMap};
+use wasm_bindgen::JsValue;
+
+use crate::wasm_filesystem::WasmFileSystem;
+
+type Engine = LanguageServerEngine<WasmFileSystem, NullProgressReporter>;
+
+struct LanguageServer {
+    target: Target,
+    engine: Engine,
+}
+
+thread_local! {
+    static LANGUAGE_SERVERS: RefCell<HashMap<usize, LanguageServer>> = RefCell::new(HashMap::new());
+}
+
+/// There's nowhere to report progress to in the browser, the editor only
+/// gets the result of each call.
+///
+#[derive(Debug, Clone, Copy)]
+pub struct NullProgressReporter;
+
+impl ProgressReporter for NullProgressReporter {
+    fn compilation_started(&self) {}
+    fn compilation_finished(&self) {}
+    fn dependency_downloading_started(&self) {}
+    fn dependency_downloading_finished(&self) {}
+}
+
+/// An error or warning found by the language server, serialised for
+/// JavaScript.
+///
+#[derive(Debug, Serialize)]
+pub struct Diagnostic {
+    /// The path of the module the diagnostic is about. It is missing for the
+    /// problems that are not specific to one module.
+    pub path: Option<String>,
+    /// The code the diagnostic is about, with zero-based lines and
+    /// characters. It is missing along with the path.
+    pub range: Option<lsp::Range>,
+    pub severity: Severity,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+    Hint,
+}
+
+impl Diagnostic {
+    fn from_compiler(diagnostic: diagnostic::Diagnostic) -> Vec<Diagnostic> {
+        let Some(path) = diagnostic.location.as_ref().map(|l| l.path.to_string()) else {
+            let mut message = diagnostic.title;
+            if !diagnostic.text.is_empty() {
+                message.push_str("\n\n");
+                message.push_str(&diagnostic.text);
+            }
+            let severity = match diagnostic.level {
+                Level::Error => Severity::Error,
+                Level::Warning => Severity::Warning,
+            };
+            return vec![Diagnostic {
+                path: None,
+                range: None,
+                severity,
+                message,
+            }];
+        };
+
+        language_server::diagnostic_to_lsp(diagnostic)
+            .into_iter()
+            .map(|diagnostic| Diagnostic {
+                path: Some(path.clone()),
+                range: Some(diagnostic.range),
+                severity: match diagnostic.severity {
+                    Some(DiagnosticSeverity::ERROR) => Severity::Error,
+                    Some(DiagnosticSeverity::WARNING) => Severity::Warning,
+                    _ => Severity::Hint,
+                },
+                message: diagnostic.message,
+            })
+            .collect()
+    }
+}
+
+/// Compile the project with its language server, starting one if there is
+/// none yet for the given target, and return the errors and warnings found.
+///
+pub fn compile(
+    project_id: usize,
+    target: Target,
+    fs: WasmFileSystem,
+) -> Result<Vec<Diagnostic>, Error> {
+    LANGUAGE_SERVERS.with(|lock| {
+        let mut servers = lock.borrow_mut();
+        let server = match servers.remove(&project_id) {
+            Some(server) if server.target == target => server,
+            _ => LanguageServer {
+                target,
+                engine: new_engine(target, fs.clone())?,
+            },
+        };
+        let server = servers.entry(project_id).or_insert(server);
+
+        // The precompiled dependency modules may have changed since the last
+        // compilation, so they are loaded again each time.
+        server.engine.load_importable_modules(|ids| {
+            crate::load_precompiled_modules(&fs, Utf8Path::new("/lib"), ids)
+        })?;
+
+        let Response {
+            result, warnings, ..
+        } = server.engine.compile_please();
+
+        let mut diagnostics = warnings
+            .iter()
+            .flat_map(|warning| Diagnostic::from_compiler(warning.to_diagnostic()))
+            .collect::<Vec<_>>();
+        if let Err(error) = result {
+            diagnostics.extend(
+                error
+                    .to_diagnostics()
+                    .into_iter()
+                    .flat_map(Diagnostic::from_compiler),
+            );
+        }
+        Ok(diagnostics)
+    })
+}
+
+fn new_engine(target: Target, fs: WasmFileSystem) -> Result<Engine, Error> {
+    let config = PackageConfig {
+        name: crate::PACKAGE_NAME.into(),
+        target,
+        ..Default::default()
+    };
+    let paths = ProjectPaths::new(Utf8PathBuf::from("/"));
+    LanguageServerEngine::new(
+        config,
+        NullProgressReporter,
+        FileSystemProxy::new(fs),
+        paths,
+    )
+}
+
+/// Stop the language server of a project, freeing any memory associated with
+/// it.
+///
+pub fn delete(project_id: usize) {
+    LANGUAGE_SERVERS.with(|lock| {
+        _ = lock.borrow_mut().remove(&project_id);
+    })
+}
+
+/// Run a request against the language server of a project, serialising its
+/// result for JavaScript.
+///
+pub fn request<T: Serialize>(
+    project_id: usize,
+    handler: impl FnOnce(&mut Engine) -> Response<T>,
+) -> Result<JsValue, String> {
+    LANGUAGE_SERVERS.with(|lock| {
+        let mut servers = lock.borrow_mut();
+        let server = servers
+            .get_mut(&project_id)
+            .ok_or("The project has not been compiled by the language server")?;
+        let value = handler(&mut server.engine)
+            .result
+            .map_err(|error| error.pretty_string())?;
+        to_js(&value)
+    })
+}
+
+/// Serialise a value for JavaScript, using plain objects rather than maps so
+/// that the results look the same as the JSON sent by the language server.
+///
+pub fn to_js(value: &impl Serialize) -> Result<JsValue, String> {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .map_err(|error| error.to_string())
+}
+
+/// The document identifier of a module in the `/src` directory.
+///
+pub fn module_document(module_name: &str) -> TextDocumentIdentifier {
+    let uri = Url::parse(&format!("file:///src/{module_name}.gleam")).expect("module URL");
+    TextDocumentIdentifier::new(uri)
+}
+
+pub fn position_params(
+    module_name: &str,
+    line: u32,
+    character: u32,
+) -> lsp::TextDocumentPositionParams {
+    lsp::TextDocumentPositionParams::new(
+        module_document(module_name),
+        Position::new(line, character),
+    )
+}
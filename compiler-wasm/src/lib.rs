@@ -1,16 +1,20 @@
+mod language_server;
 mod log_telemetry;
 #[cfg(test)]
 mod tests;
 mod wasm_filesystem;
 
-use camino::Utf8PathBuf;
+use camino::{Utf8Path, Utf8PathBuf};
 use gleam_core::{
     analyse::TargetSupport,
     build::{
         Mode, NullTelemetry, PackageCompiler, StaleTracker, Target, TargetCodegenConfiguration,
     },
     config::PackageConfig,
-    io::{FileSystemReader, FileSystemWriter},
+    io::{self, FileSystemReader, FileSystemWriter},
+    metadata::ModuleDecoder,
+    paths,
+    type_::ModuleInterface,
     uid::UniqueIdGenerator,
     warning::{VectorWarningEmitterIO, WarningEmitter},
     Error,
@@ -20,6 +24,7 @@ use im::HashMap;
 use std::{cell::RefCell, collections::HashSet, rc::Rc};
 use wasm_filesystem::WasmFileSystem;
 
+use lsp_types as lsp;
 use wasm_bindgen::prelude::*;
 
 #[derive(Debug, Clone, Default)]
@@ -28,6 +33,9 @@ struct Project {
     warnings: VectorWarningEmitterIO,
 }
 
+/// The name of the package being compiled.
+const PACKAGE_NAME: &str = "library";
+
 thread_local! {
     static PROJECTS: RefCell<HashMap<usize, Project>> = RefCell::new(HashMap::new());
 }
@@ -59,7 +67,8 @@ pub fn reset_filesystem(project_id: usize) {
 pub fn delete_project(project_id: usize) {
     PROJECTS.with(|lock| {
         _ = lock.borrow_mut().remove(&project_id);
-    })
+    });
+    language_server::delete(project_id);
 }
 
 fn get_project(project_id: usize) -> Project {
@@ -102,6 +111,38 @@ pub fn write_file_bytes(project_id: usize, path: &str, content: &[u8]) {
         .expect("writing file")
 }
 
+/// Write a precompiled module of a dependency package to the virtual file
+/// system, so that the project can import it without compiling it again.
+///
+/// `metadata` is the `.cache` file written for the module when its package
+/// was compiled, and `javascript` its compiled JavaScript, if any, which is
+/// stored as `/{package}/{module_name}.mjs`. That is where the imports of the
+/// project's JavaScript in `/build` resolve to.
+///
+#[wasm_bindgen]
+pub fn write_precompiled_module(
+    project_id: usize,
+    package: &str,
+    module_name: &str,
+    metadata: &[u8],
+    javascript: Option<String>,
+) {
+    let fs = get_filesystem(project_id);
+    let path = format!(
+        "/lib/{package}/{}/{}.cache",
+        paths::ARTEFACT_DIRECTORY_NAME,
+        module_name.replace('/', "@")
+    );
+    fs.write_bytes(&Utf8PathBuf::from(path), metadata)
+        .expect("writing file");
+
+    if let Some(javascript) = javascript {
+        let path = format!("/{package}/{module_name}.mjs");
+        fs.write(&Utf8PathBuf::from(path), &javascript)
+            .expect("writing file");
+    }
+}
+
 /// Read a file from the virtual file system.
 ///
 #[wasm_bindgen]
@@ -114,18 +155,20 @@ pub fn read_file_bytes(project_id: usize, path: &str) -> Option<Vec<u8>> {
 ///
 #[wasm_bindgen]
 pub fn compile_package(project_id: usize, target: &str) -> Result<(), String> {
-    let target = match target.to_lowercase().as_str() {
-        "erl" | "erlang" => Target::Erlang,
-        "js" | "javascript" => Target::JavaScript,
-        _ => {
-            let msg = format!("Unknown target `{target}`, expected `erlang` or `javascript`");
-            return Err(msg);
-        }
-    };
-
+    let target = parse_target(target)?;
     do_compile_package(get_project(project_id), target).map_err(|e| e.pretty_string())
 }
 
+fn parse_target(target: &str) -> Result<Target, String> {
+    match target.to_lowercase().as_str() {
+        "erl" | "erlang" => Ok(Target::Erlang),
+        "js" | "javascript" => Ok(Target::JavaScript),
+        _ => Err(format!(
+            "Unknown target `{target}`, expected `erlang` or `javascript`"
+        )),
+    }
+}
+
 /// Get the compiled JavaScript output for a given module.
 ///
 /// You need to call `compile_package` before calling this function.
@@ -165,6 +208,121 @@ pub fn pop_warning(project_id: usize) -> Option<String> {
     get_warnings(project_id).pop().map(|w| w.to_pretty_string())
 }
 
+/// Type check the project with the language server, returning the errors and
+/// warnings found. Each is an object with the `path` of the module it is
+/// about, the `range` of the code with zero-based lines and characters, its
+/// `severity` (`error`, `warning` or `hint`) and its `message`.
+///
+/// The other `language_server_` functions use the result of the last call to
+/// this function, so call it again after changing any module.
+///
+#[wasm_bindgen]
+pub fn language_server_compile(project_id: usize, target: &str) -> Result<JsValue, String> {
+    let target = parse_target(target)?;
+    let diagnostics = language_server::compile(project_id, target, get_filesystem(project_id))
+        .map_err(|e| e.pretty_string())?;
+    language_server::to_js(&diagnostics)
+}
+
+/// Get the hover information for a position in a module, as an LSP `Hover`
+/// object. Lines and characters are zero-based.
+///
+#[wasm_bindgen]
+pub fn language_server_hover(
+    project_id: usize,
+    module_name: &str,
+    line: u32,
+    character: u32,
+) -> Result<JsValue, String> {
+    let params = lsp::HoverParams {
+        text_document_position_params: language_server::position_params(
+            module_name,
+            line,
+            character,
+        ),
+        work_done_progress_params: Default::default(),
+    };
+    language_server::request(project_id, |engine| engine.hover(params))
+}
+
+/// Get the completions for a position in a module, as an array of LSP
+/// `CompletionItem` objects. Lines and characters are zero-based.
+///
+#[wasm_bindgen]
+pub fn language_server_completion(
+    project_id: usize,
+    module_name: &str,
+    line: u32,
+    character: u32,
+) -> Result<JsValue, String> {
+    let path = format!("/src/{module_name}.gleam");
+    let src = get_filesystem(project_id)
+        .read(&Utf8PathBuf::from(path))
+        .map_err(|e| e.pretty_string())?;
+    let params = language_server::position_params(module_name, line, character);
+    language_server::request(project_id, |engine| engine.completion(params, src.into()))
+}
+
+/// Get the definition of the value or type at a position in a module, as an
+/// LSP `Location` object. Lines and characters are zero-based.
+///
+#[wasm_bindgen]
+pub fn language_server_definition(
+    project_id: usize,
+    module_name: &str,
+    line: u32,
+    character: u32,
+) -> Result<JsValue, String> {
+    let params = lsp::GotoDefinitionParams {
+        text_document_position_params: language_server::position_params(
+            module_name,
+            line,
+            character,
+        ),
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    };
+    language_server::request(project_id, |engine| engine.goto_definition(params))
+}
+
+/// Get the code actions available for a range of a module, as an array of LSP
+/// `CodeAction` objects. Lines and characters are zero-based.
+///
+#[wasm_bindgen]
+pub fn language_server_code_actions(
+    project_id: usize,
+    module_name: &str,
+    start_line: u32,
+    start_character: u32,
+    end_line: u32,
+    end_character: u32,
+) -> Result<JsValue, String> {
+    let params = lsp::CodeActionParams {
+        text_document: language_server::module_document(module_name),
+        range: lsp::Range::new(
+            lsp::Position::new(start_line, start_character),
+            lsp::Position::new(end_line, end_character),
+        ),
+        context: Default::default(),
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    };
+    language_server::request(project_id, |engine| engine.code_actions(params))
+}
+
+/// Format a module, returning the edits to make to it as an array of LSP
+/// `TextEdit` objects. This doesn't need the project to have been compiled.
+///
+#[wasm_bindgen]
+pub fn language_server_format(project_id: usize, module_name: &str) -> Result<JsValue, String> {
+    let path = Utf8PathBuf::from(format!("/src/{module_name}.gleam"));
+    let edits = get_filesystem(project_id)
+        .read(&path)
+        .and_then(|src| gleam_core::language_server::format(&src, &path))
+        .map_err(|e| e.pretty_string())?;
+    language_server::to_js(&edits)
+}
+
 fn do_compile_package(project: Project, target: Target) -> Result<(), Error> {
     let ids = UniqueIdGenerator::new();
     let mut type_manifests = im::HashMap::new();
@@ -172,7 +330,7 @@ fn do_compile_package(project: Project, target: Target) -> Result<(), Error> {
     #[allow(clippy::arc_with_non_send_sync)]
     let warning_emitter = WarningEmitter::new(Rc::new(project.warnings));
     let config = PackageConfig {
-        name: "library".into(),
+        name: PACKAGE_NAME.into(),
         version: Version::new(1, 0, 0),
         target,
         ..Default::default()
@@ -189,6 +347,13 @@ fn do_compile_package(project: Project, target: Target) -> Result<(), Error> {
     tracing::info!("Compiling package");
 
     let lib = Utf8PathBuf::from("/lib");
+    let mut precompiled_packages = HashSet::new();
+    for module in load_precompiled_modules(&project.fs, &lib, &ids)? {
+        let _ = precompiled_packages.insert(module.package.clone());
+        let _ = defined_modules.insert(module.name.clone(), module.src_path.clone());
+        let _ = type_manifests.insert(module.name.clone(), module);
+    }
+
     let out = Utf8PathBuf::from("/build");
     let package = Utf8PathBuf::from("/");
     let mut compiler = PackageCompiler::new(
@@ -205,6 +370,7 @@ fn do_compile_package(project: Project, target: Target) -> Result<(), Error> {
     compiler.write_metadata = false;
     compiler.compile_beam_bytecode = true;
     compiler.target_support = TargetSupport::Enforced;
+    compiler.precompiled_packages = precompiled_packages;
     compiler
         .compile(
             &warning_emitter,
@@ -217,3 +383,21 @@ fn do_compile_package(project: Project, target: Target) -> Result<(), Error> {
         .into_result()
         .map(|_| ())
 }
+
+/// Load the interfaces of the precompiled dependency modules written with
+/// `write_precompiled_module`, so the package can import them.
+///
+fn load_precompiled_modules(
+    fs: &WasmFileSystem,
+    lib: &Utf8Path,
+    ids: &UniqueIdGenerator,
+) -> Result<Vec<ModuleInterface>, Error> {
+    io::gleam_cache_files(fs, lib)
+        .map(|path| {
+            let bytes = fs.read_bytes(&path)?;
+            let module = ModuleDecoder::new(ids.clone()).read(bytes.as_slice())?;
+            tracing::debug!(module = %module.name, "loading_precompiled_module");
+            Ok(module)
+        })
+        .collect()
+}
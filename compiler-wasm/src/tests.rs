@@ -102,3 +102,198 @@ fn test_warnings() {
     assert!(pop_warning(0).is_some());
     assert!(pop_warning(0).is_none());
 }
+
+#[wasm_bindgen_test]
+fn test_language_server_diagnostics() {
+    reset_filesystem(0);
+    write_module(0, "one", "pub fn main() { 1 + \"a\" }");
+    let diagnostics = language_server_compile(0, "erlang").unwrap();
+    let diagnostics: Vec<serde_json::Value> = serde_wasm_bindgen::from_value(diagnostics).unwrap();
+    assert_eq!(diagnostics[0]["path"], "/src/one.gleam");
+    assert_eq!(diagnostics[0]["severity"], "error");
+    assert_eq!(diagnostics[0]["range"]["start"]["character"], 20);
+
+    // Let's fix that.
+    write_module(0, "one", "pub fn main() { 1 + 2 }");
+    let diagnostics = language_server_compile(0, "erlang").unwrap();
+    let diagnostics: Vec<serde_json::Value> = serde_wasm_bindgen::from_value(diagnostics).unwrap();
+    assert!(diagnostics.is_empty());
+}
+
+#[wasm_bindgen_test]
+fn test_language_server_hover() {
+    reset_filesystem(0);
+    write_module(0, "one", "pub fn go() { 1 }");
+    write_module(0, "two", "import one\npub fn main() { one.go() }");
+    assert!(language_server_compile(0, "javascript").is_ok());
+
+    let hover = language_server_hover(0, "two", 1, 20).unwrap();
+    let hover: lsp::Hover = serde_wasm_bindgen::from_value(hover).unwrap();
+    assert_eq!(
+        hover.contents,
+        lsp::HoverContents::Scalar(lsp::MarkedString::String(
+            "```gleam\nfn() -> Int\n```\n".into()
+        ))
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_language_server_definition() {
+    reset_filesystem(0);
+    write_module(0, "one", "pub fn go() { 1 }");
+    write_module(0, "two", "import one\npub fn main() { one.go() }");
+    assert!(language_server_compile(0, "javascript").is_ok());
+
+    let location = language_server_definition(0, "two", 1, 20).unwrap();
+    let location: lsp::Location = serde_wasm_bindgen::from_value(location).unwrap();
+    assert_eq!(location.uri.path(), "/src/one.gleam");
+}
+
+#[wasm_bindgen_test]
+fn test_language_server_requires_compilation() {
+    delete_project(0);
+    write_module(0, "one", "pub fn go() { 1 }");
+    assert!(language_server_hover(0, "one", 0, 8).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_language_server_format() {
+    reset_filesystem(0);
+    write_module(0, "one", "pub fn go() {1}");
+    let edits = language_server_format(0, "one").unwrap();
+    let edits: Vec<lsp::TextEdit> = serde_wasm_bindgen::from_value(edits).unwrap();
+    assert_eq!(edits[0].new_text, "pub fn go() {\n  1\n}\n");
+}
+
+/// Compile a module as part of its own package, returning its `.cache`
+/// metadata and its JavaScript, as a build tool would.
+///
+fn precompile_module(package: &str, module_name: &str, code: &str) -> (Vec<u8>, String) {
+    let fs = WasmFileSystem::default();
+    let path = Utf8PathBuf::from(format!("/src/{module_name}.gleam"));
+    fs.write(&path, code).unwrap();
+    let config = PackageConfig {
+        name: package.into(),
+        version: Version::new(1, 0, 0),
+        target: Target::JavaScript,
+        ..Default::default()
+    };
+    let target = TargetCodegenConfiguration::JavaScript {
+        emit_typescript_definitions: false,
+        prelude_location: Utf8PathBuf::from("../gleam_prelude.mjs"),
+    };
+    let root = Utf8PathBuf::from("/");
+    let out = Utf8PathBuf::from(format!("/build/{package}"));
+    let lib = Utf8PathBuf::from("/lib");
+    let mut compiler = PackageCompiler::new(
+        &config,
+        Mode::Dev,
+        &root,
+        &out,
+        &lib,
+        &target,
+        UniqueIdGenerator::new(),
+        fs.clone(),
+    );
+    compiler.write_entrypoint = false;
+    let _ = compiler
+        .compile(
+            &WarningEmitter::null(),
+            &mut im::HashMap::new(),
+            &mut im::HashMap::new(),
+            &mut StaleTracker::default(),
+            &mut HashSet::new(),
+            &NullTelemetry,
+        )
+        .into_result()
+        .unwrap();
+
+    let artefact = module_name.replace('/', "@");
+    let metadata = fs
+        .read_bytes(&out.join(format!("_gleam_artefacts/{artefact}.cache")))
+        .unwrap();
+    let javascript = fs.read(&out.join(format!("{module_name}.mjs"))).unwrap();
+    (metadata, javascript)
+}
+
+#[wasm_bindgen_test]
+fn test_compile_package_with_precompiled_module() {
+    reset_filesystem(0);
+    let (metadata, javascript) = precompile_module(
+        "wibble",
+        "wibble/thing",
+        "pub type Thing { Thing(Int) }\npub fn new() { Thing(1) }",
+    );
+    write_precompiled_module(0, "wibble", "wibble/thing", &metadata, Some(javascript));
+    write_module(
+        0,
+        "app",
+        "import wibble/thing\npub fn main() { let thing.Thing(x) = thing.new() x }",
+    );
+    assert!(compile_package(0, "javascript").is_ok());
+    assert!(pop_warning(0).is_none());
+
+    // The import of the precompiled module resolves to its JavaScript.
+    let javascript = read_compiled_javascript(0, "app").unwrap();
+    let import = javascript
+        .lines()
+        .find_map(|line| line.strip_prefix("import * as $thing from \""))
+        .and_then(|rest| rest.strip_suffix("\";"))
+        .unwrap();
+    let path = resolve_import("/build/app.mjs", import);
+    assert_eq!(path, "/wibble/wibble/thing.mjs");
+    assert!(read_file_bytes(0, &path).is_some());
+
+    // The precompiled module is type checked against.
+    write_module(
+        0,
+        "app",
+        "import wibble/thing\npub fn main() { thing.new() + 1 }",
+    );
+    assert!(compile_package(0, "javascript")
+        .unwrap_err()
+        .contains("Type mismatch"));
+}
+
+#[wasm_bindgen_test]
+fn test_language_server_with_precompiled_module() {
+    delete_project(0);
+    let (metadata, javascript) = precompile_module(
+        "wibble",
+        "wibble/thing",
+        "pub type Thing { Thing(Int) }\npub fn new() { Thing(1) }",
+    );
+    write_precompiled_module(0, "wibble", "wibble/thing", &metadata, Some(javascript));
+    write_module(
+        0,
+        "app",
+        "import wibble/thing\npub fn main() { let thing.Thing(x) = thing.new() x }",
+    );
+    let diagnostics = language_server_compile(0, "javascript").unwrap();
+    let diagnostics: Vec<serde_json::Value> = serde_wasm_bindgen::from_value(diagnostics).unwrap();
+    assert!(diagnostics.is_empty());
+}
+
+/// The path of the file a JavaScript module at `from` imports with the given
+/// relative path.
+///
+fn resolve_import(from: &str, import: &str) -> String {
+    let mut path = Utf8PathBuf::from(from);
+    _ = path.pop();
+    for component in Utf8Path::new(import).components() {
+        match component.as_str() {
+            "." => (),
+            ".." => _ = path.pop(),
+            name => path.push(name),
+        }
+    }
+    path.to_string()
+}
+
+#[wasm_bindgen_test]
+fn test_compile_package_with_invalid_precompiled_module() {
+    reset_filesystem(0);
+    write_precompiled_module(0, "wibble", "wibble/thing", &[1, 2, 3], None);
+    write_module(0, "app", "pub fn main() { 1 }");
+    assert!(compile_package(0, "javascript").is_err());
+}
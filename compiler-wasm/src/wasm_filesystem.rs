@@ -1,22 +1,72 @@
 use camino::{Utf8Path, Utf8PathBuf};
 use gleam_core::{
+    build::Target,
     io::{
         memory::InMemoryFileSystem, BeamCompiler, CommandExecutor, FileSystemReader,
         FileSystemWriter, ReadDir, Stdio, WrappedReader,
     },
+    language_server::{DownloadDependencies, LockGuard, Locker, MakeLocker},
+    manifest::Manifest,
+    paths::ProjectPaths,
     Error, Result,
 };
-use std::collections::HashSet;
+use std::{
+    cell::Cell,
+    collections::{HashMap, HashSet},
+    rc::Rc,
+    time::Duration,
+};
 
 #[derive(Clone, Debug, Default)]
 pub struct WasmFileSystem {
     imfs: InMemoryFileSystem,
+    /// The number of files written so far, used as a clock.
+    writes: Rc<Cell<u64>>,
 }
 
 impl WasmFileSystem {
     pub fn reset(&self) {
         self.imfs.reset();
     }
+
+    /// The system clock is not available in WebAssembly, so each file written
+    /// is instead given a later modification time than the previous one. This
+    /// lets the language server tell which modules changed since it last
+    /// compiled them.
+    ///
+    fn touch(&self, path: &Utf8Path) -> Result<(), Error> {
+        let writes = self.writes.get() + 1;
+        self.writes.set(writes);
+        let time = self.imfs.modification_time(path)? + Duration::from_secs(writes);
+        self.imfs.try_set_modification_time(path, time)
+    }
+}
+
+impl MakeLocker for WasmFileSystem {
+    fn make_locker(&self, _paths: &ProjectPaths, _target: Target) -> Result<Box<dyn Locker>> {
+        Ok(Box::new(NullLocker))
+    }
+}
+
+/// Nothing else can use the virtual file system while the compiler is running,
+/// so the build directory doesn't need to be locked.
+///
+#[derive(Debug)]
+struct NullLocker;
+
+impl Locker for NullLocker {
+    fn lock_for_build(&self) -> LockGuard {
+        LockGuard(Box::new(()))
+    }
+}
+
+impl DownloadDependencies for WasmFileSystem {
+    fn download_dependencies(&self, _paths: &ProjectPaths) -> Result<Manifest> {
+        Ok(Manifest {
+            requirements: HashMap::new(),
+            packages: vec![],
+        })
+    }
 }
 
 impl CommandExecutor for WasmFileSystem {
@@ -57,6 +107,11 @@ impl FileSystemWriter for WasmFileSystem {
         Ok(())
     }
 
+    fn rename(&self, from: &Utf8Path, to: &Utf8Path) -> Result<(), Error> {
+        tracing::trace!("rename {:?} to {:?}", from, to);
+        self.imfs.rename(from, to)
+    }
+
     fn mkdir(&self, path: &Utf8Path) -> Result<(), Error> {
         tracing::trace!("mkdir {:?}", path);
         self.imfs.mkdir(path)
@@ -77,12 +132,14 @@ impl FileSystemWriter for WasmFileSystem {
 
     fn write(&self, path: &Utf8Path, content: &str) -> Result<(), Error> {
         tracing::trace!("write {:?}", path);
-        self.imfs.write(path, content)
+        self.imfs.write(path, content)?;
+        self.touch(path)
     }
 
     fn write_bytes(&self, path: &Utf8Path, content: &[u8]) -> Result<(), Error> {
         tracing::trace!("write_bytes {:?}", path);
-        self.imfs.write_bytes(path, content)
+        self.imfs.write_bytes(path, content)?;
+        self.touch(path)
     }
 
     fn exists(&self, path: &Utf8Path) -> bool {
@@ -10,7 +10,7 @@ use gleam_core::{
     paths::ProjectPaths,
     warning::VectorWarningEmitterIO,
 };
-use std::rc::Rc;
+use std::{collections::HashMap, rc::Rc};
 
 pub fn prepare(path: &str, mode: Mode) -> String {
     let root = Utf8PathBuf::from(path).canonicalize_utf8().unwrap();
@@ -28,8 +28,10 @@ pub fn prepare(path: &str, mode: Mode) -> String {
         compile: Compile::All,
         codegen: Codegen::All,
         warnings_as_errors: false,
+        warning_levels: HashMap::new(),
         root_target_support: TargetSupport::Enforced,
         no_print_progress: true,
+        tooling: false,
     };
 
     let compiler = ProjectCompiler::new(